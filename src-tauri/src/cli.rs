@@ -0,0 +1,597 @@
+//! Headless command line mode.
+//!
+//! Lets build boxes and scripts list, install, launch, export and repair profiles without
+//! opening the webview. Progress events are streamed to stdout as text or JSON lines.
+
+use crate::commands::profile_command;
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::integrations::mrpack;
+use crate::minecraft::installer::{self, InstallMode};
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::profile_state::Profile;
+use crate::state::state_manager::State;
+use crate::utils::{profile_utils, repair_utils};
+use log::{error, info};
+use sanitize_filename::sanitize;
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+const USAGE: &str = "\
+NoRisk Launcher - headless mode
+
+Usage:
+  noriskclient-launcher [--json] <command>
+
+Commands:
+  --list-profiles                    List all profiles
+  --install <profile-id>             Install a profile without launching it
+  --launch <profile-id>              Install and launch a profile, waits until the game exits
+  --export <profile-id>              Export a profile
+      --format <noriskpack|mrpack>   Export format (default: noriskpack)
+      --output <path>                Target file (default: <launcher dir>/exports/<name>.<ext>)
+      --include <path>               Instance-relative path to include, can be repeated
+  --repair <profile-id>              Repair a profile
+  --help                             Show this help
+
+Options:
+  --json                             Print events and results as JSON lines
+";
+
+/// Exit codes returned by the headless mode.
+const EXIT_OK: i32 = 0;
+const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliAction {
+    ListProfiles,
+    Install(Uuid),
+    Launch(Uuid),
+    Export {
+        profile_id: Uuid,
+        format: profile_utils::ProfileExportFormat,
+        output: Option<PathBuf>,
+        include: Vec<PathBuf>,
+    },
+    Repair(Uuid),
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliArgs {
+    pub action: CliAction,
+    pub output: OutputFormat,
+}
+
+/// Parses the process arguments (without the executable path).
+/// Returns `None` if no headless command was given, so the regular GUI should start.
+pub fn parse_args<I>(args: I) -> Option<std::result::Result<CliArgs, String>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let args: Vec<String> = args.into_iter().collect();
+    let is_cli = args.iter().any(|arg| {
+        matches!(
+            arg.as_str(),
+            "--list-profiles" | "--install" | "--launch" | "--export" | "--repair" | "--help"
+        )
+    });
+    if !is_cli {
+        return None;
+    }
+    Some(parse_cli_args(&args))
+}
+
+fn parse_cli_args(args: &[String]) -> std::result::Result<CliArgs, String> {
+    let mut action = None;
+    let mut output = OutputFormat::Text;
+    let mut format = None;
+    let mut export_output = None;
+    let mut include = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        let parse_id = |raw: String| {
+            Uuid::parse_str(&raw).map_err(|_| format!("'{}' is not a valid profile id", raw))
+        };
+
+        let next_action = match arg.as_str() {
+            "--json" => {
+                output = OutputFormat::Json;
+                None
+            }
+            "--list-profiles" => Some(CliAction::ListProfiles),
+            "--help" => Some(CliAction::Help),
+            "--install" => Some(CliAction::Install(parse_id(value(arg)?)?)),
+            "--launch" => Some(CliAction::Launch(parse_id(value(arg)?)?)),
+            "--repair" => Some(CliAction::Repair(parse_id(value(arg)?)?)),
+            "--export" => Some(CliAction::Export {
+                profile_id: parse_id(value(arg)?)?,
+                format: profile_utils::ProfileExportFormat::default(),
+                output: None,
+                include: Vec::new(),
+            }),
+            "--format" => {
+                format = Some(match value(arg)?.to_lowercase().as_str() {
+                    "noriskpack" => profile_utils::ProfileExportFormat::NoriskPack,
+                    "mrpack" => profile_utils::ProfileExportFormat::Mrpack,
+                    other => return Err(format!("Unknown export format '{}'", other)),
+                });
+                None
+            }
+            "--output" => {
+                export_output = Some(PathBuf::from(value(arg)?));
+                None
+            }
+            "--include" => {
+                include.push(PathBuf::from(value(arg)?));
+                None
+            }
+            other => return Err(format!("Unknown argument '{}'", other)),
+        };
+
+        if let Some(next_action) = next_action {
+            if action.is_some() {
+                return Err("Only one command can be given at a time".to_string());
+            }
+            action = Some(next_action);
+        }
+    }
+
+    let mut action = action.ok_or_else(|| "No command given".to_string())?;
+    if let CliAction::Export {
+        format: ref mut f,
+        output: ref mut o,
+        include: ref mut i,
+        ..
+    } = action
+    {
+        *f = format.unwrap_or_default();
+        *o = export_output;
+        *i = include;
+    } else if format.is_some() || export_output.is_some() || !include.is_empty() {
+        return Err("--format, --output and --include can only be used with --export".to_string());
+    }
+
+    Ok(CliArgs { action, output })
+}
+
+/// Runs a headless command and returns the process exit code.
+pub async fn run(args: CliArgs) -> i32 {
+    if args.action == CliAction::Help {
+        print!("{}", USAGE);
+        return EXIT_OK;
+    }
+
+    if let Err(e) = State::init_headless().await {
+        error!("Failed to initialize state in headless mode: {}", e);
+        print_result(args.output, Err(&e));
+        return EXIT_FAILURE;
+    }
+
+    let state = match State::get().await {
+        Ok(state) => state,
+        Err(e) => {
+            print_result(args.output, Err(&e));
+            return EXIT_FAILURE;
+        }
+    };
+
+    let printer = EventPrinter::spawn(&state, args.output);
+
+    let result = match args.action.clone() {
+        CliAction::ListProfiles => list_profiles(&state, args.output).await,
+        CliAction::Install(id) => install_profile(&state, id).await,
+        CliAction::Launch(id) => launch_profile(&state, id).await,
+        CliAction::Export {
+            profile_id,
+            format,
+            output,
+            include,
+        } => export_profile(&state, profile_id, format, output, include).await,
+        CliAction::Repair(id) => repair_utils::repair_profile(id).await.map(|_| None),
+        CliAction::Help => Ok(None),
+    };
+
+    printer.finish().await;
+
+    match result {
+        Ok(message) => {
+            print_result(args.output, Ok(message.as_deref()));
+            EXIT_OK
+        }
+        Err(e) => {
+            print_result(args.output, Err(&e));
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Prints all events emitted through the EventState to stdout.
+struct EventPrinter {
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl EventPrinter {
+    fn spawn(state: &State, output: OutputFormat) -> Self {
+        let mut receiver = state.event_state.subscribe();
+        let (shutdown, mut shutdown_rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    biased;
+                    event = receiver.recv() => match event {
+                        Ok(payload) => print_event(output, &payload),
+                        Err(RecvError::Lagged(skipped)) => print_skipped(output, skipped),
+                        Err(RecvError::Closed) => return,
+                    },
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+            // Everything the command emitted is already queued
+            loop {
+                match receiver.try_recv() {
+                    Ok(payload) => print_event(output, &payload),
+                    Err(TryRecvError::Lagged(skipped)) => print_skipped(output, skipped),
+                    Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => return,
+                }
+            }
+        });
+        Self { shutdown, handle }
+    }
+
+    /// Prints the events that are still queued and stops the printer
+    async fn finish(self) {
+        let _ = self.shutdown.send(());
+        if let Err(e) = self.handle.await {
+            error!("Event printer failed: {}", e);
+        }
+    }
+}
+
+fn print_skipped(output: OutputFormat, skipped: u64) {
+    if output == OutputFormat::Text {
+        println!("[warning] skipped {} events", skipped);
+    }
+}
+
+fn print_event(output: OutputFormat, payload: &EventPayload) {
+    match output {
+        OutputFormat::Json => match serde_json::to_string(&json!({ "event": payload })) {
+            Ok(line) => println!("{}", line),
+            Err(e) => error!("Failed to serialize event: {}", e),
+        },
+        OutputFormat::Text => {
+            let event_type = serde_json::to_value(&payload.event_type)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_else(|| format!("{:?}", payload.event_type));
+            match (payload.progress, &payload.error) {
+                (_, Some(error)) => println!("[{}] {} (error: {})", event_type, payload.message, error),
+                (Some(progress), None) => println!(
+                    "[{}] {:>3.0}% {}",
+                    event_type,
+                    progress * 100.0,
+                    payload.message
+                ),
+                (None, None) => println!("[{}] {}", event_type, payload.message),
+            }
+        }
+    }
+}
+
+fn print_result(output: OutputFormat, result: std::result::Result<Option<&str>, &AppError>) {
+    match (output, result) {
+        (OutputFormat::Json, Ok(message)) => {
+            println!("{}", json!({ "result": "ok", "message": message }))
+        }
+        (OutputFormat::Json, Err(e)) => {
+            println!("{}", json!({ "result": "error", "error": e.to_string() }))
+        }
+        (OutputFormat::Text, Ok(Some(message))) => println!("{}", message),
+        (OutputFormat::Text, Ok(None)) => println!("Done."),
+        (OutputFormat::Text, Err(e)) => eprintln!("Error: {}", e),
+    }
+}
+
+/// Looks up a user profile or, if none exists with that id, a standard version.
+async fn resolve_profile(state: &State, profile_id: Uuid) -> Result<Profile> {
+    match state.profile_manager.get_profile(profile_id).await {
+        Ok(profile) => Ok(profile),
+        Err(_) => state
+            .norisk_version_manager
+            .get_profile_by_id(profile_id)
+            .await
+            .ok_or(AppError::ProfileNotFound(profile_id)),
+    }
+}
+
+async fn list_profiles(state: &State, output: OutputFormat) -> Result<Option<String>> {
+    let profiles = state.profile_manager.list_profiles().await?;
+    for profile in &profiles {
+        match output {
+            OutputFormat::Json => println!(
+                "{}",
+                json!({
+                    "profile": {
+                        "id": profile.id,
+                        "name": profile.name,
+                        "game_version": profile.game_version,
+                        "loader": profile.loader,
+                        "loader_version": profile.loader_version,
+                        "group": profile.group,
+                    }
+                })
+            ),
+            OutputFormat::Text => println!(
+                "{}  {}  ({} {}{})",
+                profile.id,
+                profile.name,
+                profile.game_version,
+                profile.loader.as_str(),
+                profile
+                    .loader_version
+                    .as_ref()
+                    .map(|v| format!(" {}", v))
+                    .unwrap_or_default()
+            ),
+        }
+    }
+    Ok(Some(format!("{} profile(s)", profiles.len())))
+}
+
+async fn install_profile(state: &State, profile_id: Uuid) -> Result<Option<String>> {
+    let profile = resolve_profile(state, profile_id).await?;
+    info!("[CLI] Installing profile '{}' ({})", profile.name, profile.id);
+
+    // Credentials are only needed to start the game, installing works without an account
+    let credentials = state
+        .minecraft_account_manager_v2
+        .get_active_account()
+        .await
+        .ok()
+        .flatten();
+
+    installer::install_minecraft_version_with_mode(
+        &profile.game_version,
+        profile.loader.as_str(),
+        &profile,
+        credentials,
+        None,
+        None,
+        InstallMode::InstallOnly,
+    )
+    .await?;
+
+    Ok(Some(format!("Profile '{}' installed.", profile.name)))
+}
+
+async fn launch_profile(state: &State, profile_id: Uuid) -> Result<Option<String>> {
+    // Subscribed before the launch task starts, so its final event can't be missed
+    let mut receiver = state.event_state.subscribe();
+    profile_command::launch_profile(profile_id, None, None)
+        .await
+        .map_err(|e| AppError::Other(e.message))?;
+
+    // Wait for install + launch to finish
+    wait_for_launch(&mut receiver, profile_id).await?;
+
+    // Then wait until the game exits
+    info!("[CLI] Waiting for the game of profile {} to exit...", profile_id);
+    while !state
+        .process_manager
+        .get_process_metadata_by_profile(profile_id)
+        .await
+        .is_empty()
+    {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    Ok(Some(format!("Game of profile {} exited.", profile_id)))
+}
+
+/// Waits for the `LaunchSuccessful` or `Error` event the launch task emits when it is done
+async fn wait_for_launch(
+    receiver: &mut broadcast::Receiver<EventPayload>,
+    profile_id: Uuid,
+) -> Result<()> {
+    loop {
+        match receiver.recv().await {
+            Ok(payload) if payload.target_id == Some(profile_id) => match payload.event_type {
+                EventType::LaunchSuccessful => return Ok(()),
+                EventType::Error => {
+                    return Err(AppError::Other(format!(
+                        "Launching profile {} failed: {}",
+                        profile_id,
+                        payload.error.unwrap_or(payload.message)
+                    )))
+                }
+                _ => {}
+            },
+            Ok(_) | Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => {
+                return Err(AppError::Other(format!(
+                    "Launch of profile {} ended without a result",
+                    profile_id
+                )))
+            }
+        }
+    }
+}
+
+async fn export_profile(
+    state: &State,
+    profile_id: Uuid,
+    format: profile_utils::ProfileExportFormat,
+    output: Option<PathBuf>,
+    include: Vec<PathBuf>,
+) -> Result<Option<String>> {
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let output_path = match output {
+        Some(path) => path,
+        None => {
+            let file_name = format!("{}.{}", sanitize(&profile.name), format.extension());
+            LAUNCHER_DIRECTORY.root_dir().join("exports").join(file_name)
+        }
+    };
+
+    let include_files = if include.is_empty() {
+        None
+    } else {
+        let instance_path = state
+            .profile_manager
+            .get_profile_instance_path(profile_id)
+            .await?;
+        Some(include.iter().map(|p| instance_path.join(p)).collect())
+    };
+
+    let result_path = match format {
+        profile_utils::ProfileExportFormat::NoriskPack => {
+            profile_utils::export_profile_to_noriskpack(profile_id, Some(output_path), include_files)
+                .await?
+        }
+        profile_utils::ProfileExportFormat::Mrpack => {
+            mrpack::export_profile_to_mrpack(profile_id, output_path, include_files).await?
+        }
+    };
+
+    Ok(Some(format!("Exported to {}", result_path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "5f0c7c7e-6b1a-4f7e-9a55-2d0f1b8f2c11";
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn profile_id() -> Uuid {
+        Uuid::parse_str(ID).unwrap()
+    }
+
+    #[test]
+    fn test_parse_args_without_command_starts_gui() {
+        assert!(parse_args(args(&[])).is_none());
+        // Arguments the OS or Tauri pass to the GUI
+        assert!(parse_args(args(&["--json"])).is_none());
+        assert!(parse_args(args(&["nrc://join"])).is_none());
+    }
+
+    #[test]
+    fn test_parse_args_commands() {
+        assert_eq!(
+            parse_args(args(&["--list-profiles"])),
+            Some(Ok(CliArgs {
+                action: CliAction::ListProfiles,
+                output: OutputFormat::Text,
+            }))
+        );
+        assert_eq!(
+            parse_args(args(&["--json", "--launch", ID])),
+            Some(Ok(CliArgs {
+                action: CliAction::Launch(profile_id()),
+                output: OutputFormat::Json,
+            }))
+        );
+        assert_eq!(
+            parse_cli_args(&args(&["--install", ID])).map(|a| a.action),
+            Ok(CliAction::Install(profile_id()))
+        );
+        assert_eq!(
+            parse_cli_args(&args(&["--repair", ID, "--json"])).map(|a| a.action),
+            Ok(CliAction::Repair(profile_id()))
+        );
+    }
+
+    #[test]
+    fn test_parse_export_options() {
+        assert_eq!(
+            parse_cli_args(&args(&["--export", ID])).map(|a| a.action),
+            Ok(CliAction::Export {
+                profile_id: profile_id(),
+                format: profile_utils::ProfileExportFormat::NoriskPack,
+                output: None,
+                include: Vec::new(),
+            })
+        );
+        // Options may come before the command
+        assert_eq!(
+            parse_cli_args(&args(&[
+                "--format",
+                "MRPACK",
+                "--include",
+                "config",
+                "--export",
+                ID,
+                "--output",
+                "pack.mrpack",
+                "--include",
+                "options.txt",
+            ]))
+            .map(|a| a.action),
+            Ok(CliAction::Export {
+                profile_id: profile_id(),
+                format: profile_utils::ProfileExportFormat::Mrpack,
+                output: Some(PathBuf::from("pack.mrpack")),
+                include: vec![PathBuf::from("config"), PathBuf::from("options.txt")],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |a: &[&str]| parse_cli_args(&args(a)).unwrap_err();
+
+        assert_eq!(error(&["--launch"]), "--launch requires a value");
+        assert_eq!(
+            error(&["--launch", "not-a-uuid"]),
+            "'not-a-uuid' is not a valid profile id"
+        );
+        assert_eq!(
+            error(&["--list-profiles", "--launch", ID]),
+            "Only one command can be given at a time"
+        );
+        assert_eq!(
+            error(&["--launch", ID, "--verbose"]),
+            "Unknown argument '--verbose'"
+        );
+        assert_eq!(
+            error(&["--export", ID, "--format", "zip"]),
+            "Unknown export format 'zip'"
+        );
+        assert_eq!(
+            error(&["--install", ID, "--output", "out.zip"]),
+            "--format, --output and --include can only be used with --export"
+        );
+        assert_eq!(
+            error(&["--launch", ID, "--format", "mrpack"]),
+            "--format, --output and --include can only be used with --export"
+        );
+        assert_eq!(error(&["--json"]), "No command given");
+        assert!(matches!(
+            parse_args(args(&["--help", "--bogus"])),
+            Some(Err(_))
+        ));
+    }
+}
@@ -2,6 +2,7 @@ use crate::error::{AppError, CommandError};
 use crate::minecraft::api::cape_api::{CapeApi, CapesBrowseResponse, CosmeticCape};
 use crate::minecraft::api::mc_api::MinecraftApiService;
 use crate::state::state_manager::State;
+use crate::utils::endpoint_registry::{self, Endpoint};
 use log::{debug, error};
 use serde::Deserialize;
 use std::path::PathBuf;
@@ -741,11 +742,11 @@ pub async fn download_template_and_open_explorer(
     debug!("Using experimental mode: {}", is_experimental);
 
     // Set template URL based on experimental mode
-    let template_url = if is_experimental {
-        "https://cdn.norisk.gg/capes-staging/template.png"
-    } else {
-        "https://cdn.norisk.gg/capes/template.png"
-    };
+    let template_url = format!(
+        "{}/{}/template.png",
+        endpoint_registry::base_url(Endpoint::NoriskCdn),
+        if is_experimental { "capes-staging" } else { "capes" }
+    );
     debug!("Template URL: {}", template_url);
 
     // Get user's download directory
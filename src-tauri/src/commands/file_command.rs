@@ -1,6 +1,6 @@
 use crate::error::{AppError, CommandError};
 use crate::integrations::norisk_packs::NoriskModEntryDefinition;
-use crate::utils::file_utils;
+use crate::utils::jar_inspector;
 use crate::utils::path_utils;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use image::ImageEncoder;
@@ -262,6 +262,7 @@ pub async fn open_file_directory(
 }
 
 /// Fetches the first PNG icon found within a list of archive files (.zip, .jar) as Base64 strings.
+/// Mod jars use the icon their metadata declares and are cached by sha1.
 ///
 /// # Arguments
 ///
@@ -281,7 +282,7 @@ pub async fn get_icons_for_archives(
 
     for path_str in archive_paths {
         let archive_path = Path::new(&path_str);
-        let result = file_utils::find_first_png_in_archive_as_base64(archive_path).await;
+        let result = jar_inspector::find_archive_icon_as_base64(archive_path).await;
 
         match result {
             Ok(base64_icon) => {
@@ -354,7 +355,7 @@ pub async fn get_icons_for_norisk_mods(
     // Extrahiere Icons für jeden Mod aus dem Cache
     for (mod_id, path_str) in mod_paths {
         let archive_path = Path::new(&path_str);
-        let result = file_utils::find_first_png_in_archive_as_base64(archive_path).await;
+        let result = jar_inspector::find_archive_icon_as_base64(archive_path).await;
 
         match result {
             Ok(base64_icon) => {
@@ -1,10 +1,16 @@
 use crate::error::CommandError;
+use crate::minecraft::downloads::java_runtime_manager::{
+    self, InstalledJavaRuntime, JavaRuntimeUpgrade,
+};
+use crate::minecraft::java_requirements;
+use crate::state::state_manager::State;
 use crate::utils::java_detector::{
     detect_java_installations, find_best_java_for_minecraft, get_java_info, invalidate_java_cache,
     JavaInstallation,
 };
 use log::info;
 use std::path::PathBuf;
+use uuid::Uuid;
 
 /// Detects all Java installations on the system
 #[tauri::command]
@@ -21,16 +27,26 @@ pub async fn get_java_info_command(path: String) -> Result<JavaInstallation, Com
     Ok(get_java_info(&java_path).await?)
 }
 
-/// Finds the best Java installation for the given Minecraft version
+/// Finds the best Java installation for the given Minecraft version,
+/// also honoring the Java requirements of the profile's mods if a profile is given
 #[tauri::command]
 pub async fn find_best_java_for_minecraft_command(
     minecraft_version: String,
+    profile_id: Option<Uuid>,
 ) -> Result<Option<JavaInstallation>, CommandError> {
     info!(
         "Command: Finding best Java for Minecraft version: {}",
         minecraft_version
     );
-    Ok(find_best_java_for_minecraft(&minecraft_version).await?)
+    let mod_requirement = match profile_id {
+        Some(profile_id) => {
+            let state = State::get().await?;
+            let profile = state.profile_manager.get_profile(profile_id).await?;
+            Some(java_requirements::read_profile_mod_java_requirement(&profile).await?)
+        }
+        None => None,
+    };
+    Ok(find_best_java_for_minecraft(&minecraft_version, mod_requirement.as_ref()).await?)
 }
 
 /// Invalidates the Java installation cache, forcing a fresh scan on the next query
@@ -69,3 +85,33 @@ pub async fn validate_java_path_command(path: String) -> Result<bool, CommandErr
         }
     }
 }
+
+/// Lists the Java runtimes the launcher downloaded, with the profiles that use them
+#[tauri::command]
+pub async fn list_java_runtimes_command() -> Result<Vec<InstalledJavaRuntime>, CommandError> {
+    info!("Command: Listing downloaded Java runtimes");
+    Ok(java_runtime_manager::list_installed_runtimes().await?)
+}
+
+/// Removes a downloaded Java runtime, fails if a profile or running game uses it
+#[tauri::command]
+pub async fn uninstall_java_runtime_command(runtime_id: String) -> Result<(), CommandError> {
+    info!("Command: Uninstalling Java runtime {}", runtime_id);
+    Ok(java_runtime_manager::uninstall_runtime(&runtime_id).await?)
+}
+
+/// Removes all downloaded Java runtimes no profile uses and returns their ids
+#[tauri::command]
+pub async fn prune_java_runtimes_command() -> Result<Vec<String>, CommandError> {
+    info!("Command: Pruning unused Java runtimes");
+    Ok(java_runtime_manager::prune_runtimes().await?)
+}
+
+/// Upgrades a downloaded Java runtime to the newest patch release of its major version
+#[tauri::command]
+pub async fn upgrade_java_runtime_command(
+    runtime_id: String,
+) -> Result<JavaRuntimeUpgrade, CommandError> {
+    info!("Command: Upgrading Java runtime {}", runtime_id);
+    Ok(java_runtime_manager::upgrade_runtime(&runtime_id).await?)
+}
@@ -13,6 +13,7 @@ use crate::minecraft::dto::VersionManifest;
 use crate::state::skin_state::MinecraftSkin;
 use crate::state::state_manager::State;
 use crate::utils::mc_utils;
+use crate::utils::redaction_utils::{RedactionPreview, Redactor};
 use log::{debug, error, info};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -63,6 +64,13 @@ pub async fn upload_log_to_mclogs_command(log_content: String) -> Result<String,
     result
 }
 
+/// Shows what `upload_log_to_mclogs_command` would upload for this content
+#[tauri::command]
+pub async fn preview_log_redaction(log_content: String) -> Result<RedactionPreview, CommandError> {
+    let redactor = Redactor::from_config().await?;
+    Ok(redactor.preview(&log_content))
+}
+
 #[tauri::command]
 pub async fn get_fabric_loader_versions(
     minecraft_version: String,
@@ -40,8 +40,8 @@ pub async fn discord_auth_link(app: AppHandle) -> Result<(), CommandError> {
     let token = norisk_creds.get_token_for_mode(is_experimental)?;
 
     let url_string = format!(
-        "https://api{}.norisk.gg/api/v1/core/oauth/discord?token={}",
-        if is_experimental { "-staging" } else { "" },
+        "{}/core/oauth/discord?token={}",
+        NoRiskApi::get_api_base(is_experimental),
         token
     );
     debug!("Generated Discord auth URL string: {}", url_string);
@@ -91,6 +91,9 @@ pub async fn discord_auth_link(app: AppHandle) -> Result<(), CommandError> {
         })?;
     debug!("Discord sign-in window opened.");
 
+    let complete_url_prefixes = [false, true]
+        .map(|experimental| format!("{}/core/oauth/discord/complete", NoRiskApi::get_api_base(experimental)));
+
     while (Utc::now() - start_time) < ChronoDuration::minutes(10) {
         match window.url().map_err(|e| {
             CommandError::from(AppError::Other(format!(
@@ -100,11 +103,9 @@ pub async fn discord_auth_link(app: AppHandle) -> Result<(), CommandError> {
         }) {
             Ok(current_url) => {
                 let current_url_str = current_url.as_str();
-                if current_url_str
-                    .starts_with("https://api.norisk.gg/api/v1/core/oauth/discord/complete")
-                    || current_url_str.starts_with(
-                        "https://api-staging.norisk.gg/api/v1/core/oauth/discord/complete",
-                    )
+                if complete_url_prefixes
+                    .iter()
+                    .any(|prefix| current_url_str.starts_with(prefix.as_str()))
                 {
                     debug!("Discord authentication successful, closing window.");
                     tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
@@ -1,4 +1,9 @@
 use crate::error::CommandError;
+use crate::minecraft::crash_analyzer::{self, CrashDiagnosis};
+use crate::state::playtime_tracker::{
+    PlaySession, ProfilePlaytime, TargetPlaytime, WeeklyPlaytime,
+};
+use crate::state::process_metrics::{ResourceSample, SessionRecord};
 use crate::state::process_state::ProcessMetadata;
 use crate::state::state_manager::State;
 use tauri::Manager;
@@ -30,13 +35,82 @@ pub async fn get_processes_by_profile(
     Ok(processes)
 }
 
+/// Stops a game gracefully (see `ProcessManager::stop_process`), `force` kills it right away
 #[tauri::command]
-pub async fn stop_process(process_id: Uuid) -> Result<(), CommandError> {
+pub async fn stop_process(process_id: Uuid, force: Option<bool>) -> Result<(), CommandError> {
     let state = State::get().await?;
-    state.process_manager.stop_process(process_id).await?;
+    state
+        .process_manager
+        .stop_process(process_id, force.unwrap_or(false))
+        .await?;
     Ok(())
 }
 
+/// Runs the crash analyzer for a profile on demand, e.g. for a crash report picked in the UI.
+/// `latest.log` of the profile is always included.
+#[tauri::command]
+pub async fn analyze_profile_crash(
+    profile_id: Uuid,
+    crash_report_content: Option<String>,
+) -> Result<Vec<CrashDiagnosis>, CommandError> {
+    let state = State::get().await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+    Ok(crash_analyzer::analyze_instance_crash(&instance_path, crash_report_content.as_deref()).await)
+}
+
+/// CPU, memory and thread samples of a running process (and its children), oldest first
+#[tauri::command]
+pub async fn get_process_resource_samples(
+    process_id: Uuid,
+) -> Result<Vec<ResourceSample>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.process_manager.get_resource_samples(process_id))
+}
+
+/// Summaries of finished game sessions, newest first
+#[tauri::command]
+pub async fn get_session_history(
+    profile_id: Option<Uuid>,
+) -> Result<Vec<SessionRecord>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.process_manager.get_session_history(profile_id).await?)
+}
+
+/// Recorded play sessions with their world/server segments, newest first
+#[tauri::command]
+pub async fn get_play_sessions(profile_id: Option<Uuid>) -> Result<Vec<PlaySession>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.process_manager.playtime().get_sessions(profile_id).await)
+}
+
+/// Total playtime of every profile, most played first
+#[tauri::command]
+pub async fn get_playtime_per_profile() -> Result<Vec<ProfilePlaytime>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.process_manager.playtime().per_profile().await)
+}
+
+/// Playtime per week (weeks start on Monday, UTC), oldest first
+#[tauri::command]
+pub async fn get_playtime_per_week(
+    profile_id: Option<Uuid>,
+) -> Result<Vec<WeeklyPlaytime>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.process_manager.playtime().per_week(profile_id).await)
+}
+
+/// Playtime per server and singleplayer world, most played first
+#[tauri::command]
+pub async fn get_playtime_per_server(
+    profile_id: Option<Uuid>,
+) -> Result<Vec<TargetPlaytime>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.process_manager.playtime().per_target(profile_id).await)
+}
+
 #[tauri::command]
 pub async fn get_full_log(process_id: Uuid) -> Result<String, CommandError> {
     let state = State::get().await?;
@@ -1,15 +1,21 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, CommandError};
+use crate::integrations::curseforge::CurseForgeImport;
 use crate::integrations::modrinth::ModrinthVersion;
+use crate::integrations::modrinth_resolver::{self, ModrinthApiProvider, ModrinthInstallPlan};
 use crate::integrations::mrpack;
 use crate::integrations::norisk_packs::NoriskModpacksConfig;
 use crate::integrations::norisk_versions::NoriskVersionsConfig;
 use crate::minecraft::installer;
+use crate::minecraft::log_parser::{self, LogFilter, LogPage, LogSearchPage};
+use crate::minecraft::offline::OfflineReport;
+use crate::minecraft::LaunchCommandFormat;
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::{
     default_profile_path, CustomModInfo, ModLoader, Profile, ProfileSettings, ProfileState,
 };
 use crate::state::state_manager::State;
+use crate::utils::bulk_update_utils::{self, ProfileUpdatePlan, UpdateSnapshot};
 use crate::utils::datapack_utils::DataPackInfo;
 use crate::utils::mc_utils::{self, WorldInfo};
 use crate::utils::path_utils::find_unique_profile_segment;
@@ -20,6 +26,9 @@ use crate::utils::profile_utils::{
 };
 use crate::utils::resourcepack_utils::ResourcePackInfo;
 use crate::utils::shaderpack_utils::ShaderPackInfo;
+use crate::utils::snapshot_utils::{
+    self, ProfileSnapshotSummary, SnapshotDiff, SnapshotReason, SnapshotRestoreReport,
+};
 use crate::utils::world_utils;
 use crate::utils::{
     datapack_utils, path_utils, profile_utils, repair_utils, resourcepack_utils, shaderpack_utils,
@@ -76,6 +85,8 @@ pub struct ExportProfileParams {
     file_name: String,           // Base name without extension
     include_files: Option<Vec<PathBuf>>,
     open_folder: bool, // Whether to open the exports folder after export
+    #[serde(default)]
+    format: profile_utils::ProfileExportFormat, // Defaults to .noriskpack
 }
 
 // DTO for the new command
@@ -267,6 +278,15 @@ pub async fn launch_profile(
 
     // Spawn the installation task and get the JoinHandle
     let handle = tokio::spawn(async move {
+        // Restore point before the launch, only taken when the profile config changed. Hashing the
+        // instance can take a while, so it runs here instead of blocking the command.
+        if let Err(e) = snapshot_utils::snapshot_before_launch(&profile_clone).await {
+            warn!(
+                "Failed to create automatic snapshot for profile {}: {}",
+                profile_id, e
+            );
+        }
+
         let install_result = installer::install_minecraft_version(
             &version,
             &modloader.as_str(),
@@ -346,6 +366,133 @@ pub async fn launch_profile(
     Ok(())
 }
 
+/// Checks whether a profile can be installed and launched without network access and
+/// lists every artifact that is missing from the local cache.
+#[tauri::command]
+pub async fn check_profile_offline_readiness(
+    profile_id: Uuid,
+) -> Result<OfflineReport, CommandError> {
+    info!(
+        "[Command] check_profile_offline_readiness called for ID: {}",
+        profile_id
+    );
+
+    let state = State::get().await?;
+    let profile = match state.profile_manager.get_profile(profile_id).await {
+        Ok(profile) => profile,
+        Err(_) => state
+            .norisk_version_manager
+            .get_config()
+            .await
+            .profiles
+            .iter()
+            .find(|p| p.id == profile_id)
+            .cloned()
+            .ok_or(AppError::ProfileNotFound(profile_id))?,
+    };
+
+    // Credentials are only needed to check the NoRisk assets of the selected pack
+    let credentials = state
+        .minecraft_account_manager_v2
+        .get_active_account()
+        .await
+        .ok()
+        .flatten();
+
+    let report = installer::check_offline_readiness(&profile, credentials).await?;
+    info!(
+        "Offline check for profile '{}' finished: {} missing artifact(s)",
+        profile.name,
+        report.missing.len()
+    );
+    Ok(report)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExportLaunchCommandParams {
+    profile_id: Uuid,
+    #[serde(default)]
+    format: LaunchCommandFormat,
+    /// Keeps the access token and NoRisk token in the output instead of placeholders
+    #[serde(default)]
+    include_secrets: bool,
+    quick_play_singleplayer: Option<String>,
+    quick_play_multiplayer: Option<String>,
+}
+
+/// Installs a profile and writes the command it would be launched with to the exports folder,
+/// without starting the game. Earlier exports are kept, a new one gets a numbered name.
+/// Returns the path of the written file.
+#[tauri::command]
+pub async fn export_launch_command(
+    params: ExportLaunchCommandParams,
+) -> Result<String, CommandError> {
+    info!(
+        "Executing export_launch_command for profile {} (format: {:?}, secrets: {})",
+        params.profile_id, params.format, params.include_secrets
+    );
+
+    let state = State::get().await?;
+    let profile = match state.profile_manager.get_profile(params.profile_id).await {
+        Ok(profile) => profile,
+        Err(_) => state
+            .norisk_version_manager
+            .get_profile_by_id(params.profile_id)
+            .await
+            .ok_or(AppError::ProfileNotFound(params.profile_id))?,
+    };
+
+    let credentials = state
+        .minecraft_account_manager_v2
+        .get_active_account()
+        .await
+        .ok()
+        .flatten();
+
+    let mut command = installer::resolve_launch_command(
+        &profile,
+        credentials,
+        params.quick_play_singleplayer,
+        params.quick_play_multiplayer,
+    )
+    .await?;
+    if !params.include_secrets {
+        command = command.redacted();
+    }
+    let content = command.render(params.format)?;
+
+    let exports_dir = LAUNCHER_DIRECTORY.root_dir().join("exports");
+    TokioFs::create_dir_all(&exports_dir)
+        .await
+        .map_err(|e| CommandError::from(AppError::Io(e)))?;
+    // Never overwrite an earlier export (the user may have edited it), number the new one instead
+    let stem = format!("{}-launch", sanitize(&profile.name));
+    let extension = params.format.extension();
+    let mut export_path = exports_dir.join(format!("{}.{}", stem, extension));
+    let mut suffix = 1;
+    while TokioFs::try_exists(&export_path)
+        .await
+        .map_err(|e| CommandError::from(AppError::Io(e)))?
+    {
+        export_path = exports_dir.join(format!("{} ({}).{}", stem, suffix, extension));
+        suffix += 1;
+    }
+    TokioFs::write(&export_path, content)
+        .await
+        .map_err(|e| CommandError::from(AppError::Io(e)))?;
+
+    #[cfg(unix)]
+    if params.format == LaunchCommandFormat::Shell {
+        use std::os::unix::fs::PermissionsExt;
+        TokioFs::set_permissions(&export_path, std::fs::Permissions::from_mode(0o755))
+            .await
+            .map_err(|e| CommandError::from(AppError::Io(e)))?;
+    }
+
+    info!("Exported launch command to {}", export_path.display());
+    Ok(export_path.to_string_lossy().to_string())
+}
+
 /// Aborts an ongoing launch process for a profile.
 /// This is useful to cancel a profile installation/launch that's taking too long.
 #[tauri::command]
@@ -563,6 +710,151 @@ pub async fn add_modrinth_mod_to_profile(
         .await?)
 }
 
+/// Resolves all dependencies of a Modrinth version for a profile without changing it.
+/// The returned plan lists the mods to add, unresolved dependencies and conflicts.
+#[tauri::command]
+pub async fn resolve_modrinth_install_plan(
+    profile_id: Uuid,
+    version_id: String,
+) -> Result<ModrinthInstallPlan, CommandError> {
+    info!(
+        "Executing resolve_modrinth_install_plan command for profile {} (version {})",
+        profile_id, version_id
+    );
+
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    Ok(modrinth_resolver::resolve_install_plan(&ModrinthApiProvider, &profile, &version_id).await?)
+}
+
+/// Applies a plan returned by `resolve_modrinth_install_plan` after the user confirmed it.
+#[tauri::command]
+pub async fn apply_modrinth_install_plan(
+    profile_id: Uuid,
+    plan: ModrinthInstallPlan,
+) -> Result<usize, CommandError> {
+    info!(
+        "Executing apply_modrinth_install_plan command for profile {} ({} mods)",
+        profile_id,
+        plan.mods.len()
+    );
+
+    Ok(State::get()
+        .await?
+        .profile_manager
+        .apply_modrinth_install_plan(profile_id, &plan)
+        .await?)
+}
+
+/// Collects available updates for every Modrinth mod, resource pack, shader pack and data pack
+/// of a profile, including the changelog of each new version.
+#[tauri::command]
+pub async fn check_profile_updates(profile_id: Uuid) -> Result<ProfileUpdatePlan, CommandError> {
+    info!("Executing check_profile_updates command for profile {}", profile_id);
+
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    Ok(bulk_update_utils::check_profile_updates(&profile).await?)
+}
+
+/// Applies the (possibly filtered) plan from `check_profile_updates` in one go and returns the
+/// snapshot that can be rolled back.
+#[tauri::command]
+pub async fn apply_profile_updates(
+    plan: ProfileUpdatePlan,
+) -> Result<UpdateSnapshot, CommandError> {
+    info!(
+        "Executing apply_profile_updates command for profile {} ({} updates)",
+        plan.profile_id,
+        plan.updates.len()
+    );
+
+    Ok(bulk_update_utils::apply_profile_updates(&plan).await?)
+}
+
+/// Undoes an "update all". Without `snapshot_id` the most recent update is rolled back.
+#[tauri::command]
+pub async fn rollback_profile_update(
+    profile_id: Uuid,
+    snapshot_id: Option<Uuid>,
+) -> Result<UpdateSnapshot, CommandError> {
+    info!(
+        "Executing rollback_profile_update command for profile {} (snapshot {:?})",
+        profile_id, snapshot_id
+    );
+
+    Ok(bulk_update_utils::rollback_profile_update(profile_id, snapshot_id).await?)
+}
+
+#[tauri::command]
+pub async fn list_profile_update_snapshots(
+    profile_id: Uuid,
+) -> Result<Vec<UpdateSnapshot>, CommandError> {
+    Ok(bulk_update_utils::list_update_snapshots(profile_id).await?)
+}
+
+/// Stores the profile configuration and its mods, config, resource pack and shader pack
+/// folders (optionally saves) as a restore point.
+#[tauri::command]
+pub async fn create_profile_snapshot(
+    profile_id: Uuid,
+    label: Option<String>,
+    include_saves: Option<bool>,
+) -> Result<ProfileSnapshotSummary, CommandError> {
+    info!("Executing create_profile_snapshot command for profile {}", profile_id);
+
+    Ok(snapshot_utils::create_snapshot(
+        profile_id,
+        label,
+        SnapshotReason::Manual,
+        include_saves.unwrap_or(false),
+    )
+    .await?)
+}
+
+#[tauri::command]
+pub async fn list_profile_snapshots(
+    profile_id: Uuid,
+) -> Result<Vec<ProfileSnapshotSummary>, CommandError> {
+    Ok(snapshot_utils::list_snapshots(profile_id).await?)
+}
+
+/// Compares snapshot `from` with snapshot `to`, or with the current profile state if `to` is omitted.
+#[tauri::command]
+pub async fn diff_profile_snapshots(
+    profile_id: Uuid,
+    from: Uuid,
+    to: Option<Uuid>,
+) -> Result<SnapshotDiff, CommandError> {
+    Ok(snapshot_utils::diff_snapshots(profile_id, from, to).await?)
+}
+
+#[tauri::command]
+pub async fn restore_profile_snapshot(
+    profile_id: Uuid,
+    snapshot_id: Uuid,
+) -> Result<SnapshotRestoreReport, CommandError> {
+    info!(
+        "Executing restore_profile_snapshot command for profile {} (snapshot {})",
+        profile_id, snapshot_id
+    );
+
+    Ok(snapshot_utils::restore_snapshot(profile_id, snapshot_id).await?)
+}
+
+#[tauri::command]
+pub async fn delete_profile_snapshot(
+    profile_id: Uuid,
+    snapshot_id: Uuid,
+) -> Result<(), CommandError> {
+    info!(
+        "Executing delete_profile_snapshot command for profile {} (snapshot {})",
+        profile_id, snapshot_id
+    );
+
+    Ok(snapshot_utils::delete_snapshot(profile_id, snapshot_id).await?)
+}
+
 #[tauri::command]
 pub async fn list_profiles() -> Result<Vec<Profile>, CommandError> {
     let state = State::get().await?;
@@ -913,8 +1205,8 @@ pub async fn import_profile_from_file(app_handle: tauri::AppHandle) -> Result<()
         app_handle
             .dialog()
             .file()
-            .add_filter("Modpack Files", &["mrpack", "noriskpack"])
-            .set_title("Select Modpack File (.mrpack or .noriskpack)")
+            .add_filter("Modpack Files", &["mrpack", "noriskpack", "zip"])
+            .set_title("Select Modpack File (.mrpack, .noriskpack or CurseForge .zip)")
             .blocking_pick_file() // Use the blocking version for single file selection
     })
     .await
@@ -953,13 +1245,21 @@ pub async fn import_profile_from_file(app_handle: tauri::AppHandle) -> Result<()
                 crate::integrations::norisk_packs::import_noriskpack_as_profile(file_path_buf)
                     .await?
             }
+            Some("zip") => {
+                log::info!("File extension is .zip, proceeding with CurseForge pack processing.");
+                let import =
+                    crate::integrations::curseforge::import_curseforge_pack_as_profile(file_path_buf)
+                        .await?;
+                report_curseforge_import_failures(&import).await;
+                import.profile_id
+            }
             _ => {
                 log::error!(
                     "Selected file has an invalid extension: {:?}",
                     file_path_buf
                 );
                 return Err(CommandError::from(AppError::Other(
-                    "Invalid file type selected. Please select a .mrpack or .noriskpack file."
+                    "Invalid file type selected. Please select a .mrpack, .noriskpack or CurseForge .zip file."
                         .to_string(),
                 )));
             }
@@ -987,7 +1287,40 @@ pub async fn import_profile_from_file(app_handle: tauri::AppHandle) -> Result<()
     }
 }
 
+/// Lets the user pick a Prism Launcher / MultiMC instance folder and imports it as a profile.
+#[tauri::command]
+pub async fn import_instance_from_folder(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<Uuid>, CommandError> {
+    log::info!("Executing import_instance_from_folder command");
+
+    let dialog_result = tokio::task::spawn_blocking(move || {
+        app_handle
+            .dialog()
+            .file()
+            .set_title("Select Prism Launcher / MultiMC Instance Folder")
+            .blocking_pick_folder()
+    })
+    .await
+    .map_err(|e| CommandError::from(AppError::Other(format!("Dialog task failed: {}", e))))?;
+
+    let Some(folder_path_obj) = dialog_result else {
+        log::info!("User cancelled the instance folder dialog.");
+        return Ok(None);
+    };
+    let folder_path = folder_path_obj.into_path().map_err(|e| {
+        log::error!("Failed to convert selected folder path: {}", e);
+        CommandError::from(AppError::Other(
+            "Failed to convert selected folder path".to_string(),
+        ))
+    })?;
+
+    let new_profile_id = import_profile(folder_path.to_string_lossy().to_string()).await?;
+    Ok(Some(new_profile_id))
+}
+
 /// Imports a profile from a specified file path.
+/// Directories are treated as Prism Launcher / MultiMC instances.
 #[tauri::command]
 pub async fn import_profile(file_path_str: String) -> Result<Uuid, CommandError> {
     log::info!(
@@ -1005,6 +1338,35 @@ pub async fn import_profile(file_path_str: String) -> Result<Uuid, CommandError>
         ))));
     }
 
+    // Prism Launcher / MultiMC instances are imported from their directory
+    if file_path_buf.is_dir() {
+        if !crate::integrations::prism::is_prism_instance(&file_path_buf) {
+            log::error!(
+                "Directory is not a Prism/MultiMC instance: {:?}",
+                file_path_buf
+            );
+            return Err(CommandError::from(AppError::Other(format!(
+                "Directory {} is not a Prism Launcher or MultiMC instance (instance.cfg/mmc-pack.json missing).",
+                file_path_buf.display()
+            ))));
+        }
+        let new_profile_id =
+            crate::integrations::prism::import_prism_instance(file_path_buf).await?;
+        let state = State::get().await?;
+        if let Err(e) = state
+            .event_state
+            .trigger_profile_update(new_profile_id)
+            .await
+        {
+            log::error!(
+                "Failed to emit TriggerProfileUpdate event for new profile {}: {}",
+                new_profile_id,
+                e
+            );
+        }
+        return Ok(new_profile_id);
+    }
+
     log::info!(
         "Processing modpack file: {:?}. Triggering processing...",
         file_path_buf
@@ -1025,13 +1387,21 @@ pub async fn import_profile(file_path_str: String) -> Result<Uuid, CommandError>
             log::info!("File extension is .noriskpack, proceeding with noriskpack processing.");
             crate::integrations::norisk_packs::import_noriskpack_as_profile(file_path_buf).await?
         }
+        Some("zip") => {
+            log::info!("File extension is .zip, proceeding with CurseForge pack processing.");
+            let import =
+                crate::integrations::curseforge::import_curseforge_pack_as_profile(file_path_buf)
+                    .await?;
+            report_curseforge_import_failures(&import).await;
+            import.profile_id
+        }
         _ => {
             log::error!(
                 "Selected file has an invalid extension: {:?}",
                 file_path_buf
             );
             return Err(CommandError::from(AppError::Other(
-                "Invalid file type selected. Please select a .mrpack or .noriskpack file."
+                "Invalid file type selected. Please select a .mrpack, .noriskpack or CurseForge .zip file."
                     .to_string(),
             )));
         }
@@ -1055,6 +1425,47 @@ pub async fn import_profile(file_path_str: String) -> Result<Uuid, CommandError>
     Ok(new_profile_id)
 }
 
+/// Tells the frontend which files of an imported CurseForge pack could not be downloaded
+async fn report_curseforge_import_failures(import: &CurseForgeImport) {
+    if import.failed_files.is_empty() {
+        return;
+    }
+    let file_names: Vec<&str> = import
+        .failed_files
+        .iter()
+        .map(|f| f.file_name.as_str())
+        .collect();
+    let message = format!(
+        "{} file(s) of the CurseForge pack could not be downloaded: {}",
+        file_names.len(),
+        file_names.join(", ")
+    );
+    warn!("Profile {}: {}", import.profile_id, message);
+
+    let event_payload = EventPayload {
+        event_id: Uuid::new_v4(),
+        event_type: EventType::Error,
+        target_id: Some(import.profile_id),
+        message: message.clone(),
+        progress: None,
+        error: Some(message),
+    };
+    match State::get().await {
+        Ok(state) => {
+            if let Err(e) = state.emit_event(event_payload).await {
+                error!(
+                    "Failed to emit CurseForge import error event for profile {}: {}",
+                    import.profile_id, e
+                );
+            }
+        }
+        Err(e) => error!(
+            "Failed to get state to report CurseForge import errors: {}",
+            e
+        ),
+    }
+}
+
 // Command to get all resourcepacks in a profile
 #[tauri::command]
 pub async fn get_local_resourcepacks(
@@ -1366,15 +1777,15 @@ pub async fn copy_profile(params: CopyProfileParams) -> Result<Uuid, CommandErro
     Ok(new_profile_id)
 }
 
-/// Exports a profile to a .noriskpack file format with a fixed export directory
+/// Exports a profile to a .noriskpack or .mrpack file with a fixed export directory
 #[tauri::command]
 pub async fn export_profile(
     app_handle: tauri::AppHandle,
     params: ExportProfileParams,
 ) -> Result<String, CommandError> {
     info!(
-        "Executing export_profile command for profile {}",
-        params.profile_id
+        "Executing export_profile command for profile {} (format: {:?})",
+        params.profile_id, params.format
     );
 
     // Ensure the exports directory exists
@@ -1383,7 +1794,7 @@ pub async fn export_profile(
         .await
         .map_err(|e| CommandError::from(AppError::Io(e)))?;
 
-    // Sanitize the filename and add .noriskpack extension
+    // Sanitize the filename and add the extension of the selected format
     let sanitized_name = sanitize(&params.file_name);
     if sanitized_name.is_empty() {
         return Err(CommandError::from(AppError::Other(
@@ -1392,20 +1803,32 @@ pub async fn export_profile(
     }
 
     // Generate complete filename with extension
-    let noriskpack_filename = format!("{}.noriskpack", sanitized_name);
+    let export_filename = format!("{}.{}", sanitized_name, params.format.extension());
 
     // Create full export path
-    let export_path = exports_dir.join(&noriskpack_filename);
+    let export_path = exports_dir.join(&export_filename);
 
     info!("Exporting profile to {}", export_path.display());
 
     // Perform the export
-    let result_path = profile_utils::export_profile_to_noriskpack(
-        params.profile_id,
-        Some(export_path.clone()),
-        params.include_files,
-    )
-    .await?;
+    let result_path = match params.format {
+        profile_utils::ProfileExportFormat::NoriskPack => {
+            profile_utils::export_profile_to_noriskpack(
+                params.profile_id,
+                Some(export_path.clone()),
+                params.include_files,
+            )
+            .await?
+        }
+        profile_utils::ProfileExportFormat::Mrpack => {
+            mrpack::export_profile_to_mrpack(
+                params.profile_id,
+                export_path.clone(),
+                params.include_files,
+            )
+            .await?
+        }
+    };
 
     // Open the export directory if requested
     if params.open_folder {
@@ -1666,6 +2089,33 @@ pub async fn get_log_file_content(log_file_path: PathBuf) -> Result<String, Comm
     Ok(crate::utils::file_utils::read_log_file_content(&log_file_path).await?)
 }
 
+/// Parses a log file (.log or .log.gz) into log4j records and returns one filtered page.
+#[tauri::command]
+pub async fn query_log_file(
+    log_file_path: PathBuf,
+    filter: Option<LogFilter>,
+) -> Result<LogPage, CommandError> {
+    info!(
+        "Executing query_log_file command for file: {}",
+        log_file_path.display()
+    );
+    let filter = filter.unwrap_or_default();
+    Ok(log_parser::query_log_file(&log_file_path, &filter).await?)
+}
+
+/// Searches all log files of a profile, including rotated .log.gz files, newest first.
+#[tauri::command]
+pub async fn search_profile_logs(
+    profile_id: Uuid,
+    filter: LogFilter,
+) -> Result<LogSearchPage, CommandError> {
+    info!(
+        "Executing search_profile_logs command for profile {}",
+        profile_id
+    );
+    Ok(log_parser::search_profile_logs(profile_id, &filter).await?)
+}
+
 #[tauri::command]
 pub async fn get_worlds_for_profile(profile_id: Uuid) -> Result<Vec<WorldInfo>, CommandError> {
     info!(
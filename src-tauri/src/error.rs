@@ -34,6 +34,9 @@ pub enum AppError {
     #[error("Java download error: {0}")]
     JavaDownload(String),
 
+    #[error("Java requirement not met: {0}")]
+    JavaRequirementNotMet(String),
+
     #[error("Version not found: {0}")]
     VersionNotFound(String),
 
@@ -127,6 +130,9 @@ pub enum AppError {
     #[error("Mrpack error: {0}")]
     MrpackError(String),
 
+    #[error("CurseForge error: {0}")]
+    CurseForgeError(String),
+
     #[error("Unsupported OS: {0}")]
     UnsupportedOS(String),
 
@@ -185,6 +191,12 @@ pub enum AppError {
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
 
+    #[error("Not available offline: {0}")]
+    OfflineArtifactMissing(String),
+
+    #[error("{0}")]
+    OfflineNotReady(String),
+
     #[error("Insufficient disk space on {path:?}. Required: {required_mb} MB, Available: {available_mb} MB, Shortfall: {shortfall_mb} MB")]
     InsufficientDiskSpace {
         path: std::path::PathBuf,
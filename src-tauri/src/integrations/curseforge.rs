@@ -0,0 +1,685 @@
+use crate::error::{AppError, Result};
+use crate::integrations::mrpack;
+use crate::state::profile_state::{
+    Mod, ModLoader, ModSource, Profile, ProfileSettings, ProfileState,
+};
+use crate::state::state_manager::State;
+use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::utils::endpoint_registry::{self, Endpoint};
+use async_trait::async_trait;
+use async_zip::tokio::read::seek::ZipFileReader;
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use reqwest::{Client, Response};
+use sanitize_filename::sanitize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::fs::File;
+use tokio::io::BufReader;
+use uuid::Uuid;
+
+const CURSEFORGE_API_KEY_ENV: &str = "CURSEFORGE_API_KEY";
+const CURSEFORGE_MANIFEST_FILE: &str = "manifest.json";
+
+// CurseForge hash algorithm ids (see HashAlgo in the CurseForge API docs)
+const CURSEFORGE_HASH_ALGO_SHA1: u32 = 1;
+
+// Minecraft project class ids (`classId` of a mod in the CurseForge API)
+pub const CURSEFORGE_CLASS_MODS: u32 = 6;
+pub const CURSEFORGE_CLASS_RESOURCE_PACKS: u32 = 12;
+pub const CURSEFORGE_CLASS_SHADERS: u32 = 6552;
+pub const CURSEFORGE_CLASS_DATA_PACKS: u32 = 6945;
+
+/// Represents the manifest.json at the root of a CurseForge modpack export.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeManifest {
+    pub minecraft: CurseForgeMinecraft,
+    #[serde(default)]
+    pub manifest_type: Option<String>, // "minecraftModpack"
+    #[serde(default)]
+    pub manifest_version: Option<u32>, // Usually 1
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub files: Vec<CurseForgeManifestFile>,
+    #[serde(default = "default_overrides_dir")]
+    pub overrides: String, // Directory inside the archive, usually "overrides"
+}
+
+fn default_overrides_dir() -> String {
+    "overrides".to_string()
+}
+
+fn default_required() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeModLoader {
+    pub id: String, // e.g. "forge-47.2.0", "fabric-0.15.7", "neoforge-20.4.80"
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u32,
+    #[serde(rename = "fileID")]
+    pub file_id: u32,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+/// File metadata as returned by the CurseForge API (`POST /mods/files`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: u32,
+    pub mod_id: u32,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub file_name: String,
+    #[serde(default)]
+    pub download_url: Option<String>, // null if the author disabled third-party downloads
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeFileHash>,
+    #[serde(default)]
+    pub file_length: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeFileHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+impl CurseForgeFile {
+    pub fn sha1(&self) -> Option<String> {
+        self.hashes
+            .iter()
+            .find(|h| h.algo == CURSEFORGE_HASH_ALGO_SHA1)
+            .map(|h| h.value.to_lowercase())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct CurseForgeFilesResponse {
+    data: Vec<CurseForgeFile>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeFilesRequest<'a> {
+    file_ids: &'a [u32],
+}
+
+/// The part of a project (`POST /mods`) needed to tell what kind of content it is.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeProject {
+    id: u32,
+    #[serde(default)]
+    class_id: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CurseForgeProjectsResponse {
+    data: Vec<CurseForgeProject>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeProjectsRequest<'a> {
+    mod_ids: &'a [u32],
+}
+
+/// A manifest file that could not be downloaded during an import
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeFailedFile {
+    pub project_id: u32,
+    pub file_id: u32,
+    pub file_name: String,
+    pub error: String,
+}
+
+/// Result of a CurseForge pack import. The profile is created even if some non-mod files failed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeImport {
+    pub profile_id: Uuid,
+    pub failed_files: Vec<CurseForgeFailedFile>,
+}
+
+/// Instance directory a non-jar manifest file belongs in, based on the class of its project.
+/// Files of unknown projects are almost always resource packs.
+pub fn content_dir_for_class(class_id: Option<u32>) -> &'static str {
+    match class_id {
+        Some(CURSEFORGE_CLASS_SHADERS) => "shaderpacks",
+        Some(CURSEFORGE_CLASS_DATA_PACKS) => "datapacks",
+        _ => "resourcepacks",
+    }
+}
+
+/// Builds the CDN download URL for a CurseForge file. Used when the API does not expose a
+/// `downloadUrl` for a file.
+pub fn cdn_download_url(file_id: u32, file_name: &str) -> String {
+    format!(
+        "{}/{}/{}/{}",
+        endpoint_registry::base_url(Endpoint::CurseForgeCdn),
+        file_id / 1000,
+        file_id % 1000,
+        urlencoding::encode(file_name)
+    )
+}
+
+/// Resolves CurseForge file ids to downloadable file metadata.
+/// Implemented by the API client below; tests can plug in their own resolver.
+#[async_trait]
+pub trait CurseForgeFileResolver: Send + Sync {
+    /// Returns metadata for the requested file ids. Unknown ids are simply missing from the map.
+    async fn get_files(&self, file_ids: &[u32]) -> Result<HashMap<u32, CurseForgeFile>>;
+
+    /// Returns the class id (`CURSEFORGE_CLASS_*`) of the requested projects.
+    /// Unknown projects are missing from the map.
+    async fn get_project_classes(&self, _project_ids: &[u32]) -> Result<HashMap<u32, u32>> {
+        Ok(HashMap::new())
+    }
+
+    /// Returns the URL a file should be downloaded from.
+    fn download_url(&self, file: &CurseForgeFile) -> String {
+        file.download_url
+            .clone()
+            .unwrap_or_else(|| cdn_download_url(file.id, &file.file_name))
+    }
+}
+
+/// Resolver backed by the CurseForge REST API.
+pub struct CurseForgeApiResolver {
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl CurseForgeApiResolver {
+    /// Creates a resolver for the configured API endpoint. The API key is read from `CURSEFORGE_API_KEY`.
+    pub fn new() -> Self {
+        Self {
+            base_url: endpoint_registry::base_url(Endpoint::CurseForge),
+            api_key: std::env::var(CURSEFORGE_API_KEY_ENV).ok(),
+        }
+    }
+
+    /// Like `new`, but uses the API key from the launcher config if one is set
+    pub async fn from_config() -> Self {
+        let resolver = Self::new();
+        let api_key = match State::get().await {
+            Ok(state) => state.config_manager.get_config().await.curseforge_api_key,
+            Err(_) => None,
+        };
+        match api_key.filter(|key| !key.trim().is_empty()) {
+            Some(key) => resolver.with_api_key(key.trim()),
+            None => resolver,
+        }
+    }
+
+    fn post(&self, url: &str) -> reqwest::RequestBuilder {
+        let mut request = Client::new().post(url).header(
+            "User-Agent",
+            format!(
+                "NoRiskClient-Launcher/{} (support@norisk.gg)",
+                env!("CARGO_PKG_VERSION")
+            ),
+        );
+        if let Some(key) = &self.api_key {
+            request = request.header("x-api-key", key);
+        } else {
+            warn!(
+                "No CurseForge API key configured (launcher settings or {}), request will likely be rejected",
+                CURSEFORGE_API_KEY_ENV
+            );
+        }
+        request
+    }
+
+    /// Posts `body` as JSON to `path`, falling back to the configured mirrors
+    async fn send_post<T: Serialize>(&self, path: &str, body: &T) -> reqwest::Result<Response> {
+        let urls = endpoint_registry::resolve_url(&format!("{}/{}", self.base_url, path));
+        endpoint_registry::send_with_fallback(&urls, |url| self.post(url).json(body)).await
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+}
+
+#[async_trait]
+impl CurseForgeFileResolver for CurseForgeApiResolver {
+    async fn get_files(&self, file_ids: &[u32]) -> Result<HashMap<u32, CurseForgeFile>> {
+        if file_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        info!(
+            "Requesting metadata for {} CurseForge files from {}/mods/files",
+            file_ids.len(),
+            self.base_url
+        );
+
+        let response = self
+            .send_post("mods/files", &CurseForgeFilesRequest { file_ids })
+            .await
+            .map_err(|e| {
+                error!("Failed to request CurseForge file metadata: {}", e);
+                AppError::CurseForgeError(format!("Failed to request file metadata: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            error!("CurseForge API returned {}: {}", status, body);
+            return Err(AppError::CurseForgeError(format!(
+                "CurseForge API returned HTTP {}",
+                status
+            )));
+        }
+
+        let parsed: CurseForgeFilesResponse = response.json().await.map_err(|e| {
+            error!("Failed to parse CurseForge file metadata: {}", e);
+            AppError::CurseForgeError(format!("Failed to parse file metadata: {}", e))
+        })?;
+
+        Ok(parsed.data.into_iter().map(|f| (f.id, f)).collect())
+    }
+
+    async fn get_project_classes(&self, project_ids: &[u32]) -> Result<HashMap<u32, u32>> {
+        if project_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        debug!(
+            "Requesting classes of {} CurseForge projects",
+            project_ids.len()
+        );
+
+        let response = self
+            .send_post(
+                "mods",
+                &CurseForgeProjectsRequest {
+                    mod_ids: project_ids,
+                },
+            )
+            .await
+            .map_err(|e| {
+                AppError::CurseForgeError(format!("Failed to request project metadata: {}", e))
+            })?;
+        if !response.status().is_success() {
+            return Err(AppError::CurseForgeError(format!(
+                "CurseForge API returned HTTP {}",
+                response.status()
+            )));
+        }
+
+        let parsed: CurseForgeProjectsResponse = response.json().await.map_err(|e| {
+            AppError::CurseForgeError(format!("Failed to parse project metadata: {}", e))
+        })?;
+        Ok(parsed
+            .data
+            .into_iter()
+            .filter_map(|project| project.class_id.map(|class_id| (project.id, class_id)))
+            .collect())
+    }
+}
+
+/// Determines the ModLoader and its version from the manifest's modLoaders list.
+/// Forge versions are stored maven-style ("{mc}-{forge}") like the rest of the launcher expects.
+pub fn determine_loader_from_mod_loaders(
+    minecraft: &CurseForgeMinecraft,
+) -> (ModLoader, Option<String>) {
+    let loader_entry = minecraft
+        .mod_loaders
+        .iter()
+        .find(|l| l.primary)
+        .or_else(|| minecraft.mod_loaders.first());
+
+    let Some(entry) = loader_entry else {
+        return (ModLoader::Vanilla, None);
+    };
+
+    // NeoForge must be checked before Forge, the prefix would otherwise never match
+    if let Some(version) = entry.id.strip_prefix("neoforge-") {
+        (ModLoader::NeoForge, Some(version.to_string()))
+    } else if let Some(version) = entry.id.strip_prefix("forge-") {
+        if version.starts_with(&format!("{}-", minecraft.version)) {
+            (ModLoader::Forge, Some(version.to_string()))
+        } else {
+            (
+                ModLoader::Forge,
+                Some(format!("{}-{}", minecraft.version, version)),
+            )
+        }
+    } else if let Some(version) = entry.id.strip_prefix("fabric-") {
+        (ModLoader::Fabric, Some(version.to_string()))
+    } else if let Some(version) = entry.id.strip_prefix("quilt-") {
+        (ModLoader::Quilt, Some(version.to_string()))
+    } else {
+        warn!(
+            "Unknown CurseForge mod loader id '{}', falling back to Vanilla",
+            entry.id
+        );
+        (ModLoader::Vanilla, None)
+    }
+}
+
+/// Reads the manifest.json from a CurseForge modpack zip.
+/// Returns `Ok(None)` if the archive has no manifest at its root.
+pub async fn read_curseforge_manifest(pack_path: &Path) -> Result<Option<CurseForgeManifest>> {
+    let file = File::open(pack_path).await.map_err(|e| {
+        error!("Failed to open CurseForge pack {:?}: {}", pack_path, e);
+        AppError::Io(e)
+    })?;
+    let mut buf_reader = BufReader::new(file);
+
+    let mut zip = ZipFileReader::with_tokio(&mut buf_reader)
+        .await
+        .map_err(|e| {
+            error!("Failed to read zip archive {:?}: {}", pack_path, e);
+            AppError::CurseForgeError(format!("Failed to read pack zip: {}", e))
+        })?;
+
+    let manifest_entry_index = match zip.file().entries().iter().position(|e| {
+        e.filename()
+            .as_str()
+            .map_or(false, |name| name == CURSEFORGE_MANIFEST_FILE)
+    }) {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+
+    let mut entry_reader = zip
+        .reader_with_entry(manifest_entry_index)
+        .await
+        .map_err(|e| {
+            error!("Failed to get entry reader for manifest: {}", e);
+            AppError::CurseForgeError(format!("Failed to read manifest entry: {}", e))
+        })?;
+
+    let mut buffer = Vec::new();
+    entry_reader
+        .read_to_end_checked(&mut buffer)
+        .await
+        .map_err(|e| {
+            error!("Failed to read manifest content to buffer: {}", e);
+            AppError::CurseForgeError(format!("Zip entry read error: {}", e))
+        })?;
+
+    let manifest: CurseForgeManifest = serde_json::from_slice(&buffer).map_err(|e| {
+        error!("Failed to parse CurseForge manifest.json: {}", e);
+        AppError::Json(e)
+    })?;
+
+    Ok(Some(manifest))
+}
+
+/// Returns true if the given zip looks like a CurseForge modpack export.
+pub async fn is_curseforge_pack(pack_path: &Path) -> bool {
+    matches!(read_curseforge_manifest(pack_path).await, Ok(Some(_)))
+}
+
+/// Processes a CurseForge modpack zip and creates a *potential* Profile struct (not saved)
+/// together with the parsed manifest.
+pub async fn process_curseforge_pack(
+    pack_path: PathBuf,
+) -> Result<(Profile, CurseForgeManifest)> {
+    info!("Processing CurseForge pack: {:?}", pack_path);
+
+    let manifest = read_curseforge_manifest(&pack_path).await?.ok_or_else(|| {
+        error!("manifest.json not found in archive: {:?}", pack_path);
+        AppError::CurseForgeError("manifest.json not found in archive".into())
+    })?;
+    info!("Parsed manifest for pack: '{}'", manifest.name);
+
+    let (loader, loader_version) = determine_loader_from_mod_loaders(&manifest.minecraft);
+    info!(
+        "Determined requirements: MC={}, Loader={:?}, LoaderVersion={:?}",
+        manifest.minecraft.version, loader, loader_version
+    );
+
+    let profile = Profile {
+        id: Uuid::new_v4(),
+        name: manifest.name.clone(),
+        path: sanitize(&manifest.name),
+        game_version: manifest.minecraft.version.clone(),
+        loader,
+        loader_version,
+        created: Utc::now(),
+        last_played: None,
+        settings: ProfileSettings::default(),
+        state: ProfileState::NotInstalled,
+        mods: Vec::new(),
+        selected_norisk_pack_id: None,
+        disabled_norisk_mods_detailed: HashSet::new(),
+        source_standard_profile_id: None,
+        group: Some("MODPACKS".to_string()),
+        is_standard_version: false,
+        description: manifest
+            .author
+            .as_ref()
+            .map(|author| format!("CurseForge modpack by {}", author)),
+        norisk_information: None,
+        banner: None,
+        background: None,
+    };
+
+    Ok((profile, manifest))
+}
+
+/// Resolves the manifest files through the given resolver.
+/// Returns the jar files as `Mod` entries and all other files (resource packs, shaders, ...)
+/// separately, since those are not handled by the mod sync.
+pub async fn resolve_manifest_files(
+    manifest: &CurseForgeManifest,
+    resolver: &dyn CurseForgeFileResolver,
+) -> Result<(Vec<Mod>, Vec<CurseForgeFile>)> {
+    let (pack_loader, _) = determine_loader_from_mod_loaders(&manifest.minecraft);
+    let game_version = manifest.minecraft.version.clone();
+
+    let file_ids: Vec<u32> = manifest.files.iter().map(|f| f.file_id).collect();
+    let mut resolved = resolver.get_files(&file_ids).await?;
+    info!(
+        "Resolved {}/{} CurseForge files from the manifest.",
+        resolved.len(),
+        file_ids.len()
+    );
+
+    let mut mods = Vec::new();
+    let mut other_files = Vec::new();
+
+    for manifest_file in &manifest.files {
+        let Some(file) = resolved.remove(&manifest_file.file_id) else {
+            if manifest_file.required {
+                error!(
+                    "Required CurseForge file {} (project {}) could not be resolved",
+                    manifest_file.file_id, manifest_file.project_id
+                );
+                return Err(AppError::CurseForgeError(format!(
+                    "Could not resolve required file {} of project {}",
+                    manifest_file.file_id, manifest_file.project_id
+                )));
+            }
+            warn!(
+                "Optional CurseForge file {} (project {}) could not be resolved, skipping.",
+                manifest_file.file_id, manifest_file.project_id
+            );
+            continue;
+        };
+
+        if !file.file_name.ends_with(".jar") {
+            debug!(
+                "CurseForge file '{}' is not a jar, handling it as a non-mod file",
+                file.file_name
+            );
+            other_files.push(file);
+            continue;
+        }
+
+        let download_url = resolver.download_url(&file);
+        mods.push(Mod {
+            id: Uuid::new_v4(),
+            source: ModSource::CurseForge {
+                project_id: manifest_file.project_id,
+                file_id: file.id,
+                file_name: file.file_name.clone(),
+                download_url: Some(download_url),
+                file_hash_sha1: file.sha1(),
+            },
+            enabled: manifest_file.required,
+            display_name: file.display_name.clone().or(Some(file.file_name.clone())),
+            version: None,
+            game_versions: Some(vec![game_version.clone()]),
+            file_name_override: None,
+            associated_loader: Some(pack_loader),
+        });
+    }
+
+    info!(
+        "Prepared {} mods and {} other files from the CurseForge manifest.",
+        mods.len(),
+        other_files.len()
+    );
+    Ok((mods, other_files))
+}
+
+/// Imports a CurseForge modpack zip as a new profile using the public CurseForge API.
+pub async fn import_curseforge_pack_as_profile(pack_path: PathBuf) -> Result<CurseForgeImport> {
+    let resolver = CurseForgeApiResolver::from_config().await;
+    import_curseforge_pack_as_profile_with_resolver(pack_path, &resolver).await
+}
+
+/// Imports a CurseForge modpack zip as a new profile, resolving files through `resolver`.
+/// Non-mod files that fail to download don't abort the import, they are returned instead.
+pub async fn import_curseforge_pack_as_profile_with_resolver(
+    pack_path: PathBuf,
+    resolver: &dyn CurseForgeFileResolver,
+) -> Result<CurseForgeImport> {
+    info!("Starting full import process for CurseForge pack: {:?}", pack_path);
+
+    // 1. Read manifest and build the base profile
+    let (mut profile, manifest) = process_curseforge_pack(pack_path.clone()).await?;
+
+    // 2. Resolve files through the resolver
+    let (resolved_mods, other_files) = resolve_manifest_files(&manifest, resolver).await?;
+    profile.mods = resolved_mods;
+
+    // 3. Determine unique profile path segment
+    let base_profiles_dir = crate::state::profile_state::default_profile_path();
+    let mut base_name = sanitize(&profile.name);
+    if base_name.is_empty() {
+        base_name = format!("imported-pack-{}", Utc::now().timestamp_millis());
+        warn!(
+            "Profile name '{}' became empty after sanitization. Using default: {}",
+            profile.name, base_name
+        );
+        profile.name = base_name.clone();
+    }
+    profile.path =
+        crate::utils::path_utils::find_unique_profile_segment(&base_profiles_dir, &base_name)
+            .await?;
+    info!("Determined unique profile directory segment: {}", profile.path);
+
+    let target_dir = base_profiles_dir.join(&profile.path);
+    if !target_dir.exists() {
+        fs::create_dir_all(&target_dir).await.map_err(|e| {
+            error!(
+                "Failed to create target profile directory {:?}: {}",
+                target_dir, e
+            );
+            AppError::Io(e)
+        })?;
+    }
+
+    // 4. Extract overrides (the directory name is configurable in the manifest)
+    let overrides_prefix = format!("{}/", manifest.overrides.trim_end_matches('/'));
+    mrpack::extract_pack_overrides(&pack_path, &profile, &[overrides_prefix.as_str()]).await?;
+    info!("Successfully extracted overrides.");
+
+    // 5. Non-jar files go where their project class says (resource packs, shaders, data packs)
+    let mut failed_files = Vec::new();
+    if !other_files.is_empty() {
+        let project_ids: Vec<u32> = other_files.iter().map(|f| f.mod_id).collect();
+        let classes = match resolver.get_project_classes(&project_ids).await {
+            Ok(classes) => classes,
+            Err(e) => {
+                warn!(
+                    "Could not look up CurseForge project classes, treating non-mod files as resource packs: {}",
+                    e
+                );
+                HashMap::new()
+            }
+        };
+
+        for file in &other_files {
+            let content_dir = content_dir_for_class(classes.get(&file.mod_id).copied());
+            let target_path = target_dir.join(content_dir).join(sanitize(&file.file_name));
+            let mut config = DownloadConfig::new().with_retries(3);
+            if let Some(sha1) = file.sha1() {
+                config = config.with_sha1(&sha1);
+            }
+            if let Err(e) =
+                DownloadUtils::download_file(&resolver.download_url(file), &target_path, config)
+                    .await
+            {
+                warn!(
+                    "Failed to download non-mod file '{}' from CurseForge pack: {}",
+                    file.file_name, e
+                );
+                failed_files.push(CurseForgeFailedFile {
+                    project_id: file.mod_id,
+                    file_id: file.id,
+                    file_name: file.file_name.clone(),
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    // 6. Save the profile
+    let state = State::get().await?;
+    info!(
+        "Saving the new profile '{}' (ID: {})...",
+        profile.name, profile.id
+    );
+    let profile_id = state.profile_manager.create_profile(profile).await?;
+    info!(
+        "Successfully created and saved profile with ID: {}",
+        profile_id
+    );
+    if !failed_files.is_empty() {
+        warn!(
+            "{} file(s) of the CurseForge pack could not be downloaded",
+            failed_files.len()
+        );
+    }
+
+    Ok(CurseForgeImport {
+        profile_id,
+        failed_files,
+    })
+}
@@ -1,7 +1,10 @@
+pub mod curseforge;
 pub mod modrinth;
+pub mod modrinth_resolver;
 pub mod mrpack;
 pub mod norisk_packs;
 pub mod norisk_versions;
+pub mod prism;
 
 pub use norisk_packs::*;
 pub use norisk_versions::*;
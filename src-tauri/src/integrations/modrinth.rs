@@ -1,4 +1,5 @@
 use crate::error::{AppError, Result};
+use crate::utils::endpoint_registry::{self, Endpoint};
 use futures::future::join_all;
 use log::{self, error, info};
 use reqwest;
@@ -6,8 +7,10 @@ use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
 
-// Base URL for Modrinth API v2
-const MODRINTH_API_BASE_URL: &str = "https://api.modrinth.com/v2";
+// Base URL for Modrinth API v2 (configurable through the endpoint registry)
+fn modrinth_api_base_url() -> String {
+    endpoint_registry::base_url(Endpoint::Modrinth)
+}
 
 // Structures for deserializing Modrinth API responses (Search)
 // Based on https://docs.modrinth.com/api-spec/#tag/projects/operation/searchProjects
@@ -296,7 +299,7 @@ pub async fn search_projects(
     server_side_filter: Option<String>,
 ) -> Result<ModrinthSearchResponse> {
     let client = reqwest::Client::new();
-    let base_url = format!("{}/search", MODRINTH_API_BASE_URL);
+    let base_url = format!("{}/search", modrinth_api_base_url());
 
     let mut query_params: Vec<(String, String)> = Vec::new();
 
@@ -399,20 +402,24 @@ pub async fn search_projects(
 
     log::info!("Searching Modrinth: {}", final_url);
 
-    let response = client
-        .get(final_url)
-        // It's good practice to set a User-Agent
-        // Use format! correctly and ensure CARGO_PKG_VERSION is available
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(final_url.as_str()),
+        |url| {
+            client
+                .get(url)
+                // It's good practice to set a User-Agent
+                // Use format! correctly and ensure CARGO_PKG_VERSION is available
+                .header(
+                    "User-Agent",
+                    format!(
+                        "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                        env!("CARGO_PKG_VERSION")
+                    ),
+                )
+        },
+    )
+    .await
+    .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -466,7 +473,7 @@ pub async fn get_mod_versions(
     let client = reqwest::Client::new();
     let url = format!(
         "{}/project/{}/version",
-        MODRINTH_API_BASE_URL, project_id_or_slug
+        modrinth_api_base_url(), project_id_or_slug
     );
 
     let mut query_params: Vec<(String, String)> = Vec::new();
@@ -505,18 +512,20 @@ pub async fn get_mod_versions(
 
     log::info!("Getting Modrinth versions: {}", final_url);
 
-    let response = client
-        .get(final_url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(final_url.as_str()),
+        |url| {
+            client.get(url).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -546,27 +555,29 @@ pub async fn get_mod_versions(
 // Based on https://docs.modrinth.com/api-spec/#tag/versions/operation/getVersion
 pub async fn get_version_details(version_id: String) -> Result<ModrinthVersion> {
     let client = reqwest::Client::new();
-    let url = format!("{}/version/{}", MODRINTH_API_BASE_URL, version_id);
+    let url = format!("{}/version/{}", modrinth_api_base_url(), version_id);
 
     log::info!("Getting Modrinth version details: {}", url);
 
-    let response = client
-        .get(url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request failed for version {}: {}",
-                version_id, e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client.get(url).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request failed for version {}: {}",
+            version_id, e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -596,6 +607,61 @@ pub async fn get_version_details(version_id: String) -> Result<ModrinthVersion>
     Ok(version_details)
 }
 
+// Function to get details for multiple Modrinth version IDs in one request
+// Based on https://docs.modrinth.com/api-spec/#tag/versions/operation/getVersions
+pub async fn get_versions_by_ids(version_ids: Vec<String>) -> Result<Vec<ModrinthVersion>> {
+    if version_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let client = reqwest::Client::new();
+    let ids_json = serde_json::to_string(&version_ids)
+        .map_err(|e| AppError::Other(format!("Failed to serialize version IDs: {}", e)))?;
+    let url = format!("{}/versions", modrinth_api_base_url());
+
+    log::info!(
+        "Getting Modrinth details for {} versions: {}",
+        version_ids.len(),
+        url
+    );
+
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client.get(url).query(&[("ids", &ids_json)]).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| AppError::Other(format!("Modrinth API request for versions failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Failed to read error body".to_string());
+        log::error!(
+            "Modrinth API error getting versions ({}): {}",
+            status,
+            error_text
+        );
+        return Err(AppError::Other(format!(
+            "Modrinth API returned error {} getting versions: {}",
+            status, error_text
+        )));
+    }
+
+    response.json::<Vec<ModrinthVersion>>().await.map_err(|e| {
+        AppError::Other(format!("Failed to parse Modrinth versions response: {}", e))
+    })
+}
+
 /// Fetches ALL compatible versions for a list of Modrinth projects concurrently,
 /// using specific filters for each project.
 ///
@@ -681,7 +747,7 @@ pub async fn get_version_by_hash(file_hash: String) -> Result<ModrinthVersion> {
     let client = reqwest::Client::new();
     let url = format!(
         "{}/version_file/{}?algorithm={}", // Correct endpoint path
-        MODRINTH_API_BASE_URL, file_hash, algorithm
+        modrinth_api_base_url(), file_hash, algorithm
     );
 
     log::info!(
@@ -690,23 +756,25 @@ pub async fn get_version_by_hash(file_hash: String) -> Result<ModrinthVersion> {
         url
     );
 
-    let response = client
-        .get(&url) // Pass URL by reference
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (support@norisk.gg)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request failed for hash {}: {}",
-                file_hash, e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client.get(url).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (support@norisk.gg)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request failed for hash {}: {}",
+            file_hash, e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -760,7 +828,7 @@ pub async fn get_versions_by_hashes(
     }
 
     let client = reqwest::Client::new();
-    let url = format!("{}/version_files", MODRINTH_API_BASE_URL); // POST endpoint
+    let url = format!("{}/version_files", modrinth_api_base_url()); // POST endpoint
 
     let request_body = HashesRequestBody {
         hashes: hashes.clone(), // Clone hashes for the body
@@ -774,25 +842,29 @@ pub async fn get_versions_by_hashes(
         url
     );
 
-    let response = client
-        .post(&url) // Use POST
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (support@norisk.gg)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .header("Content-Type", "application/json") // Set content type
-        .json(&request_body) // Send the serialized request body
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API POST request failed for hashes: {}",
-                e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client
+                .post(url)
+                .header(
+                    "User-Agent",
+                    format!(
+                        "NoRiskClient-Launcher/{} (support@norisk.gg)",
+                        env!("CARGO_PKG_VERSION")
+                    ),
+                )
+                .header("Content-Type", "application/json") // Set content type
+                .json(&request_body) // Send the serialized request body
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API POST request failed for hashes: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -885,32 +957,36 @@ pub async fn check_bulk_updates(
     request: ModrinthBulkUpdateRequestBody,
 ) -> Result<HashMap<String, ModrinthVersion>> {
     let client = reqwest::Client::new();
-    let url = format!("{}/version_files/update", MODRINTH_API_BASE_URL); // Update check endpoint
+    let url = format!("{}/version_files/update", modrinth_api_base_url()); // Update check endpoint
 
     log::info!(
         "Checking for updates for {} mods via Modrinth bulk API",
         request.hashes.len()
     );
 
-    let response = client
-        .post(&url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (support@norisk.gg)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API bulk update check request failed: {}",
-                e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client
+                .post(url)
+                .header(
+                    "User-Agent",
+                    format!(
+                        "NoRiskClient-Launcher/{} (support@norisk.gg)",
+                        env!("CARGO_PKG_VERSION")
+                    ),
+                )
+                .header("Content-Type", "application/json")
+                .json(&request)
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API bulk update check request failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -964,7 +1040,7 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
 
     let client = reqwest::Client::new();
     // Note: No trailing slash needed for the base URL when using parse_with_params
-    let base_url = format!("{}/projects", MODRINTH_API_BASE_URL);
+    let base_url = format!("{}/projects", modrinth_api_base_url());
 
     let final_url =
         reqwest::Url::parse_with_params(&base_url, &[("ids", ids_json)]).map_err(|e| {
@@ -977,23 +1053,25 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
         final_url
     );
 
-    let response = client
-        .get(final_url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (support@norisk.gg)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::RequestError(format!(
-                "Modrinth API request failed for bulk projects: {}",
-                e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(final_url.as_str()),
+        |url| {
+            client.get(url).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (support@norisk.gg)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::RequestError(format!(
+            "Modrinth API request failed for bulk projects: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -1065,27 +1143,29 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
 /// https://docs.modrinth.com/api/operations/categorylist/
 pub async fn get_modrinth_categories() -> Result<Vec<ModrinthCategory>> {
     let client = reqwest::Client::new();
-    let url = format!("{}/tag/category", MODRINTH_API_BASE_URL);
+    let url = format!("{}/tag/category", modrinth_api_base_url());
 
     log::info!("Fetching Modrinth categories from: {}", url);
 
-    let response = client
-        .get(&url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request to fetch categories failed: {}",
-                e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client.get(url).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request to fetch categories failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -1122,27 +1202,29 @@ pub async fn get_modrinth_categories() -> Result<Vec<ModrinthCategory>> {
 /// https://docs.modrinth.com/api/operations/loaderlist/
 pub async fn get_modrinth_loaders() -> Result<Vec<ModrinthLoader>> {
     let client = reqwest::Client::new();
-    let url = format!("{}/tag/loader", MODRINTH_API_BASE_URL);
+    let url = format!("{}/tag/loader", modrinth_api_base_url());
 
     log::info!("Fetching Modrinth loaders from: {}", url);
 
-    let response = client
-        .get(&url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request to fetch loaders failed: {}",
-                e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client.get(url).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request to fetch loaders failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -1173,27 +1255,29 @@ pub async fn get_modrinth_loaders() -> Result<Vec<ModrinthLoader>> {
 /// https://docs.modrinth.com/api/operations/versionlist/
 pub async fn get_modrinth_game_versions() -> Result<Vec<ModrinthGameVersion>> {
     let client = reqwest::Client::new();
-    let url = format!("{}/tag/game_version", MODRINTH_API_BASE_URL);
+    let url = format!("{}/tag/game_version", modrinth_api_base_url());
 
     log::info!("Fetching Modrinth game versions from: {}", url);
 
-    let response = client
-        .get(&url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request to fetch game versions failed: {}",
-                e
-            ))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(url.as_str()),
+        |url| {
+            client.get(url).header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request to fetch game versions failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -0,0 +1,626 @@
+//! Dependency resolution for Modrinth mods.
+//!
+//! Before a mod is added to a profile, [`resolve_install_plan`] computes the transitive
+//! closure of its required dependencies for the profile's game version and loader, picks a
+//! compatible version for each of them and checks `incompatible` declarations in both
+//! directions against the mods already installed. Nothing is written to the profile; the
+//! resulting [`ModrinthInstallPlan`] is shown to the user and applied with
+//! `ProfileManager::apply_modrinth_install_plan`.
+
+use crate::error::{AppError, Result};
+use crate::integrations::modrinth::{
+    self, ModrinthDependencyType, ModrinthFile, ModrinthVersion, ModrinthVersionType,
+};
+use crate::state::profile_state::{ModLoader, ModSource, Profile};
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+/// Source of Modrinth version metadata.
+/// Implemented by the API client below; tests can plug in their own provider.
+#[async_trait]
+pub trait ModrinthVersionProvider: Send + Sync {
+    async fn get_version(&self, version_id: &str) -> Result<ModrinthVersion>;
+
+    /// Returns the requested versions. Unknown ids are simply missing from the result.
+    async fn get_versions(&self, version_ids: &[String]) -> Result<Vec<ModrinthVersion>>;
+
+    /// Returns all versions of a project that support one of the loaders and the game version
+    async fn get_project_versions(
+        &self,
+        project_id: &str,
+        loaders: &[String],
+        game_version: &str,
+    ) -> Result<Vec<ModrinthVersion>>;
+}
+
+/// Provider backed by the Modrinth REST API.
+pub struct ModrinthApiProvider;
+
+#[async_trait]
+impl ModrinthVersionProvider for ModrinthApiProvider {
+    async fn get_version(&self, version_id: &str) -> Result<ModrinthVersion> {
+        modrinth::get_version_details(version_id.to_string()).await
+    }
+
+    async fn get_versions(&self, version_ids: &[String]) -> Result<Vec<ModrinthVersion>> {
+        modrinth::get_versions_by_ids(version_ids.to_vec()).await
+    }
+
+    async fn get_project_versions(
+        &self,
+        project_id: &str,
+        loaders: &[String],
+        game_version: &str,
+    ) -> Result<Vec<ModrinthVersion>> {
+        let loaders = if loaders.is_empty() {
+            None
+        } else {
+            Some(loaders.to_vec())
+        };
+        modrinth::get_mod_versions(
+            project_id.to_string(),
+            loaders,
+            Some(vec![game_version.to_string()]),
+        )
+        .await
+    }
+}
+
+/// A mod version that will be added to the profile
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlannedMod {
+    pub project_id: String,
+    pub version_id: String,
+    pub name: String,
+    pub version_number: String,
+    pub file_name: String,
+    pub download_url: String,
+    pub file_hash_sha1: Option<String>,
+    pub loaders: Vec<String>,
+    pub game_versions: Vec<String>,
+    /// Project that pulled this mod in, `None` for the mod the user selected
+    pub required_by: Option<String>,
+    /// Version of the same project that is currently installed and will be replaced
+    pub replaces_version_id: Option<String>,
+}
+
+/// A required dependency that is already covered by an installed mod
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SatisfiedDependency {
+    pub project_id: String,
+    pub installed_version_id: String,
+    pub required_by: String,
+}
+
+/// A dependency that is not needed but can be installed additionally
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OptionalDependency {
+    pub project_id: String,
+    pub suggested_by: String,
+}
+
+/// A required dependency for which no usable version was found
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UnresolvedDependency {
+    pub project_id: Option<String>,
+    pub version_id: Option<String>,
+    pub required_by: String,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// One of the mods declares the other one as incompatible
+    Incompatible,
+    /// A specific version is required but a different version is installed or planned
+    VersionMismatch,
+    /// The selected version does not support the profile's game version or loader
+    UnsupportedByProfile,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DependencyConflict {
+    pub kind: ConflictKind,
+    pub project_id: String,
+    pub other_project_id: Option<String>,
+    pub message: String,
+}
+
+/// Everything that adding a Modrinth mod to a profile would change
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModrinthInstallPlan {
+    pub profile_id: Uuid,
+    pub game_version: String,
+    pub loader: ModLoader,
+    /// Mods to add, the selected mod first followed by its dependencies in resolution order
+    pub mods: Vec<PlannedMod>,
+    pub satisfied: Vec<SatisfiedDependency>,
+    pub optional: Vec<OptionalDependency>,
+    pub unresolved: Vec<UnresolvedDependency>,
+    pub conflicts: Vec<DependencyConflict>,
+}
+
+impl ModrinthInstallPlan {
+    /// Whether the plan can be applied without breaking the profile
+    pub fn can_apply(&self) -> bool {
+        self.conflicts.is_empty() && self.unresolved.is_empty()
+    }
+}
+
+/// A Modrinth mod that is already part of a profile
+#[derive(Debug, Clone)]
+struct InstalledModrinthMod {
+    project_id: String,
+    version_id: String,
+    name: String,
+    /// Disabled mods don't satisfy or conflict with anything, but still get replaced
+    enabled: bool,
+}
+
+fn installed_modrinth_mods(profile: &Profile) -> Vec<InstalledModrinthMod> {
+    profile
+        .mods
+        .iter()
+        .filter_map(|m| match &m.source {
+            ModSource::Modrinth {
+                project_id,
+                version_id,
+                file_name,
+                ..
+            } => Some(InstalledModrinthMod {
+                project_id: project_id.clone(),
+                version_id: version_id.clone(),
+                name: m.display_name.clone().unwrap_or_else(|| file_name.clone()),
+                enabled: m.enabled,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Modrinth loader names whose mods run on the given loader
+pub fn compatible_loaders(loader: &ModLoader) -> Vec<String> {
+    match loader {
+        ModLoader::Vanilla => Vec::new(),
+        // Quilt loads Fabric mods as well
+        ModLoader::Quilt => vec!["quilt".to_string(), "fabric".to_string()],
+        other => vec![other.as_str().to_string()],
+    }
+}
+
+/// Loader to record for a mod version installed into a profile with the given loader.
+/// Fabric mods in a Quilt profile are recorded as Quilt mods, the launch skips mods of other loaders.
+pub fn associated_loader(
+    profile_loader: &ModLoader,
+    version_loaders: &[String],
+) -> Option<ModLoader> {
+    let compatible = compatible_loaders(profile_loader);
+    if version_loaders.iter().any(|l| compatible.contains(l)) {
+        return Some(*profile_loader);
+    }
+    version_loaders.first().and_then(|l| ModLoader::from_str(l).ok())
+}
+
+fn supports_profile(version: &ModrinthVersion, loaders: &[String], game_version: &str) -> bool {
+    let loader_ok = loaders.is_empty() || version.loaders.iter().any(|l| loaders.contains(l));
+    loader_ok && version.game_versions.iter().any(|gv| gv == game_version)
+}
+
+fn version_type_rank(version_type: &ModrinthVersionType) -> u8 {
+    match version_type {
+        ModrinthVersionType::Release => 2,
+        ModrinthVersionType::Beta => 1,
+        ModrinthVersionType::Alpha => 0,
+    }
+}
+
+/// Picks the best candidate: releases before betas before alphas, newest first
+fn pick_best_version(candidates: Vec<ModrinthVersion>) -> Option<ModrinthVersion> {
+    candidates.into_iter().max_by(|a, b| {
+        version_type_rank(&a.version_type)
+            .cmp(&version_type_rank(&b.version_type))
+            .then_with(|| a.date_published.cmp(&b.date_published))
+    })
+}
+
+fn primary_file(version: &ModrinthVersion) -> Option<&ModrinthFile> {
+    version
+        .files
+        .iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+}
+
+/// Whether a declared dependency (project and optional exact version) matches a mod
+fn dependency_matches(
+    dep_project_id: Option<&str>,
+    dep_version_id: Option<&str>,
+    project_id: &str,
+    version_id: &str,
+) -> bool {
+    match (dep_project_id, dep_version_id) {
+        (_, Some(dep_version)) => dep_version == version_id,
+        (Some(dep_project), None) => dep_project == project_id,
+        (None, None) => false,
+    }
+}
+
+/// Computes what adding `version_id` to `profile` requires, without changing the profile.
+pub async fn resolve_install_plan(
+    provider: &dyn ModrinthVersionProvider,
+    profile: &Profile,
+    version_id: &str,
+) -> Result<ModrinthInstallPlan> {
+    let loaders = compatible_loaders(&profile.loader);
+    let game_version = profile.game_version.as_str();
+    let installed = installed_modrinth_mods(profile);
+    let installed_by_project: HashMap<&str, &InstalledModrinthMod> = installed
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| (m.project_id.as_str(), m))
+        .collect();
+    // A planned version replaces a disabled copy of its project as well
+    let replaceable_by_project: HashMap<&str, &InstalledModrinthMod> = installed
+        .iter()
+        .map(|m| (m.project_id.as_str(), m))
+        .collect();
+
+    let mut plan = ModrinthInstallPlan {
+        profile_id: profile.id,
+        game_version: profile.game_version.clone(),
+        loader: profile.loader,
+        mods: Vec::new(),
+        satisfied: Vec::new(),
+        optional: Vec::new(),
+        unresolved: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
+    let root = provider.get_version(version_id).await?;
+    info!(
+        "Resolving dependencies of Modrinth version {} ({}) for profile '{}' ({} {})",
+        root.name,
+        root.id,
+        profile.name,
+        profile.loader.as_str(),
+        game_version
+    );
+
+    if let Some(existing) = installed_by_project.get(root.project_id.as_str()) {
+        if existing.version_id == root.id {
+            return Err(AppError::InvalidOperation(format!(
+                "{} ({}) is already installed in this profile",
+                root.name, root.version_number
+            )));
+        }
+    }
+
+    // All versions that end up in the plan, used for the incompatibility checks below
+    let mut planned_versions: Vec<ModrinthVersion> = Vec::new();
+    let mut queued_projects: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<(ModrinthVersion, Option<String>)> = VecDeque::new();
+    queued_projects.insert(root.project_id.clone());
+    queue.push_back((root, None));
+
+    while let Some((version, required_by)) = queue.pop_front() {
+        if !supports_profile(&version, &loaders, game_version) {
+            plan.conflicts.push(DependencyConflict {
+                kind: ConflictKind::UnsupportedByProfile,
+                project_id: version.project_id.clone(),
+                other_project_id: None,
+                message: format!(
+                    "{} ({}) does not support {} {}",
+                    version.name,
+                    version.version_number,
+                    profile.loader.as_str(),
+                    game_version
+                ),
+            });
+        }
+
+        match primary_file(&version) {
+            Some(file) => plan.mods.push(PlannedMod {
+                project_id: version.project_id.clone(),
+                version_id: version.id.clone(),
+                name: version.name.clone(),
+                version_number: version.version_number.clone(),
+                file_name: file.filename.clone(),
+                download_url: file.url.clone(),
+                file_hash_sha1: file.hashes.sha1.clone(),
+                loaders: version.loaders.clone(),
+                game_versions: version.game_versions.clone(),
+                required_by: required_by.clone(),
+                replaces_version_id: replaceable_by_project
+                    .get(version.project_id.as_str())
+                    .map(|m| m.version_id.clone()),
+            }),
+            None => plan.unresolved.push(UnresolvedDependency {
+                project_id: Some(version.project_id.clone()),
+                version_id: Some(version.id.clone()),
+                required_by: required_by.clone().unwrap_or_default(),
+                reason: format!("Version {} has no downloadable files", version.id),
+            }),
+        }
+
+        for dependency in &version.dependencies {
+            match dependency.dependency_type {
+                ModrinthDependencyType::Required => {}
+                ModrinthDependencyType::Optional => {
+                    if let Some(project_id) = &dependency.project_id {
+                        if !queued_projects.contains(project_id)
+                            && !installed_by_project.contains_key(project_id.as_str())
+                        {
+                            plan.optional.push(OptionalDependency {
+                                project_id: project_id.clone(),
+                                suggested_by: version.project_id.clone(),
+                            });
+                        }
+                    }
+                    continue;
+                }
+                // Incompatibilities are checked once the whole closure is known
+                ModrinthDependencyType::Incompatible | ModrinthDependencyType::Embedded => continue,
+            }
+
+            // Dependencies that only name a version need it fetched to know the project
+            let mut pinned_version = None;
+            let dep_project_id = match (&dependency.project_id, &dependency.version_id) {
+                (Some(project_id), _) => project_id.clone(),
+                (None, Some(dep_version_id)) => match provider.get_version(dep_version_id).await {
+                    Ok(dep_version) => {
+                        let project_id = dep_version.project_id.clone();
+                        pinned_version = Some(dep_version);
+                        project_id
+                    }
+                    Err(e) => {
+                        plan.unresolved.push(UnresolvedDependency {
+                            project_id: None,
+                            version_id: Some(dep_version_id.clone()),
+                            required_by: version.project_id.clone(),
+                            reason: format!("Could not fetch version: {}", e),
+                        });
+                        continue;
+                    }
+                },
+                (None, None) => {
+                    debug!(
+                        "Skipping dependency of {} without project and version (file: {:?})",
+                        version.project_id, dependency.file_name
+                    );
+                    continue;
+                }
+            };
+
+            if let Some(planned) = plan.mods.iter().find(|m| m.project_id == dep_project_id) {
+                if let Some(dep_version_id) = &dependency.version_id {
+                    if &planned.version_id != dep_version_id {
+                        plan.conflicts.push(DependencyConflict {
+                            kind: ConflictKind::VersionMismatch,
+                            project_id: dep_project_id.clone(),
+                            other_project_id: Some(version.project_id.clone()),
+                            message: format!(
+                                "{} requires version {} of {}, but {} is planned",
+                                version.name, dep_version_id, planned.name, planned.version_number
+                            ),
+                        });
+                    }
+                }
+                continue;
+            }
+            if queued_projects.contains(&dep_project_id) {
+                continue;
+            }
+
+            if let Some(existing) = installed_by_project.get(dep_project_id.as_str()) {
+                match &dependency.version_id {
+                    Some(dep_version_id) if dep_version_id != &existing.version_id => {
+                        plan.conflicts.push(DependencyConflict {
+                            kind: ConflictKind::VersionMismatch,
+                            project_id: dep_project_id.clone(),
+                            other_project_id: Some(version.project_id.clone()),
+                            message: format!(
+                                "{} requires version {} of {}, but version {} is installed",
+                                version.name, dep_version_id, existing.name, existing.version_id
+                            ),
+                        });
+                    }
+                    _ => plan.satisfied.push(SatisfiedDependency {
+                        project_id: dep_project_id.clone(),
+                        installed_version_id: existing.version_id.clone(),
+                        required_by: version.project_id.clone(),
+                    }),
+                }
+                continue;
+            }
+
+            // An exact version was requested: use it if it fits the profile
+            if pinned_version.is_none() {
+                if let Some(dep_version_id) = &dependency.version_id {
+                    match provider.get_version(dep_version_id).await {
+                        Ok(dep_version) => pinned_version = Some(dep_version),
+                        Err(e) => warn!(
+                            "Could not fetch pinned dependency version {}: {}",
+                            dep_version_id, e
+                        ),
+                    }
+                }
+            }
+            let selected = match pinned_version
+                .filter(|v| supports_profile(v, &loaders, game_version))
+            {
+                Some(dep_version) => Some(dep_version),
+                None => {
+                    let candidates = match provider
+                        .get_project_versions(&dep_project_id, &loaders, game_version)
+                        .await
+                    {
+                        Ok(candidates) => candidates,
+                        Err(e) => {
+                            plan.unresolved.push(UnresolvedDependency {
+                                project_id: Some(dep_project_id.clone()),
+                                version_id: dependency.version_id.clone(),
+                                required_by: version.project_id.clone(),
+                                reason: format!("Could not fetch versions: {}", e),
+                            });
+                            continue;
+                        }
+                    };
+                    pick_best_version(
+                        candidates
+                            .into_iter()
+                            .filter(|v| supports_profile(v, &loaders, game_version))
+                            .collect(),
+                    )
+                }
+            };
+
+            match selected {
+                Some(dep_version) => {
+                    debug!(
+                        "Selected {} ({}) for dependency {} of {}",
+                        dep_version.version_number,
+                        dep_version.id,
+                        dep_project_id,
+                        version.project_id
+                    );
+                    queued_projects.insert(dep_project_id);
+                    queue.push_back((dep_version, Some(version.project_id.clone())));
+                }
+                None => plan.unresolved.push(UnresolvedDependency {
+                    project_id: Some(dep_project_id),
+                    version_id: dependency.version_id.clone(),
+                    required_by: version.project_id.clone(),
+                    reason: format!(
+                        "No version for {} {} available",
+                        profile.loader.as_str(),
+                        game_version
+                    ),
+                }),
+            }
+        }
+
+        planned_versions.push(version);
+    }
+
+    check_incompatibilities(provider, &mut plan, &planned_versions, &installed).await;
+
+    info!(
+        "Resolved plan for profile '{}': {} mods, {} satisfied, {} unresolved, {} conflicts",
+        profile.name,
+        plan.mods.len(),
+        plan.satisfied.len(),
+        plan.unresolved.len(),
+        plan.conflicts.len()
+    );
+    Ok(plan)
+}
+
+/// Checks `incompatible` declarations of the planned mods against the profile and the
+/// other planned mods, and those of the installed mods against the planned ones.
+async fn check_incompatibilities(
+    provider: &dyn ModrinthVersionProvider,
+    plan: &mut ModrinthInstallPlan,
+    planned_versions: &[ModrinthVersion],
+    installed: &[InstalledModrinthMod],
+) {
+    // Installed versions that get replaced by the plan no longer count
+    let replaced: HashSet<&str> = plan
+        .mods
+        .iter()
+        .filter_map(|m| m.replaces_version_id.as_deref())
+        .collect();
+    let remaining_installed: Vec<&InstalledModrinthMod> = installed
+        .iter()
+        .filter(|m| m.enabled && !replaced.contains(m.version_id.as_str()))
+        .collect();
+
+    for version in planned_versions {
+        for dependency in version
+            .dependencies
+            .iter()
+            .filter(|d| d.dependency_type == ModrinthDependencyType::Incompatible)
+        {
+            let dep_project = dependency.project_id.as_deref();
+            let dep_version = dependency.version_id.as_deref();
+
+            for existing in &remaining_installed {
+                if dependency_matches(
+                    dep_project,
+                    dep_version,
+                    &existing.project_id,
+                    &existing.version_id,
+                ) {
+                    plan.conflicts.push(DependencyConflict {
+                        kind: ConflictKind::Incompatible,
+                        project_id: version.project_id.clone(),
+                        other_project_id: Some(existing.project_id.clone()),
+                        message: format!(
+                            "{} is incompatible with installed mod {}",
+                            version.name, existing.name
+                        ),
+                    });
+                }
+            }
+            for other in planned_versions.iter().filter(|v| v.id != version.id) {
+                if dependency_matches(dep_project, dep_version, &other.project_id, &other.id) {
+                    plan.conflicts.push(DependencyConflict {
+                        kind: ConflictKind::Incompatible,
+                        project_id: version.project_id.clone(),
+                        other_project_id: Some(other.project_id.clone()),
+                        message: format!("{} is incompatible with {}", version.name, other.name),
+                    });
+                }
+            }
+        }
+    }
+
+    if remaining_installed.is_empty() {
+        return;
+    }
+
+    let installed_ids: Vec<String> = remaining_installed
+        .iter()
+        .map(|m| m.version_id.clone())
+        .collect();
+    let installed_versions = match provider.get_versions(&installed_ids).await {
+        Ok(versions) => versions,
+        Err(e) => {
+            warn!(
+                "Could not fetch installed mod versions, skipping their incompatibility declarations: {}",
+                e
+            );
+            return;
+        }
+    };
+
+    for installed_version in &installed_versions {
+        for dependency in installed_version
+            .dependencies
+            .iter()
+            .filter(|d| d.dependency_type == ModrinthDependencyType::Incompatible)
+        {
+            for planned in planned_versions {
+                if dependency_matches(
+                    dependency.project_id.as_deref(),
+                    dependency.version_id.as_deref(),
+                    &planned.project_id,
+                    &planned.id,
+                ) {
+                    plan.conflicts.push(DependencyConflict {
+                        kind: ConflictKind::Incompatible,
+                        project_id: planned.project_id.clone(),
+                        other_project_id: Some(installed_version.project_id.clone()),
+                        message: format!(
+                            "Installed mod {} is incompatible with {}",
+                            installed_version.name, planned.name
+                        ),
+                    });
+                }
+            }
+        }
+    }
+}
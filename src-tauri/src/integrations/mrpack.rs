@@ -1,7 +1,8 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::integrations::modrinth;
 use crate::state::profile_state::{
-    Mod, ModLoader, ModSource, Profile, ProfileSettings, ProfileState,
+    self, Mod, ModLoader, ModSource, Profile, ProfileSettings, ProfileState,
 };
 use crate::state::state_manager::State;
 use async_zip::tokio::read::seek::ZipFileReader;
@@ -342,9 +343,20 @@ pub async fn resolve_manifest_files(manifest: &ModrinthIndex) -> Result<Vec<Mod>
 /// Extracts files from the "overrides" or "client-overrides" directory within a .mrpack archive
 /// into the specified target profile directory, using concurrent streaming operations.
 pub async fn extract_mrpack_overrides(pack_path: &Path, profile: &Profile) -> Result<()> {
+    extract_pack_overrides(pack_path, profile, &["overrides/", "client-overrides/"]).await
+}
+
+/// Extracts all archive entries below one of the given prefixes (e.g. "overrides/") into the
+/// profile directory. Shared by the mrpack and CurseForge importers; `mods/` entries are
+/// redirected to `custom_mods/` so they are not touched by the mod sync.
+pub async fn extract_pack_overrides(
+    pack_path: &Path,
+    profile: &Profile,
+    override_prefixes: &[&str],
+) -> Result<()> {
     info!(
-        "Extracting overrides for profile '{}' from {:?} using concurrent streaming...",
-        profile.name, pack_path
+        "Extracting overrides {:?} for profile '{}' from {:?} using concurrent streaming...",
+        override_prefixes, profile.name, pack_path
     );
     let state = State::get().await?;
     let io_semaphore = state.io_semaphore.clone();
@@ -420,14 +432,13 @@ pub async fn extract_mrpack_overrides(pack_path: &Path, profile: &Profile) -> Re
             entry_uncompressed_size = entry.uncompressed_size();
         }
 
-        let (is_override_type, path_prefix_to_strip) =
-            if entry_filename_str.starts_with("overrides/") {
-                (true, "overrides/")
-            } else if entry_filename_str.starts_with("client-overrides/") {
-                (true, "client-overrides/")
-            } else {
-                (false, "")
-            };
+        let (is_override_type, path_prefix_to_strip) = match override_prefixes
+            .iter()
+            .find(|prefix| entry_filename_str.starts_with(**prefix))
+        {
+            Some(prefix) => (true, *prefix),
+            None => (false, ""),
+        };
 
         if is_override_type {
             let path_after_prefix = match entry_filename_str.strip_prefix(path_prefix_to_strip) {
@@ -903,3 +914,402 @@ pub async fn download_and_process_mrpack(download_url: &str, file_name: &str) ->
     // Return the profile ID
     Ok(profile_id)
 }
+
+// --- Export ---
+
+const MOD_CACHE_DIR_NAME: &str = "mod_cache";
+
+/// Instance directories whose content is managed by the launcher itself and therefore
+/// never copied as-is into `overrides/` (mods are exported through the index or `custom_mods`).
+const MANAGED_EXPORT_DIRS: [&str; 2] = ["mods", "custom_mods"];
+
+/// Builds the mrpack `dependencies` map from the profile's game version and loader.
+pub fn dependencies_for_profile(profile: &Profile) -> Result<HashMap<String, String>> {
+    let mut dependencies = HashMap::new();
+    dependencies.insert(
+        MINECRAFT_DEPENDENCY.to_string(),
+        profile.game_version.clone(),
+    );
+
+    let loader_key = match profile.loader {
+        ModLoader::Vanilla => None,
+        ModLoader::Fabric => Some(FABRIC_LOADER_DEPENDENCY),
+        ModLoader::Quilt => Some(QUILT_LOADER_DEPENDENCY),
+        ModLoader::Forge => Some(FORGE_DEPENDENCY),
+        ModLoader::NeoForge => Some(NEOFORGE_DEPENDENCY),
+    };
+
+    if let Some(key) = loader_key {
+        let loader_version = profile.loader_version.clone().ok_or_else(|| {
+            AppError::MrpackError(format!(
+                "Profile '{}' uses {} but has no loader version set",
+                profile.name,
+                profile.loader.as_str()
+            ))
+        })?;
+        // Forge versions are stored maven-style ("1.20.1-47.2.0"), mrpack expects the plain version
+        let loader_version = match profile.loader {
+            ModLoader::Forge => loader_version
+                .strip_prefix(&format!("{}-", profile.game_version))
+                .map(str::to_string)
+                .unwrap_or(loader_version),
+            _ => loader_version,
+        };
+        dependencies.insert(key.to_string(), loader_version);
+    }
+
+    Ok(dependencies)
+}
+
+/// Returns the mrpack `env` markers for a project that only runs on one side.
+/// Projects supported on both sides get no markers (the spec treats that as required everywhere).
+pub fn env_for_project(project: &modrinth::ModrinthProject) -> Option<HashMap<String, String>> {
+    env_for_sides(&project.client_side, &project.server_side)
+}
+
+/// `env_for_project` for the raw `client_side`/`server_side` values of a project
+pub fn env_for_sides(client_side: &str, server_side: &str) -> Option<HashMap<String, String>> {
+    let (client, server) = match (client_side, server_side) {
+        (_, "unsupported") => ("required", "unsupported"),
+        ("unsupported", _) => ("unsupported", "required"),
+        _ => return None,
+    };
+    Some(HashMap::from([
+        ("client".to_string(), client.to_string()),
+        ("server".to_string(), server.to_string()),
+    ]))
+}
+
+/// Builds the index entry for a Modrinth mod. Hashes are taken from the cached jar if it
+/// exists and matches, otherwise from the Modrinth API.
+async fn build_index_file_for_modrinth_mod(
+    version_id: &str,
+    file_name: &str,
+    download_url: &str,
+    expected_sha1: Option<&str>,
+    enabled: bool,
+) -> Result<ModrinthIndexFile> {
+    let cache_path = LAUNCHER_DIRECTORY
+        .meta_dir()
+        .join(MOD_CACHE_DIR_NAME)
+        .join(file_name);
+
+    let mut hashes = HashMap::new();
+    let mut file_size = 0;
+
+    if cache_path.exists() {
+        let sha1 = crate::utils::hash_utils::calculate_sha1(&cache_path).await?;
+        if expected_sha1.map_or(true, |expected| expected.eq_ignore_ascii_case(&sha1)) {
+            let sha512 = crate::utils::hash_utils::calculate_sha512_from_file(&cache_path).await?;
+            file_size = fs::metadata(&cache_path).await?.len();
+            hashes.insert("sha1".to_string(), sha1);
+            hashes.insert("sha512".to_string(), sha512);
+        } else {
+            warn!(
+                "Cached file {:?} does not match the expected SHA1, using Modrinth API hashes instead",
+                cache_path
+            );
+        }
+    }
+
+    if hashes.is_empty() {
+        debug!(
+            "Fetching hashes for {} from Modrinth version {}",
+            file_name, version_id
+        );
+        let version = modrinth::get_version_details(version_id.to_string()).await?;
+        let file = version
+            .files
+            .iter()
+            .find(|f| f.filename == file_name)
+            .or_else(|| version.files.iter().find(|f| f.primary))
+            .ok_or_else(|| {
+                AppError::MrpackError(format!(
+                    "Modrinth version {} has no file '{}'",
+                    version_id, file_name
+                ))
+            })?;
+        match (&file.hashes.sha1, &file.hashes.sha512) {
+            (Some(sha1), Some(sha512)) => {
+                hashes.insert("sha1".to_string(), sha1.clone());
+                hashes.insert("sha512".to_string(), sha512.clone());
+            }
+            _ => {
+                return Err(AppError::MrpackError(format!(
+                    "Modrinth did not provide sha1/sha512 hashes for '{}'",
+                    file_name
+                )))
+            }
+        }
+        file_size = file.size;
+    }
+
+    let mut path = format!("mods/{}", file_name);
+    if !enabled {
+        // Picked up again as disabled by `resolve_manifest_files`
+        path.push_str(".disabled");
+    }
+
+    Ok(ModrinthIndexFile {
+        path,
+        hashes,
+        env: None,
+        downloads: vec![download_url.to_string()],
+        file_size,
+    })
+}
+
+/// Exports a profile as a `.mrpack` that other launchers can import.
+///
+/// - Modrinth mods are referenced in `modrinth.index.json` (sha1/sha512, download URL, env markers)
+/// - Custom mods and mods from other sources are stored under `overrides/mods/`
+/// - Files selected via `include_files` are stored under `overrides/`
+pub async fn export_profile_to_mrpack(
+    profile_id: Uuid,
+    output_path: PathBuf,
+    include_files: Option<Vec<PathBuf>>,
+) -> Result<PathBuf> {
+    info!("Exporting profile {} to .mrpack", profile_id);
+
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+
+    let dependencies = dependencies_for_profile(&profile)?;
+
+    // 1. Modrinth mods -> index entries, everything else we can find locally -> overrides/mods
+    let mut index_files = Vec::new();
+    let mut override_entries: Vec<(String, PathBuf)> = Vec::new();
+    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME);
+
+    let mut project_ids_by_path = HashMap::new();
+    // Mods that are neither on Modrinth nor in the mod cache, reported instead of dropped
+    let mut unexportable = Vec::new();
+    for mod_info in &profile.mods {
+        match &mod_info.source {
+            ModSource::Modrinth {
+                project_id,
+                version_id,
+                file_name,
+                download_url,
+                file_hash_sha1,
+            } => {
+                let index_file = build_index_file_for_modrinth_mod(
+                    version_id,
+                    file_name,
+                    download_url,
+                    file_hash_sha1.as_deref(),
+                    mod_info.enabled,
+                )
+                .await?;
+                project_ids_by_path.insert(index_file.path.clone(), project_id.clone());
+                index_files.push(index_file);
+            }
+            ModSource::Embedded { name } => {
+                debug!(
+                    "Embedded mod '{}' comes with the loader, not exporting it",
+                    name
+                );
+            }
+            // CurseForge, URL, Maven and local mods aren't on the mrpack download whitelist,
+            // ship the cached jar instead
+            other => {
+                let cache_path = profile_state::get_profile_mod_filename(other)
+                    .ok()
+                    .map(|file_name| (mod_cache_dir.join(&file_name), file_name))
+                    .filter(|(cache_path, _)| cache_path.is_file());
+                match cache_path {
+                    Some((cache_path, file_name)) => {
+                        let suffix = if mod_info.enabled { "" } else { ".disabled" };
+                        override_entries.push((
+                            format!("overrides/mods/{}{}", file_name, suffix),
+                            cache_path,
+                        ));
+                    }
+                    None => unexportable.push(mod_info),
+                }
+            }
+        }
+    }
+
+    // Env markers for client-/server-only projects
+    let project_ids: Vec<String> = project_ids_by_path
+        .values()
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    match modrinth::get_multiple_projects(project_ids).await {
+        Ok(projects) => {
+            let projects_by_id: HashMap<&str, &modrinth::ModrinthProject> =
+                projects.iter().map(|p| (p.id.as_str(), p)).collect();
+            for index_file in index_files.iter_mut() {
+                if let Some(project) = project_ids_by_path
+                    .get(&index_file.path)
+                    .and_then(|id| projects_by_id.get(id.as_str()))
+                {
+                    index_file.env = env_for_project(project);
+                }
+            }
+        }
+        Err(e) => warn!(
+            "Failed to fetch Modrinth projects for env markers, exporting without them: {}",
+            e
+        ),
+    }
+
+    // Custom mods (custom_mods/ -> overrides/mods/)
+    let mut custom_mod_names = HashSet::new();
+    for custom_mod in state.profile_manager.list_custom_mods(&profile).await? {
+        if let Some(file_name) = custom_mod.path.file_name().and_then(|n| n.to_str()) {
+            custom_mod_names.insert(file_name.trim_end_matches(".disabled").to_string());
+            override_entries.push((format!("overrides/mods/{}", file_name), custom_mod.path.clone()));
+        }
+    }
+
+    // Local mod entries usually point at a custom mod that is exported above
+    unexportable.retain(|mod_info| match &mod_info.source {
+        ModSource::Local { file_name } => !custom_mod_names.contains(file_name),
+        _ => true,
+    });
+    if !unexportable.is_empty() {
+        let names: Vec<String> = unexportable
+            .iter()
+            .map(|m| {
+                m.display_name
+                    .clone()
+                    .or_else(|| profile_state::get_profile_mod_filename(&m.source).ok())
+                    .unwrap_or_else(|| m.id.to_string())
+            })
+            .collect();
+        return Err(AppError::MrpackError(format!(
+            "These mods are not on Modrinth and not in the mod cache, so they can't be exported: {}",
+            names.join(", ")
+        )));
+    }
+
+    // 2. User selected files -> overrides/
+    if let Some(include_paths) = include_files {
+        override_entries.extend(collect_override_files(&instance_path, &include_paths).await?);
+    }
+
+    // 3. Write archive
+    let index = ModrinthIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: Utc::now().format("%Y.%m.%d").to_string(),
+        name: profile.name.clone(),
+        summary: profile.description.clone(),
+        files: index_files,
+        dependencies,
+    };
+
+    let _permit = state.io_semaphore.acquire().await;
+    write_mrpack(&output_path, &index, &override_entries).await?;
+
+    info!("Successfully exported profile to: {}", output_path.display());
+    Ok(output_path)
+}
+
+/// Returns the instance files selected via `include_paths` as `(zip path, source path)`
+/// pairs under `overrides/`. Directories managed by the launcher are never included.
+pub async fn collect_override_files(
+    instance_path: &Path,
+    include_paths: &[PathBuf],
+) -> Result<Vec<(String, PathBuf)>> {
+    let include_rel_paths: Vec<&Path> = include_paths
+        .iter()
+        .filter_map(|p| p.strip_prefix(instance_path).ok())
+        .collect();
+
+    let mut all_files = Vec::new();
+    crate::utils::profile_utils::collect_all_files_recursive(instance_path, &mut all_files).await?;
+
+    let mut override_entries = Vec::new();
+    for file_path in all_files {
+        let Ok(rel_path) = file_path.strip_prefix(instance_path) else {
+            continue;
+        };
+        let rel_path_str = rel_path.to_string_lossy().replace('\\', "/");
+        let is_managed = MANAGED_EXPORT_DIRS
+            .iter()
+            .any(|dir| rel_path_str.starts_with(&format!("{}/", dir)));
+        // Whole components, so `config` doesn't pull in `config_backup`
+        if is_managed
+            || !include_rel_paths
+                .iter()
+                .any(|include| rel_path.starts_with(include))
+        {
+            continue;
+        }
+        override_entries.push((format!("overrides/{}", rel_path_str), file_path));
+    }
+    Ok(override_entries)
+}
+
+/// Writes `modrinth.index.json` and the override files into a new `.mrpack` at `output_path`.
+/// When several override files map to the same zip path, only the first one is written.
+pub async fn write_mrpack(
+    output_path: &Path,
+    index: &ModrinthIndex,
+    override_entries: &[(String, PathBuf)],
+) -> Result<()> {
+    use async_zip::tokio::write::ZipFileWriter;
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    info!(
+        "Creating .mrpack archive at {} ({} index files, {} override files)",
+        output_path.display(),
+        index.files.len(),
+        override_entries.len()
+    );
+
+    let mut file = File::create(output_path).await?;
+    let mut writer = ZipFileWriter::with_tokio(&mut file);
+
+    let index_json = serde_json::to_vec_pretty(&index)?;
+    writer
+        .write_entry_whole(
+            ZipEntryBuilder::new("modrinth.index.json".into(), Compression::Deflate),
+            &index_json,
+        )
+        .await
+        .map_err(|e| {
+            AppError::MrpackError(format!("Failed to write modrinth.index.json: {}", e))
+        })?;
+
+    let mut written = HashSet::from(["modrinth.index.json".to_string()]);
+    for (zip_path, source_path) in override_entries {
+        if !written.insert(zip_path.clone()) {
+            warn!(
+                "Skipping {:?}, {} is already part of the mrpack",
+                source_path, zip_path
+            );
+            continue;
+        }
+        debug!("Adding {:?} as {}", source_path, zip_path);
+        let data = fs::read(source_path).await?;
+        writer
+            .write_entry_whole(
+                ZipEntryBuilder::new(zip_path.clone().into(), Compression::Deflate),
+                &data,
+            )
+            .await
+            .map_err(|e| {
+                AppError::MrpackError(format!("Failed to write {} to mrpack: {}", zip_path, e))
+            })?;
+    }
+
+    writer
+        .close()
+        .await
+        .map_err(|e| AppError::MrpackError(format!("Failed to finalize mrpack: {}", e)))?;
+    Ok(())
+}
@@ -4,12 +4,9 @@ use crate::state::profile_state::Profile;
 use crate::state::state_manager::State;
 use log::{self, error, info, warn};
 use serde::{Deserialize, Serialize};
-use std::env;
 use std::path::PathBuf;
 use tokio::fs;
 
-const NORISK_API_BASE_URL: &str = "https://api.noriskclient.com/v1";
-
 /// Helper to compute versions file path based on experimental flag
 fn norisk_versions_path_for(is_experimental: bool) -> PathBuf {
     let filename = if is_experimental {
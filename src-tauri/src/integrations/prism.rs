@@ -0,0 +1,396 @@
+use crate::error::{AppError, Result};
+use crate::integrations::modrinth;
+use crate::state::profile_state::{
+    Mod, ModLoader, ModSource, Profile, ProfileSettings, ProfileState,
+};
+use crate::state::state_manager::State;
+use crate::utils::{hash_utils, path_utils};
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use sanitize_filename::sanitize;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+pub const INSTANCE_CFG_FILE: &str = "instance.cfg";
+pub const MMC_PACK_FILE: &str = "mmc-pack.json";
+
+// Component uids used in mmc-pack.json
+const MINECRAFT_UID: &str = "net.minecraft";
+const FABRIC_LOADER_UID: &str = "net.fabricmc.fabric-loader";
+const QUILT_LOADER_UID: &str = "org.quiltmc.quilt-loader";
+const FORGE_UID: &str = "net.minecraftforge";
+const NEOFORGE_UID: &str = "net.neoforged";
+
+/// Represents the mmc-pack.json of a Prism Launcher / MultiMC instance.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MmcPack {
+    #[serde(default)]
+    pub format_version: Option<u32>,
+    #[serde(default)]
+    pub components: Vec<MmcPackComponent>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MmcPackComponent {
+    pub uid: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub cached_name: Option<String>,
+}
+
+/// Parses the key/value pairs of an instance.cfg. Prism writes a `[General]` section,
+/// older MultiMC versions write the keys without any section.
+pub fn parse_instance_cfg(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('[') && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// Determines game version, loader and loader version from the instance components.
+/// Forge versions are stored maven-style ("{mc}-{forge}") like the rest of the launcher expects.
+pub fn determine_versions_from_components(
+    pack: &MmcPack,
+) -> Result<(String, ModLoader, Option<String>)> {
+    let component_version = |uid: &str| {
+        pack.components
+            .iter()
+            .find(|c| c.uid == uid)
+            .and_then(|c| c.version.clone())
+    };
+
+    let game_version = component_version(MINECRAFT_UID).ok_or_else(|| {
+        AppError::Other(format!(
+            "{} does not contain a {} component",
+            MMC_PACK_FILE, MINECRAFT_UID
+        ))
+    })?;
+
+    let (loader, loader_version) = if let Some(version) = component_version(FABRIC_LOADER_UID) {
+        (ModLoader::Fabric, Some(version))
+    } else if let Some(version) = component_version(QUILT_LOADER_UID) {
+        (ModLoader::Quilt, Some(version))
+    } else if let Some(version) = component_version(NEOFORGE_UID) {
+        (ModLoader::NeoForge, Some(version))
+    } else if let Some(version) = component_version(FORGE_UID) {
+        let version = if version.starts_with(&format!("{}-", game_version)) {
+            version
+        } else {
+            format!("{}-{}", game_version, version)
+        };
+        (ModLoader::Forge, Some(version))
+    } else {
+        (ModLoader::Vanilla, None)
+    };
+
+    Ok((game_version, loader, loader_version))
+}
+
+/// Returns true if the directory looks like a Prism Launcher / MultiMC instance.
+pub fn is_prism_instance(instance_dir: &Path) -> bool {
+    instance_dir.join(INSTANCE_CFG_FILE).is_file() && instance_dir.join(MMC_PACK_FILE).is_file()
+}
+
+/// Builds the profile settings from the instance.cfg overrides.
+fn settings_from_instance_cfg(cfg: &HashMap<String, String>) -> ProfileSettings {
+    let mut settings = ProfileSettings::default();
+    let is_enabled = |key: &str| {
+        cfg.get(key)
+            .map_or(false, |v| v.eq_ignore_ascii_case("true"))
+    };
+
+    if is_enabled("OverrideMemory") {
+        if let Some(max) = cfg.get("MaxMemAlloc").and_then(|v| v.parse().ok()) {
+            settings.memory.max = max;
+        }
+        if let Some(min) = cfg.get("MinMemAlloc").and_then(|v| v.parse().ok()) {
+            settings.memory.min = min;
+        }
+    }
+    if is_enabled("OverrideJavaArgs") {
+        settings.custom_jvm_args = cfg.get("JvmArgs").filter(|v| !v.is_empty()).cloned();
+    }
+    if is_enabled("OverrideJavaLocation") {
+        if let Some(java_path) = cfg.get("JavaPath").filter(|v| !v.is_empty()) {
+            settings.java_path = Some(java_path.clone());
+            settings.use_custom_java_path = true;
+        }
+    }
+
+    settings
+}
+
+/// Hashes the mod jars (enabled and disabled) of an instance's mods directory.
+/// Identical jars share one entry. Jars that can't be hashed are returned separately.
+pub async fn hash_mod_jars(
+    mods_dir: &Path,
+) -> Result<(HashMap<String, Vec<PathBuf>>, Vec<PathBuf>)> {
+    let mut hash_to_paths: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let mut unhashed = Vec::new();
+
+    if mods_dir.is_dir() {
+        let mut entries = fs::read_dir(mods_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !path.is_file()
+                || !(file_name.ends_with(".jar") || file_name.ends_with(".jar.disabled"))
+            {
+                continue;
+            }
+            match hash_utils::calculate_sha1(&path).await {
+                Ok(hash) => hash_to_paths.entry(hash).or_default().push(path),
+                Err(e) => {
+                    warn!(
+                        "Failed to hash {:?}, importing it as custom mod: {}",
+                        path, e
+                    );
+                    unhashed.push(path);
+                }
+            }
+        }
+    }
+
+    // Stable order, enabled copies first
+    for paths in hash_to_paths.values_mut() {
+        paths.sort_by_key(|path| (path.to_string_lossy().ends_with(".disabled"), path.clone()));
+    }
+    Ok((hash_to_paths, unhashed))
+}
+
+/// Looks up the instance's mod jars on Modrinth by SHA1.
+/// Returns the identified mods and the paths of all jars that have to be imported as custom mods.
+async fn resolve_instance_mods(
+    mods_dir: &Path,
+    game_version: &str,
+    loader: ModLoader,
+) -> Result<(Vec<Mod>, Vec<PathBuf>)> {
+    let (hash_to_paths, mut unresolved) = hash_mod_jars(mods_dir).await?;
+
+    if hash_to_paths.is_empty() {
+        return Ok((Vec::new(), unresolved));
+    }
+
+    info!(
+        "Looking up {} instance mods on Modrinth by hash...",
+        hash_to_paths.len()
+    );
+    let versions_map =
+        match modrinth::get_versions_by_hashes(hash_to_paths.keys().cloned().collect(), "sha1")
+            .await
+        {
+            Ok(map) => map,
+            Err(e) => {
+                error!(
+                    "Modrinth hash lookup failed, importing all mods as custom mods: {}",
+                    e
+                );
+                unresolved.extend(hash_to_paths.into_values().flatten());
+                return Ok((Vec::new(), unresolved));
+            }
+        };
+
+    let mut mods = Vec::new();
+    for (hash, mut paths) in hash_to_paths {
+        let Some(version) = versions_map.get(&hash) else {
+            debug!(
+                "{:?} not found on Modrinth, importing as custom mods",
+                paths
+            );
+            unresolved.extend(paths);
+            continue;
+        };
+        // A profile holds a Modrinth version once, further copies of the jar stay custom mods
+        let path = paths.remove(0);
+        unresolved.extend(paths);
+        let Some(file) = version
+            .files
+            .iter()
+            .find(|f| f.hashes.sha1.as_deref() == Some(hash.as_str()))
+            .or_else(|| version.files.iter().find(|f| f.primary))
+        else {
+            unresolved.push(path);
+            continue;
+        };
+
+        let enabled = !path.to_string_lossy().ends_with(".disabled");
+        mods.push(Mod {
+            id: Uuid::new_v4(),
+            source: ModSource::Modrinth {
+                project_id: version.project_id.clone(),
+                version_id: version.id.clone(),
+                file_name: file.filename.clone(),
+                download_url: file.url.clone(),
+                file_hash_sha1: Some(hash.clone()),
+            },
+            enabled,
+            display_name: Some(version.name.clone()),
+            version: Some(version.version_number.clone()),
+            game_versions: Some(vec![game_version.to_string()]),
+            file_name_override: None,
+            associated_loader: Some(loader),
+        });
+    }
+
+    info!(
+        "Identified {} Modrinth mods, {} mods will be imported as custom mods",
+        mods.len(),
+        unresolved.len()
+    );
+    Ok((mods, unresolved))
+}
+
+/// Imports a Prism Launcher / MultiMC instance directory as a new profile.
+///
+/// Versions are taken from mmc-pack.json, settings from instance.cfg. The game directory
+/// (`.minecraft` or `minecraft`) is copied into the new profile, except for `mods/`:
+/// mods found on Modrinth become regular profile mods, everything else goes to `custom_mods/`.
+pub async fn import_prism_instance(instance_dir: PathBuf) -> Result<Uuid> {
+    info!(
+        "Starting import of Prism/MultiMC instance: {:?}",
+        instance_dir
+    );
+
+    // 1. Read instance metadata
+    let cfg_content = fs::read_to_string(instance_dir.join(INSTANCE_CFG_FILE))
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to read {} in {:?}: {}",
+                INSTANCE_CFG_FILE, instance_dir, e
+            );
+            AppError::Io(e)
+        })?;
+    let cfg = parse_instance_cfg(&cfg_content);
+
+    let pack_content = fs::read_to_string(instance_dir.join(MMC_PACK_FILE))
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to read {} in {:?}: {}",
+                MMC_PACK_FILE, instance_dir, e
+            );
+            AppError::Io(e)
+        })?;
+    let pack: MmcPack = serde_json::from_str(&pack_content)?;
+    let (game_version, loader, loader_version) = determine_versions_from_components(&pack)?;
+    info!(
+        "Determined requirements: MC={}, Loader={:?}, LoaderVersion={:?}",
+        game_version, loader, loader_version
+    );
+
+    let game_dir = [".minecraft", "minecraft"]
+        .iter()
+        .map(|name| instance_dir.join(name))
+        .find(|path| path.is_dir());
+
+    // 2. Build the profile
+    let name = cfg
+        .get("name")
+        .cloned()
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| {
+            instance_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Imported Instance".to_string())
+        });
+
+    let mut profile = Profile {
+        id: Uuid::new_v4(),
+        name: name.clone(),
+        path: String::new(),
+        game_version: game_version.clone(),
+        loader,
+        loader_version,
+        created: Utc::now(),
+        last_played: None,
+        settings: settings_from_instance_cfg(&cfg),
+        state: ProfileState::NotInstalled,
+        mods: Vec::new(),
+        selected_norisk_pack_id: None,
+        disabled_norisk_mods_detailed: HashSet::new(),
+        source_standard_profile_id: None,
+        group: None,
+        is_standard_version: false,
+        description: cfg.get("notes").cloned().filter(|n| !n.is_empty()),
+        norisk_information: None,
+        banner: None,
+        background: None,
+    };
+
+    let base_profiles_dir = crate::state::profile_state::default_profile_path();
+    let mut base_name = sanitize(&name);
+    if base_name.is_empty() {
+        base_name = format!("imported-instance-{}", Utc::now().timestamp_millis());
+    }
+    profile.path = path_utils::find_unique_profile_segment(&base_profiles_dir, &base_name).await?;
+    let target_dir = base_profiles_dir.join(&profile.path);
+    fs::create_dir_all(&target_dir).await?;
+    info!("Importing instance into profile directory {:?}", target_dir);
+
+    // 3. Copy game directory content and resolve mods
+    if let Some(game_dir) = game_dir {
+        let state = State::get().await?;
+
+        let mut entries = fs::read_dir(&game_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let source = entry.path();
+            let file_name = entry.file_name();
+            if file_name == "mods" {
+                continue; // handled below
+            }
+            let destination = target_dir.join(&file_name);
+            if source.is_dir() {
+                path_utils::copy_dir_recursively(&source, &destination, state.io_semaphore.clone())
+                    .await?;
+            } else {
+                fs::copy(&source, &destination).await?;
+            }
+        }
+
+        let (mods, custom_mod_paths) =
+            resolve_instance_mods(&game_dir.join("mods"), &game_version, loader).await?;
+        profile.mods = mods;
+
+        if !custom_mod_paths.is_empty() {
+            let custom_mods_dir = target_dir.join("custom_mods");
+            fs::create_dir_all(&custom_mods_dir).await?;
+            for source in custom_mod_paths {
+                if let Some(file_name) = source.file_name() {
+                    fs::copy(&source, custom_mods_dir.join(file_name)).await?;
+                }
+            }
+        }
+    } else {
+        warn!(
+            "Instance {:?} has no .minecraft directory, importing versions and settings only",
+            instance_dir
+        );
+    }
+
+    // 4. Save the profile
+    let state = State::get().await?;
+    let profile_id = state.profile_manager.create_profile(profile).await?;
+    info!(
+        "Successfully imported Prism/MultiMC instance as profile {}",
+        profile_id
+    );
+    Ok(profile_id)
+}
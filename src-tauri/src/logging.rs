@@ -18,7 +18,8 @@ const LOG_FILE_BACKUP_COUNT: u32 = 5;
 
 /// Initializes the logging system using log4rs.
 /// Configures a rolling file appender and a console appender.
-pub async fn setup_logging() -> Result<(), Box<dyn std::error::Error>> {
+/// In headless mode the console logs go to stderr, stdout is reserved for the command output.
+pub async fn setup_logging(headless: bool) -> Result<(), Box<dyn std::error::Error>> {
     let log_dir = LAUNCHER_DIRECTORY.root_dir().join(LOG_DIR_NAME);
 
     // Ensure the log directory exists
@@ -47,9 +48,14 @@ pub async fn setup_logging() -> Result<(), Box<dyn std::error::Error>> {
         .build(log_file_path, Box::new(compound_policy))?;
 
     // --- Configure Console Appender ---
+    let console_target = if headless {
+        Target::Stderr
+    } else {
+        Target::Stdout
+    };
     let console_appender = ConsoleAppender::builder()
         .encoder(Box::new(PatternEncoder::new(CONSOLE_LOG_PATTERN)))
-        .target(Target::Stdout)
+        .target(console_target)
         .build();
 
     // --- Configure log4rs ---
@@ -4,6 +4,7 @@
     windows_subsystem = "windows"
 )]
 
+mod cli;
 mod commands;
 mod config;
 mod error;
@@ -23,7 +24,9 @@ use utils::debug_utils;
 use utils::updater_utils;
 
 use crate::commands::process_command::{
-    get_full_log, get_process, get_processes, get_processes_by_profile, open_log_window,
+    analyze_profile_crash, get_full_log, get_play_sessions, get_playtime_per_profile,
+    get_playtime_per_server, get_playtime_per_week, get_process, get_process_resource_samples,
+    get_processes, get_processes_by_profile, get_session_history, open_log_window,
     set_discord_state, stop_process,
 };
 use commands::minecraft_auth_command::{
@@ -43,6 +46,7 @@ use commands::minecraft_command::{
     // Skin management commands
     get_user_skin_data,
     ping_minecraft_server,
+    preview_log_redaction,
     remove_skin,
     reset_skin,
     update_skin_properties,
@@ -51,16 +55,24 @@ use commands::minecraft_command::{
 };
 use commands::profile_command::{
     abort_profile_launch, add_modrinth_content_to_profile, add_modrinth_mod_to_profile,
-    batch_check_content_installed, check_world_lock_status, copy_profile, copy_world,
+    apply_modrinth_install_plan, apply_profile_updates, check_profile_updates,
+    list_profile_update_snapshots, rollback_profile_update, create_profile_snapshot,
+    delete_profile_snapshot, diff_profile_snapshots, list_profile_snapshots,
+    restore_profile_snapshot,
+    batch_check_content_installed, check_profile_offline_readiness, check_world_lock_status,
+    copy_profile, copy_world,
     create_profile, delete_custom_mod, delete_mod_from_profile, delete_profile, delete_world,
-    export_profile, get_all_profiles_and_last_played, get_custom_mods, get_local_content,
+    export_launch_command, export_profile, get_all_profiles_and_last_played, get_custom_mods,
+    get_local_content,
     get_local_datapacks, get_local_resourcepacks, get_local_shaderpacks, get_log_file_content,
     get_norisk_packs, get_norisk_packs_resolved, get_profile, get_profile_directory_structure,
     get_profile_latest_log_content, get_profile_log_files, get_servers_for_profile,
-    get_standard_profiles, get_system_ram_mb, get_worlds_for_profile, import_local_mods,
-    import_profile, import_profile_from_file, is_content_installed, is_profile_launching,
+    get_standard_profiles, get_system_ram_mb, get_worlds_for_profile, import_instance_from_folder,
+    import_local_mods, import_profile, import_profile_from_file, is_content_installed,
+    is_profile_launching,
     launch_profile, list_profile_screenshots, list_profiles, open_profile_folder,
-    open_profile_latest_log, refresh_norisk_packs, refresh_standard_versions, repair_profile,
+    open_profile_latest_log, query_log_file, refresh_norisk_packs, refresh_standard_versions,
+    repair_profile, resolve_modrinth_install_plan, search_profile_logs,
     search_profiles, set_custom_mod_enabled, set_norisk_mod_status, set_profile_mod_enabled,
     update_datapack_from_modrinth, update_modrinth_mod_version, update_profile,
     update_resourcepack_from_modrinth, update_shaderpack_from_modrinth,
@@ -108,15 +120,33 @@ use commands::content_command::{
 // Import Java commands
 use commands::java_command::{
     detect_java_installations_command, find_best_java_for_minecraft_command, get_java_info_command,
-    invalidate_java_cache_command, validate_java_path_command,
+    invalidate_java_cache_command, list_java_runtimes_command, prune_java_runtimes_command,
+    uninstall_java_runtime_command, upgrade_java_runtime_command, validate_java_path_command,
 };
 
 #[tokio::main]
 async fn main() {
-    if let Err(e) = logging::setup_logging().await {
+    // Headless mode (--list-profiles, --launch, ...) runs without any window
+    let cli_args = cli::parse_args(std::env::args().skip(1));
+
+    if let Err(e) = logging::setup_logging(cli_args.is_some()).await {
         eprintln!("FEHLER: Logging konnte nicht initialisiert werden: {}", e);
     }
 
+    if let Some(cli_args) = cli_args {
+        let exit_code = match cli_args {
+            Ok(args) => {
+                info!("Starting NoRiskClient Launcher in headless mode: {:?}", args);
+                cli::run(args).await
+            }
+            Err(e) => {
+                eprintln!("{}\nRun with --help for usage.", e);
+                cli::EXIT_USAGE
+            }
+        };
+        std::process::exit(exit_code);
+    }
+
     info!("Starting NoRiskClient Launcher...");
 
     tauri::Builder::default()
@@ -345,6 +375,7 @@ async fn main() {
             search_profiles,
             get_minecraft_versions,
             launch_profile,
+            check_profile_offline_readiness,
             abort_profile_launch,
             is_profile_launching,
             get_processes,
@@ -362,6 +393,17 @@ async fn main() {
             search_modrinth_projects,
             get_modrinth_mod_versions,
             add_modrinth_mod_to_profile,
+            resolve_modrinth_install_plan,
+            apply_modrinth_install_plan,
+            check_profile_updates,
+            apply_profile_updates,
+            rollback_profile_update,
+            list_profile_update_snapshots,
+            create_profile_snapshot,
+            list_profile_snapshots,
+            diff_profile_snapshots,
+            restore_profile_snapshot,
+            delete_profile_snapshot,
             add_modrinth_content_to_profile,
             get_modrinth_project_details,
             check_modrinth_updates,
@@ -374,6 +416,13 @@ async fn main() {
             update_modrinth_mod_version,
             get_all_modrinth_versions_for_contexts,
             get_full_log,
+            analyze_profile_crash,
+            get_process_resource_samples,
+            get_session_history,
+            get_play_sessions,
+            get_playtime_per_profile,
+            get_playtime_per_week,
+            get_playtime_per_server,
             get_custom_mods,
             get_local_resourcepacks,
             get_local_shaderpacks,
@@ -384,8 +433,10 @@ async fn main() {
             delete_custom_mod,
             open_profile_folder,
             import_profile_from_file,
-            import_profile, 
+            import_profile,
+            import_instance_from_folder, 
             upload_log_to_mclogs_command,
+            preview_log_redaction,
             get_fabric_loader_versions,
             get_forge_versions,
             get_neoforge_versions,
@@ -399,6 +450,7 @@ async fn main() {
             get_profile_directory_structure,
             copy_profile,
             export_profile,
+            export_launch_command,
             get_launcher_config,
             set_launcher_config,
             get_launcher_directory,
@@ -436,6 +488,10 @@ async fn main() {
             find_best_java_for_minecraft_command,
             invalidate_java_cache_command,
             validate_java_path_command,
+            list_java_runtimes_command,
+            uninstall_java_runtime_command,
+            prune_java_runtimes_command,
+            upgrade_java_runtime_command,
             get_worlds_for_profile,
             get_servers_for_profile,
             copy_world,
@@ -444,6 +500,8 @@ async fn main() {
             delete_world,
             get_profile_log_files,
             get_log_file_content,
+            query_log_file,
+            search_profile_logs,
             list_profile_screenshots,
             open_file,
             read_file_bytes,
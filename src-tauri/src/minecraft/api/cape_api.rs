@@ -1,6 +1,7 @@
 use crate::{
     config::HTTP_CLIENT,
     error::{AppError, Result},
+    utils::endpoint_registry::{self, Endpoint},
 };
 use log::{debug, error, info};
 use reqwest::StatusCode;
@@ -90,10 +91,10 @@ impl CapeApi {
     fn get_api_base(is_experimental: bool) -> String {
         if is_experimental {
             debug!("[Cape API] Using experimental API endpoint");
-            String::from("https://api-staging.norisk.gg/api/v1/cosmetics")
+            format!("{}/cosmetics", endpoint_registry::base_url(Endpoint::NoriskApiStaging))
         } else {
             debug!("[Cape API] Using production API endpoint");
-            String::from("https://api.norisk.gg/api/v1/cosmetics")
+            format!("{}/cosmetics", endpoint_registry::base_url(Endpoint::NoriskApi))
         }
     }
 
@@ -160,11 +161,13 @@ impl CapeApi {
             query_params
         );
 
-        let response = HTTP_CLIENT
-            .get(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&query_params)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .get(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&query_params)
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
@@ -225,11 +228,13 @@ impl CapeApi {
             query_params
         );
 
-        let response = HTTP_CLIENT
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&query_params)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(url), |url| {
+                HTTP_CLIENT
+                    .get(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&query_params)
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API get_player_capes] Request failed: {}", e);
@@ -302,11 +307,13 @@ impl CapeApi {
             query_params
         );
 
-        let response = HTTP_CLIENT
-            .post(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&query_params)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .post(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&query_params)
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
@@ -373,11 +380,13 @@ impl CapeApi {
             query_params
         );
 
-        let response = HTTP_CLIENT
-            .delete(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&query_params)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .delete(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&query_params)
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
@@ -458,12 +467,14 @@ impl CapeApi {
             query_params
         );
 
-        let response = HTTP_CLIENT
-            .post(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&query_params)
-            .body(image_data)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .post(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&query_params)
+                    .body(image_data.clone())
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
@@ -515,18 +526,17 @@ impl CapeApi {
         );
         debug!("[Cape API] Full URL: {}", url);
 
-        let response = HTTP_CLIENT
-            .get(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&[("hash", joined)])
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .get(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&[("hash", &joined)])
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
-                AppError::RequestError(format!(
-                    "Failed to send get capes by hashes request: {}",
-                    e
-                ))
+                AppError::RequestError(format!("Failed to send get capes by hashes request: {}", e))
             })?;
 
         let status = response.status();
@@ -583,17 +593,16 @@ impl CapeApi {
         );
         debug!("[Cape API] Full URL: {}", url);
 
-        let response = HTTP_CLIENT
-            .put(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .put(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
-                AppError::RequestError(format!(
-                    "Failed to send add favorite cape request: {}",
-                    e
-                ))
+                AppError::RequestError(format!("Failed to send add favorite cape request: {}", e))
             })?;
 
         let status = response.status();
@@ -656,10 +665,12 @@ impl CapeApi {
         );
         debug!("[Cape API] Full URL: {}", url);
 
-        let response = HTTP_CLIENT
-            .delete(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .delete(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
@@ -736,11 +747,13 @@ impl CapeApi {
         );
 
         // Note: Using DELETE method as per the original code for the unequip endpoint
-        let response = HTTP_CLIENT
-            .delete(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&query_params)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .delete(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&query_params)
+            })
             .await
             .map_err(|e| {
                 error!("[Cape API] Request failed: {}", e);
@@ -1,6 +1,8 @@
 use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::Result;
 use crate::minecraft::dto::fabric_meta::FabricVersionInfo;
+use crate::minecraft::offline;
+use crate::utils::endpoint_registry::{self, Endpoint};
 use log::{debug, error};
 use serde_json;
 use std::path::PathBuf;
@@ -20,7 +22,7 @@ impl FabricApi {
             });
         }
         Self {
-            base_url: "https://meta.fabricmc.net/v2".to_string(),
+            base_url: endpoint_registry::base_url(Endpoint::FabricMeta),
             cache_dir,
         }
     }
@@ -30,10 +32,23 @@ impl FabricApi {
         minecraft_version: &str,
         cache_path: &PathBuf,
     ) -> Result<Vec<FabricVersionInfo>> {
+        if offline::is_active() {
+            return Err(offline::missing_artifact(
+                format!("Fabric loader versions for Minecraft {}", minecraft_version),
+                cache_path,
+                None,
+            ));
+        }
+
         let url = format!("{}/versions/loader/{}", base_url, minecraft_version);
         debug!("Fetching Fabric versions from: {}", url);
 
-        let response = HTTP_CLIENT.get(&url).send().await.map_err(|e| {
+        let response = endpoint_registry::send_with_fallback(
+            &endpoint_registry::resolve_url(&url),
+            |url| HTTP_CLIENT.get(url),
+        )
+        .await
+        .map_err(|e| {
             crate::error::AppError::FabricError(format!("Failed to fetch Fabric versions: {}", e))
         })?;
 
@@ -99,9 +114,11 @@ impl FabricApi {
                             let minecraft_version = minecraft_version.to_string();
                             let cache_path_clone = cache_path.clone();
                             
-                            tokio::spawn(async move {
-                                Self::background_update(base_url, minecraft_version, cache_path_clone).await;
-                            });
+                            if !offline::is_active() {
+                                tokio::spawn(async move {
+                                    Self::background_update(base_url, minecraft_version, cache_path_clone).await;
+                                });
+                            }
                             
                             return Ok(cached_versions);
                         }
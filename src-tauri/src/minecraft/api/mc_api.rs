@@ -1,8 +1,10 @@
 use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::minecraft::offline;
 use crate::minecraft::dto::minecraft_profile::MinecraftProfile;
 use crate::minecraft::dto::piston_meta::PistonMeta;
 use crate::minecraft::dto::version_manifest::VersionManifest;
+use crate::utils::endpoint_registry::{self, Endpoint};
 use log::{debug, error};
 use reqwest;
 use serde::{Deserialize, Serialize};
@@ -16,9 +18,15 @@ use rand;
 use tokio;
 use tokio::fs as tokio_fs;
 
-const VERSION_MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
-const MOJANG_API_URL: &str = "https://api.mojang.com";
-const MOJANG_SESSION_URL: &str = "https://sessionserver.mojang.com";
+const VERSION_MANIFEST_PATH: &str = "mc/game/version_manifest.json";
+
+fn mojang_api_url() -> String {
+    endpoint_registry::base_url(Endpoint::MojangApi)
+}
+
+fn mojang_session_url() -> String {
+    endpoint_registry::base_url(Endpoint::MojangSession)
+}
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -44,10 +52,18 @@ impl MinecraftApiService {
     }
 
     async fn fetch_and_cache_manifest(cache_path: &PathBuf) -> Result<VersionManifest> {
-        debug!("Fetching Minecraft version manifest from: {}", VERSION_MANIFEST_URL);
+        if offline::is_active() {
+            return Err(offline::missing_artifact(
+                "Minecraft version manifest",
+                cache_path,
+                None,
+            ));
+        }
+
+        let urls = endpoint_registry::urls_for(Endpoint::PistonMeta, VERSION_MANIFEST_PATH);
+        debug!("Fetching Minecraft version manifest from: {:?}", urls);
         
-        let response = HTTP_CLIENT.get(VERSION_MANIFEST_URL)
-            .send()
+        let response = endpoint_registry::send_with_fallback(&urls, |url| HTTP_CLIENT.get(url))
             .await
             .map_err(AppError::MinecraftApi)?;
 
@@ -91,9 +107,11 @@ impl MinecraftApiService {
                             // Spawn background update
                             let cache_path_clone = cache_path.clone();
                             
-                            tokio::spawn(async move {
-                                Self::background_update(cache_path_clone).await;
-                            });
+                            if !offline::is_active() {
+                                tokio::spawn(async move {
+                                    Self::background_update(cache_path_clone).await;
+                                });
+                            }
                             
                             return Ok(cached_manifest);
                         }
@@ -114,12 +132,22 @@ impl MinecraftApiService {
     }
 
     async fn fetch_and_cache_piston_meta(cache_path: &PathBuf, url: &str) -> Result<PistonMeta> {
+        if offline::is_active() {
+            return Err(offline::missing_artifact(
+                format!("Minecraft version metadata ({})", url),
+                cache_path,
+                Some(url),
+            ));
+        }
+
         debug!("Fetching Piston Meta from: {}", url);
         
-        let response = HTTP_CLIENT.get(url)
-            .send()
-            .await
-            .map_err(AppError::MinecraftApi)?;
+        let response = endpoint_registry::send_with_fallback(
+            &endpoint_registry::resolve_url(url),
+            |url| HTTP_CLIENT.get(url),
+        )
+        .await
+        .map_err(AppError::MinecraftApi)?;
 
         let meta = response
             .json::<PistonMeta>()
@@ -166,9 +194,11 @@ impl MinecraftApiService {
                             let cache_path_clone = cache_path.clone();
                             let url_clone = url.to_string();
                             
-                            tokio::spawn(async move {
-                                Self::background_update_piston_meta(cache_path_clone, url_clone).await;
-                            });
+                            if !offline::is_active() {
+                                tokio::spawn(async move {
+                                    Self::background_update_piston_meta(cache_path_clone, url_clone).await;
+                                });
+                            }
                             
                             return Ok(cached_meta);
                         }
@@ -191,10 +221,15 @@ impl MinecraftApiService {
     // Get user profile including skin information
     pub async fn get_user_profile(&self, uuid: &str) -> Result<MinecraftProfile> {
         debug!("API call: get_user_profile for UUID: {}", uuid);
-        let url = format!("{}/session/minecraft/profile/{}", MOJANG_SESSION_URL, uuid);
+        let url = format!("{}/session/minecraft/profile/{}", mojang_session_url(), uuid);
         debug!("Request URL: {}", url);
 
-        let response = match HTTP_CLIENT.get(&url).send().await {
+        let response = match endpoint_registry::send_with_fallback(
+            &endpoint_registry::resolve_url(&url),
+            |url| HTTP_CLIENT.get(url),
+        )
+        .await
+        {
             Ok(resp) => {
                 debug!("Received response with status: {}", resp.status());
                 resp
@@ -239,11 +274,16 @@ impl MinecraftApiService {
         debug!("Query is likely a username. Attempting to resolve to UUID.");
         let username_lookup_url = format!(
             "{}/users/profiles/minecraft/{}",
-            MOJANG_API_URL, name_or_uuid_query
+            mojang_api_url(), name_or_uuid_query
         );
         debug!("Username lookup URL: {}", username_lookup_url);
 
-        let response = HTTP_CLIENT.get(&username_lookup_url).send().await.map_err(|e| {
+        let response = endpoint_registry::send_with_fallback(
+            &endpoint_registry::resolve_url(&username_lookup_url),
+            |url| HTTP_CLIENT.get(url),
+        )
+        .await
+        .map_err(|e| {
             debug!("Failed to call Mojang API for username lookup: {:?}", e);
             AppError::MinecraftApi(e)
         })?;
@@ -388,16 +428,18 @@ impl MinecraftApiService {
     pub async fn reset_skin(&self, access_token: &str, uuid: &str) -> Result<()> {
         debug!("API call: reset_skin for UUID: {}", uuid);
 
-        let url = format!("{}/user/profile/{}/skin", MOJANG_API_URL, uuid);
+        let url = format!("{}/user/profile/{}/skin", mojang_api_url(), uuid);
         debug!("Request URL: {}", url);
 
         let client = reqwest::Client::new();
         debug!("Sending skin reset request to Minecraft API");
 
-        let response_result = client
-            .delete(&url)
-            .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+        let response_result =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                client
+                    .delete(url)
+                    .header("Authorization", format!("Bearer {}", access_token))
+            })
             .await;
 
         if let Err(ref e) = response_result {
@@ -526,7 +568,7 @@ impl MinecraftApiService {
             selected_profile, server_id
         );
 
-        let url = format!("{}/session/minecraft/join", MOJANG_SESSION_URL);
+        let url = format!("{}/session/minecraft/join", mojang_session_url());
         debug!("Request URL: {}", url);
 
         let join_request = JoinServerRequest {
@@ -540,11 +582,13 @@ impl MinecraftApiService {
         let client = reqwest::Client::new();
         debug!("Sending join server request to Minecraft Session API");
 
-        let response_result = client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&join_request)
-            .send()
+        let response_result =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                client
+                    .post(url)
+                    .header("Content-Type", "application/json")
+                    .json(&join_request)
+            })
             .await;
 
         if let Err(ref e) = response_result {
@@ -595,7 +639,7 @@ impl MinecraftApiService {
         // Build the URL with query parameters
         let mut url = format!(
             "{}/session/minecraft/hasJoined?username={}&serverId={}",
-            MOJANG_SESSION_URL,
+            mojang_session_url(),
             urlencoding::encode(username),
             urlencoding::encode(server_id)
         );
@@ -607,7 +651,11 @@ impl MinecraftApiService {
 
         debug!("Request URL: {}", url);
 
-        let response_result = HTTP_CLIENT.get(&url).send().await;
+        let response_result =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT.get(url)
+            })
+            .await;
 
         if let Err(ref e) = response_result {
             debug!("API request failed: {:?}", e);
@@ -1,11 +1,12 @@
 use crate::error::{AppError, Result};
+use crate::utils::endpoint_registry::{self, Endpoint};
+use crate::utils::redaction_utils::Redactor;
 use log::{debug, error, info};
 use once_cell::sync::Lazy;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-const MCLOGS_API_URL: &str = "https://api.mclo.gs/1/log";
 const REQUEST_TIMEOUT_SECONDS: u64 = 30;
 
 // Static HTTP Client using once_cell
@@ -35,7 +36,9 @@ struct MclogsApiResponse {
 }
 
 /// Uploads the given log content to mclo.gs and returns the structured result.
+/// The content is redacted with the configured rules first, so no caller can skip that.
 pub async fn upload_log_to_mclogs(log_content: String) -> Result<MclogsUploadResult> {
+    let log_content = Redactor::from_config().await?.redact(&log_content);
     info!(
         "Attempting to upload log to mclo.gs ({} bytes)",
         log_content.len()
@@ -44,15 +47,15 @@ pub async fn upload_log_to_mclogs(log_content: String) -> Result<MclogsUploadRes
     // Create the form data payload
     let params = [("content", log_content)];
 
-    let response = HTTP_CLIENT
-        .post(MCLOGS_API_URL)
-        .form(&params)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Network error during mclo.gs upload: {}", e);
-            AppError::MclogsUploadFailed(format!("Network request failed: {}", e))
-        })?;
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::urls_for(Endpoint::Mclogs, "log"),
+        |url| HTTP_CLIENT.post(url).form(&params),
+    )
+    .await
+    .map_err(|e| {
+        error!("Network error during mclo.gs upload: {}", e);
+        AppError::MclogsUploadFailed(format!("Network request failed: {}", e))
+    })?;
 
     // Check for HTTP errors (4xx, 5xx)
     let response = response.error_for_status().map_err(|e| {
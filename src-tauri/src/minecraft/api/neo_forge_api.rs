@@ -1,13 +1,14 @@
 use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::neo_forge_maven_meta::NeoForgeMavenMetadata;
+use crate::minecraft::offline;
+use crate::utils::endpoint_registry::{self, Endpoint};
 use log::{debug, error, info};
 use quick_xml::de::from_str;
 use std::path::PathBuf;
 use tokio::fs as tokio_fs;
 
-const NEO_FORGE_MAVEN_METADATA_URL: &str =
-    "https://maven.neoforged.net/net/neoforged/neoforge/maven-metadata.xml";
+const NEO_FORGE_MAVEN_METADATA_PATH: &str = "net/neoforged/neoforge/maven-metadata.xml";
 
 pub struct NeoForgeApi {
     base_url: String,
@@ -23,18 +24,32 @@ impl NeoForgeApi {
             });
         }
         Self {
-            base_url: NEO_FORGE_MAVEN_METADATA_URL.to_string(),
+            base_url: format!(
+                "{}/{}",
+                endpoint_registry::base_url(Endpoint::NeoForgeMaven),
+                NEO_FORGE_MAVEN_METADATA_PATH
+            ),
             cache_dir,
         }
     }
 
     async fn fetch_and_cache_metadata(base_url: &str, cache_path: &PathBuf) -> Result<NeoForgeMavenMetadata> {
+        if offline::is_active() {
+            return Err(offline::missing_artifact(
+                "NeoForge version metadata",
+                cache_path,
+                Some(base_url),
+            ));
+        }
+
         debug!("Fetching NeoForge metadata from: {}", base_url);
 
-        let response = HTTP_CLIENT.get(base_url)
-            .send()
-            .await
-            .map_err(|e| AppError::ForgeError(format!("Failed to fetch NeoForge versions: {}", e)))?;
+        let response = endpoint_registry::send_with_fallback(
+            &endpoint_registry::resolve_url(base_url),
+            |url| HTTP_CLIENT.get(url),
+        )
+        .await
+        .map_err(|e| AppError::ForgeError(format!("Failed to fetch NeoForge versions: {}", e)))?;
 
         if !response.status().is_success() {
             return Err(AppError::ForgeError(format!(
@@ -46,7 +61,7 @@ impl NeoForgeApi {
         let xml_content = response
             .text()
             .await
-            .map_err(|e| AppError::ForgeError(format!("Failed to read response: {}", e)))?;
+        .map_err(|e| AppError::ForgeError(format!("Failed to read response: {}", e)))?;
 
         let metadata: NeoForgeMavenMetadata = from_str(&xml_content)
             .map_err(|e| AppError::ForgeError(format!("Failed to parse NeoForge metadata: {}", e)))?;
@@ -81,9 +96,11 @@ impl NeoForgeApi {
                             let base_url = self.base_url.clone();
                             let cache_path_clone = cache_path.clone();
                             
-                            tokio::spawn(async move {
-                                Self::background_update(base_url, cache_path_clone).await;
-                            });
+                            if !offline::is_active() {
+                                tokio::spawn(async move {
+                                    Self::background_update(base_url, cache_path_clone).await;
+                                });
+                            }
                             
                             return Ok(cached_metadata);
                         }
@@ -3,6 +3,8 @@ use crate::integrations::norisk_versions::NoriskVersionsConfig;
 use crate::minecraft::auth::minecraft_auth::NoRiskToken;
 use crate::minecraft::dto::norisk_meta::NoriskAssets;
 use crate::state::process_state::ProcessMetadata;
+use crate::utils::endpoint_registry::{self, Endpoint};
+use crate::utils::redaction_utils::Redactor;
 use crate::{
     config::HTTP_CLIENT,
     error::{AppError, Result},
@@ -36,10 +38,10 @@ impl NoRiskApi {
     pub fn get_api_base(is_experimental: bool) -> String {
         if is_experimental {
             debug!("[NoRisk API] Using experimental API endpoint");
-            String::from("https://api-staging.norisk.gg/api/v1")
+            endpoint_registry::base_url(Endpoint::NoriskApiStaging)
         } else {
             debug!("[NoRisk API] Using production API endpoint");
-            String::from("https://api.norisk.gg/api/v1")
+            endpoint_registry::base_url(Endpoint::NoriskApi)
         }
     }
 
@@ -51,13 +53,17 @@ impl NoRiskApi {
         debug!("[NoRisk API] Requesting new server ID");
         debug!("[NoRisk API] Full URL: {}", url);
 
-        let response = HTTP_CLIENT
-            .post(url)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT.post(url)
+            })
             .await
             .map_err(|e| {
                 error!("[NoRisk API] Server ID request failed: {}", e);
-                AppError::RequestError(format!("Failed to request server ID from NoRisk API: {}", e))
+                AppError::RequestError(format!(
+                    "Failed to request server ID from NoRisk API: {}",
+                    e
+                ))
             })?;
 
         let status = response.status();
@@ -130,11 +136,13 @@ impl NoRiskApi {
             "[NoRisk API] Sending POST request with {} parameters",
             query_params.len()
         );
-        let response = HTTP_CLIENT
-            .post(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&query_params)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .post(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&query_params)
+            })
             .await
             .map_err(|e| {
                 error!("[NoRisk API] Request failed: {}", e);
@@ -171,20 +179,26 @@ impl NoRiskApi {
         debug!("[NoRisk API] Making GET request to endpoint: {}", endpoint);
         debug!("[NoRisk API] Full URL: {}", url);
 
-        let mut request = HTTP_CLIENT
-            .get(url)
-            .header("Authorization", format!("Bearer {}", norisk_token));
-
-        if let Some(extra) = extra_params {
+        if let Some(extra) = &extra_params {
             debug!("[NoRisk API] Adding {} query parameters", extra.len());
-            request = request.query(&extra);
         }
 
         debug!("[NoRisk API] Sending GET request");
-        let response = request.send().await.map_err(|e| {
-            error!("[NoRisk API] GET request failed: {}", e);
-            AppError::RequestError(format!("Failed to send GET request to NoRisk API: {}", e))
-        })?;
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                let request = HTTP_CLIENT
+                    .get(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token));
+                match &extra_params {
+                    Some(extra) => request.query(extra),
+                    None => request,
+                }
+            })
+            .await
+            .map_err(|e| {
+                error!("[NoRisk API] GET request failed: {}", e);
+                AppError::RequestError(format!("Failed to send GET request to NoRisk API: {}", e))
+            })?;
 
         let status = response.status();
         debug!("[NoRisk API] Response status: {}", status);
@@ -219,23 +233,29 @@ impl NoRiskApi {
         );
         debug!("[NoRisk API] Full URL: {}", url);
 
-        let mut request = HTTP_CLIENT
-            .delete(url)
-            .header("Authorization", format!("Bearer {}", norisk_token));
-
-        if let Some(extra) = extra_params {
+        if let Some(extra) = &extra_params {
             debug!("[NoRisk API] Adding {} query parameters", extra.len());
-            request = request.query(&extra);
         }
 
         debug!("[NoRisk API] Sending DELETE request");
-        let response = request.send().await.map_err(|e| {
-            error!("[NoRisk API] DELETE request failed: {}", e);
-            AppError::RequestError(format!(
-                "Failed to send DELETE request to NoRisk API: {}",
-                e
-            ))
-        })?;
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                let request = HTTP_CLIENT
+                    .delete(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token));
+                match &extra_params {
+                    Some(extra) => request.query(extra),
+                    None => request,
+                }
+            })
+            .await
+            .map_err(|e| {
+                error!("[NoRisk API] DELETE request failed: {}", e);
+                AppError::RequestError(format!(
+                    "Failed to send DELETE request to NoRisk API: {}",
+                    e
+                ))
+            })?;
 
         let status = response.status();
         debug!("[NoRisk API] Response status: {}", status);
@@ -298,14 +318,17 @@ impl NoRiskApi {
         query_params.insert("server_id", server_id);
 
         debug!("[NoRisk API] Sending POST request with server-provided server ID");
-        let response = HTTP_CLIENT
-            .post(url)
-            .query(&query_params)
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT.post(url).query(&query_params)
+            })
             .await
             .map_err(|e| {
                 error!("[NoRisk API] v3 token refresh request failed: {}", e);
-                AppError::RequestError(format!("Failed to send v3 token refresh request to NoRisk API: {}", e))
+                AppError::RequestError(format!(
+                    "Failed to send v3 token refresh request to NoRisk API: {}",
+                    e
+                ))
             })?;
 
         let status = response.status();
@@ -470,6 +493,7 @@ impl NoRiskApi {
     }
 
     /// Submits a crash log to the NoRisk API.
+    /// The process metadata is redacted with the configured rules first, so no caller can skip that.
     pub async fn submit_crash_log(
         norisk_token: &str,
         crash_log_data: &CrashlogDto,
@@ -486,14 +510,28 @@ impl NoRiskApi {
         );
         debug!("[NoRisk API] Full URL: {}", url);
         debug!("[NoRisk API] With request UUID: {}", request_uuid);
-        debug!("[NoRisk API] Crash log data: {:?}", crash_log_data);
-
-        let response = HTTP_CLIENT
-            .post(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&[("uuid", request_uuid)])
-            .json(crash_log_data)
-            .send()
+
+        // Paths, hook commands and the like in the process metadata
+        let redactor = Redactor::from_config().await?;
+        let metadata = crash_log_data
+            .metadata
+            .as_ref()
+            .map(|metadata| redactor.redact_value(metadata))
+            .transpose()?;
+        let body = serde_json::json!({
+            "mc_logs_url": crash_log_data.mc_logs_url,
+            "metadata": metadata,
+        });
+        debug!("[NoRisk API] Crash log data: {}", body);
+
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .post(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&[("uuid", request_uuid)])
+                    .json(&body)
+            })
             .await
             .map_err(|e| {
                 error!("[NoRisk API] Crash log submission request failed: {}", e);
@@ -537,15 +575,20 @@ impl NoRiskApi {
         info!("[NoRisk API] Requesting mcreal app token");
         debug!("[NoRisk API] Full URL: {}", url);
         
-        let response = HTTP_CLIENT
-            .get(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&[("uuid", request_uuid)])
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .get(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&[("uuid", request_uuid)])
+            })
             .await
             .map_err(|e| {
                 error!("[NoRisk API] McReal app token request failed: {}", e);
-                AppError::RequestError(format!("Failed to get mobile app token from NoRisk API: {}", e))
+                AppError::RequestError(format!(
+                    "Failed to get mobile app token from NoRisk API: {}",
+                    e
+                ))
             })?;
 
         let status = response.status();
@@ -584,15 +627,20 @@ impl NoRiskApi {
         info!("[NoRisk API] Resetting mcreal app token");
         debug!("[NoRisk API] Full URL: {}", url);
         
-        let response = HTTP_CLIENT
-            .post(url)
-            .header("Authorization", format!("Bearer {}", norisk_token))
-            .query(&[("uuid", request_uuid)])
-            .send()
+        let response =
+            endpoint_registry::send_with_fallback(&endpoint_registry::resolve_url(&url), |url| {
+                HTTP_CLIENT
+                    .post(url)
+                    .header("Authorization", format!("Bearer {}", norisk_token))
+                    .query(&[("uuid", request_uuid)])
+            })
             .await
             .map_err(|e| {
                 error!("[NoRisk API] McReal app token reset request failed: {}", e);
-                AppError::RequestError(format!("Failed to reset mobile app token from NoRisk API: {}", e))
+                AppError::RequestError(format!(
+                    "Failed to reset mobile app token from NoRisk API: {}",
+                    e
+                ))
             })?;
 
         let status = response.status();
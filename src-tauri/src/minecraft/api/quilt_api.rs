@@ -1,6 +1,8 @@
 use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::Result;
 use crate::minecraft::dto::quilt_meta::QuiltVersionInfo;
+use crate::minecraft::offline;
+use crate::utils::endpoint_registry::{self, Endpoint};
 use log::{debug, error};
 use serde_json;
 use std::path::PathBuf;
@@ -20,7 +22,7 @@ impl QuiltApi {
             });
         }
         Self {
-            base_url: "https://meta.quiltmc.org/v3".to_string(),
+            base_url: endpoint_registry::base_url(Endpoint::QuiltMeta),
             cache_dir,
         }
     }
@@ -30,10 +32,23 @@ impl QuiltApi {
         minecraft_version: &str,
         cache_path: &PathBuf,
     ) -> Result<Vec<QuiltVersionInfo>> {
+        if offline::is_active() {
+            return Err(offline::missing_artifact(
+                format!("Quilt loader versions for Minecraft {}", minecraft_version),
+                cache_path,
+                None,
+            ));
+        }
+
         let url = format!("{}/versions/loader/{}", base_url, minecraft_version);
         debug!("Fetching Quilt versions from: {}", url);
 
-        let response = HTTP_CLIENT.get(&url).send().await.map_err(|e| {
+        let response = endpoint_registry::send_with_fallback(
+            &endpoint_registry::resolve_url(&url),
+            |url| HTTP_CLIENT.get(url),
+        )
+        .await
+        .map_err(|e| {
             crate::error::AppError::QuiltError(format!("Failed to fetch Quilt versions: {}", e))
         })?;
 
@@ -93,9 +108,11 @@ impl QuiltApi {
                             let minecraft_version = minecraft_version.to_string();
                             let cache_path_clone = cache_path.clone();
                             
-                            tokio::spawn(async move {
-                                Self::background_update(base_url, minecraft_version, cache_path_clone).await;
-                            });
+                            if !offline::is_active() {
+                                tokio::spawn(async move {
+                                    Self::background_update(base_url, minecraft_version, cache_path_clone).await;
+                                });
+                            }
                             
                             return Ok(cached_versions);
                         }
@@ -1,6 +1,7 @@
 use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::state::event_state::{EventPayload, EventType};
+use crate::utils::endpoint_registry::{self, Endpoint};
 use crate::utils::hash_utils::calculate_sha1_from_bytes;
 use log::{debug, error, warn};
 use reqwest;
@@ -11,7 +12,9 @@ use tokio::fs as tokio_fs;
 use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
-const STARLIGHT_API_BASE: &str = "https://starlightskins.lunareclipse.studio";
+fn starlight_api_base() -> String {
+    endpoint_registry::base_url(Endpoint::Starlight)
+}
 
 fn generate_cache_filename(
     player_name: &str,
@@ -58,7 +61,7 @@ impl StarlightApiService {
     ) -> Result<Vec<u8>> {
         let base_url = format!(
             "{}/render/{}/{}/{}",
-            STARLIGHT_API_BASE, render_type, player_name, render_view
+            starlight_api_base(), render_type, player_name, render_view
         );
 
         let mut query_params = Vec::new();
@@ -70,12 +73,16 @@ impl StarlightApiService {
         }
 
         // Use global HTTP_CLIENT
-        let mut request_builder = HTTP_CLIENT.get(&base_url);
-        if !query_params.is_empty() {
-            request_builder = request_builder.query(&query_params);
-        }
+        let build_request = |url: &str| {
+            let request_builder = HTTP_CLIENT.get(url);
+            if query_params.is_empty() {
+                request_builder
+            } else {
+                request_builder.query(&query_params)
+            }
+        };
 
-        let request = request_builder.build().map_err(|e| {
+        let request = build_request(&base_url).build().map_err(|e| {
             error!("Failed to build Starlight API request: {}", e);
             AppError::Other(format!("Failed to build Starlight API request: {}", e))
         })?;
@@ -91,8 +98,13 @@ impl StarlightApiService {
             base64_skin_data.is_some()
         );
 
-        // Use global HTTP_CLIENT to execute the request
-        let response = HTTP_CLIENT.execute(request).await.map_err(|e| {
+        // Try the configured Starlight URL first, then its mirrors
+        let response = endpoint_registry::send_with_fallback(
+            &endpoint_registry::resolve_url(&base_url),
+            build_request,
+        )
+        .await
+        .map_err(|e| {
             warn!(
                 "Starlight API request failed for player {} (type: {}, view: {}, custom_skin: {}): {:?}",
                 player_name,
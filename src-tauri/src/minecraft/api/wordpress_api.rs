@@ -1,6 +1,7 @@
 use crate::{
     config::HTTP_CLIENT,
     error::{AppError, Result},
+    utils::endpoint_registry::{self, Endpoint},
 };
 use log::{debug, error, info};
 use serde::{Deserialize, Serialize};
@@ -39,7 +40,7 @@ impl WordPressApi {
 
     /// Retrieves the base URL for the WordPress API
     pub fn get_api_base() -> String {
-        String::from("https://blog.norisk.gg/wp-json/wp/v2")
+        endpoint_registry::base_url(Endpoint::Blog)
     }
 
     /// Fetch blog posts from WordPress API
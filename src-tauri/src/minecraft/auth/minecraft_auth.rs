@@ -805,6 +805,7 @@ impl MinecraftAuthStore {
             "[Account Manager] Global experimental mode is: {}",
             is_experimental
         );
+        let offline_mode = state.config_manager.get_config().await.offline_mode;
 
         // Zuerst nur lesen um den aktiven Account zu finden
         let active_account = {
@@ -820,6 +821,14 @@ impl MinecraftAuthStore {
         };
 
         if let Some(account) = active_account {
+            if offline_mode {
+                info!(
+                    "[Account Manager] Offline mode enabled, using stored credentials for: {}",
+                    account.username
+                );
+                return Ok(Some(account));
+            }
+
             info!(
                 "[Account Manager] Refreshing credentials for active account: {}",
                 account.username
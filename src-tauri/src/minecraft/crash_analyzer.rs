@@ -0,0 +1,273 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use log::{debug, info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Rules shipped with the launcher. Files in `<meta_dir>/crash_rules/*.json` are loaded on top:
+/// a rule with the same `id` replaces the built-in one, `"enabled": false` turns it off.
+const BUILTIN_RULES: &str = include_str!("crash_rules.json");
+/// Only the end of `latest.log` is relevant for a crash
+const LOG_TAIL_BYTES: u64 = 256 * 1024;
+/// Matches kept per rule, e.g. several missing dependencies
+const MAX_MATCHES_PER_RULE: usize = 5;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashSource {
+    CrashReport,
+    LatestLog,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashSeverity {
+    Critical,
+    Error,
+    Warning,
+}
+
+fn default_sources() -> Vec<CrashSource> {
+    vec![CrashSource::CrashReport, CrashSource::LatestLog]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A known crash signature. Patterns are regexes matched line by line; their named groups can be
+/// used as `{placeholders}` in `cause` and `suggestion`. A `class_version` group also provides
+/// `{java_version}`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrashRule {
+    pub id: String,
+    pub title: String,
+    pub category: String,
+    pub severity: CrashSeverity,
+    /// Higher priorities are listed first
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_sources")]
+    pub sources: Vec<CrashSource>,
+    pub patterns: Vec<String>,
+    /// Named group holding the offending mod, defaults to `mod`
+    #[serde(default)]
+    pub mod_group: Option<String>,
+    pub cause: String,
+    pub suggestion: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Result of a matched rule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrashDiagnosis {
+    pub rule_id: String,
+    pub title: String,
+    pub category: String,
+    pub severity: CrashSeverity,
+    pub offending_mod: Option<String>,
+    pub cause: String,
+    pub suggested_action: String,
+    pub source: CrashSource,
+    pub matched_line: String,
+}
+
+struct CompiledRule {
+    rule: CrashRule,
+    patterns: Vec<Regex>,
+}
+
+pub struct CrashRuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl CrashRuleSet {
+    /// Compiles the given rules. Rules with an invalid pattern are skipped with a warning.
+    pub fn from_rules(rules: Vec<CrashRule>) -> Self {
+        let mut compiled = Vec::new();
+        for rule in rules.into_iter().filter(|r| r.enabled) {
+            let patterns: std::result::Result<Vec<Regex>, regex::Error> =
+                rule.patterns.iter().map(|p| Regex::new(p)).collect();
+            match patterns {
+                Ok(patterns) => compiled.push(CompiledRule { rule, patterns }),
+                Err(e) => warn!("Skipping crash rule '{}' with invalid pattern: {}", rule.id, e),
+            }
+        }
+        compiled.sort_by(|a, b| b.rule.priority.cmp(&a.rule.priority));
+        Self { rules: compiled }
+    }
+
+    /// Only the rules shipped with the launcher
+    pub fn builtin() -> Self {
+        Self::from_rules(parse_rules(BUILTIN_RULES).expect("built-in crash rules are valid JSON"))
+    }
+
+    /// Built-in rules merged with the user rules from `<meta_dir>/crash_rules`
+    pub async fn load() -> Self {
+        let mut rules = parse_rules(BUILTIN_RULES).expect("built-in crash rules are valid JSON");
+        for extra in load_user_rules(&get_user_rules_dir()).await {
+            match rules.iter_mut().find(|r| r.id == extra.id) {
+                Some(existing) => *existing = extra,
+                None => rules.push(extra),
+            }
+        }
+        Self::from_rules(rules)
+    }
+
+    /// Matches the crash report and the end of `latest.log` against all rules. Returns one
+    /// diagnosis per rule and offending mod, most important first.
+    pub fn analyze(&self, crash_report: Option<&str>, latest_log: Option<&str>) -> Vec<CrashDiagnosis> {
+        let inputs = [
+            (CrashSource::CrashReport, crash_report),
+            (CrashSource::LatestLog, latest_log),
+        ];
+        let mut diagnoses = Vec::new();
+
+        for compiled in &self.rules {
+            let rule = &compiled.rule;
+            let mut seen = HashSet::new();
+
+            'sources: for (source, text) in inputs {
+                let Some(text) = text else { continue };
+                if !rule.sources.contains(&source) {
+                    continue;
+                }
+                for line in text.lines() {
+                    let Some((pattern, captures)) = compiled
+                        .patterns
+                        .iter()
+                        .find_map(|p| p.captures(line).map(|c| (p, c)))
+                    else {
+                        continue;
+                    };
+
+                    let mut values: HashMap<&str, String> = HashMap::new();
+                    for name in pattern.capture_names().flatten() {
+                        if let Some(value) = captures.name(name) {
+                            values.insert(name, value.as_str().trim().to_string());
+                        }
+                    }
+                    if let Some(version) = values
+                        .get("class_version")
+                        .and_then(|v| v.parse::<u32>().ok())
+                    {
+                        // Class file version 52 is Java 8, each release adds one
+                        values.insert("java_version", version.saturating_sub(44).to_string());
+                    }
+
+                    let offending_mod = values
+                        .get(rule.mod_group.as_deref().unwrap_or("mod"))
+                        .cloned();
+                    if !seen.insert(offending_mod.clone()) {
+                        continue;
+                    }
+
+                    diagnoses.push(CrashDiagnosis {
+                        rule_id: rule.id.clone(),
+                        title: rule.title.clone(),
+                        category: rule.category.clone(),
+                        severity: rule.severity,
+                        offending_mod,
+                        cause: fill_template(&rule.cause, &values),
+                        suggested_action: fill_template(&rule.suggestion, &values),
+                        source,
+                        matched_line: line.trim().to_string(),
+                    });
+                    if seen.len() >= MAX_MATCHES_PER_RULE {
+                        break 'sources;
+                    }
+                }
+            }
+        }
+
+        diagnoses
+    }
+}
+
+/// Parses a JSON array of crash rules
+pub fn parse_rules(json: &str) -> Result<Vec<CrashRule>> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Returns the directory for additional crash rules: <meta_dir>/crash_rules
+pub fn get_user_rules_dir() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join("crash_rules")
+}
+
+async fn load_user_rules(dir: &Path) -> Vec<CrashRule> {
+    let mut rules = Vec::new();
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return rules;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        match fs::read_to_string(&path).await.map_err(AppError::from).and_then(|c| parse_rules(&c)) {
+            Ok(file_rules) => {
+                debug!("Loaded {} crash rule(s) from {:?}", file_rules.len(), path);
+                rules.extend(file_rules);
+            }
+            Err(e) => warn!("Ignoring crash rule file {:?}: {}", path, e),
+        }
+    }
+    rules
+}
+
+/// Replaces `{name}` placeholders, unknown ones become "unknown"
+fn fill_template(template: &str, values: &HashMap<&str, String>) -> String {
+    static PLACEHOLDER: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"\{(\w+)\}").unwrap());
+    PLACEHOLDER
+        .replace_all(template, |caps: &regex::Captures| {
+            values
+                .get(&caps[1])
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string())
+        })
+        .into_owned()
+}
+
+/// Reads the last `max_bytes` of a log file
+pub async fn read_log_tail(path: &Path, max_bytes: u64) -> Result<String> {
+    let mut file = fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    if len > max_bytes {
+        file.seek(std::io::SeekFrom::Start(len - max_bytes)).await?;
+    }
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer).await?;
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+/// Analyzes a crash of the game in `instance_dir`, using the given crash report (if any)
+/// and the end of `logs/latest.log`
+pub async fn analyze_instance_crash(
+    instance_dir: &Path,
+    crash_report: Option<&str>,
+) -> Vec<CrashDiagnosis> {
+    let log_path = instance_dir.join("logs").join("latest.log");
+    let latest_log = match read_log_tail(&log_path, LOG_TAIL_BYTES).await {
+        Ok(log) => Some(log),
+        Err(e) => {
+            debug!("No latest.log to analyze at {:?}: {}", log_path, e);
+            None
+        }
+    };
+
+    let diagnoses = CrashRuleSet::load()
+        .await
+        .analyze(crash_report, latest_log.as_deref());
+    info!(
+        "Crash analysis for {:?} found {} diagnosis(es)",
+        instance_dir,
+        diagnoses.len()
+    );
+    diagnoses
+}
@@ -2,6 +2,8 @@ use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::forge_install_profile::ForgeInstallProfile;
 use crate::minecraft::dto::forge_meta::ForgeVersion;
+use crate::minecraft::offline;
+use crate::utils::endpoint_registry::{self, Endpoint};
 use async_zip::tokio::read::seek::ZipFileReader;
 use log::info;
 use reqwest;
@@ -40,19 +42,29 @@ impl ForgeInstallerDownloadService {
             return Ok(jar_path);
         }
 
+        if offline::is_active() {
+            return Err(offline::missing_artifact(
+                format!("Forge installer {}", version),
+                &jar_path,
+                None,
+            ));
+        }
+
         // Erstelle das Verzeichnis falls es nicht existiert
         if let Some(parent) = jar_path.parent() {
             fs::create_dir_all(parent).await?;
         }
 
         // Konstruiere die Download-URL
-        let url = format!("https://maven.minecraftforge.net/{}", maven_path);
+        let urls = endpoint_registry::urls_for(Endpoint::ForgeMaven, &maven_path);
 
         // Lade die JAR herunter
-        info!("Downloading from: {}", url);
-        let response = HTTP_CLIENT.get(&url).send().await.map_err(|e| {
-            AppError::Download(format!("Failed to download Forge installer: {}", e))
-        })?;
+        info!("Downloading from: {:?}", urls);
+        let response = endpoint_registry::send_with_fallback(&urls, |url| HTTP_CLIENT.get(url))
+            .await
+            .map_err(|e| {
+                AppError::Download(format!("Failed to download Forge installer: {}", e))
+            })?;
 
         if !response.status().is_success() {
             return Err(AppError::Download(format!(
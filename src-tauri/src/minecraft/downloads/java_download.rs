@@ -1,17 +1,21 @@
 use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
-use crate::minecraft::dto::{JavaDistribution, ZuluApiResponse};
+use crate::minecraft::downloads::java_runtime_manager;
+use crate::minecraft::downloads::java_vendors::{
+    self, JavaPackage, JavaPackageSource, JavaPlatform,
+};
+use crate::minecraft::dto::JavaDistribution;
+use crate::minecraft::offline;
 use crate::state::State;
 use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
 use crate::utils::system_info::{Architecture, OperatingSystem, ARCHITECTURE, OS};
 use async_zip::tokio::read::seek::ZipFileReader;
 use flate2::read::GzDecoder;
 use futures::future::try_join_all;
-use log::{debug, error, info};
-use reqwest;
+use log::{debug, error, info, warn};
 use std::fs::File;
 use std::io::Cursor;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
 use tar::Archive;
@@ -39,6 +43,25 @@ impl JavaDownloadService {
         }
     }
 
+    /// Directory all downloaded runtimes live in (`meta/java`)
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Name of the directory a runtime is installed to, e.g. `zulu_17` or `zulu_8_x86_64`
+    pub fn runtime_dir_name(
+        distribution: &JavaDistribution,
+        version: u32,
+        force_x86_64: bool,
+    ) -> String {
+        format!(
+            "{}_{}{}",
+            distribution.get_name(),
+            version,
+            if force_x86_64 { "_x86_64" } else { "" }
+        )
+    }
+
     // Check if we need to use x86_64 Java based on the Java component
     pub fn needs_x86_64_java(&self, java_component: Option<&str>) -> bool {
         // Only needed on Apple Silicon Macs
@@ -67,6 +90,16 @@ impl JavaDownloadService {
         // Handle architecture override for legacy Java component on ARM64 Mac
         let force_x86_64 = self.needs_x86_64_java(java_component);
 
+        // A game might have been running from the old runtime when it was upgraded
+        if let Err(e) = java_runtime_manager::apply_pending_upgrade(
+            &self.base_path,
+            &Self::runtime_dir_name(distribution, version, force_x86_64),
+        )
+        .await
+        {
+            warn!("Could not apply pending Java runtime upgrade: {}", e);
+        }
+
         // Check if Java is already downloaded
         if let Ok(java_binary) = self
             .find_java_binary(distribution, &version, force_x86_64)
@@ -76,13 +109,59 @@ impl JavaDownloadService {
             return Ok(java_binary);
         }
 
+        let fallback = JavaDistribution::Zulu;
+        if offline::is_active() {
+            // A runtime of the default vendor works just as well
+            if *distribution != fallback {
+                if let Ok(java_binary) = self
+                    .find_java_binary(&fallback, &version, force_x86_64)
+                    .await
+                {
+                    info!(
+                        "Using installed {} Java offline: {:?}",
+                        fallback.get_name(),
+                        java_binary
+                    );
+                    return Ok(java_binary);
+                }
+            }
+            return Err(offline::missing_artifact(
+                format!("Java {} ({})", version, distribution.get_name()),
+                &self.base_path,
+                None,
+            ));
+        }
+
         // Download and setup Java
         info!("Downloading Java {}...", version);
-        self.download_java(version, distribution, force_x86_64)
-            .await?;
+        let installed_distribution = match self
+            .download_java(version, distribution, force_x86_64, java_component)
+            .await
+        {
+            Ok(_) => *distribution,
+            Err(e) if *distribution != fallback => {
+                warn!(
+                    "Could not download Java {} from {}: {}. Falling back to {}.",
+                    version,
+                    distribution.get_name(),
+                    e,
+                    fallback.get_name()
+                );
+                if self
+                    .find_java_binary(&fallback, &version, force_x86_64)
+                    .await
+                    .is_err()
+                {
+                    self.download_java(version, &fallback, force_x86_64, java_component)
+                        .await?;
+                }
+                fallback
+            }
+            Err(e) => return Err(e),
+        };
 
         // Find and return Java binary
-        self.find_java_binary(distribution, &version, force_x86_64)
+        self.find_java_binary(&installed_distribution, &version, force_x86_64)
             .await
     }
 
@@ -91,79 +170,98 @@ impl JavaDownloadService {
         version: u32,
         distribution: &JavaDistribution,
         force_x86_64: bool,
+        java_component: Option<&str>,
     ) -> Result<PathBuf> {
-        let arch_suffix = if force_x86_64 { "_x86_64" } else { "" };
         info!(
             "Downloading Java {} for distribution: {}{}",
             version,
             distribution.get_name(),
-            arch_suffix
+            if force_x86_64 { "_x86_64" } else { "" }
         );
 
-        // Get the initial URL
-        let initial_url = distribution.get_url(&version, force_x86_64)?;
-        info!("Java Download URL: {}", initial_url);
-
-        // For Zulu, we need to make an extra API call to get the actual download URL
-        let download_url = if distribution.requires_api_response() {
-            info!("Fetching actual download URL from Zulu API...");
-            let client = reqwest::Client::new();
-            let response = client
-                .get(&initial_url)
-                .header("Accept", "application/json")
-                .send()
-                .await
-                .map_err(|e| AppError::JavaDownload(format!("Failed to fetch Zulu API: {}", e)))?;
-
-            if !response.status().is_success() {
-                return Err(AppError::JavaDownload(format!(
-                    "Zulu API returned error status: {}",
-                    response.status()
-                )));
-            }
+        // Create version-specific directory with architecture suffix for legacy support
+        let version_dir = self
+            .base_path
+            .join(Self::runtime_dir_name(distribution, version, force_x86_64));
+        let package = self
+            .latest_release(version, distribution, force_x86_64, java_component)
+            .await?;
+        self.install_package(&package, &version_dir).await?;
+        Ok(version_dir)
+    }
 
-            // Parse the JSON response
-            let zulu_response: ZuluApiResponse = response.json().await.map_err(|e| {
-                AppError::JavaDownload(format!("Failed to parse Zulu API response: {}", e))
-            })?;
+    /// Resolves the newest build of a major version. `java_component` picks the Mojang runtime
+    /// (`java-runtime-gamma`, `jre-legacy`), the other vendors ignore it.
+    pub async fn latest_release(
+        &self,
+        version: u32,
+        distribution: &JavaDistribution,
+        force_x86_64: bool,
+        java_component: Option<&str>,
+    ) -> Result<JavaPackage> {
+        let package = java_vendors::resolve_package(
+            *distribution,
+            version,
+            java_component,
+            JavaPlatform::current(force_x86_64),
+        )
+        .await?;
+        debug!("Resolved Java package: {:?}", package);
+        Ok(package)
+    }
 
-            info!("Actual download URL: {}", zulu_response.url);
-            zulu_response.url
-        } else {
-            initial_url
-        };
+    /// Downloads a package into `target_dir`, verifying the checksum its vendor publishes.
+    /// `target_dir` is removed again if anything fails.
+    pub async fn install_package(&self, package: &JavaPackage, target_dir: &Path) -> Result<()> {
+        let result = self.install_package_inner(package, target_dir).await;
+        if result.is_err() && target_dir.exists() {
+            if let Err(e) = fs::remove_dir_all(target_dir).await {
+                warn!("Failed to clean up {:?}: {}", target_dir, e);
+            }
+        }
+        result
+    }
 
-        // Create version-specific directory with architecture suffix for legacy support
-        let dir_name = format!(
-            "{}_{}{}",
-            distribution.get_name(),
-            version,
-            if force_x86_64 { "_x86_64" } else { "" }
-        );
-        let version_dir = self.base_path.join(dir_name);
+    async fn install_package_inner(&self, package: &JavaPackage, target_dir: &Path) -> Result<()> {
+        if target_dir.exists() {
+            fs::remove_dir_all(target_dir).await?;
+        }
+        let version_dir = target_dir.to_path_buf();
         fs::create_dir_all(&version_dir).await?;
 
-        // Save the downloaded file
-        let archive_path = version_dir.join(format!("java.{}", OS.get_archive_type()?));
+        match &package.source {
+            JavaPackageSource::Archive { url, sha256 } => {
+                info!("Java Download URL: {}", url);
 
-        // Download Java archive using the centralized utility (fixes issue #1203)
-        let config = DownloadConfig::new()
-            .with_streaming(true)  // Java archives are large files
-            .with_retries(3)  // Built-in retry logic for network issues
-            .with_force_overwrite(true);  // Always download fresh Java
+                // Save the downloaded file
+                let archive_path = version_dir.join(format!("java.{}", OS.get_archive_type()?));
 
-        DownloadUtils::download_file(&download_url, &archive_path, config)
-            .await
-            .map_err(|e| AppError::JavaDownload(format!("Failed to download Java archive: {}", e)))?;
+                // Download Java archive using the centralized utility (fixes issue #1203)
+                let config = DownloadConfig::new()
+                    .with_streaming(true) // Java archives are large files
+                    .with_retries(3) // Built-in retry logic for network issues
+                    .with_force_overwrite(true) // Always download fresh Java
+                    .with_sha256(sha256.as_str());
 
-        // Extract the archive
-        self.extract_java_archive(&archive_path, &version_dir)
-            .await?;
+                DownloadUtils::download_file(url, &archive_path, config)
+                    .await
+                    .map_err(|e| {
+                        AppError::JavaDownload(format!("Failed to download Java archive: {}", e))
+                    })?;
 
-        // Clean up the archive
-        fs::remove_file(&archive_path).await?;
+                // Extract the archive
+                self.extract_java_archive(&archive_path, &version_dir)
+                    .await?;
 
-        Ok(version_dir)
+                // Clean up the archive
+                fs::remove_file(&archive_path).await?;
+            }
+            JavaPackageSource::MojangManifest { url, sha1 } => {
+                java_vendors::install_mojang_runtime(url, sha1, &version_dir).await?;
+            }
+        }
+
+        Ok(())
     }
 
     async fn extract_java_archive(
@@ -496,13 +594,15 @@ impl JavaDownloadService {
             version,
             force_x86_64
         );
-        let arch_suffix = if force_x86_64 { "_x86_64" } else { "" };
-        let runtime_path = self.base_path.join(format!(
-            "{}_{}{}",
-            distribution.get_name(),
-            version,
-            arch_suffix
-        ));
+        let runtime_path = self
+            .base_path
+            .join(Self::runtime_dir_name(distribution, *version, force_x86_64));
+        self.find_java_binary_in(&runtime_path).await
+    }
+
+    /// Finds the java executable of the runtime extracted to `runtime_path`
+    pub async fn find_java_binary_in(&self, runtime_path: &Path) -> Result<PathBuf> {
+        let runtime_path = runtime_path.to_path_buf();
 
         // Now that we extract directly to the target directory without the root folder,
         // we should look for the Java binary directly in standard locations
@@ -551,9 +651,8 @@ impl JavaDownloadService {
         }
 
         debug!(
-            "Java binary not found in standard locations for distribution: {}, version: {}. Attempting recursive search.",
-            distribution.get_name(),
-            version
+            "Java binary not found in standard locations of {:?}. Attempting recursive search.",
+            runtime_path
         );
         // If we couldn't find a binary in the expected locations, let's scan the directory recursively
         self.find_java_binary_recursive(&runtime_path).await
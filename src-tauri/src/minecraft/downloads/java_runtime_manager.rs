@@ -0,0 +1,687 @@
+//! Manages the Java runtimes the launcher downloaded to `meta/java`.
+//!
+//! `JavaDownloadService` installs one runtime per distribution and major version (`zulu_17`,
+//! `temurin_21`, `zulu_8_x86_64`). This module lists them together with the profiles that use them, removes
+//! the unused ones and upgrades a major line to its newest patch release. An upgrade is
+//! downloaded to `<id>.pending` and swapped in with two renames, but only while no running game
+//! uses the runtime. Otherwise the swap happens right before the next launch that needs it.
+
+use crate::error::{AppError, Result};
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::downloads::java_download::JavaDownloadService;
+use crate::minecraft::dto::{JavaDistribution, VersionManifest};
+use crate::minecraft::java_requirements;
+use crate::state::process_state::ProcessState;
+use crate::state::profile_state::Profile;
+use crate::state::state_manager::State;
+use crate::utils::java_detector;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+const PENDING_SUFFIX: &str = ".pending";
+const OLD_SUFFIX: &str = ".old";
+
+/// How a profile ends up with a runtime
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JavaRuntimeUsage {
+    /// `ProfileSettings.java_path` points into the runtime
+    CustomPath,
+    /// The runtime of the Java version Minecraft requires, downloaded on launch
+    AutoSelected,
+    /// A newer runtime the profile's mods require, downloaded on launch instead
+    ModRequirement,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JavaRuntimeReference {
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    pub usage: JavaRuntimeUsage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstalledJavaRuntime {
+    /// Directory name, e.g. `zulu_17`
+    pub id: String,
+    pub path: PathBuf,
+    pub java_binary: Option<PathBuf>,
+    pub distribution: String,
+    /// `IMPLEMENTOR` of the runtime's `release` file, e.g. "Azul Systems, Inc."
+    pub vendor: Option<String>,
+    pub major_version: u32,
+    /// Full version, e.g. `17.0.9` or `1.8.0_392`
+    pub version: Option<String>,
+    pub architecture: Option<String>,
+    pub size_bytes: u64,
+    pub referenced_by: Vec<JavaRuntimeReference>,
+    /// A running game was started with it
+    pub in_use: bool,
+    /// Version of a downloaded upgrade that waits for running games to exit
+    pub pending_version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JavaRuntimeUpgrade {
+    UpToDate {
+        version: Option<String>,
+    },
+    Upgraded {
+        from: Option<String>,
+        to: String,
+    },
+    /// Downloaded, swapped in before the next launch once no running game uses the runtime
+    Pending {
+        from: Option<String>,
+        to: String,
+    },
+}
+
+/// Splits a runtime directory name into distribution, major version and the x86_64 override
+pub fn parse_runtime_dir_name(name: &str) -> Option<(JavaDistribution, u32, bool)> {
+    let (name, force_x86_64) = match name.strip_suffix("_x86_64") {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    let (distribution, major) = name.split_once('_')?;
+    Some((
+        JavaDistribution::from_name(distribution)?,
+        major.parse().ok()?,
+        force_x86_64,
+    ))
+}
+
+/// Compares Java versions in either scheme (`1.8.0_392`, `17.0.9`, `21.0.1+12`)
+pub fn compare_java_versions(a: &str, b: &str) -> Ordering {
+    fn numbers(version: &str) -> Vec<u32> {
+        let mut numbers: Vec<u32> = version
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|part| part.parse().ok())
+            .collect();
+        // 1.8.0_392 -> 8.0.392
+        if numbers.len() > 1 && numbers[0] == 1 {
+            numbers.remove(0);
+        }
+        numbers
+    }
+    numbers(a).cmp(&numbers(b))
+}
+
+/// Parses the `KEY="value"` lines of a runtime's `release` file
+pub fn parse_release_file(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_string(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect()
+}
+
+/// The `release` file next to the runtime's `bin` directory
+async fn read_release_info(java_binary: &Path) -> HashMap<String, String> {
+    let Some(home) = java_binary.parent().and_then(Path::parent) else {
+        return HashMap::new();
+    };
+    match fs::read_to_string(home.join("release")).await {
+        Ok(content) => parse_release_file(&content),
+        Err(_) => HashMap::new(),
+    }
+}
+
+async fn runtime_version(service: &JavaDownloadService, runtime_path: &Path) -> Option<String> {
+    let binary = service.find_java_binary_in(runtime_path).await.ok()?;
+    if let Some(version) = read_release_info(&binary).await.remove("JAVA_VERSION") {
+        return Some(version);
+    }
+    java_detector::get_java_info(&binary)
+        .await
+        .ok()
+        .map(|info| info.version)
+}
+
+async fn dir_size(path: &Path) -> u64 {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || fs_extra::dir::get_size(path).unwrap_or(0))
+        .await
+        .unwrap_or(0)
+}
+
+/// Java executables of the games that are currently running
+async fn running_java_paths() -> Vec<PathBuf> {
+    let Ok(state) = State::get().await else {
+        return Vec::new();
+    };
+    state
+        .process_manager
+        .list_processes()
+        .await
+        .into_iter()
+        .filter(|process| {
+            matches!(
+                process.state,
+                ProcessState::Starting | ProcessState::Running | ProcessState::Stopping
+            )
+        })
+        .filter_map(|process| process.hook_context.map(|context| context.java_path))
+        .collect()
+}
+
+async fn is_runtime_in_use(runtime_path: &Path) -> bool {
+    running_java_paths()
+        .await
+        .iter()
+        .any(|java| java.starts_with(runtime_path))
+}
+
+enum ReferenceTarget {
+    Path(PathBuf),
+    RuntimeId(String),
+}
+
+struct RuntimeReferences {
+    references: Vec<(ReferenceTarget, JavaRuntimeReference)>,
+    /// Profiles whose auto-selected runtime is unknown, e.g. because the version manifest
+    /// couldn't be loaded while offline
+    unresolved: Vec<String>,
+}
+
+/// What every user and standard profile would launch with
+async fn collect_references(service: &JavaDownloadService) -> Result<RuntimeReferences> {
+    let state = State::get().await?;
+    let global_vendor = state.config_manager.get_config().await.java_vendor;
+    let mut profiles: Vec<Profile> = state.profile_manager.list_profiles().await?;
+    profiles.extend(state.norisk_version_manager.get_config().await.profiles);
+
+    let api_service = MinecraftApiService::new();
+    let manifest = match api_service.get_version_manifest().await {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            warn!(
+                "Could not load the version manifest, auto-selected runtimes are unknown: {}",
+                e
+            );
+            None
+        }
+    };
+    // Required Java version per game version
+    let mut required: HashMap<String, Option<(u32, bool)>> = HashMap::new();
+
+    let mut references = Vec::new();
+    let mut unresolved = Vec::new();
+    for profile in profiles {
+        let target = match profile.settings.java_path.as_deref() {
+            Some(java_path) if profile.settings.use_custom_java_path => {
+                ReferenceTarget::Path(PathBuf::from(java_path))
+            }
+            _ => {
+                if !required.contains_key(&profile.game_version) {
+                    let java_version = match &manifest {
+                        Some(manifest) => {
+                            required_java_version(
+                                service,
+                                &api_service,
+                                manifest,
+                                &profile.game_version,
+                            )
+                            .await
+                        }
+                        None => None,
+                    };
+                    required.insert(profile.game_version.clone(), java_version);
+                }
+                let Some((major_version, force_x86_64)) =
+                    required.get(&profile.game_version).cloned().flatten()
+                else {
+                    unresolved.push(profile.name.clone());
+                    continue;
+                };
+                let vendor = if profile.is_standard_version {
+                    global_vendor
+                } else {
+                    profile.settings.java_vendor.unwrap_or(global_vendor)
+                };
+                // The installer switches to this runtime when the mods need a newer Java
+                match java_requirements::mod_required_java_version(&profile, major_version).await {
+                    Ok(Some(mod_major_version)) => references.push((
+                        ReferenceTarget::RuntimeId(selected_runtime_id(
+                            service,
+                            vendor,
+                            mod_major_version,
+                            false,
+                        )),
+                        JavaRuntimeReference {
+                            profile_id: profile.id,
+                            profile_name: profile.name.clone(),
+                            usage: JavaRuntimeUsage::ModRequirement,
+                        },
+                    )),
+                    Ok(None) => {}
+                    Err(e) => debug!(
+                        "Could not read the mod Java requirements of profile '{}': {}",
+                        profile.name, e
+                    ),
+                }
+                ReferenceTarget::RuntimeId(selected_runtime_id(
+                    service,
+                    vendor,
+                    major_version,
+                    force_x86_64,
+                ))
+            }
+        };
+        let usage = match target {
+            ReferenceTarget::Path(_) => JavaRuntimeUsage::CustomPath,
+            ReferenceTarget::RuntimeId(_) => JavaRuntimeUsage::AutoSelected,
+        };
+        references.push((
+            target,
+            JavaRuntimeReference {
+                profile_id: profile.id,
+                profile_name: profile.name.clone(),
+                usage,
+            },
+        ));
+    }
+    if !unresolved.is_empty() {
+        warn!(
+            "Required Java version of {} profile(s) is unknown: {}",
+            unresolved.len(),
+            unresolved.join(", ")
+        );
+    }
+    Ok(RuntimeReferences {
+        references,
+        unresolved,
+    })
+}
+
+/// Major version and x86_64 override the installer needs for a game version
+async fn required_java_version(
+    service: &JavaDownloadService,
+    api_service: &MinecraftApiService,
+    manifest: &VersionManifest,
+    game_version: &str,
+) -> Option<(u32, bool)> {
+    let version = manifest.versions.iter().find(|v| v.id == game_version)?;
+    let piston_meta = match api_service.get_piston_meta(&version.url).await {
+        Ok(piston_meta) => piston_meta,
+        Err(e) => {
+            warn!("Could not load version meta of {}: {}", game_version, e);
+            return None;
+        }
+    };
+    Some((
+        piston_meta.java_version.major_version as u32,
+        service.needs_x86_64_java(Some(&piston_meta.java_version.component)),
+    ))
+}
+
+/// The runtime of the preferred vendor, or the Zulu runtime the launch fell back to
+fn selected_runtime_id(
+    service: &JavaDownloadService,
+    vendor: JavaDistribution,
+    major_version: u32,
+    force_x86_64: bool,
+) -> String {
+    let runtime_id = JavaDownloadService::runtime_dir_name(&vendor, major_version, force_x86_64);
+    let fallback_id = JavaDownloadService::runtime_dir_name(
+        &JavaDistribution::Zulu,
+        major_version,
+        force_x86_64,
+    );
+    if !service.base_path().join(&runtime_id).exists()
+        && service.base_path().join(&fallback_id).exists()
+    {
+        return fallback_id;
+    }
+    runtime_id
+}
+
+/// All runtimes in `meta/java` with the profiles that use them
+pub async fn list_installed_runtimes() -> Result<Vec<InstalledJavaRuntime>> {
+    Ok(list_runtimes().await?.0)
+}
+
+/// The installed runtimes and the profiles whose runtime couldn't be determined
+async fn list_runtimes() -> Result<(Vec<InstalledJavaRuntime>, Vec<String>)> {
+    let service = JavaDownloadService::new();
+    let base_path = service.base_path().to_path_buf();
+    if !base_path.exists() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let RuntimeReferences {
+        references,
+        unresolved,
+    } = collect_references(&service).await?;
+    let running = running_java_paths().await;
+
+    let mut runtimes = Vec::new();
+    let mut entries = fs::read_dir(&base_path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let id = entry.file_name().to_string_lossy().into_owned();
+        if !path.is_dir() {
+            continue;
+        }
+        // Skips the `.pending` and `.old` directories of upgrades
+        let Some((distribution, major_version, force_x86_64)) = parse_runtime_dir_name(&id) else {
+            continue;
+        };
+
+        let java_binary = service.find_java_binary_in(&path).await.ok();
+        let release = match &java_binary {
+            Some(binary) => read_release_info(binary).await,
+            None => HashMap::new(),
+        };
+        let version = match (release.get("JAVA_VERSION"), &java_binary) {
+            (Some(version), _) => Some(version.clone()),
+            (None, Some(binary)) => java_detector::get_java_info(binary)
+                .await
+                .ok()
+                .map(|info| info.version),
+            (None, None) => None,
+        };
+        let architecture = release
+            .get("OS_ARCH")
+            .cloned()
+            .or_else(|| force_x86_64.then(|| "x86_64".to_string()));
+
+        let pending_path = base_path.join(format!("{}{}", id, PENDING_SUFFIX));
+        let pending_version = if pending_path.exists() {
+            runtime_version(&service, &pending_path).await
+        } else {
+            None
+        };
+
+        let referenced_by = references
+            .iter()
+            .filter(|(target, _)| match target {
+                ReferenceTarget::Path(java_path) => java_path.starts_with(&path),
+                ReferenceTarget::RuntimeId(runtime_id) => *runtime_id == id,
+            })
+            .map(|(_, reference)| reference.clone())
+            .collect();
+
+        runtimes.push(InstalledJavaRuntime {
+            size_bytes: dir_size(&path).await,
+            in_use: running.iter().any(|java| java.starts_with(&path)),
+            distribution: distribution.get_name().to_string(),
+            vendor: release.get("IMPLEMENTOR").cloned(),
+            id,
+            path,
+            java_binary,
+            major_version,
+            version,
+            architecture,
+            referenced_by,
+            pending_version,
+        });
+    }
+
+    runtimes.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok((runtimes, unresolved))
+}
+
+/// Fails if the runtime of any profile is unknown, since that profile could use any of them
+pub fn ensure_references_complete(unresolved_profiles: &[String]) -> Result<()> {
+    if unresolved_profiles.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::Other(format!(
+        "Can't tell which Java runtime these profiles use, nothing was removed: {}",
+        unresolved_profiles.join(", ")
+    )))
+}
+
+/// Runtimes no profile references and no running game uses
+pub fn removable_runtimes<'a>(
+    runtimes: &'a [InstalledJavaRuntime],
+    unresolved_profiles: &[String],
+) -> Result<Vec<&'a InstalledJavaRuntime>> {
+    ensure_references_complete(unresolved_profiles)?;
+    Ok(runtimes
+        .iter()
+        .filter(|runtime| !runtime.in_use && runtime.referenced_by.is_empty())
+        .collect())
+}
+
+/// Removes a runtime no profile references and no running game uses
+pub async fn uninstall_runtime(runtime_id: &str) -> Result<()> {
+    let (runtimes, unresolved) = list_runtimes().await?;
+    ensure_references_complete(&unresolved)?;
+    let runtime = runtimes
+        .into_iter()
+        .find(|runtime| runtime.id == runtime_id)
+        .ok_or_else(|| AppError::Other(format!("Java runtime '{}' not found", runtime_id)))?;
+
+    if runtime.in_use {
+        return Err(AppError::Other(format!(
+            "Java runtime '{}' is used by a running game",
+            runtime_id
+        )));
+    }
+    if !runtime.referenced_by.is_empty() {
+        let names: Vec<&str> = runtime
+            .referenced_by
+            .iter()
+            .map(|reference| reference.profile_name.as_str())
+            .collect();
+        return Err(AppError::Other(format!(
+            "Java runtime '{}' is used by: {}",
+            runtime_id,
+            names.join(", ")
+        )));
+    }
+
+    remove_runtime_dirs(&runtime.path).await
+}
+
+/// Removes every unreferenced runtime that isn't in use, plus leftovers of upgrades.
+/// Returns the removed runtime ids. Removes nothing while the runtime of any profile is unknown.
+pub async fn prune_runtimes() -> Result<Vec<String>> {
+    let service = JavaDownloadService::new();
+    let mut removed = Vec::new();
+
+    let (runtimes, unresolved) = list_runtimes().await?;
+    for runtime in removable_runtimes(&runtimes, &unresolved)? {
+        info!("Pruning unused Java runtime {}", runtime.id);
+        remove_runtime_dirs(&runtime.path).await?;
+        removed.push(runtime.id.clone());
+    }
+
+    // `.old` directories that couldn't be removed right after a swap. Games report the path
+    // they were started with, which is the runtime's regular name.
+    let base_path = service.base_path();
+    if !base_path.exists() {
+        return Ok(removed);
+    }
+    let mut entries = fs::read_dir(base_path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(runtime_id) = name.strip_suffix(OLD_SUFFIX) else {
+            continue;
+        };
+        if !is_runtime_in_use(&base_path.join(runtime_id)).await {
+            if let Err(e) = fs::remove_dir_all(entry.path()).await {
+                warn!(
+                    "Could not remove old Java runtime {:?}: {}",
+                    entry.path(),
+                    e
+                );
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
+async fn remove_runtime_dirs(runtime_path: &Path) -> Result<()> {
+    info!("Removing Java runtime {:?}", runtime_path);
+    fs::remove_dir_all(runtime_path).await?;
+    let pending_path = sibling(runtime_path, PENDING_SUFFIX);
+    if pending_path.exists() {
+        fs::remove_dir_all(pending_path).await?;
+    }
+    Ok(())
+}
+
+fn sibling(runtime_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = runtime_path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    runtime_path.with_file_name(name)
+}
+
+/// Downloads the newest patch release of the runtime's major line and swaps it in
+pub async fn upgrade_runtime(runtime_id: &str) -> Result<JavaRuntimeUpgrade> {
+    let state = State::get().await?;
+    if state.config_manager.get_config().await.offline_mode {
+        return Err(AppError::Other(
+            "Java runtimes can't be upgraded in offline mode".to_string(),
+        ));
+    }
+
+    let (distribution, major_version, force_x86_64) = parse_runtime_dir_name(runtime_id)
+        .ok_or_else(|| AppError::Other(format!("Invalid Java runtime id '{}'", runtime_id)))?;
+    let service = JavaDownloadService::new();
+    let runtime_path = service.base_path().join(runtime_id);
+    if !runtime_path.exists() {
+        return Err(AppError::Other(format!(
+            "Java runtime '{}' not found",
+            runtime_id
+        )));
+    }
+
+    let current = runtime_version(&service, &runtime_path).await;
+    let latest = service
+        .latest_release(major_version, &distribution, force_x86_64, None)
+        .await?;
+    if let (Some(current), Some(latest)) = (&current, &latest.version) {
+        if compare_java_versions(latest, current) != Ordering::Greater {
+            info!("Java runtime {} is up to date ({})", runtime_id, current);
+            return Ok(JavaRuntimeUpgrade::UpToDate {
+                version: Some(current.clone()),
+            });
+        }
+    }
+
+    let pending_path = sibling(&runtime_path, PENDING_SUFFIX);
+    if pending_path.exists() {
+        fs::remove_dir_all(&pending_path).await?;
+    }
+    info!(
+        "Upgrading Java runtime {} ({:?} -> {:?})",
+        runtime_id, current, latest.version
+    );
+    service.install_package(&latest, &pending_path).await?;
+
+    // Make sure the new runtime actually starts before it replaces the old one
+    let verified = match service.find_java_binary_in(&pending_path).await {
+        Ok(binary) => java_detector::get_java_info(&binary).await,
+        Err(e) => Err(e),
+    };
+    let new_version = match verified {
+        Ok(info) if info.major_version == major_version => runtime_version(&service, &pending_path)
+            .await
+            .unwrap_or(info.version),
+        Ok(info) => {
+            let _ = fs::remove_dir_all(&pending_path).await;
+            return Err(AppError::JavaDownload(format!(
+                "Downloaded Java {} instead of Java {}",
+                info.major_version, major_version
+            )));
+        }
+        Err(e) => {
+            let _ = fs::remove_dir_all(&pending_path).await;
+            return Err(AppError::JavaDownload(format!(
+                "Downloaded Java runtime doesn't work: {}",
+                e
+            )));
+        }
+    };
+
+    // Distributions without version info are only known after the download
+    if let Some(current) = &current {
+        if compare_java_versions(&new_version, current) != Ordering::Greater {
+            fs::remove_dir_all(&pending_path).await?;
+            return Ok(JavaRuntimeUpgrade::UpToDate {
+                version: Some(current.clone()),
+            });
+        }
+    }
+
+    if is_runtime_in_use(&runtime_path).await {
+        info!(
+            "Java runtime {} is in use, the upgrade to {} is applied before the next launch",
+            runtime_id, new_version
+        );
+        return Ok(JavaRuntimeUpgrade::Pending {
+            from: current,
+            to: new_version,
+        });
+    }
+
+    swap_in_pending(&runtime_path).await?;
+    Ok(JavaRuntimeUpgrade::Upgraded {
+        from: current,
+        to: new_version,
+    })
+}
+
+/// Swaps in a downloaded upgrade of `runtime_id` if there is one and no running game uses it
+pub async fn apply_pending_upgrade(base_path: &Path, runtime_id: &str) -> Result<()> {
+    let runtime_path = base_path.join(runtime_id);
+    if !sibling(&runtime_path, PENDING_SUFFIX).exists() {
+        return Ok(());
+    }
+    if is_runtime_in_use(&runtime_path).await {
+        debug!(
+            "Java runtime {} is still in use, keeping its upgrade pending",
+            runtime_id
+        );
+        return Ok(());
+    }
+    swap_in_pending(&runtime_path).await
+}
+
+/// `<id>` -> `<id>.old`, `<id>.pending` -> `<id>`, then removes `<id>.old`.
+/// The old runtime is moved back if the second rename fails.
+async fn swap_in_pending(runtime_path: &Path) -> Result<()> {
+    let pending_path = sibling(runtime_path, PENDING_SUFFIX);
+    let old_path = sibling(runtime_path, OLD_SUFFIX);
+
+    if old_path.exists() {
+        fs::remove_dir_all(&old_path).await?;
+    }
+    if runtime_path.exists() {
+        fs::rename(runtime_path, &old_path).await?;
+    }
+    if let Err(e) = fs::rename(&pending_path, runtime_path).await {
+        if old_path.exists() {
+            if let Err(restore_err) = fs::rename(&old_path, runtime_path).await {
+                warn!(
+                    "Could not restore Java runtime {:?}: {}",
+                    runtime_path, restore_err
+                );
+            }
+        }
+        return Err(e.into());
+    }
+    info!("Swapped in upgraded Java runtime {:?}", runtime_path);
+
+    // Removed by the next prune if this fails
+    if let Err(e) = fs::remove_dir_all(&old_path).await {
+        warn!("Could not remove old Java runtime {:?}: {}", old_path, e);
+    }
+    Ok(())
+}
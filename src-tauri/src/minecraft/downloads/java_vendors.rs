@@ -0,0 +1,714 @@
+//! Vendors managed Java runtimes can be downloaded from.
+//!
+//! Every vendor resolves a Java major version to a `JavaPackage`: where its newest build is
+//! downloaded from and the checksum the vendor publishes for it. Zulu, Temurin (Adoptium),
+//! Corretto and GraalVM ship a single archive; Mojang lists its runtimes in the `java-runtime`
+//! manifest on piston-meta, keyed by the `javaVersion.component` of a version JSON, and ships
+//! them file by file. The `parse_*` functions only work on response bodies so the adapters can
+//! be tested against recorded API responses.
+
+use crate::config::HTTP_CLIENT;
+use crate::error::{AppError, Result};
+use crate::minecraft::downloads::java_runtime_manager::compare_java_versions;
+use crate::minecraft::dto::{format_java_version, JavaDistribution, ZuluApiResponse};
+use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::utils::endpoint_registry::{self, Endpoint};
+use crate::utils::hash_utils;
+use crate::utils::system_info::{Architecture, OperatingSystem, ARCHITECTURE, OS};
+use futures::stream::{self, StreamExt};
+use log::{debug, info};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Component, Path};
+use tokio::fs;
+
+/// Index of all Mojang runtimes, the same one the vanilla launcher uses
+const MOJANG_RUNTIME_INDEX_PATH: &str =
+    "v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+const CONCURRENT_RUNTIME_DOWNLOADS: usize = 16;
+
+/// OS and architecture a runtime is downloaded for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JavaPlatform {
+    pub os: OperatingSystem,
+    pub arch: Architecture,
+}
+
+impl JavaPlatform {
+    /// The running platform, or its x86_64 variant for legacy runtimes on Apple Silicon
+    pub fn current(force_x86_64: bool) -> Self {
+        Self {
+            os: OS,
+            arch: if force_x86_64 {
+                Architecture::X64
+            } else {
+                ARCHITECTURE
+            },
+        }
+    }
+
+    /// Platform key of the Mojang runtime index
+    pub fn mojang_key(&self) -> Option<&'static str> {
+        Some(match (self.os, self.arch) {
+            (OperatingSystem::WINDOWS, Architecture::X64) => "windows-x64",
+            (OperatingSystem::WINDOWS, Architecture::X86) => "windows-x86",
+            (OperatingSystem::WINDOWS, Architecture::AARCH64) => "windows-arm64",
+            (OperatingSystem::OSX, Architecture::X64) => "mac-os",
+            (OperatingSystem::OSX, Architecture::AARCH64) => "mac-os-arm64",
+            (OperatingSystem::LINUX, Architecture::X64) => "linux",
+            (OperatingSystem::LINUX, Architecture::X86) => "linux-i386",
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JavaPackageSource {
+    /// A zip (Windows) or tar.gz archive with the runtime in a single root directory
+    Archive { url: String, sha256: String },
+    /// A Mojang runtime manifest listing every file of the runtime
+    MojangManifest { url: String, sha1: String },
+}
+
+/// The newest build of a Java major version from one vendor
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaPackage {
+    pub distribution: JavaDistribution,
+    pub major_version: u32,
+    /// e.g. `17.0.9` or `1.8.0_392`, if the vendor reports it before downloading
+    pub version: Option<String>,
+    pub source: JavaPackageSource,
+}
+
+/// One entry of a Mojang runtime manifest. Paths are relative to the runtime directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MojangRuntimeEntry {
+    Directory {
+        path: String,
+    },
+    File {
+        path: String,
+        url: String,
+        sha1: String,
+        size: u64,
+        executable: bool,
+    },
+    Link {
+        path: String,
+        target: String,
+    },
+}
+
+fn parse_error(vendor: &str, e: impl std::fmt::Display) -> AppError {
+    AppError::JavaDownload(format!("Failed to parse {} response: {}", vendor, e))
+}
+
+/// The hex digest of a `.sha256` file (`<hash>` or `<hash>  <file name>`)
+fn parse_sha256(text: &str) -> Result<String> {
+    text.split_whitespace()
+        .next()
+        .filter(|hash| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|hash| hash.to_ascii_lowercase())
+        .ok_or_else(|| {
+            AppError::JavaDownload(format!("Invalid SHA-256 checksum '{}'", text.trim()))
+        })
+}
+
+pub fn parse_zulu_package(body: &str, major_version: u32) -> Result<JavaPackage> {
+    let response: ZuluApiResponse =
+        serde_json::from_str(body).map_err(|e| parse_error("Zulu API", e))?;
+    let sha256 = response.sha256_hash.as_deref().ok_or_else(|| {
+        AppError::JavaDownload(format!(
+            "Zulu didn't publish a checksum for {}",
+            response.url
+        ))
+    })?;
+    Ok(JavaPackage {
+        distribution: JavaDistribution::Zulu,
+        major_version,
+        version: response.version_string(),
+        source: JavaPackageSource::Archive {
+            sha256: parse_sha256(sha256)?,
+            url: response.url,
+        },
+    })
+}
+
+#[derive(Deserialize)]
+struct AdoptiumAsset {
+    binary: AdoptiumBinary,
+    version: AdoptiumVersion,
+}
+
+#[derive(Deserialize)]
+struct AdoptiumBinary {
+    package: AdoptiumPackage,
+}
+
+#[derive(Deserialize)]
+struct AdoptiumPackage {
+    link: String,
+    checksum: String,
+}
+
+#[derive(Deserialize)]
+struct AdoptiumVersion {
+    major: u32,
+    minor: u32,
+    security: u32,
+}
+
+/// Parses `assets/latest/{major}/hotspot`. `None` if there's no build for the platform.
+pub fn parse_adoptium_package(body: &str, major_version: u32) -> Result<Option<JavaPackage>> {
+    let assets: Vec<AdoptiumAsset> =
+        serde_json::from_str(body).map_err(|e| parse_error("Adoptium API", e))?;
+    let Some(asset) = assets
+        .into_iter()
+        .find(|asset| asset.version.major == major_version)
+    else {
+        return Ok(None);
+    };
+    Ok(Some(JavaPackage {
+        distribution: JavaDistribution::Temurin,
+        major_version,
+        version: Some(format_java_version(
+            asset.version.major,
+            asset.version.minor,
+            asset.version.security,
+        )),
+        source: JavaPackageSource::Archive {
+            url: asset.binary.package.link,
+            sha256: parse_sha256(&asset.binary.package.checksum)?,
+        },
+    }))
+}
+
+/// `resolved_url` is where `downloads/latest/..` redirects to, e.g.
+/// `.../downloads/resources/17.0.9.8.1/amazon-corretto-17.0.9.8.1-linux-x64.tar.gz`
+pub fn parse_corretto_package(
+    major_version: u32,
+    resolved_url: &str,
+    sha256: &str,
+) -> Result<JavaPackage> {
+    // 17.0.9.8.1 -> 17.0.9, 8.392.08.1 -> 1.8.0_392
+    let version = resolved_url
+        .split_once("/resources/")
+        .and_then(|(_, rest)| rest.split('/').next())
+        .map(|version| {
+            version
+                .split('.')
+                .map_while(|part| part.parse::<u32>().ok())
+                .collect::<Vec<_>>()
+        })
+        .and_then(|numbers| match numbers.as_slice() {
+            [8, security, ..] if major_version == 8 => Some(format_java_version(8, 0, *security)),
+            [major, minor, security, ..] if *major == major_version => {
+                Some(format_java_version(*major, *minor, *security))
+            }
+            _ => None,
+        });
+    Ok(JavaPackage {
+        distribution: JavaDistribution::Corretto,
+        major_version,
+        version,
+        source: JavaPackageSource::Archive {
+            url: resolved_url.to_string(),
+            sha256: parse_sha256(sha256)?,
+        },
+    })
+}
+
+/// GraalVM only reports the version for archived builds (`graalvm-jdk-17.0.12_linux-x64_bin`)
+pub fn parse_graalvm_package(major_version: u32, url: &str, sha256: &str) -> Result<JavaPackage> {
+    let version = url
+        .rsplit('/')
+        .next()
+        .and_then(|file_name| file_name.strip_prefix("graalvm-jdk-"))
+        .and_then(|rest| rest.split_once('_'))
+        .map(|(version, _)| version)
+        .filter(|version| version.split('.').count() == 3)
+        .map(str::to_string);
+    Ok(JavaPackage {
+        distribution: JavaDistribution::GraalVM,
+        major_version,
+        version,
+        source: JavaPackageSource::Archive {
+            url: url.to_string(),
+            sha256: parse_sha256(sha256)?,
+        },
+    })
+}
+
+#[derive(Deserialize)]
+struct MojangRuntime {
+    manifest: MojangDownload,
+    version: MojangRuntimeVersion,
+}
+
+#[derive(Deserialize)]
+struct MojangDownload {
+    sha1: String,
+    #[serde(default)]
+    size: u64,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct MojangRuntimeVersion {
+    name: String,
+}
+
+/// `17.0.8` -> 17, `8u51` -> 8
+fn mojang_major_version(name: &str) -> Option<u32> {
+    name.split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// `8u51` -> `1.8.0_51`, everything else is kept
+fn mojang_version_string(name: &str) -> String {
+    match name.split_once('u') {
+        Some(("8", update)) if update.chars().all(|c| c.is_ascii_digit()) => {
+            format!("1.8.0_{}", update)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Picks a runtime from the `all.json` index. With a component (`java-runtime-gamma`,
+/// `jre-legacy`) that component is used, otherwise the newest runtime of the major version.
+/// `None` if Mojang has no matching runtime for the platform.
+pub fn parse_mojang_runtime_index(
+    body: &str,
+    platform_key: &str,
+    java_component: Option<&str>,
+    major_version: u32,
+) -> Result<Option<JavaPackage>> {
+    let index: HashMap<String, HashMap<String, Vec<MojangRuntime>>> =
+        serde_json::from_str(body).map_err(|e| parse_error("Mojang runtime index", e))?;
+    let Some(components) = index.get(platform_key) else {
+        return Ok(None);
+    };
+
+    let candidates: Vec<&MojangRuntime> = match java_component {
+        Some(component) => components
+            .get(component)
+            .map(|runtimes| runtimes.iter().collect())
+            .unwrap_or_default(),
+        None => components.values().flatten().collect(),
+    };
+    let runtime = candidates
+        .into_iter()
+        .filter(|runtime| mojang_major_version(&runtime.version.name) == Some(major_version))
+        .max_by(|a, b| {
+            compare_java_versions(
+                &mojang_version_string(&a.version.name),
+                &mojang_version_string(&b.version.name),
+            )
+        });
+
+    Ok(runtime.map(|runtime| JavaPackage {
+        distribution: JavaDistribution::Mojang,
+        major_version,
+        version: Some(mojang_version_string(&runtime.version.name)),
+        source: JavaPackageSource::MojangManifest {
+            url: runtime.manifest.url.clone(),
+            sha1: runtime.manifest.sha1.clone(),
+        },
+    }))
+}
+
+#[derive(Deserialize)]
+struct MojangRuntimeManifest {
+    files: HashMap<String, MojangRuntimeFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum MojangRuntimeFile {
+    Directory,
+    File {
+        downloads: MojangRuntimeFileDownloads,
+        #[serde(default)]
+        executable: bool,
+    },
+    Link {
+        target: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct MojangRuntimeFileDownloads {
+    raw: MojangDownload,
+}
+
+/// Parses a runtime manifest. Entries are sorted by path so directories come before their
+/// content; paths escaping the runtime directory are rejected.
+pub fn parse_mojang_runtime_manifest(body: &str) -> Result<Vec<MojangRuntimeEntry>> {
+    let manifest: MojangRuntimeManifest =
+        serde_json::from_str(body).map_err(|e| parse_error("Mojang runtime manifest", e))?;
+
+    let mut entries = Vec::with_capacity(manifest.files.len());
+    for (path, file) in manifest.files {
+        let is_safe = !path.is_empty()
+            && Path::new(&path)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_safe {
+            return Err(AppError::JavaDownload(format!(
+                "Invalid path '{}' in Mojang runtime manifest",
+                path
+            )));
+        }
+        entries.push(match file {
+            MojangRuntimeFile::Directory => MojangRuntimeEntry::Directory { path },
+            MojangRuntimeFile::File {
+                downloads,
+                executable,
+            } => MojangRuntimeEntry::File {
+                path,
+                url: downloads.raw.url,
+                sha1: downloads.raw.sha1,
+                size: downloads.raw.size,
+                executable,
+            },
+            MojangRuntimeFile::Link { target } => MojangRuntimeEntry::Link { path, target },
+        });
+    }
+    entries.sort_by(|a, b| entry_path(a).cmp(entry_path(b)));
+    Ok(entries)
+}
+
+fn entry_path(entry: &MojangRuntimeEntry) -> &str {
+    match entry {
+        MojangRuntimeEntry::Directory { path }
+        | MojangRuntimeEntry::File { path, .. }
+        | MojangRuntimeEntry::Link { path, .. } => path,
+    }
+}
+
+/// Resolves the newest build of `major_version` from a vendor
+pub async fn resolve_package(
+    distribution: JavaDistribution,
+    major_version: u32,
+    java_component: Option<&str>,
+    platform: JavaPlatform,
+) -> Result<JavaPackage> {
+    if !distribution.supports_version(major_version) {
+        return Err(AppError::JavaDownload(format!(
+            "{} doesn't provide Java {}",
+            distribution.get_name(),
+            major_version
+        )));
+    }
+    info!(
+        "Resolving Java {} from {} for {:?}",
+        major_version,
+        distribution.get_name(),
+        platform
+    );
+
+    match distribution {
+        JavaDistribution::Zulu => resolve_zulu(major_version, platform).await,
+        JavaDistribution::Temurin => resolve_adoptium(major_version, platform).await,
+        JavaDistribution::Corretto => resolve_corretto(major_version, platform).await,
+        JavaDistribution::GraalVM => resolve_graalvm(major_version, platform).await,
+        JavaDistribution::Mojang => resolve_mojang(major_version, java_component, platform).await,
+    }
+}
+
+fn unsupported_platform(distribution: JavaDistribution, platform: JavaPlatform) -> AppError {
+    AppError::JavaDownload(format!(
+        "{} doesn't provide Java for {:?} {:?}",
+        distribution.get_name(),
+        platform.os,
+        platform.arch
+    ))
+}
+
+async fn fetch_text(urls: &[String], vendor: &str) -> Result<String> {
+    let response = endpoint_registry::send_with_fallback(urls, |url| HTTP_CLIENT.get(url))
+        .await
+        .map_err(|e| AppError::JavaDownload(format!("Failed to reach {}: {}", vendor, e)))?;
+    if !response.status().is_success() {
+        return Err(AppError::JavaDownload(format!(
+            "{} returned error status: {}",
+            vendor,
+            response.status()
+        )));
+    }
+    response
+        .text()
+        .await
+        .map_err(|e| AppError::JavaDownload(format!("Failed to read {} response: {}", vendor, e)))
+}
+
+async fn resolve_zulu(major_version: u32, platform: JavaPlatform) -> Result<JavaPackage> {
+    let arch = match platform.arch {
+        Architecture::X64 => "x64",
+        Architecture::AARCH64 => "aarch64",
+        Architecture::ARM => "arm32-vfp-hflt",
+        _ => return Err(unsupported_platform(JavaDistribution::Zulu, platform)),
+    };
+    let (os, ext) = match platform.os {
+        OperatingSystem::WINDOWS => ("win", "zip"),
+        OperatingSystem::LINUX => ("linux", "tar.gz"),
+        OperatingSystem::OSX => ("macosx", "tar.gz"),
+        _ => return Err(unsupported_platform(JavaDistribution::Zulu, platform)),
+    };
+    let path = format!(
+        "bundles/latest/?jdk_version={}&bundle_type=jre&ext={}&arch={}&os={}",
+        major_version, ext, arch, os
+    );
+    let body = fetch_text(
+        &endpoint_registry::urls_for(Endpoint::Zulu, &path),
+        "Zulu API",
+    )
+    .await?;
+    parse_zulu_package(&body, major_version)
+}
+
+async fn resolve_adoptium(major_version: u32, platform: JavaPlatform) -> Result<JavaPackage> {
+    let arch = match platform.arch {
+        Architecture::X64 => "x64",
+        Architecture::X86 => "x32",
+        Architecture::AARCH64 => "aarch64",
+        Architecture::ARM => "arm",
+        _ => return Err(unsupported_platform(JavaDistribution::Temurin, platform)),
+    };
+    let os = platform.os.get_adoptium_name()?;
+
+    // Not every version has a JRE build (e.g. macOS aarch64 for older releases)
+    for image_type in ["jre", "jdk"] {
+        let path = format!(
+            "assets/latest/{}/hotspot?architecture={}&image_type={}&os={}&vendor=eclipse",
+            major_version, arch, image_type, os
+        );
+        let body = fetch_text(
+            &endpoint_registry::urls_for(Endpoint::Adoptium, &path),
+            "Adoptium API",
+        )
+        .await?;
+        if let Some(package) = parse_adoptium_package(&body, major_version)? {
+            return Ok(package);
+        }
+    }
+    Err(unsupported_platform(JavaDistribution::Temurin, platform))
+}
+
+async fn resolve_corretto(major_version: u32, platform: JavaPlatform) -> Result<JavaPackage> {
+    let arch = match platform.arch {
+        Architecture::X64 => "x64",
+        Architecture::X86 => "x86",
+        Architecture::AARCH64 => "aarch64",
+        _ => return Err(unsupported_platform(JavaDistribution::Corretto, platform)),
+    };
+    let (os, ext) = match platform.os {
+        OperatingSystem::WINDOWS => ("windows", "zip"),
+        OperatingSystem::LINUX => ("linux", "tar.gz"),
+        OperatingSystem::OSX => ("macos", "tar.gz"),
+        _ => return Err(unsupported_platform(JavaDistribution::Corretto, platform)),
+    };
+    let file_name = format!(
+        "amazon-corretto-{}-{}-{}-jdk.{}",
+        major_version, arch, os, ext
+    );
+
+    // The "latest" link redirects to the versioned archive, which is what gets downloaded so
+    // the checksum can't change in between
+    let urls = endpoint_registry::urls_for(
+        Endpoint::Corretto,
+        &format!("downloads/latest/{}", file_name),
+    );
+    let response = endpoint_registry::send_with_fallback(&urls, |url| HTTP_CLIENT.head(url))
+        .await
+        .map_err(|e| AppError::JavaDownload(format!("Failed to reach Corretto: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(AppError::JavaDownload(format!(
+            "Corretto returned error status: {}",
+            response.status()
+        )));
+    }
+    let resolved_url = response.url().to_string();
+
+    let sha256 = fetch_text(
+        &endpoint_registry::urls_for(
+            Endpoint::Corretto,
+            &format!("downloads/latest_sha256/{}", file_name),
+        ),
+        "Corretto",
+    )
+    .await?;
+    parse_corretto_package(major_version, &resolved_url, &sha256)
+}
+
+async fn resolve_graalvm(major_version: u32, platform: JavaPlatform) -> Result<JavaPackage> {
+    let arch = match platform.arch {
+        Architecture::X64 => "x64",
+        Architecture::AARCH64 => "aarch64",
+        _ => return Err(unsupported_platform(JavaDistribution::GraalVM, platform)),
+    };
+    let os = platform.os.get_graal_name()?;
+    let ext = platform.os.get_archive_type()?;
+
+    let path = if major_version == 17 {
+        // GraalVM for JDK 17 is no longer updated, the last release is only in the archive
+        format!("17/archive/graalvm-jdk-17.0.12_{}-{}_bin.{}", os, arch, ext)
+    } else {
+        format!(
+            "{}/latest/graalvm-jdk-{}_{}-{}_bin.{}",
+            major_version, major_version, os, arch, ext
+        )
+    };
+    let url = format!(
+        "{}/{}",
+        endpoint_registry::base_url(Endpoint::GraalVm),
+        path
+    );
+    let sha256 = fetch_text(
+        &endpoint_registry::urls_for(Endpoint::GraalVm, &format!("{}.sha256", path)),
+        "GraalVM",
+    )
+    .await?;
+    parse_graalvm_package(major_version, &url, &sha256)
+}
+
+async fn resolve_mojang(
+    major_version: u32,
+    java_component: Option<&str>,
+    platform: JavaPlatform,
+) -> Result<JavaPackage> {
+    let platform_key = platform
+        .mojang_key()
+        .ok_or_else(|| unsupported_platform(JavaDistribution::Mojang, platform))?;
+    let body = fetch_text(
+        &endpoint_registry::urls_for(Endpoint::PistonMeta, MOJANG_RUNTIME_INDEX_PATH),
+        "Mojang runtime index",
+    )
+    .await?;
+    parse_mojang_runtime_index(&body, platform_key, java_component, major_version)?.ok_or_else(
+        || {
+            AppError::JavaDownload(format!(
+                "Mojang has no Java {} runtime ({}) for {}",
+                major_version,
+                java_component.unwrap_or("any component"),
+                platform_key
+            ))
+        },
+    )
+}
+
+/// Downloads every file of a Mojang runtime into `target_dir`, verifying the manifest and
+/// each file against their SHA-1
+pub async fn install_mojang_runtime(
+    manifest_url: &str,
+    manifest_sha1: &str,
+    target_dir: &Path,
+) -> Result<()> {
+    let response = endpoint_registry::send_with_fallback(
+        &endpoint_registry::resolve_url(manifest_url),
+        |url| HTTP_CLIENT.get(url),
+    )
+    .await
+    .map_err(|e| AppError::JavaDownload(format!("Failed to fetch runtime manifest: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(AppError::JavaDownload(format!(
+            "Runtime manifest returned error status: {}",
+            response.status()
+        )));
+    }
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::JavaDownload(format!("Failed to read runtime manifest: {}", e)))?;
+
+    let calculated = hash_utils::calculate_sha1_from_bytes(&body);
+    if !calculated.eq_ignore_ascii_case(manifest_sha1) {
+        return Err(AppError::JavaDownload(format!(
+            "Runtime manifest checksum mismatch: expected {}, got {}",
+            manifest_sha1, calculated
+        )));
+    }
+    let entries = parse_mojang_runtime_manifest(&String::from_utf8_lossy(&body))?;
+    debug!("Mojang runtime manifest has {} entries", entries.len());
+
+    fs::create_dir_all(target_dir).await?;
+    for entry in &entries {
+        if let MojangRuntimeEntry::Directory { path } = entry {
+            fs::create_dir_all(target_dir.join(path)).await?;
+        }
+    }
+
+    let files: Vec<_> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            MojangRuntimeEntry::File {
+                path,
+                url,
+                sha1,
+                size,
+                executable,
+            } => Some((path, url, sha1, *size, *executable)),
+            _ => None,
+        })
+        .collect();
+    info!(
+        "Downloading {} files of the Mojang runtime to {:?}",
+        files.len(),
+        target_dir
+    );
+
+    let results: Vec<Result<()>> = stream::iter(files)
+        .map(|(path, url, sha1, size, executable)| async move {
+            let file_path = target_dir.join(path);
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            let config = DownloadConfig::new()
+                .with_sha1(sha1.as_str())
+                .with_size(size)
+                .with_retries(3)
+                .with_force_overwrite(true);
+            DownloadUtils::download_file(url, &file_path, config)
+                .await
+                .map_err(|e| {
+                    AppError::JavaDownload(format!("Failed to download {}: {}", path, e))
+                })?;
+
+            #[cfg(unix)]
+            if executable {
+                use std::os::unix::fs::PermissionsExt;
+                let mut permissions = fs::metadata(&file_path).await?.permissions();
+                permissions.set_mode(permissions.mode() | 0o755);
+                fs::set_permissions(&file_path, permissions).await?;
+            }
+            #[cfg(not(unix))]
+            let _ = executable;
+
+            Ok::<(), AppError>(())
+        })
+        .buffer_unordered(CONCURRENT_RUNTIME_DOWNLOADS)
+        .collect()
+        .await;
+    for result in results {
+        result?;
+    }
+
+    for entry in &entries {
+        if let MojangRuntimeEntry::Link { path, target } = entry {
+            #[cfg(unix)]
+            {
+                let link_path = target_dir.join(path);
+                if fs::symlink_metadata(&link_path).await.is_ok() {
+                    fs::remove_file(&link_path).await?;
+                }
+                fs::symlink(target, &link_path).await?;
+            }
+            #[cfg(not(unix))]
+            debug!("Skipping link {} -> {} of the Mojang runtime", path, target);
+        }
+    }
+
+    Ok(())
+}
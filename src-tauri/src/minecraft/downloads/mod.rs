@@ -2,6 +2,8 @@ pub mod fabric_libraries_download;
 pub mod forge_installer_download;
 pub mod forge_libraries_download;
 pub mod java_download;
+pub mod java_runtime_manager;
+pub mod java_vendors;
 pub mod logging_config_download;
 pub mod mc_assets_download;
 pub mod mc_client_download;
@@ -1,5 +1,6 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::integrations::curseforge;
 use crate::minecraft::downloads::mod_resolver::TargetMod;
 use crate::state::profile_state::{self, ModSource, Profile};
 use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
@@ -95,6 +96,30 @@ impl ModDownloadService {
                             e
                         })
                     }
+                    ModSource::CurseForge {
+                        file_id,
+                        download_url,
+                        file_hash_sha1,
+                        ..
+                    } => {
+                        info!(
+                            "Preparing CurseForge mod for cache: {} ({})",
+                            display_name, filename
+                        );
+                        // Packs whose authors disabled third-party downloads only resolve to the CDN URL
+                        let url = download_url
+                            .unwrap_or_else(|| curseforge::cdn_download_url(file_id, &filename));
+                        Self::download_and_verify_file(
+                            &url,
+                            &target_path,
+                            file_hash_sha1.as_deref(),
+                        )
+                        .await
+                        .map_err(|e| {
+                            error!("Failed cache mod {}: {}", display_name, e);
+                            e
+                        })
+                    }
                     ModSource::Url { url, file_name, .. } => {
                         let fname = file_name.as_deref().unwrap_or("unknown");
                         debug!(
@@ -166,6 +166,7 @@ pub async fn resolve_target_mods(
     fn get_canonical_key_profile(source: &ModSource) -> Option<String> {
         match source {
             ModSource::Modrinth { project_id, .. } => Some(format!("modrinth:{}", project_id)),
+            ModSource::CurseForge { project_id, .. } => Some(format!("curseforge:{}", project_id)),
             ModSource::Url { url, .. } => Some(format!("url:{}", url)),
             ModSource::Maven { coordinates, .. } => Some(format!("maven:{}", coordinates)),
             _ => None, // Ignore other types
@@ -447,7 +448,7 @@ pub async fn resolve_target_mods(
                     );
                 }
             }
-            ModSource::Url { .. } | ModSource::Maven { .. } => {
+            ModSource::Url { .. } | ModSource::Maven { .. } | ModSource::CurseForge { .. } => {
                 // Common logic for sources that can override pack mods
                 if let Some(canonical_key) = get_canonical_key_profile(&mod_info.source) {
                     match profile_state::get_profile_mod_filename(&mod_info.source) {
@@ -455,6 +456,7 @@ pub async fn resolve_target_mods(
                             let mod_type_str = match &mod_info.source {
                                 ModSource::Url { .. } => "profile URL",
                                 ModSource::Maven { .. } => "profile Maven",
+                                ModSource::CurseForge { .. } => "profile CurseForge",
                                 _ => "profile Unknown", // Should not happen here
                             };
                             let mod_id_string = mod_info.id.to_string();
@@ -466,7 +468,7 @@ pub async fn resolve_target_mods(
                                 &mut final_mods,
                                 mod_type_str,
                                 mod_name,
-                                None, // URL/Maven mods don't have project IDs
+                                None, // URL/Maven/CurseForge mods have no Modrinth project IDs
                                 enable_flagsmith_blocking,
                             ).await;
                         }
@@ -3,6 +3,7 @@ use crate::error::{AppError, Result};
 use crate::minecraft::dto::neo_forge_install_profile::NeoForgeInstallProfile;
 use crate::minecraft::dto::neo_forge_meta::NeoForgeVersion;
 use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::utils::endpoint_registry::{self, Endpoint};
 use async_zip::tokio::read::seek::ZipFileReader;
 use log::info;
 use std::path::PathBuf;
@@ -34,7 +35,11 @@ impl NeoForgeInstallerDownloadService {
         let jar_path = self.base_path.join(&maven_path);
 
         // Konstruiere die Download-URL
-        let url = format!("https://maven.neoforged.net/{}", maven_path);
+        let url = format!(
+            "{}/{}",
+            endpoint_registry::base_url(Endpoint::NeoForgeMaven),
+            maven_path
+        );
 
         info!("Downloading from: {}", url);
 
@@ -4,9 +4,11 @@ use crate::minecraft::api::NoRiskApi;
 use crate::minecraft::auth::minecraft_auth::Credentials;
 use crate::minecraft::dto::norisk_meta::NoriskAssets;
 use crate::minecraft::dto::piston_meta::AssetObject;
+use crate::minecraft::offline;
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::Profile;
 use crate::state::State;
+use crate::utils::endpoint_registry::{self, Endpoint};
 use futures::stream::{iter, StreamExt};
 use log::{debug, error, info, trace, warn};
 use std::collections::HashSet;
@@ -222,7 +224,9 @@ impl NoriskClientAssetsDownloadService {
         }
 
         // --- Cleanup Orphan Assets (only if keep_local_assets is false) ---
-        if !keep_local_assets {
+        if offline::is_check_only() {
+            info!("[NRC Assets Cleanup] Skipping cleanup, only checking the cache.");
+        } else if !keep_local_assets {
             info!(
                 "[NRC Assets Cleanup] Cleaning up orphan files in target directory: {}",
                 target_base_dir.display()
@@ -304,7 +308,8 @@ impl NoriskClientAssetsDownloadService {
         .await?;
 
         let assets =
-            match NoRiskApi::norisk_assets(asset_id, norisk_token, request_uuid, is_experimental)
+            match self
+                .fetch_asset_index(asset_id, norisk_token, request_uuid, is_experimental)
                 .await
             {
                 Ok(fetched_assets) => {
@@ -420,6 +425,11 @@ impl NoriskClientAssetsDownloadService {
             }
         }
 
+        // The copy only needs the cache that was just checked
+        if offline::is_check_only() {
+            return Ok(expected_paths_for_group);
+        }
+
         // 3. Copy assets
         self.emit_progress_event(
             state,
@@ -470,6 +480,63 @@ impl NoriskClientAssetsDownloadService {
         Ok(expected_paths_for_group)
     }
 
+    /// Path of the locally cached asset index for an asset group
+    fn asset_index_cache_path(&self, asset_id: &str) -> PathBuf {
+        self.base_path
+            .join(NORISK_ASSETS_DIR)
+            .join(asset_id)
+            .join("index.json")
+    }
+
+    /// Fetches the asset index for a group and keeps a local copy of it, so the assets
+    /// can be verified without the API in offline mode.
+    async fn fetch_asset_index(
+        &self,
+        asset_id: &str,
+        norisk_token: &str,
+        request_uuid: &str,
+        is_experimental: bool,
+    ) -> Result<NoriskAssets> {
+        let cache_path = self.asset_index_cache_path(asset_id);
+
+        if offline::is_active() {
+            let cached = match fs::read_to_string(&cache_path).await {
+                Ok(content) => serde_json::from_str::<NoriskAssets>(&content).ok(),
+                Err(_) => None,
+            };
+            return cached.ok_or_else(|| {
+                offline::missing_artifact(
+                    format!("NoRisk asset index '{}'", asset_id),
+                    &cache_path,
+                    None,
+                )
+            });
+        }
+
+        let assets =
+            NoRiskApi::norisk_assets(asset_id, norisk_token, request_uuid, is_experimental).await?;
+
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        match serde_json::to_string(&assets) {
+            Ok(json) => {
+                if let Err(e) = fs::write(&cache_path, json).await {
+                    warn!(
+                        "[NRC Assets Group '{}'] Failed to cache asset index: {}",
+                        asset_id, e
+                    );
+                }
+            }
+            Err(e) => warn!(
+                "[NRC Assets Group '{}'] Failed to serialize asset index: {}",
+                asset_id, e
+            ),
+        }
+
+        Ok(assets)
+    }
+
     /// Downloads NoRisk client assets for a specific asset ID (pack or asset group).
     async fn download_nrc_assets(
         &self,
@@ -484,7 +551,7 @@ impl NoriskClientAssetsDownloadService {
             asset_id
         );
         let assets_path = self.base_path.join(NORISK_ASSETS_DIR).join(asset_id);
-        if !fs::try_exists(&assets_path).await? {
+        if !offline::is_check_only() && !fs::try_exists(&assets_path).await? {
             fs::create_dir_all(&assets_path).await?;
             info!(
                 "[NRC Assets Download '{}'] Created directory: {}",
@@ -510,6 +577,7 @@ impl NoriskClientAssetsDownloadService {
             assets_list.len()
         );
         let mut job_count = 0;
+        let mut missing_offline = 0;
 
         let state = if profile_id.is_some() {
             State::get().await.ok() // Change to ok() to allow optional state
@@ -546,6 +614,16 @@ impl NoriskClientAssetsDownloadService {
                 continue;
             }
 
+            if offline::is_active() {
+                offline::missing_artifact(
+                    format!("NoRisk asset '{}' ({})", name_clone, asset_id_clone),
+                    &target_path,
+                    None,
+                );
+                missing_offline += 1;
+                continue;
+            }
+
             job_count += 1;
             total_to_download_clone.fetch_add(1, Ordering::SeqCst);
             downloads.push(async move {
@@ -553,15 +631,18 @@ impl NoriskClientAssetsDownloadService {
                 trace!("[NRC Assets Download '{}' Task {}] Starting download for: {}", asset_id_clone, task_id, name_clone);
 
                 // Use updated URL format from user edit
-                let url = format!(
-                    "{}/{}/assets/{}",
-                    "https://cdn.norisk.gg/assets", asset_id_clone, name_clone
+                let urls = endpoint_registry::urls_for(
+                    Endpoint::NoriskCdn,
+                    &format!("assets/{}/assets/{}", asset_id_clone, name_clone),
                 );
 
-                let mut request = HTTP_CLIENT.get(&url);
-                request = request.header("Authorization", format!("Bearer {}", norisk_token_clone));
-
-                let response = match request.send().await {
+                let response = match endpoint_registry::send_with_fallback(&urls, |url| {
+                    HTTP_CLIENT
+                        .get(url)
+                        .header("Authorization", format!("Bearer {}", norisk_token_clone))
+                })
+                .await
+                {
                     Ok(resp) => resp,
                     Err(e) => {
                         error!("[NRC Assets Download '{}' Task {}] Request error for {}: {}", asset_id_clone, task_id, name_clone, e);
@@ -619,6 +700,13 @@ impl NoriskClientAssetsDownloadService {
             asset_id, job_count
         );
 
+        if missing_offline > 0 {
+            return Err(AppError::OfflineArtifactMissing(format!(
+                "{} NoRisk assets of group '{}'",
+                missing_offline, asset_id
+            )));
+        }
+
         if job_count == 0 {
             info!(
                 "[NRC Assets Download '{}'] No new assets to download.",
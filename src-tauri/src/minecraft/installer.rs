@@ -9,8 +9,8 @@ use crate::minecraft::downloads::mc_libraries_download::MinecraftLibrariesDownlo
 use crate::minecraft::downloads::mc_natives_download::MinecraftNativesDownloadService;
 use crate::minecraft::downloads::NoriskPackDownloadService;
 use crate::minecraft::downloads::{ModDownloadService, NoriskClientAssetsDownloadService};
-use crate::minecraft::dto::JavaDistribution;
-use crate::minecraft::{MinecraftLaunchParameters, MinecraftLauncher};
+use crate::minecraft::java_requirements::{self, JavaRequirement};
+use crate::minecraft::{LaunchCommand, MinecraftLaunchParameters, MinecraftLauncher};
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::{ModLoader, Profile};
 use crate::state::state_manager::State;
@@ -19,8 +19,10 @@ use rand::Rng;
 use uuid::Uuid;
 
 use super::minecraft_auth::Credentials;
-use super::modloader::ModloaderFactory;
+use super::modloader::{ModloaderFactory, ModloaderInstallResult};
+use super::offline::{self, OfflineReport};
 use crate::minecraft::downloads::MinecraftLoggingDownloadService;
+use crate::utils::hook_utils::{resolve_hooks, HookContext};
 use crate::utils::mc_utils;
 use tokio::fs as async_fs;
 
@@ -46,6 +48,18 @@ async fn emit_progress_event(
     Ok(event_id)
 }
 
+/// What the installer does once all game files are in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallMode {
+    /// Install everything, run the pre-launch hook and start the game
+    Launch,
+    /// Install everything but do not start the game (headless `--install`)
+    InstallOnly,
+    /// Install everything like `InstallOnly`, then resolve the game command without running
+    /// hooks or spawning it. Not a dry run: missing game files are downloaded.
+    ResolveCommand,
+}
+
 pub async fn install_minecraft_version(
     version_id: &str,
     modloader_str: &str,
@@ -54,6 +68,160 @@ pub async fn install_minecraft_version(
     quick_play_singleplayer: Option<String>,
     quick_play_multiplayer: Option<String>,
 ) -> Result<()> {
+    install_minecraft_version_with_mode(
+        version_id,
+        modloader_str,
+        profile,
+        credentials,
+        quick_play_singleplayer,
+        quick_play_multiplayer,
+        InstallMode::Launch,
+    )
+    .await
+}
+
+pub async fn install_minecraft_version_with_mode(
+    version_id: &str,
+    modloader_str: &str,
+    profile: &Profile,
+    credentials: Option<Credentials>,
+    quick_play_singleplayer: Option<String>,
+    quick_play_multiplayer: Option<String>,
+    mode: InstallMode,
+) -> Result<()> {
+    run_install(
+        version_id,
+        modloader_str,
+        profile,
+        credentials,
+        quick_play_singleplayer,
+        quick_play_multiplayer,
+        mode,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Installs the profile and returns the command it would be launched with, without starting it.
+/// Secrets are not redacted, see `LaunchCommand::redacted`.
+pub async fn resolve_launch_command(
+    profile: &Profile,
+    credentials: Option<Credentials>,
+    quick_play_singleplayer: Option<String>,
+    quick_play_multiplayer: Option<String>,
+) -> Result<LaunchCommand> {
+    run_install(
+        &profile.game_version,
+        profile.loader.as_str(),
+        profile,
+        credentials,
+        quick_play_singleplayer,
+        quick_play_multiplayer,
+        InstallMode::ResolveCommand,
+    )
+    .await?
+    .ok_or_else(|| AppError::Other("Installation did not resolve a launch command".to_string()))
+}
+
+async fn run_install(
+    version_id: &str,
+    modloader_str: &str,
+    profile: &Profile,
+    credentials: Option<Credentials>,
+    quick_play_singleplayer: Option<String>,
+    quick_play_multiplayer: Option<String>,
+    mode: InstallMode,
+) -> Result<Option<LaunchCommand>> {
+    let state = State::get().await?;
+    if !state.config_manager.get_config().await.offline_mode {
+        return install(
+            version_id,
+            modloader_str,
+            profile,
+            credentials,
+            quick_play_singleplayer,
+            quick_play_multiplayer,
+            mode,
+        )
+        .await;
+    }
+
+    info!(
+        "[Offline] Offline mode enabled, installing profile '{}' from local cache only",
+        profile.name
+    );
+    let (result, report) = offline::run(
+        profile,
+        install(
+            version_id,
+            modloader_str,
+            profile,
+            credentials,
+            quick_play_singleplayer,
+            quick_play_multiplayer,
+            mode,
+        ),
+    )
+    .await;
+
+    if report.is_ready() {
+        return result;
+    }
+
+    let summary = report.summary();
+    error!("[Offline] {}", summary);
+    Err(AppError::OfflineNotReady(summary))
+}
+
+/// Runs the install pipeline for a profile in offline mode without launching it and
+/// returns everything that would be missing to start it from the local cache.
+/// Only inspects the disk, nothing is extracted, copied or created.
+pub async fn check_offline_readiness(
+    profile: &Profile,
+    credentials: Option<Credentials>,
+) -> Result<OfflineReport> {
+    let (result, report) = offline::run_check(
+        profile,
+        install(
+            &profile.game_version,
+            profile.loader.as_str(),
+            profile,
+            credentials,
+            None,
+            None,
+            InstallMode::InstallOnly,
+        ),
+    )
+    .await;
+
+    match result {
+        Err(e) if report.is_ready() => Err(e),
+        _ => Ok(report),
+    }
+}
+
+/// In offline mode a step that fails because artifacts are missing does not abort the
+/// installation, so the remaining steps can still report what they are missing.
+fn continue_offline<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if offline::has_missing() => {
+            warn!("[Offline] Step incomplete, continuing to check remaining steps: {}", e);
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+async fn install(
+    version_id: &str,
+    modloader_str: &str,
+    profile: &Profile,
+    credentials: Option<Credentials>,
+    quick_play_singleplayer: Option<String>,
+    quick_play_multiplayer: Option<String>,
+    mode: InstallMode,
+) -> Result<Option<LaunchCommand>> {
     // Convert string modloader to ModLoader enum
     let modloader_enum = match modloader_str {
         "vanilla" => ModLoader::Vanilla,
@@ -115,6 +283,7 @@ pub async fn install_minecraft_version(
         );
     }
 
+    offline::set_step("metadata");
     let api_service = MinecraftApiService::new();
     let manifest = api_service.get_version_manifest().await?;
     let version = manifest
@@ -144,6 +313,10 @@ pub async fn install_minecraft_version(
 
     // Check if profile uses a custom Java path
     let mut custom_java_valid = false;
+    // The custom Java can be newer than required, JVM preset flags depend on the actual version
+    let mut java_major_version = java_version;
+    // Vendor and VM of the custom Java, some JVM presets' collectors are missing in some builds
+    let mut custom_java_vendor = None;
     let java_path = if profile.settings.use_custom_java_path && profile.settings.java_path.is_some()
     {
         // Try to use the custom Java path
@@ -169,6 +342,12 @@ pub async fn install_minecraft_version(
                             java_info.major_version, java_version
                         );
                         custom_java_valid = true;
+                        java_major_version = java_info.major_version;
+                        custom_java_vendor = Some(format!(
+                            "{} {}",
+                            java_info.vendor,
+                            java_info.vm_name.as_deref().unwrap_or_default()
+                        ));
                         path
                     } else {
                         info!(
@@ -203,8 +382,18 @@ pub async fn install_minecraft_version(
         std::path::PathBuf::new()
     };
 
+    // Java vendor: global for standard profiles, profile-specific override for custom
+    let global_java_vendor = state.config_manager.get_config().await.java_vendor;
+    let java_vendor = if profile.is_standard_version {
+        global_java_vendor
+    } else {
+        profile.settings.java_vendor.unwrap_or(global_java_vendor)
+    };
+    let java_runtime_vendor =
+        custom_java_vendor.unwrap_or_else(|| java_vendor.get_name().to_string());
+
     // Download and setup Java if necessary
-    let java_path = if custom_java_valid {
+    let mut java_path = if custom_java_valid {
         info!("Using verified custom Java path: {:?}", java_path);
 
         // Update progress to 100% since we're using a custom path
@@ -222,14 +411,18 @@ pub async fn install_minecraft_version(
     } else {
         // Download Java since custom path is not valid or not set
         info!("Downloading Java {}...", java_version);
+        offline::set_step("java");
         let java_service = JavaDownloadService::new();
-        let downloaded_path = java_service
-            .get_or_download_java(
-                java_version,
-                &JavaDistribution::Zulu,
-                Some(&piston_meta.java_version.component),
-            )
-            .await?;
+        let downloaded_path = continue_offline(
+            java_service
+                .get_or_download_java(
+                    java_version,
+                    &java_vendor,
+                    Some(&piston_meta.java_version.component),
+                )
+                .await,
+        )?
+        .unwrap_or_default();
 
         info!("Java installation path: {:?}", downloaded_path);
 
@@ -251,19 +444,21 @@ pub async fn install_minecraft_version(
     let game_directory = state
         .profile_manager
         .calculate_instance_path_for_profile(profile)?;
-    std::fs::create_dir_all(&game_directory)?;
-
-    // --- NEW: Copy initial data from default Minecraft installation ---
-    info!("\nChecking for user data to import...");
-    if let Err(e) =
-        mc_utils::copy_initial_data_from_default_minecraft(profile, &game_directory).await
-    {
-        // We will only log a warning because this is not a critical step for launching the game.
-        // The installation can proceed even if this fails.
-        warn!("Failed to import user data (non-critical error): {}", e);
+    if !offline::is_check_only() {
+        std::fs::create_dir_all(&game_directory)?;
+
+        // --- NEW: Copy initial data from default Minecraft installation ---
+        info!("\nChecking for user data to import...");
+        if let Err(e) =
+            mc_utils::copy_initial_data_from_default_minecraft(profile, &game_directory).await
+        {
+            // We will only log a warning because this is not a critical step for launching the game.
+            // The installation can proceed even if this fails.
+            warn!("Failed to import user data (non-critical error): {}", e);
+        }
+        info!("User data import check complete.");
+        // --- END NEW ---
     }
-    info!("User data import check complete.");
-    // --- END NEW ---
 
     // Emit libraries download event
     let libraries_event_id = emit_progress_event(
@@ -278,11 +473,14 @@ pub async fn install_minecraft_version(
 
     // Download all required files
     info!("\nDownloading libraries...");
+    offline::set_step("libraries");
     let libraries_service = MinecraftLibrariesDownloadService::new()
         .with_concurrent_downloads(launcher_config.concurrent_downloads);
-    libraries_service
-        .download_libraries(&piston_meta.libraries)
-        .await?;
+    continue_offline(
+        libraries_service
+            .download_libraries(&piston_meta.libraries)
+            .await,
+    )?;
     info!("Library download completed!");
 
     emit_progress_event(
@@ -306,12 +504,18 @@ pub async fn install_minecraft_version(
     )
     .await?;
 
-    info!("\nExtracting natives...");
-    let natives_service = MinecraftNativesDownloadService::new();
-    natives_service
-        .extract_natives(&piston_meta.libraries, version_id)
-        .await?;
-    info!("Native extraction completed!");
+    // The native jars were checked with the libraries, extracting them needs no network
+    if !offline::is_check_only() {
+        info!("\nExtracting natives...");
+        offline::set_step("natives");
+        let natives_service = MinecraftNativesDownloadService::new();
+        continue_offline(
+            natives_service
+                .extract_natives(&piston_meta.libraries, version_id)
+                .await,
+        )?;
+        info!("Native extraction completed!");
+    }
 
     emit_progress_event(
         &state,
@@ -324,11 +528,14 @@ pub async fn install_minecraft_version(
     .await?;
 
     info!("\nDownloading assets...");
+    offline::set_step("assets");
     let assets_service = MinecraftAssetsDownloadService::new()
         .with_concurrent_downloads(launcher_config.concurrent_downloads);
-    assets_service
-        .download_assets_with_progress(&piston_meta.asset_index, profile.id)
-        .await?;
+    continue_offline(
+        assets_service
+            .download_assets_with_progress(&piston_meta.asset_index, profile.id)
+            .await,
+    )?;
     info!("Asset download completed!");
 
     // Download NoRiskClient assets if profile has a selected pack
@@ -338,9 +545,12 @@ pub async fn install_minecraft_version(
         .with_concurrent_downloads(launcher_config.concurrent_downloads);
 
     // Download assets for this profile - progress events are now handled internally
-    norisk_assets_service
-        .download_nrc_assets_for_profile(&profile, credentials.as_ref(), is_experimental_mode)
-        .await?;
+    offline::set_step("norisk_assets");
+    continue_offline(
+        norisk_assets_service
+            .download_nrc_assets_for_profile(&profile, credentials.as_ref(), is_experimental_mode)
+            .await,
+    )?;
 
     info!("NoRiskClient Asset download completed!");
 
@@ -356,10 +566,13 @@ pub async fn install_minecraft_version(
     .await?;
 
     info!("\nDownloading Minecraft client...");
+    offline::set_step("client");
     let client_service = MinecraftClientDownloadService::new();
-    client_service
-        .download_client(&piston_meta.downloads.client, &piston_meta.id)
-        .await?;
+    continue_offline(
+        client_service
+            .download_client(&piston_meta.downloads.client, &piston_meta.id)
+            .await,
+    )?;
     info!("Client download completed!");
 
     emit_progress_event(
@@ -373,7 +586,7 @@ pub async fn install_minecraft_version(
     .await?;
 
     // Create and use Minecraft launcher
-    let launcher = MinecraftLauncher::new(
+    let mut launcher = MinecraftLauncher::new(
         java_path.clone(),
         game_directory.clone(),
         credentials.clone(),
@@ -390,10 +603,20 @@ pub async fn install_minecraft_version(
         profile.settings.memory.max
     };
 
+    // JVM preset: always the global one for standard profiles (like memory and vendor),
+    // the profile-specific override only applies to custom profiles
+    let global_jvm_preset = state.config_manager.get_config().await.jvm_preset;
+    let jvm_preset = if profile.is_standard_version {
+        global_jvm_preset
+    } else {
+        profile.settings.jvm_preset.unwrap_or(global_jvm_preset)
+    };
+
     let mut launch_params = MinecraftLaunchParameters::new(profile.id, memory_max)
         .with_old_minecraft_arguments(piston_meta.minecraft_arguments.clone())
         .with_resolution(profile.settings.resolution.clone())
-        .with_experimental_mode(is_experimental_mode);
+        .with_experimental_mode(is_experimental_mode)
+        .with_jvm_preset(jvm_preset, java_major_version, &java_runtime_vendor);
 
     // Add Quick Play parameters if provided
     if let Some(world_name) = quick_play_singleplayer {
@@ -463,12 +686,24 @@ pub async fn install_minecraft_version(
                 }
             }
 
+        offline::set_step("modloader");
         let modloader_installer = ModloaderFactory::create_installer_with_config(
             &modloader_enum,
             java_path.clone(),
             launcher_config.concurrent_downloads,
         );
-        let modloader_result = modloader_installer.install(version_id, &install_profile).await?;
+        let modloader_result = continue_offline(
+            modloader_installer.install(version_id, &install_profile).await,
+        )?
+        .unwrap_or_else(|| ModloaderInstallResult {
+            libraries: Vec::new(),
+            main_class: None,
+            jvm_args: None,
+            game_args: None,
+            minecraft_arguments: None,
+            custom_client_path: None,
+            force_include_minecraft_jar: false,
+        });
 
         // Apply modloader specific parameters to launch parameters
         if let Some(main_class) = modloader_result.main_class {
@@ -533,7 +768,12 @@ pub async fn install_minecraft_version(
             "Fetching Norisk config because pack '{}' is selected. Attempting to refresh first.",
             pack_id
         );
-        if let Some(creds) = credentials.as_ref() {
+        if offline::is_active() {
+            info!(
+                "[Offline] Using cached Norisk pack configuration for pack '{}'",
+                pack_id
+            );
+        } else if let Some(creds) = credentials.as_ref() {
             match creds
                 .norisk_credentials
                 .get_token_for_mode(is_experimental_mode)
@@ -591,11 +831,14 @@ pub async fn install_minecraft_version(
         "Ensuring profile-defined mods for profile '{}' are downloaded to cache...",
         profile.name
     );
+    offline::set_step("mods");
     let mod_downloader_service =
         ModDownloadService::with_concurrency(launcher_config.concurrent_downloads);
-    mod_downloader_service
-        .download_mods_to_cache(&profile)
-        .await?;
+    continue_offline(
+        mod_downloader_service
+            .download_mods_to_cache(&profile)
+            .await,
+    )?;
     info!(
         "Profile mod cache check/download completed successfully for profile '{}'",
         profile.name
@@ -709,7 +952,12 @@ pub async fn install_minecraft_version(
 
     // ---> NEW: Get custom mods for this profile <---
     info!("Listing custom mods for profile '{}'...", profile.name);
-    let custom_mod_infos = state.profile_manager.list_custom_mods(&profile).await?;
+    // Custom mods only exist locally, listing them would create their folder
+    let custom_mod_infos = if offline::is_check_only() {
+        Vec::new()
+    } else {
+        state.profile_manager.list_custom_mods(&profile).await?
+    };
     info!(
         "Found {} custom mods for profile '{}'",
         custom_mod_infos.len(),
@@ -738,8 +986,79 @@ pub async fn install_minecraft_version(
     )
     .await?;
 
+    // --- Step: Check the Java requirements the mods declare ---
+    let mut java_requirement = JavaRequirement::for_minecraft(version_id, java_version);
+    let mod_jars: Vec<(String, std::path::PathBuf)> = target_mods
+        .iter()
+        .map(|target| (target.filename.clone(), target.cache_path.clone()))
+        .collect();
+    for constraint in java_requirements::read_mod_java_constraints(&mod_jars, modloader_enum).await
+    {
+        java_requirement.add(constraint.source, constraint.range);
+    }
+
+    if !java_requirement.is_satisfied_by(java_major_version) {
+        let unmet = java_requirement.unmet_by(java_major_version);
+        if custom_java_valid {
+            return Err(AppError::JavaRequirementNotMet(format!(
+                "The custom Java {} of this profile can't run {}. Choose a matching Java or disable the custom Java path.",
+                java_major_version,
+                JavaRequirement::describe(&unmet)
+            )));
+        }
+
+        let required = java_requirement.lowest_satisfying().ok_or_else(|| {
+            let constraints: Vec<_> = java_requirement.constraints.iter().collect();
+            AppError::JavaRequirementNotMet(format!(
+                "No Java version satisfies {}",
+                JavaRequirement::describe(&constraints)
+            ))
+        })?;
+        info!(
+            "Java {} doesn't satisfy {}, switching to Java {}",
+            java_major_version,
+            JavaRequirement::describe(&unmet),
+            required
+        );
+
+        emit_progress_event(
+            &state,
+            EventType::InstallingJava,
+            profile.id,
+            &format!("Installing Java {} required by mods...", required),
+            0.0,
+            None,
+        )
+        .await?;
+        offline::set_step("java");
+        if let Some(required_java_path) = continue_offline(
+            JavaDownloadService::new()
+                .get_or_download_java(required, &java_vendor, None)
+                .await,
+        )? {
+            java_path = required_java_path;
+            java_major_version = required;
+            launcher = MinecraftLauncher::new(
+                java_path.clone(),
+                game_directory.clone(),
+                credentials.clone(),
+            );
+            launch_params =
+                launch_params.with_jvm_preset(jvm_preset, java_major_version, &java_runtime_vendor);
+            emit_progress_event(
+                &state,
+                EventType::InstallingJava,
+                profile.id,
+                &format!("Java {} installation completed!", required),
+                1.0,
+                None,
+            )
+            .await?;
+        }
+    }
+
     // --- Prototype: Provide managed mods via Fabric addMods meta file (Fabric only) ---
-    if modloader_enum == ModLoader::Fabric {
+    if modloader_enum == ModLoader::Fabric && !offline::is_check_only() {
         let add_mods_arg = crate::minecraft::downloads::mod_resolver::build_fabric_add_mods_arg(
             profile.id,
             version_id,
@@ -772,17 +1091,23 @@ pub async fn install_minecraft_version(
     let profile_mods_path = state.profile_manager.get_profile_mods_path(profile)?;
 
     // Ensure mods folder exists for all loaders before launch/sync
-    async_fs::create_dir_all(&profile_mods_path).await?;
+    if !offline::is_check_only() {
+        async_fs::create_dir_all(&profile_mods_path).await?;
+    }
 
     // Pass the resolved target_mods list and the specific mods path to the sync function
-    if modloader_enum == ModLoader::Fabric {
+    if offline::is_check_only() {
+        info!("Skipping mods folder sync, the mods were checked in the cache.");
+    } else if modloader_enum == ModLoader::Fabric {
         info!(
             "Skipping mods folder sync for Fabric (using addMods meta file instead)."
         );
     } else {
-        mod_downloader_service
-            .sync_mods_to_profile(&target_mods, &profile_mods_path)
-            .await?;
+        continue_offline(
+            mod_downloader_service
+                .sync_mods_to_profile(&target_mods, &profile_mods_path)
+                .await,
+        )?;
     }
 
     info!("Mod sync completed for profile '{}'", profile.name);
@@ -800,12 +1125,16 @@ pub async fn install_minecraft_version(
     let mut log4j_arg = None;
     if let Some(logging) = &piston_meta.logging {
         info!("\nDownloading log4j configuration...");
+        offline::set_step("logging");
         let logging_service = MinecraftLoggingDownloadService::new();
-        let config_path = logging_service
-            .download_logging_config(&logging.client)
-            .await?;
-        log4j_arg = Some(logging_service.get_jvm_argument(&config_path));
-        info!("Log4j configuration download completed!");
+        if let Some(config_path) = continue_offline(
+            logging_service
+                .download_logging_config(&logging.client)
+                .await,
+        )? {
+            log4j_arg = Some(logging_service.get_jvm_argument(&config_path));
+            info!("Log4j configuration download completed!");
+        }
     }
 
     // Add log4j configuration to JVM arguments if available
@@ -816,10 +1145,38 @@ pub async fn install_minecraft_version(
         launch_params = launch_params.with_additional_jvm_args(jvm_args);
     }
 
+    // Never launch with an incomplete installation, the caller reports what is missing
+    if offline::has_missing() {
+        return Err(AppError::OfflineNotReady(format!(
+            "Profile '{}' is not fully available offline",
+            profile.name
+        )));
+    }
+
+    if mode == InstallMode::InstallOnly {
+        info!(
+            "Installation of profile '{}' complete, not launching (install only).",
+            profile.name
+        );
+        return Ok(None);
+    }
+
+    if mode == InstallMode::ResolveCommand {
+        let command = launcher
+            .build_command(&piston_meta, &launch_params, Some(profile))
+            .await?;
+        info!(
+            "Resolved launch command of profile '{}' (installed, not launching).",
+            profile.name
+        );
+        return Ok(Some(LaunchCommand::from_command(&command)));
+    }
+
     // --- Execute pre-launch hooks ---
     let launcher_config = state.config_manager.get_config().await;
-    if let Some(hook) = &launcher_config.hooks.pre_launch {
-        info!("Executing pre-launch hook: {}", hook);
+    let pre_launch_hooks =
+        resolve_hooks(&launcher_config.hooks, Some(&profile.settings.hooks)).pre_launch;
+    if !pre_launch_hooks.is_empty() {
         let hook_event_id = emit_progress_event(
             &state,
             EventType::LaunchingMinecraft,
@@ -830,23 +1187,25 @@ pub async fn install_minecraft_version(
         )
         .await?;
 
-        let mut cmd = hook.split(' ');
-        if let Some(command) = cmd.next() {
-            let result = std::process::Command::new(command)
-                .args(cmd.collect::<Vec<&str>>())
-                .current_dir(&game_directory)
-                .spawn()
-                .map_err(|e| AppError::Io(e))?
-                .wait()
-                .map_err(|e| AppError::Io(e))?;
-
-            if !result.success() {
-                let error_msg = format!(
-                    "Pre-launch hook failed with exit code: {}",
-                    result.code().unwrap_or(-1)
-                );
-                error!("{}", error_msg);
-                return Err(AppError::Other(error_msg));
+        let hook_context =
+            HookContext::for_profile(profile, game_directory.clone(), java_path.clone());
+        for hook in &pre_launch_hooks {
+            info!("Executing pre-launch hook: {}", hook);
+            if let Some(mut command) = hook_context.command(hook) {
+                let result = command
+                    .spawn()
+                    .map_err(|e| AppError::Io(e))?
+                    .wait()
+                    .map_err(|e| AppError::Io(e))?;
+
+                if !result.success() {
+                    let error_msg = format!(
+                        "Pre-launch hook failed with exit code: {}",
+                        result.code().unwrap_or(-1)
+                    );
+                    error!("{}", error_msg);
+                    return Err(AppError::Other(error_msg));
+                }
             }
         }
         info!("Pre-launch hook executed successfully");
@@ -878,5 +1237,5 @@ pub async fn install_minecraft_version(
     )
     .await?;
 
-    Ok(())
+    Ok(None)
 }
@@ -0,0 +1,373 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::Result;
+use crate::minecraft::downloads::mod_resolver;
+use crate::state::profile_state::{ModLoader, Profile};
+use crate::state::state_manager::State;
+use crate::utils::jar_inspector::{self, ModMetadata, ModMetadataFormat};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Inclusive range of Java major versions, `None` leaves that side open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JavaVersionRange {
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+impl JavaVersionRange {
+    pub fn new(min: Option<u32>, max: Option<u32>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn at_least(major: u32) -> Self {
+        Self::new(Some(major), None)
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn contains(&self, major: u32) -> bool {
+        self.min.map_or(true, |min| major >= min) && self.max.map_or(true, |max| major <= max)
+    }
+
+    /// Both ranges have to hold
+    fn intersect(self, other: Self) -> Self {
+        Self::new(
+            self.min.max(other.min),
+            match (self.max, other.max) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+        )
+    }
+
+    /// Either range may hold
+    fn union(self, other: Self) -> Self {
+        Self::new(
+            self.min.zip(other.min).map(|(a, b)| a.min(b)),
+            self.max.zip(other.max).map(|(a, b)| a.max(b)),
+        )
+    }
+}
+
+impl fmt::Display for JavaVersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min == max => write!(f, "Java {}", min),
+            (Some(min), Some(max)) => write!(f, "Java {} to {}", min, max),
+            (Some(min), None) => write!(f, "Java {} or newer", min),
+            (None, Some(max)) => write!(f, "Java {} or older", max),
+            (None, None) => write!(f, "any Java"),
+        }
+    }
+}
+
+/// A Java range and what declared it (the game version or a mod file)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaConstraint {
+    pub source: String,
+    pub range: JavaVersionRange,
+}
+
+/// Combined Java requirements of the game and its mods
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JavaRequirement {
+    pub constraints: Vec<JavaConstraint>,
+}
+
+impl JavaRequirement {
+    /// Requirement of the Minecraft version itself, from `java_version.major_version`
+    pub fn for_minecraft(version_id: &str, major_version: u32) -> Self {
+        let mut requirement = Self::default();
+        requirement.add(
+            format!("Minecraft {}", version_id),
+            JavaVersionRange::at_least(major_version),
+        );
+        requirement
+    }
+
+    pub fn add(&mut self, source: impl Into<String>, range: JavaVersionRange) {
+        if !range.is_unbounded() {
+            self.constraints.push(JavaConstraint {
+                source: source.into(),
+                range,
+            });
+        }
+    }
+
+    pub fn range(&self) -> JavaVersionRange {
+        self.constraints
+            .iter()
+            .fold(JavaVersionRange::default(), |range, constraint| {
+                range.intersect(constraint.range)
+            })
+    }
+
+    pub fn is_satisfied_by(&self, major: u32) -> bool {
+        self.range().contains(major)
+    }
+
+    /// Lowest Java major version that satisfies every constraint, `None` if they conflict
+    pub fn lowest_satisfying(&self) -> Option<u32> {
+        let range = self.range();
+        let lowest = range.min.unwrap_or(8);
+        range.contains(lowest).then_some(lowest)
+    }
+
+    /// Constraints the given Java major version violates
+    pub fn unmet_by(&self, major: u32) -> Vec<&JavaConstraint> {
+        self.constraints
+            .iter()
+            .filter(|constraint| !constraint.range.contains(major))
+            .collect()
+    }
+
+    /// Human readable list of the constraints, e.g. `sodium.jar (Java 21 or newer)`
+    pub fn describe(constraints: &[&JavaConstraint]) -> String {
+        constraints
+            .iter()
+            .map(|constraint| format!("{} ({})", constraint.source, constraint.range))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Parses a Java version into its major version, `1.8` and `1.8.0_392` are Java 8.
+/// The flag tells whether the version was only a major version.
+fn parse_java_bound(version: &str) -> Option<(u32, bool)> {
+    let version = version.trim().trim_end_matches(".x").trim_end_matches(".*");
+    let parts: Vec<&str> = version.split(['.', '_', '-', '+']).collect();
+    let first = parts.first()?.parse::<u32>().ok()?;
+    if first == 1 && parts.len() > 1 {
+        Some((parts[1].parse().ok()?, parts.len() == 2))
+    } else {
+        Some((first, parts.len() == 1))
+    }
+}
+
+/// Parses a single Fabric version predicate like `>=17`, `<22`, `17.x` or `*`
+fn parse_fabric_predicate(predicate: &str) -> Option<JavaVersionRange> {
+    let predicate = predicate.trim();
+    if predicate.is_empty() || predicate == "*" {
+        return Some(JavaVersionRange::default());
+    }
+
+    let (operator, version) = [">=", "<=", ">", "<", "=", "^", "~"]
+        .iter()
+        .find_map(|op| predicate.strip_prefix(op).map(|v| (*op, v)))
+        .unwrap_or(("=", predicate));
+    let (major, only_major) = parse_java_bound(version)?;
+
+    Some(match operator {
+        ">=" => JavaVersionRange::at_least(major),
+        ">" if only_major => JavaVersionRange::at_least(major + 1),
+        ">" => JavaVersionRange::at_least(major),
+        "<=" => JavaVersionRange::new(None, Some(major)),
+        "<" if only_major => JavaVersionRange::new(None, Some(major.saturating_sub(1))),
+        "<" => JavaVersionRange::new(None, Some(major)),
+        _ => JavaVersionRange::new(Some(major), Some(major)),
+    })
+}
+
+/// Parses a Fabric version requirement, space separated predicates all have to match
+/// and ` || ` separated alternatives may each match
+fn parse_fabric_version_requirement(requirement: &str) -> Option<JavaVersionRange> {
+    requirement
+        .split("||")
+        .map(|alternative| {
+            alternative
+                .split_whitespace()
+                .try_fold(JavaVersionRange::default(), |range, predicate| {
+                    Some(range.intersect(parse_fabric_predicate(predicate)?))
+                })
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .reduce(JavaVersionRange::union)
+}
+
+/// Parses a single Maven range like `[17,)`, `(8,21]` or `[21]`
+fn parse_maven_range(range: &str) -> Option<JavaVersionRange> {
+    let range = range.trim();
+    let lower_inclusive = match range.chars().next()? {
+        '[' => true,
+        '(' => false,
+        // A bare version is a soft requirement, Forge treats it as a minimum
+        _ => return Some(JavaVersionRange::at_least(parse_java_bound(range)?.0)),
+    };
+    let upper_inclusive = match range.chars().last()? {
+        ']' => true,
+        ')' => false,
+        _ => return None,
+    };
+    let inner = &range[1..range.len() - 1];
+
+    let Some((lower, upper)) = inner.split_once(',') else {
+        let (major, _) = parse_java_bound(inner)?;
+        return Some(JavaVersionRange::new(Some(major), Some(major)));
+    };
+    let min = match lower.trim() {
+        "" => None,
+        lower => {
+            let (major, only_major) = parse_java_bound(lower)?;
+            Some(if !lower_inclusive && only_major {
+                major + 1
+            } else {
+                major
+            })
+        }
+    };
+    let max = match upper.trim() {
+        "" => None,
+        upper => {
+            let (major, only_major) = parse_java_bound(upper)?;
+            Some(if !upper_inclusive && only_major {
+                major.saturating_sub(1)
+            } else {
+                major
+            })
+        }
+    };
+    Some(JavaVersionRange::new(min, max))
+}
+
+/// Parses a Maven version range, several comma separated ranges may each match
+fn parse_maven_version_range(spec: &str) -> Option<JavaVersionRange> {
+    let spec = spec.trim();
+    if spec.is_empty() || spec == "*" {
+        return Some(JavaVersionRange::default());
+    }
+    if !spec.starts_with(['[', '(']) {
+        return parse_maven_range(spec);
+    }
+
+    let mut range: Option<JavaVersionRange> = None;
+    let mut rest = spec;
+    while !rest.is_empty() {
+        let end = rest.find([']', ')'])? + 1;
+        let parsed = parse_maven_range(&rest[..end])?;
+        range = Some(range.map_or(parsed, |range| range.union(parsed)));
+        rest = rest[end..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    }
+    range
+}
+
+/// Java range of a mod's required `java` dependency
+pub fn java_requirement_of(metadata: &ModMetadata) -> Option<JavaVersionRange> {
+    let dependency = metadata.required_dependency("java")?;
+    let range = match metadata.format {
+        ModMetadataFormat::Fabric | ModMetadataFormat::Quilt => {
+            parse_fabric_version_requirement(dependency.version_range.as_deref().unwrap_or("*"))?
+        }
+        ModMetadataFormat::Forge | ModMetadataFormat::NeoForge => {
+            parse_maven_version_range(dependency.version_range.as_deref()?)?
+        }
+        ModMetadataFormat::LegacyForge => return None,
+    };
+    Some(range).filter(|range| !range.is_unbounded())
+}
+
+/// Reads the `depends.java` requirement of a `fabric.mod.json`
+pub fn parse_fabric_java_requirement(fabric_mod_json: &str) -> Result<Option<JavaVersionRange>> {
+    let metadata = jar_inspector::parse_fabric_mod_json(fabric_mod_json)?;
+    Ok(java_requirement_of(&metadata))
+}
+
+/// Reads the Java requirement of a Forge `mods.toml` or `neoforge.mods.toml`,
+/// declared as a required dependency on the `java` mod id
+pub fn parse_mods_toml_java_requirement(mods_toml: &str) -> Result<Option<JavaVersionRange>> {
+    let metadata = jar_inspector::parse_mods_toml(mods_toml, ModMetadataFormat::Forge)?;
+    Ok(java_requirement_of(&metadata))
+}
+
+/// Reads the Java requirement a mod jar declares for the given loader, without the cache
+pub async fn read_jar_java_requirement(
+    jar_path: &Path,
+    loader: ModLoader,
+) -> Result<Option<JavaVersionRange>> {
+    let mods = jar_inspector::inspect_jar(jar_path).await?;
+    Ok(java_requirement_for_loader(&mods, loader))
+}
+
+fn java_requirement_for_loader(
+    mods: &[ModMetadata],
+    loader: ModLoader,
+) -> Option<JavaVersionRange> {
+    mods.iter()
+        .find(|metadata| metadata.format.is_read_by(loader))
+        .and_then(java_requirement_of)
+}
+
+/// Reads the Java requirements of the given mod jars, keyed by their file name.
+/// Jars that can't be read are skipped, the game reports broken mods itself.
+pub async fn read_mod_java_constraints(
+    mods: &[(String, PathBuf)],
+    loader: ModLoader,
+) -> Vec<JavaConstraint> {
+    let jars = mods.iter().map(|(_, path)| (path.clone(), None)).collect();
+    let metadata = jar_inspector::get_jars_metadata(jars).await;
+
+    let mut constraints: Vec<JavaConstraint> = mods
+        .iter()
+        .filter_map(|(filename, path)| {
+            let range = java_requirement_for_loader(&metadata.get(path)?.mods, loader)?;
+            Some(JavaConstraint {
+                source: filename.clone(),
+                range,
+            })
+        })
+        .collect();
+    constraints.sort_by(|a, b| a.source.cmp(&b.source));
+    constraints
+}
+
+/// Java requirements of the profile's mods, only mods that are already downloaded are read
+pub async fn read_profile_mod_java_requirement(profile: &Profile) -> Result<JavaRequirement> {
+    let state = State::get().await?;
+    let norisk_config = match profile.selected_norisk_pack_id {
+        Some(_) => Some(state.norisk_pack_manager.get_config().await),
+        None => None,
+    };
+    let custom_mod_infos = state.profile_manager.list_custom_mods(profile).await?;
+    let target_mods = mod_resolver::resolve_target_mods(
+        profile,
+        norisk_config.as_ref(),
+        Some(&custom_mod_infos),
+        &profile.game_version,
+        profile.loader.as_str(),
+        &LAUNCHER_DIRECTORY.meta_dir().join("mod_cache"),
+    )
+    .await?;
+
+    let mod_jars: Vec<(String, PathBuf)> = target_mods
+        .into_iter()
+        .filter(|target| target.cache_path.exists())
+        .map(|target| (target.filename, target.cache_path))
+        .collect();
+    let mut requirement = JavaRequirement::default();
+    for constraint in read_mod_java_constraints(&mod_jars, profile.loader).await {
+        requirement.add(constraint.source, constraint.range);
+    }
+    Ok(requirement)
+}
+
+/// Java version a launch of the profile switches to because of its mods. `None` when the version
+/// Minecraft requires already satisfies them, or when they conflict and the launch would fail.
+pub async fn mod_required_java_version(
+    profile: &Profile,
+    minecraft_major_version: u32,
+) -> Result<Option<u32>> {
+    let mut requirement =
+        JavaRequirement::for_minecraft(&profile.game_version, minecraft_major_version);
+    for constraint in read_profile_mod_java_requirement(profile)
+        .await?
+        .constraints
+    {
+        requirement.add(constraint.source, constraint.range);
+    }
+    if requirement.is_satisfied_by(minecraft_major_version) {
+        return Ok(None);
+    }
+    Ok(requirement.lowest_satisfying())
+}
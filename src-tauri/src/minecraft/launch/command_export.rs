@@ -0,0 +1,170 @@
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Placeholder for the Minecraft access token (`--accessToken <token>`)
+pub const ACCESS_TOKEN_PLACEHOLDER: &str = "ACCESS_TOKEN";
+/// Placeholder for the NoRisk token (`-Dnorisk.token=<token>`)
+pub const NORISK_TOKEN_PLACEHOLDER: &str = "NORISK_TOKEN";
+
+const NORISK_TOKEN_PREFIX: &str = "-Dnorisk.token=";
+
+/// Output format of an exported launch command
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchCommandFormat {
+    /// POSIX shell script
+    #[default]
+    Shell,
+    /// JSON object with the argv
+    Json,
+}
+
+impl LaunchCommandFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LaunchCommandFormat::Shell => "sh",
+            LaunchCommandFormat::Json => "json",
+        }
+    }
+}
+
+/// A fully resolved game command, as it would be spawned by `MinecraftLauncher::launch`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LaunchCommand {
+    /// java, or the wrapper command if one is configured
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_directory: Option<PathBuf>,
+    /// Environment variables set on top of the launcher's environment
+    pub env: Vec<(String, String)>,
+    /// Names of the placeholders that replaced secrets, e.g. `ACCESS_TOKEN`
+    pub redacted: Vec<String>,
+}
+
+impl LaunchCommand {
+    pub fn from_command(command: &Command) -> Self {
+        Self {
+            program: command.get_program().to_string_lossy().into_owned(),
+            args: command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            working_directory: command.get_current_dir().map(PathBuf::from),
+            env: command
+                .get_envs()
+                .filter_map(|(key, value)| {
+                    Some((
+                        key.to_string_lossy().into_owned(),
+                        value?.to_string_lossy().into_owned(),
+                    ))
+                })
+                .collect(),
+            redacted: Vec::new(),
+        }
+    }
+
+    /// Replaces the access token and the NoRisk token with `${PLACEHOLDER}` references
+    pub fn redacted(mut self) -> Self {
+        let mut redacted: Vec<String> = Vec::new();
+        let mut args = self.args.iter_mut();
+        while let Some(arg) = args.next() {
+            if arg.starts_with(NORISK_TOKEN_PREFIX) {
+                *arg = format!("{}${{{}}}", NORISK_TOKEN_PREFIX, NORISK_TOKEN_PLACEHOLDER);
+                if !redacted.iter().any(|n| n == NORISK_TOKEN_PLACEHOLDER) {
+                    redacted.push(NORISK_TOKEN_PLACEHOLDER.to_string());
+                }
+            } else if arg == "--accessToken" {
+                if let Some(token) = args.next() {
+                    *token = format!("${{{}}}", ACCESS_TOKEN_PLACEHOLDER);
+                    if !redacted.iter().any(|n| n == ACCESS_TOKEN_PLACEHOLDER) {
+                        redacted.push(ACCESS_TOKEN_PLACEHOLDER.to_string());
+                    }
+                }
+            }
+        }
+        self.redacted = redacted;
+        self
+    }
+
+    /// Renders the command as a POSIX shell script. Redacted values stay `${NAME}` references,
+    /// so the script fails early unless they're exported before running it.
+    pub fn to_shell_script(&self) -> String {
+        let mut script = String::from("#!/bin/sh\n# Generated by the NoRisk Launcher\nset -e\n\n");
+
+        for name in &self.redacted {
+            script.push_str(&format!(
+                ": \"${{{}:?{} has to be set, it was redacted from this script}}\"\n",
+                name, name
+            ));
+        }
+        if !self.redacted.is_empty() {
+            script.push('\n');
+        }
+
+        for (key, value) in &self.env {
+            script.push_str(&format!(
+                "export {}={}\n",
+                key,
+                shell_quote(value, &self.redacted)
+            ));
+        }
+        if let Some(dir) = &self.working_directory {
+            script.push_str(&format!(
+                "cd {}\n",
+                shell_quote(&dir.to_string_lossy(), &[])
+            ));
+        }
+
+        script.push_str("\nexec ");
+        script.push_str(&shell_quote(&self.program, &[]));
+        for arg in &self.args {
+            script.push_str(" \\\n  ");
+            script.push_str(&shell_quote(arg, &self.redacted));
+        }
+        script.push('\n');
+        script
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| AppError::Other(format!("Failed to serialize launch command: {}", e)))
+    }
+
+    pub fn render(&self, format: LaunchCommandFormat) -> Result<String> {
+        match format {
+            LaunchCommandFormat::Shell => Ok(self.to_shell_script()),
+            LaunchCommandFormat::Json => self.to_json(),
+        }
+    }
+}
+
+/// Single-quotes `value` for a POSIX shell. `${NAME}` references of the given placeholders
+/// are left outside the quotes so the shell expands them.
+fn shell_quote(value: &str, placeholders: &[String]) -> String {
+    for name in placeholders {
+        let reference = format!("${{{}}}", name);
+        if let Some((before, after)) = value.split_once(&reference) {
+            let mut quoted = String::new();
+            if !before.is_empty() {
+                quoted.push_str(&shell_quote(before, &[]));
+            }
+            quoted.push_str(&format!("\"{}\"", reference));
+            if !after.is_empty() {
+                quoted.push_str(&shell_quote(after, placeholders));
+            }
+            return quoted;
+        }
+    }
+
+    let is_safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if is_safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
@@ -0,0 +1,226 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Named set of garbage collector flags added to every launch
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JvmPreset {
+    /// G1 with the tuning of the vanilla launcher
+    #[default]
+    VanillaG1,
+    /// G1 with Aikar's flags, tuned for large heaps and short pauses
+    AikarG1,
+    /// Generational ZGC, needs Java 21 or newer
+    GenerationalZgc,
+    /// Shenandoah, left out of Oracle, GraalVM and OpenJ9 builds
+    Shenandoah,
+    /// No GC flags, only what the JVM picks by itself
+    None,
+}
+
+/// A JVM flag and the Java versions that accept it
+#[derive(Debug, Clone, Copy)]
+struct JvmFlag {
+    flag: &'static str,
+    min_java: u32,
+    /// Last version that accepts the flag, newer ones removed it
+    max_java: Option<u32>,
+}
+
+const fn flag(flag: &'static str) -> JvmFlag {
+    JvmFlag {
+        flag,
+        min_java: 8,
+        max_java: None,
+    }
+}
+
+const fn flag_between(flag: &'static str, min_java: u32, max_java: u32) -> JvmFlag {
+    JvmFlag {
+        flag,
+        min_java,
+        max_java: Some(max_java),
+    }
+}
+
+const VANILLA_G1: &[JvmFlag] = &[
+    flag("-XX:+UnlockExperimentalVMOptions"),
+    flag("-XX:+UseG1GC"),
+    flag("-XX:G1NewSizePercent=20"),
+    flag("-XX:G1ReservePercent=20"),
+    flag("-XX:MaxGCPauseMillis=50"),
+    flag("-XX:G1HeapRegionSize=32M"),
+];
+
+const AIKAR_G1: &[JvmFlag] = &[
+    flag("-XX:+UseG1GC"),
+    flag("-XX:+ParallelRefProcEnabled"),
+    flag("-XX:MaxGCPauseMillis=200"),
+    flag("-XX:+UnlockExperimentalVMOptions"),
+    flag("-XX:+DisableExplicitGC"),
+    flag("-XX:+AlwaysPreTouch"),
+    flag("-XX:G1HeapWastePercent=5"),
+    flag("-XX:G1MixedGCCountTarget=4"),
+    flag("-XX:G1MixedGCLiveThresholdPercent=90"),
+    flag_between("-XX:G1RSetUpdatingPauseTimePercent=5", 8, 19),
+    flag("-XX:SurvivorRatio=32"),
+    flag("-XX:+PerfDisableSharedMem"),
+    flag("-XX:MaxTenuringThreshold=1"),
+];
+
+/// Aikar's sizing for heaps up to 12 GB
+const AIKAR_G1_SMALL_HEAP: &[JvmFlag] = &[
+    flag("-XX:G1NewSizePercent=30"),
+    flag("-XX:G1MaxNewSizePercent=40"),
+    flag("-XX:G1HeapRegionSize=8M"),
+    flag("-XX:G1ReservePercent=20"),
+    flag("-XX:InitiatingHeapOccupancyPercent=15"),
+];
+
+/// Aikar's sizing for heaps above 12 GB
+const AIKAR_G1_LARGE_HEAP: &[JvmFlag] = &[
+    flag("-XX:G1NewSizePercent=40"),
+    flag("-XX:G1MaxNewSizePercent=50"),
+    flag("-XX:G1HeapRegionSize=16M"),
+    flag("-XX:G1ReservePercent=15"),
+    flag("-XX:InitiatingHeapOccupancyPercent=20"),
+];
+
+const AIKAR_LARGE_HEAP_MB: u32 = 12 * 1024;
+
+/// Parts of a runtime's vendor or VM name that mark builds without Shenandoah
+const BUILDS_WITHOUT_SHENANDOAH: &[&str] = &["oracle", "graalvm", "j9"];
+
+const GENERATIONAL_ZGC: &[JvmFlag] = &[
+    flag("-XX:+UseZGC"),
+    // Generational mode is the default from 23 on, the flag is deprecated there
+    flag_between("-XX:+ZGenerational", 21, 22),
+    flag("-XX:+AlwaysPreTouch"),
+    flag("-XX:+DisableExplicitGC"),
+];
+
+const SHENANDOAH: &[JvmFlag] = &[
+    flag_between("-XX:+UnlockExperimentalVMOptions", 12, 14),
+    flag("-XX:+UseShenandoahGC"),
+    flag("-XX:+AlwaysPreTouch"),
+    flag("-XX:+DisableExplicitGC"),
+    flag("-XX:+ParallelRefProcEnabled"),
+];
+
+impl JvmPreset {
+    /// Oldest Java version the preset's collector is available in
+    pub fn min_java_version(&self) -> u32 {
+        match self {
+            JvmPreset::GenerationalZgc => 21,
+            JvmPreset::Shenandoah => 12,
+            JvmPreset::VanillaG1 | JvmPreset::AikarG1 | JvmPreset::None => 8,
+        }
+    }
+
+    fn flag_table(&self, memory_max_mb: u32) -> Vec<JvmFlag> {
+        match self {
+            JvmPreset::VanillaG1 => VANILLA_G1.to_vec(),
+            JvmPreset::AikarG1 => {
+                let sizing = if memory_max_mb > AIKAR_LARGE_HEAP_MB {
+                    AIKAR_G1_LARGE_HEAP
+                } else {
+                    AIKAR_G1_SMALL_HEAP
+                };
+                AIKAR_G1.iter().chain(sizing).copied().collect()
+            }
+            JvmPreset::GenerationalZgc => GENERATIONAL_ZGC.to_vec(),
+            JvmPreset::Shenandoah => SHENANDOAH.to_vec(),
+            JvmPreset::None => Vec::new(),
+        }
+    }
+
+    /// Whether the preset's collector is in the given Java build. `java_vendor` is the vendor or
+    /// VM name the runtime reports, an unknown vendor is assumed to ship every collector.
+    pub fn is_available(&self, java_major_version: u32, java_vendor: Option<&str>) -> bool {
+        if java_major_version < self.min_java_version() {
+            return false;
+        }
+        match (self, java_vendor) {
+            (JvmPreset::Shenandoah, Some(vendor)) => {
+                let vendor = vendor.to_lowercase();
+                !BUILDS_WITHOUT_SHENANDOAH
+                    .iter()
+                    .any(|name| vendor.contains(name))
+            }
+            _ => true,
+        }
+    }
+
+    /// The preset's flags that `java_major_version` accepts. If the collector itself isn't
+    /// available in that version or build the vanilla G1 flags are used instead.
+    pub fn flags(
+        &self,
+        java_major_version: u32,
+        java_vendor: Option<&str>,
+        memory_max_mb: u32,
+    ) -> Vec<String> {
+        let preset = if !self.is_available(java_major_version, java_vendor) {
+            warn!(
+                "JVM preset {:?} isn't available in Java {} ({}), it needs Java {}+ with the collector included. Falling back to {:?}.",
+                self,
+                java_major_version,
+                java_vendor.unwrap_or("unknown vendor"),
+                self.min_java_version(),
+                JvmPreset::VanillaG1
+            );
+            JvmPreset::VanillaG1
+        } else {
+            *self
+        };
+
+        preset
+            .flag_table(memory_max_mb)
+            .into_iter()
+            .filter(|f| {
+                java_major_version >= f.min_java
+                    && f.max_java.map_or(true, |max| java_major_version <= max)
+            })
+            .map(|f| f.flag.to_string())
+            .collect()
+    }
+}
+
+/// Option name of a `-XX` flag, e.g. `UseG1GC` for `-XX:+UseG1GC` and
+/// `MaxGCPauseMillis` for `-XX:MaxGCPauseMillis=50`
+fn xx_option_name(arg: &str) -> Option<&str> {
+    let option = arg.strip_prefix("-XX:")?;
+    let option = option.trim_start_matches(['+', '-']);
+    Some(option.split('=').next().unwrap_or(option))
+}
+
+fn selects_collector(arg: &str) -> bool {
+    matches!(xx_option_name(arg), Some(name) if name.starts_with("Use") && name.ends_with("GC"))
+        && arg.starts_with("-XX:+")
+}
+
+/// Merges the preset's flags with the user's JVM arguments, the preset first. The user's arguments
+/// always win: preset flags that set the same `-XX` option are dropped, and if the user selects a
+/// collector themselves the preset is skipped entirely so the JVM never sees two.
+pub fn merge_with_custom_args(preset_flags: Vec<String>, custom_args: &[String]) -> Vec<String> {
+    let preset_flags = if custom_args.iter().any(|arg| selects_collector(arg)) {
+        info!("Custom JVM arguments select a garbage collector, skipping the JVM preset");
+        Vec::new()
+    } else {
+        let overridden: HashSet<&str> = custom_args
+            .iter()
+            .filter_map(|arg| xx_option_name(arg))
+            // Unlocking twice is harmless and keeps the preset's experimental flags valid
+            .filter(|name| !name.starts_with("Unlock"))
+            .collect();
+        preset_flags
+            .into_iter()
+            .filter(|arg| xx_option_name(arg).map_or(true, |name| !overridden.contains(name)))
+            .collect()
+    };
+
+    preset_flags
+        .into_iter()
+        .chain(custom_args.iter().cloned())
+        .collect()
+}
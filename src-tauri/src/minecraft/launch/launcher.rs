@@ -5,8 +5,11 @@ use crate::minecraft::minecraft_auth::Credentials;
 use crate::minecraft::ClasspathBuilder;
 use crate::minecraft::GameArguments;
 use crate::minecraft::JvmArguments;
+use crate::minecraft::{merge_with_custom_args, JvmPreset};
+use crate::state::playtime_tracker::PlayTarget;
 use crate::state::profile_state::{Profile, WindowSize};
 use crate::state::state_manager::State;
+use crate::utils::hook_utils::{resolve_hooks, wrapper_args, HookContext};
 use log::{debug, error, info, warn};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -30,6 +33,11 @@ pub struct MinecraftLaunchParameters {
     pub resolution: Option<WindowSize>,
     pub quick_play_singleplayer: Option<String>,
     pub quick_play_multiplayer: Option<String>,
+    pub jvm_preset: JvmPreset,
+    /// Major version of the Java the game runs on, used to drop unsupported preset flags
+    pub java_major_version: u32,
+    /// Vendor or VM name of that Java, some builds leave collectors out. None if unknown.
+    pub java_vendor: Option<String>,
 }
 
 impl MinecraftLaunchParameters {
@@ -48,6 +56,9 @@ impl MinecraftLaunchParameters {
             resolution: None,
             quick_play_singleplayer: None,
             quick_play_multiplayer: None,
+            jvm_preset: JvmPreset::default(),
+            java_major_version: 8,
+            java_vendor: None,
         }
     }
 
@@ -110,6 +121,18 @@ impl MinecraftLaunchParameters {
         self.quick_play_multiplayer = Some(server_address);
         self
     }
+
+    pub fn with_jvm_preset(
+        mut self,
+        preset: JvmPreset,
+        java_major_version: u32,
+        java_vendor: &str,
+    ) -> Self {
+        self.jvm_preset = preset;
+        self.java_major_version = java_major_version;
+        self.java_vendor = Some(java_vendor.to_string());
+        self
+    }
 }
 
 pub struct MinecraftLauncher {
@@ -204,42 +227,63 @@ impl MinecraftLauncher {
         parts.join(" ")
     }
 
-    pub async fn launch(
+    /// Resolves the full java command (wrapper, JVM args, classpath, game args) without spawning it.
+    pub async fn build_command(
         &self,
         piston_meta: &PistonMeta,
-        params: MinecraftLaunchParameters,
-        profile: Option<Profile>,
-    ) -> Result<()> {
+        params: &MinecraftLaunchParameters,
+        profile: Option<&Profile>,
+    ) -> Result<Command> {
         let state = State::get().await?;
-        let process_manager = &state.process_manager;
-
-        // Remove fetching the profile just for RAM
-        // let profile = state.profile_manager.get_profile(params.profile_id).await?;
-        // let settings = &profile.settings;
 
         // 2. Java-Befehl initialisieren (mit wrapper support)
         let launcher_config = state.config_manager.get_config().await;
-        let mut command = match launcher_config.hooks.wrapper {
-            Some(wrapper) => {
-                info!("Using wrapper command: {}", wrapper);
-                // Exactly like Modrinth: use the whole wrapper string as command and add java path as arg
-                {
-                    let mut it = Command::new(wrapper);
-                    it.arg(&self.java_path);
-                    it
+        let hooks = resolve_hooks(&launcher_config.hooks, profile.map(|p| &p.settings.hooks));
+        let hook_context = profile.map(|p| {
+            HookContext::for_profile(p, self.game_directory.clone(), self.java_path.clone())
+        });
+        let substitute = |value: &str| match &hook_context {
+            Some(context) => context.substitute(value),
+            None => value.to_string(),
+        };
+
+        let wrapper = wrapper_args(&hooks.wrapper, hook_context.as_ref());
+        let mut command = match wrapper.split_first() {
+            Some((program, args)) => {
+                info!("Using wrapper command(s): {:?}", hooks.wrapper);
+                // The wrapper runs java with the java path as its last argument.
+                // Chained wrappers wrap each other, the global one outermost.
+                let mut it = Command::new(program);
+                it.args(args);
+                it.arg(&self.java_path);
+                if let Some(context) = &hook_context {
+                    context.apply_env(&mut it);
                 }
+                it
             }
             None => Command::new(&self.java_path),
         };
         command.current_dir(&self.game_directory);
 
+        // Profile environment variables, values can use the hook placeholders
+        if let Some(p) = profile {
+            for (name, value) in &p.settings.env_vars {
+                if name.is_empty() || name.contains('=') || name.contains('\0') {
+                    warn!("Skipping invalid environment variable name {:?}", name);
+                    continue;
+                }
+                info!("Setting environment variable {} for the game", name);
+                command.env(name, substitute(value));
+            }
+        }
+
         // Define paths
         let natives_path = LAUNCHER_DIRECTORY.meta_dir()
             .join("natives")
             .join(&piston_meta.id);
 
         // Build classpath first as it's needed for JVM arguments
-        let classpath = if let Some(client_jar) = params.custom_client_jar {
+        let classpath = if let Some(client_jar) = params.custom_client_jar.clone() {
             ClasspathBuilder::new(&piston_meta.id)
                 .add_additional_libraries(&params.additional_libraries, 1)
                 .add_piston_libraries(&piston_meta.libraries)
@@ -281,15 +325,6 @@ impl MinecraftLauncher {
         info!("Adding RAM JVM argument: -Xmx{}M", params.memory_max_mb);
         command.arg(format!("-Xmx{}M", params.memory_max_mb));
 
-        // Add recommended GC flags
-        command.arg("-XX:+UnlockExperimentalVMOptions");
-        command.arg("-XX:+UseG1GC");
-        // Add additional G1GC optimization flags like vanilla launcher
-        command.arg("-XX:G1NewSizePercent=20");
-        command.arg("-XX:G1ReservePercent=20");
-        command.arg("-XX:MaxGCPauseMillis=50");
-        command.arg("-XX:G1HeapRegionSize=32M");
-
         // Add NoRisk client specific parameters
         if let Some(creds) = &self.credentials {
             // Get the appropriate NoRisk token based on experimental mode setting
@@ -329,7 +364,7 @@ impl MinecraftLauncher {
 
         // Add Fabric specific mods folder argument if loader is Fabric
         // Note: When using -Dfabric.addMods (prototype), this is still harmless and allows user mods in mods/.
-        if let Some(p_ref) = &profile {
+        if let Some(p_ref) = profile {
             if p_ref.loader == crate::state::profile_state::ModLoader::Fabric {
                 match state.profile_manager.get_profile_mods_path(p_ref) {
                     Ok(mods_path) => {
@@ -351,8 +386,17 @@ impl MinecraftLauncher {
             }
         }
 
-        // Add additional JVM arguments
-        for arg in params.additional_jvm_args {
+        // Add the GC preset and the additional JVM arguments, which override the preset
+        let preset_flags = params.jvm_preset.flags(
+            params.java_major_version,
+            params.java_vendor.as_deref(),
+            params.memory_max_mb,
+        );
+        info!(
+            "Using JVM preset {:?} for Java {}: {:?}",
+            params.jvm_preset, params.java_major_version, preset_flags
+        );
+        for arg in merge_with_custom_args(preset_flags, &params.additional_jvm_args) {
             command.arg(arg);
         }
 
@@ -388,7 +432,7 @@ impl MinecraftLauncher {
                 command.arg(arg);
             }
         } else if let Some(processed_args) =
-            self.process_old_arguments(params.old_minecraft_arguments, piston_meta)
+            self.process_old_arguments(params.old_minecraft_arguments.clone(), piston_meta)
         {
             for arg in processed_args {
                 command.arg(arg);
@@ -425,10 +469,26 @@ impl MinecraftLauncher {
         }
 
         // Add additional game arguments (from profile's extra_game_args)
-        for arg in params.additional_game_args {
+        for arg in &params.additional_game_args {
             command.arg(arg);
         }
 
+        Ok(command)
+    }
+
+    pub async fn launch(
+        &self,
+        piston_meta: &PistonMeta,
+        params: MinecraftLaunchParameters,
+        profile: Option<Profile>,
+    ) -> Result<()> {
+        let state = State::get().await?;
+        let process_manager = &state.process_manager;
+
+        let command = self
+            .build_command(piston_meta, &params, profile.as_ref())
+            .await?;
+
         // Log the command before execution, with sensitive information redacted.
         let loggable_command_view = Self::create_loggable_command_string(&command);
         info!("Executing command: {}", loggable_command_view);
@@ -440,6 +500,17 @@ impl MinecraftLauncher {
             (None, None)
         };
 
+        // Get post-exit hooks at launch time (not at exit time)
+        let launcher_config = state.config_manager.get_config().await;
+        let post_exit_hooks = resolve_hooks(
+            &launcher_config.hooks,
+            profile.as_ref().map(|p| &p.settings.hooks),
+        )
+        .post_exit;
+        let hook_context = profile.as_ref().map(|p| {
+            HookContext::for_profile(p, self.game_directory.clone(), self.java_path.clone())
+        });
+
         // Extract optional profile information for process metadata
         let (profile_loader, profile_loader_version, profile_norisk_pack, profile_name) =
             match profile {
@@ -452,9 +523,17 @@ impl MinecraftLauncher {
                 None => (None, None, None, None),
             };
 
-        // Get post-exit hook from config at launch time (not at exit time)
-        let launcher_config = state.config_manager.get_config().await;
-        let post_exit_hook = launcher_config.hooks.post_exit.clone();
+        // Quick Play tells us up front where the session's playtime goes
+        let quick_play = match (
+            &params.quick_play_singleplayer,
+            &params.quick_play_multiplayer,
+        ) {
+            (Some(world), _) => Some(PlayTarget::Singleplayer {
+                world: world.clone(),
+            }),
+            (None, Some(server)) => Some(PlayTarget::multiplayer(server)),
+            (None, None) => None,
+        };
 
         // Start the process using ProcessManager with additional metadata
         process_manager
@@ -468,7 +547,10 @@ impl MinecraftLauncher {
                 profile_loader_version,
                 profile_norisk_pack,
                 profile_name,
-                post_exit_hook,
+                post_exit_hooks,
+                hook_context,
+                Some(params.memory_max_mb),
+                quick_play,
             )
             .await?;
 
@@ -1,16 +1,20 @@
 pub mod classpath;
+pub mod command_export;
 pub mod forge_arguments;
 pub mod forge_patcher;
 pub mod game_arguments;
 pub mod jvm_arguments;
+pub mod jvm_presets;
 pub mod launcher;
 pub mod neo_forge_arguments;
 pub mod neo_forge_patcher;
 pub mod rules;
 pub mod version;
 pub use classpath::*;
+pub use command_export::*;
 pub use forge_patcher::*;
 pub use game_arguments::*;
 pub use jvm_arguments::*;
+pub use jvm_presets::*;
 pub use launcher::*;
 pub use neo_forge_patcher::*;
@@ -0,0 +1,428 @@
+use crate::error::{AppError, Result};
+use crate::utils::{file_utils, profile_utils};
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Default page size if a filter doesn't set one
+const DEFAULT_PAGE_SIZE: usize = 500;
+
+/// Header of a plain pattern line, e.g.
+/// `[12:00:01] [Render thread/INFO]: msg` (vanilla),
+/// `[12:00:01] [main/INFO] (FabricLoader/GameProvider) msg` (Fabric) or
+/// `[16Jun2023 22:21:44.123] [main/INFO] [cpw.mods.modlauncher.Launcher/MODLAUNCHER]: msg` (Forge)
+static PLAIN_HEADER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\[(?P<time>[^\]]+)\] \[(?P<thread>.+?)/(?P<level>TRACE|DEBUG|INFO|WARN|WARNING|ERROR|FATAL)\](?: \((?P<paren_logger>[^)]*)\)| \[(?P<bracket_logger>[^\]]*)\])?:? ?(?P<message>.*)$",
+    )
+    .expect("Invalid log header regex")
+});
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    pub fn parse(value: &str) -> Option<Self> {
+        Some(match value.trim().to_ascii_uppercase().as_str() {
+            "TRACE" => LogLevel::Trace,
+            "DEBUG" => LogLevel::Debug,
+            "INFO" => LogLevel::Info,
+            "WARN" | "WARNING" => LogLevel::Warn,
+            "ERROR" => LogLevel::Error,
+            "FATAL" => LogLevel::Fatal,
+            _ => return None,
+        })
+    }
+}
+
+/// One log4j event. Lines that don't start a new event (stack traces, multi-line messages)
+/// belong to the event before them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    /// Position of the record in its file, starting at 0
+    pub index: usize,
+    /// As written by the plain pattern (often only the time of day), RFC 3339 for XML events
+    pub timestamp: Option<String>,
+    pub thread: Option<String>,
+    pub level: Option<LogLevel>,
+    pub logger: Option<String>,
+    pub message: String,
+    /// Lines following the message, usually a stack trace
+    pub stack_trace: Vec<String>,
+}
+
+impl LogRecord {
+    fn plain(index: usize, line: &str) -> Self {
+        match PLAIN_HEADER.captures(line) {
+            Some(caps) => Self {
+                index,
+                timestamp: caps.name("time").map(|m| m.as_str().to_string()),
+                thread: caps.name("thread").map(|m| m.as_str().to_string()),
+                level: caps.name("level").and_then(|m| LogLevel::parse(m.as_str())),
+                logger: caps
+                    .name("paren_logger")
+                    .or_else(|| caps.name("bracket_logger"))
+                    .map(|m| m.as_str().to_string()),
+                message: caps["message"].to_string(),
+                stack_trace: Vec::new(),
+            },
+            // Output that bypasses log4j, e.g. System.out before the logger is set up
+            None => Self {
+                index,
+                timestamp: None,
+                thread: None,
+                level: None,
+                logger: None,
+                message: line.to_string(),
+                stack_trace: Vec::new(),
+            },
+        }
+    }
+
+    /// Message and stack trace, what full-text search and regex filters look at
+    fn text(&self) -> String {
+        let mut text = self.message.clone();
+        for line in &self.stack_trace {
+            text.push('\n');
+            text.push_str(line);
+        }
+        text
+    }
+}
+
+/// Parses a log in Minecraft's plain pattern layout or the (legacy) XML layout
+pub fn parse_log(content: &str) -> Vec<LogRecord> {
+    if content.contains("<log4j:Event") || content.trim_start().starts_with("<Event") {
+        parse_xml_log(content)
+    } else {
+        parse_plain_log(content)
+    }
+}
+
+pub fn parse_plain_log(content: &str) -> Vec<LogRecord> {
+    let mut records: Vec<LogRecord> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+        if !PLAIN_HEADER.is_match(line) {
+            if let Some(last) = records.last_mut().filter(|r| r.level.is_some()) {
+                last.stack_trace.push(line.to_string());
+                continue;
+            }
+        }
+        let index = records.len();
+        records.push(LogRecord::plain(index, line));
+    }
+    records
+}
+
+/// Parses the XML layout the vanilla logging configs print to stdout
+/// (`<log4j:Event logger=".." timestamp=".." level=".." thread="..">` with a `log4j:Message`
+/// and an optional `log4j:Throwable`). log4j2's `<Event>` elements work as well.
+/// Text between events is parsed as plain lines.
+pub fn parse_xml_log(content: &str) -> Vec<LogRecord> {
+    let mut reader = Reader::from_str(content);
+    reader.trim_text(true);
+
+    let mut records: Vec<LogRecord> = Vec::new();
+    let mut current: Option<LogRecord> = None;
+    let mut field: Option<Vec<u8>> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match local_name(&e).as_slice() {
+                b"Event" => current = Some(xml_event_record(records.len(), &e)),
+                name => field = Some(name.to_vec()),
+            },
+            Ok(Event::End(e)) => {
+                if e.local_name().as_ref() == b"Event" {
+                    records.extend(current.take());
+                }
+                field = None;
+            }
+            Ok(Event::Text(text)) => {
+                let text = text.unescape().map(|t| t.into_owned()).unwrap_or_default();
+                match current.as_mut() {
+                    Some(record) => append_field(record, field.as_deref(), &text),
+                    None => {
+                        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+                            records.push(LogRecord::plain(records.len(), line));
+                        }
+                    }
+                }
+            }
+            Ok(Event::CData(data)) => {
+                let text = String::from_utf8_lossy(&data.into_inner()).into_owned();
+                if let Some(record) = current.as_mut() {
+                    append_field(record, field.as_deref(), &text);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                // A truncated file (game still running or killed) ends in the middle of an event
+                debug!(
+                    "Stopped parsing XML log at position {}: {}",
+                    reader.buffer_position(),
+                    e
+                );
+                break;
+            }
+        }
+    }
+    records.extend(current.take());
+    records
+}
+
+fn local_name(e: &BytesStart) -> Vec<u8> {
+    e.local_name().as_ref().to_vec()
+}
+
+fn xml_event_record(index: usize, e: &BytesStart) -> LogRecord {
+    let mut record = LogRecord {
+        index,
+        timestamp: None,
+        thread: None,
+        level: None,
+        logger: None,
+        message: String::new(),
+        stack_trace: Vec::new(),
+    };
+    for attr in e.attributes().flatten() {
+        let value = attr
+            .unescape_value()
+            .map(|v| v.into_owned())
+            .unwrap_or_default();
+        match attr.key.local_name().as_ref() {
+            b"logger" | b"loggerName" => record.logger = Some(value),
+            b"thread" => record.thread = Some(value),
+            b"level" => record.level = LogLevel::parse(&value),
+            b"timestamp" | b"timeMillis" => {
+                record.timestamp = value
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(DateTime::<Utc>::from_timestamp_millis)
+                    .map(|t| t.to_rfc3339())
+            }
+            _ => {}
+        }
+    }
+    record
+}
+
+fn append_field(record: &mut LogRecord, field: Option<&[u8]>, text: &str) {
+    match field {
+        Some(b"Message") => record.message.push_str(text),
+        Some(b"Throwable") | Some(b"Thrown") => record.stack_trace.extend(
+            text.lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(str::to_string),
+        ),
+        _ => {}
+    }
+}
+
+/// Which records to return. All conditions have to match.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Empty means all levels. Lines without a level only match an empty list.
+    #[serde(default)]
+    pub levels: Vec<LogLevel>,
+    /// Case-insensitive substring of the logger name
+    pub logger: Option<String>,
+    /// Case-insensitive text searched in message and stack trace
+    pub text: Option<String>,
+    /// Regex matched against message and stack trace
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    /// Defaults to 500
+    pub limit: Option<usize>,
+}
+
+/// A `LogFilter` with its regex compiled
+pub struct CompiledLogFilter<'a> {
+    filter: &'a LogFilter,
+    logger: Option<String>,
+    text: Option<String>,
+    pattern: Option<Regex>,
+}
+
+impl LogFilter {
+    pub fn compile(&self) -> Result<CompiledLogFilter<'_>> {
+        let pattern = match self.pattern.as_deref().filter(|p| !p.is_empty()) {
+            Some(pattern) => Some(
+                RegexBuilder::new(pattern)
+                    .size_limit(1 << 20)
+                    .build()
+                    .map_err(|e| AppError::Other(format!("Invalid log filter regex: {}", e)))?,
+            ),
+            None => None,
+        };
+        Ok(CompiledLogFilter {
+            filter: self,
+            logger: self
+                .logger
+                .as_deref()
+                .filter(|l| !l.is_empty())
+                .map(str::to_lowercase),
+            text: self
+                .text
+                .as_deref()
+                .filter(|t| !t.is_empty())
+                .map(str::to_lowercase),
+            pattern,
+        })
+    }
+
+    fn page_size(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+}
+
+impl CompiledLogFilter<'_> {
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        if !self.filter.levels.is_empty()
+            && !record
+                .level
+                .is_some_and(|level| self.filter.levels.contains(&level))
+        {
+            return false;
+        }
+        if let Some(logger) = &self.logger {
+            if !record
+                .logger
+                .as_deref()
+                .is_some_and(|l| l.to_lowercase().contains(logger.as_str()))
+            {
+                return false;
+            }
+        }
+        if self.text.is_none() && self.pattern.is_none() {
+            return true;
+        }
+
+        let text = record.text();
+        self.text
+            .as_ref()
+            .map_or(true, |t| text.to_lowercase().contains(t.as_str()))
+            && self.pattern.as_ref().map_or(true, |p| p.is_match(&text))
+    }
+}
+
+/// One page of matching records
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogPage {
+    pub records: Vec<LogRecord>,
+    /// Number of matching records over all pages
+    pub total: usize,
+    pub offset: usize,
+}
+
+pub fn filter_records(records: Vec<LogRecord>, filter: &LogFilter) -> Result<LogPage> {
+    let compiled = filter.compile()?;
+    let matching: Vec<LogRecord> = records
+        .into_iter()
+        .filter(|r| compiled.matches(r))
+        .collect();
+    let total = matching.len();
+    Ok(LogPage {
+        records: matching
+            .into_iter()
+            .skip(filter.offset)
+            .take(filter.page_size())
+            .collect(),
+        total,
+        offset: filter.offset,
+    })
+}
+
+/// Reads, parses and filters a `.log` or `.log.gz` file
+pub async fn query_log_file(log_path: &std::path::Path, filter: &LogFilter) -> Result<LogPage> {
+    let content = file_utils::read_log_file_content(log_path).await?;
+    filter_records(parse_log(&content), filter)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogSearchHit {
+    pub file: PathBuf,
+    pub record: LogRecord,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogSearchPage {
+    pub hits: Vec<LogSearchHit>,
+    /// Number of hits over all pages and files
+    pub total: usize,
+    pub offset: usize,
+    pub files_searched: usize,
+}
+
+/// Searches all logs of a profile (`latest.log`, `debug.log` and the rotated `.log.gz` files),
+/// newest file first
+pub async fn search_profile_logs(profile_id: Uuid, filter: &LogFilter) -> Result<LogSearchPage> {
+    let compiled = filter.compile()?;
+
+    let mut files = Vec::new();
+    for path in profile_utils::list_log_files(profile_id).await? {
+        let modified = tokio::fs::metadata(&path)
+            .await
+            .and_then(|m| m.modified())
+            .ok();
+        files.push((modified, path));
+    }
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    let mut hits = Vec::new();
+    let mut total = 0;
+    for (_, path) in &files {
+        let content = match file_utils::read_log_file_content(path).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Skipping unreadable log file {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        for record in parse_log(&content) {
+            if !compiled.matches(&record) {
+                continue;
+            }
+            if total >= filter.offset && hits.len() < filter.page_size() {
+                hits.push(LogSearchHit {
+                    file: path.clone(),
+                    record,
+                });
+            }
+            total += 1;
+        }
+    }
+
+    info!(
+        "Log search for profile {} found {} hit(s) in {} file(s)",
+        profile_id,
+        total,
+        files.len()
+    );
+    Ok(LogSearchPage {
+        hits,
+        total,
+        offset: filter.offset,
+        files_searched: files.len(),
+    })
+}
@@ -1,10 +1,14 @@
 pub mod api;
 pub mod auth;
+pub mod crash_analyzer;
 pub mod downloads;
 pub mod dto;
 pub mod installer;
+pub mod java_requirements;
 pub mod launch;
+pub mod log_parser;
 pub mod modloader;
+pub mod offline;
 
 pub use api::*;
 pub use auth::*;
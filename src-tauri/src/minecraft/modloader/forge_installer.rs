@@ -3,6 +3,7 @@ use crate::error::{AppError, Result};
 use crate::minecraft::api::forge_api::ForgeApi;
 use crate::minecraft::downloads::{ForgeInstallerDownloadService, ForgeLibrariesDownload};
 use crate::minecraft::launch::forge_arguments::ForgeArguments;
+use crate::minecraft::offline;
 use crate::minecraft::ForgePatcher;
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::Profile;
@@ -146,15 +147,18 @@ impl ForgeInstaller {
         let profile_json = forge_installer_download
             .extract_install_profile(&target_forge_version)
             .await?;
-        forge_installer_download
-            .extract_data_folder(&target_forge_version)
-            .await?;
-        forge_installer_download
-            .extract_maven_folder(&target_forge_version)
-            .await?;
-        forge_installer_download
-            .extract_jars(&target_forge_version)
-            .await?;
+        // Only reads the installer when checking the offline cache
+        if !offline::is_check_only() {
+            forge_installer_download
+                .extract_data_folder(&target_forge_version)
+                .await?;
+            forge_installer_download
+                .extract_maven_folder(&target_forge_version)
+                .await?;
+            forge_installer_download
+                .extract_jars(&target_forge_version)
+                .await?;
+        }
 
         state
             .emit_event(EventPayload {
@@ -250,7 +254,7 @@ impl ForgeInstaller {
             }
 
             // Patcher nur ausführen, wenn nötig
-            if should_run_patcher {
+            if should_run_patcher && !offline::is_check_only() {
                 state
                     .emit_event(EventPayload {
                         event_id: forge_event_id,
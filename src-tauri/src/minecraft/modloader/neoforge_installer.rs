@@ -2,6 +2,7 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::downloads::{NeoForgeInstallerDownloadService, NeoForgeLibrariesDownload};
 use crate::minecraft::launch::neo_forge_arguments::NeoForgeArguments;
+use crate::minecraft::offline;
 use crate::minecraft::{NeoForgeApi, NeoForgePatcher};
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::Profile;
@@ -151,15 +152,18 @@ impl NeoForgeInstaller {
         let profile_json = neoforge_installer_download
             .extract_install_profile(&target_neoforge_version)
             .await?;
-        neoforge_installer_download
-            .extract_data_folder(&target_neoforge_version)
-            .await?;
-        neoforge_installer_download
-            .extract_maven_folder(&target_neoforge_version)
-            .await?;
-        neoforge_installer_download
-            .extract_jars(&target_neoforge_version)
-            .await?;
+        // Only reads the installer when checking the offline cache
+        if !offline::is_check_only() {
+            neoforge_installer_download
+                .extract_data_folder(&target_neoforge_version)
+                .await?;
+            neoforge_installer_download
+                .extract_maven_folder(&target_neoforge_version)
+                .await?;
+            neoforge_installer_download
+                .extract_jars(&target_neoforge_version)
+                .await?;
+        }
 
         state
             .emit_event(EventPayload {
@@ -257,7 +261,7 @@ impl NeoForgeInstaller {
             }
 
             // Patcher nur ausführen, wenn nötig
-            if should_run_patcher {
+            if should_run_patcher && !offline::is_check_only() {
                 state
                     .emit_event(EventPayload {
                         event_id: neoforge_event_id,
@@ -0,0 +1,172 @@
+//! Offline mode for the install pipeline.
+//!
+//! While an install runs inside [`run`], every service that would normally touch the
+//! network (metadata APIs, `DownloadUtils`, Java and NoRisk asset downloads) only checks
+//! its local cache. Anything that is not present (and verified) is recorded here instead
+//! of being downloaded, so the installer can report exactly what is missing for a profile.
+//!
+//! [`run_check`] goes one step further and only inspects the disk: steps that derive files
+//! from the cache (extracting natives, patching Forge, copying assets and mods into the
+//! instance) are skipped, they need nothing from the network.
+
+use crate::error::AppError;
+use crate::state::profile_state::Profile;
+use log::warn;
+use serde::Serialize;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+tokio::task_local! {
+    static OFFLINE_SESSION: Arc<OfflineSession>;
+}
+
+/// A single artifact the install pipeline needs but could not find locally
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingArtifact {
+    /// Install step that needed the artifact (e.g. "libraries", "assets")
+    pub step: String,
+    pub description: String,
+    /// Where the artifact is expected in the local cache
+    pub path: PathBuf,
+    pub url: Option<String>,
+}
+
+/// Result of an offline install run for one profile
+#[derive(Debug, Clone, Serialize)]
+pub struct OfflineReport {
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    pub missing: Vec<MissingArtifact>,
+}
+
+impl OfflineReport {
+    pub fn is_ready(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Human readable list of all missing artifacts, grouped by step
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "{} artifact(s) missing for offline launch of '{}':",
+            self.missing.len(),
+            self.profile_name
+        )];
+        for artifact in &self.missing {
+            lines.push(format!(
+                "  [{}] {} ({})",
+                artifact.step,
+                artifact.description,
+                artifact.path.display()
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+#[derive(Default)]
+struct OfflineSession {
+    check_only: bool,
+    step: Mutex<String>,
+    missing: Mutex<Vec<MissingArtifact>>,
+}
+
+/// Runs `future` in offline mode and returns its result together with the report of
+/// all artifacts that were missing.
+pub async fn run<F: Future>(profile: &Profile, future: F) -> (F::Output, OfflineReport) {
+    run_session(profile, false, future).await
+}
+
+/// Like [`run`], but nothing is written: the install only checks what is already on disk
+pub async fn run_check<F: Future>(profile: &Profile, future: F) -> (F::Output, OfflineReport) {
+    run_session(profile, true, future).await
+}
+
+async fn run_session<F: Future>(
+    profile: &Profile,
+    check_only: bool,
+    future: F,
+) -> (F::Output, OfflineReport) {
+    let session = Arc::new(OfflineSession {
+        check_only,
+        ..OfflineSession::default()
+    });
+    let output = OFFLINE_SESSION.scope(session.clone(), future).await;
+    let missing = session
+        .missing
+        .lock()
+        .map(|missing| missing.clone())
+        .unwrap_or_default();
+    (
+        output,
+        OfflineReport {
+            profile_id: profile.id,
+            profile_name: profile.name.clone(),
+            missing,
+        },
+    )
+}
+
+/// Whether the current task runs in offline mode
+pub fn is_active() -> bool {
+    OFFLINE_SESSION.try_with(|_| ()).is_ok()
+}
+
+/// Whether the current task only checks the disk, see [`run_check`]. Steps that write
+/// files are skipped then.
+pub fn is_check_only() -> bool {
+    OFFLINE_SESSION
+        .try_with(|session| session.check_only)
+        .unwrap_or(false)
+}
+
+/// Whether anything was reported missing so far in the current offline run
+pub fn has_missing() -> bool {
+    OFFLINE_SESSION
+        .try_with(|session| {
+            session
+                .missing
+                .lock()
+                .map(|missing| !missing.is_empty())
+                .unwrap_or(false)
+        })
+        .unwrap_or(false)
+}
+
+/// Sets the install step that following missing artifacts are attributed to
+pub fn set_step(step: &str) {
+    let _ = OFFLINE_SESSION.try_with(|session| {
+        if let Ok(mut current) = session.step.lock() {
+            *current = step.to_string();
+        }
+    });
+}
+
+/// Records a missing artifact and returns the error the caller should fail with
+pub fn missing_artifact(
+    description: impl Into<String>,
+    path: impl AsRef<Path>,
+    url: Option<&str>,
+) -> AppError {
+    let description = description.into();
+    warn!("[Offline] Missing artifact: {}", description);
+
+    let _ = OFFLINE_SESSION.try_with(|session| {
+        let step = session
+            .step
+            .lock()
+            .map(|step| step.clone())
+            .unwrap_or_default();
+        if let Ok(mut missing) = session.missing.lock() {
+            missing.push(MissingArtifact {
+                step,
+                description: description.clone(),
+                path: path.as_ref().to_path_buf(),
+                url: url.map(str::to_string),
+            });
+        }
+    });
+
+    AppError::OfflineArtifactMissing(description)
+}
@@ -1,7 +1,11 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY, update_custom_game_dir};
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::minecraft::dto::JavaDistribution;
+use crate::minecraft::JvmPreset;
 use crate::state::post_init::PostInitializationHandler;
 use crate::state::profile_state::MemorySettings;
+use crate::utils::endpoint_registry::{update_endpoint_registry, EndpointRegistry};
+use crate::utils::redaction_utils::{RedactionSettings, Redactor};
 use async_trait::async_trait;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
@@ -53,6 +57,27 @@ pub struct LauncherConfig {
     pub global_memory_settings: MemorySettings,
     #[serde(default)]
     pub custom_game_directory: Option<PathBuf>,
+    /// Base URL overrides and fallback mirrors per remote service
+    #[serde(default)]
+    pub endpoints: EndpointRegistry,
+    /// Install and launch strictly from the local cache without any network calls
+    #[serde(default)]
+    pub offline_mode: bool,
+    /// Seconds the game gets to shut down on its own before it is killed
+    #[serde(default = "default_stop_grace_period_secs")]
+    pub stop_grace_period_secs: u64,
+    /// GC flags for every launch, profiles can override it
+    #[serde(default)]
+    pub jvm_preset: JvmPreset,
+    /// Vendor managed Java runtimes are downloaded from, profiles can override it
+    #[serde(default = "default_java_vendor")]
+    pub java_vendor: JavaDistribution,
+    /// What gets masked in logs and crash reports before they are uploaded
+    #[serde(default)]
+    pub redaction: RedactionSettings,
+    /// Key for the CurseForge API, used for pack imports. Falls back to `CURSEFORGE_API_KEY`
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
 }
 
 fn default_config_version() -> u32 {
@@ -83,6 +108,17 @@ fn default_hide_on_process_start() -> bool {
     false
 }
 
+fn default_stop_grace_period_secs() -> u64 {
+    15
+}
+
+/// Longest time a stopped game gets to exit on its own before it's killed
+pub const MAX_STOP_GRACE_PERIOD_SECS: u64 = 300;
+
+fn default_java_vendor() -> JavaDistribution {
+    JavaDistribution::Zulu
+}
+
 fn default_global_memory_settings() -> MemorySettings {
     MemorySettings {
         min: 3072, // 2GB
@@ -107,6 +143,13 @@ impl Default for LauncherConfig {
             hide_on_process_start: default_hide_on_process_start(),
             global_memory_settings: default_global_memory_settings(),
             custom_game_directory: None,
+            endpoints: EndpointRegistry::default(),
+            offline_mode: false,
+            stop_grace_period_secs: default_stop_grace_period_secs(),
+            jvm_preset: JvmPreset::default(),
+            java_vendor: default_java_vendor(),
+            redaction: RedactionSettings::default(),
+            curseforge_api_key: None,
         }
     }
 }
@@ -157,6 +200,7 @@ impl ConfigManager {
                 
                 // Update cache
                 update_custom_game_dir(loaded_config.custom_game_directory);
+                update_endpoint_registry(loaded_config.endpoints);
             }
             Err(e) => {
                 error!("Failed to parse config file: {}", e);
@@ -199,6 +243,9 @@ impl ConfigManager {
                             if let Some(hide) = obj.get("hide_on_process_start").and_then(|v| v.as_bool()) {
                                 migrated_config.hide_on_process_start = hide;
                             }
+                            if let Some(offline) = obj.get("offline_mode").and_then(|v| v.as_bool()) {
+                                migrated_config.offline_mode = offline;
+                            }
                             
                             // Migrate numeric fields
                             if let Some(downloads) = obj.get("concurrent_downloads").and_then(|v| v.as_u64()) {
@@ -211,6 +258,11 @@ impl ConfigManager {
                                     migrated_config.concurrent_io_limit = io_limit as usize;
                                 }
                             }
+                            if let Some(grace) = obj.get("stop_grace_period_secs").and_then(|v| v.as_u64()) {
+                                if grace <= MAX_STOP_GRACE_PERIOD_SECS {
+                                    migrated_config.stop_grace_period_secs = grace;
+                                }
+                            }
                             
                             // Migrate string fields
                             if let Some(grouping) = obj.get("profile_grouping_criterion").and_then(|v| v.as_str()) {
@@ -240,6 +292,43 @@ impl ConfigManager {
                             if let Some(custom_dir_str) = obj.get("custom_game_directory").and_then(|v| v.as_str()) {
                                 migrated_config.custom_game_directory = Some(PathBuf::from(custom_dir_str));
                             }
+
+                            // Migrate JVM preset
+                            if let Some(preset) = obj.get("jvm_preset") {
+                                match serde_json::from_value::<JvmPreset>(preset.clone()) {
+                                    Ok(preset) => migrated_config.jvm_preset = preset,
+                                    Err(e) => warn!("Could not migrate JVM preset: {}", e),
+                                }
+                            }
+
+                            // Migrate Java vendor
+                            if let Some(vendor) = obj.get("java_vendor") {
+                                match serde_json::from_value::<JavaDistribution>(vendor.clone()) {
+                                    Ok(vendor) => migrated_config.java_vendor = vendor,
+                                    Err(e) => warn!("Could not migrate Java vendor: {}", e),
+                                }
+                            }
+
+                            // Migrate redaction rules
+                            if let Some(redaction) = obj.get("redaction") {
+                                match serde_json::from_value::<RedactionSettings>(redaction.clone()) {
+                                    Ok(redaction) => migrated_config.redaction = redaction,
+                                    Err(e) => warn!("Could not migrate redaction rules: {}", e),
+                                }
+                            }
+
+                            // Migrate CurseForge API key
+                            if let Some(key) = obj.get("curseforge_api_key").and_then(|v| v.as_str()) {
+                                migrated_config.curseforge_api_key = Some(key.to_string());
+                            }
+
+                            // Migrate endpoint overrides
+                            if let Some(endpoints) = obj.get("endpoints") {
+                                match serde_json::from_value::<EndpointRegistry>(endpoints.clone()) {
+                                    Ok(endpoints) => migrated_config.endpoints = endpoints,
+                                    Err(e) => warn!("Could not migrate endpoint overrides: {}", e),
+                                }
+                            }
                         }
                         
                         info!("Migration completed, saving migrated configuration");
@@ -252,6 +341,7 @@ impl ConfigManager {
                         
                         // Update cache
                         update_custom_game_dir(migrated_config.custom_game_directory);
+                        update_endpoint_registry(migrated_config.endpoints);
                     }
                     Err(json_err) => {
                         error!("Config file is not valid JSON: {}", json_err);
@@ -309,6 +399,15 @@ impl ConfigManager {
     }
 
     pub async fn set_config(&self, new_config: LauncherConfig) -> Result<()> {
+        // Reject custom redaction rules that don't compile instead of failing on the next upload
+        Redactor::new(&new_config.redaction)?;
+        if new_config.stop_grace_period_secs > MAX_STOP_GRACE_PERIOD_SECS {
+            return Err(AppError::Config(format!(
+                "The stop grace period can be at most {} seconds, got {}",
+                MAX_STOP_GRACE_PERIOD_SECS, new_config.stop_grace_period_secs
+            )));
+        }
+
         let should_save = {
             let mut config = self.config.write().await;
             let current = &*config;
@@ -328,6 +427,13 @@ impl ConfigManager {
                 && current.global_memory_settings.min == new_config.global_memory_settings.min
                 && current.global_memory_settings.max == new_config.global_memory_settings.max
                 && current.custom_game_directory == new_config.custom_game_directory
+                && current.endpoints == new_config.endpoints
+                && current.offline_mode == new_config.offline_mode
+                && current.stop_grace_period_secs == new_config.stop_grace_period_secs
+                && current.jvm_preset == new_config.jvm_preset
+                && current.java_vendor == new_config.java_vendor
+                && current.redaction == new_config.redaction
+                && current.curseforge_api_key == new_config.curseforge_api_key
             {
                 debug!("No config changes detected, skipping save");
                 false
@@ -417,6 +523,51 @@ impl ConfigManager {
                     );
                 }
 
+                if current.endpoints != new_config.endpoints {
+                    info!(
+                        "Changing endpoint overrides: {:?} -> {:?}",
+                        current.endpoints, new_config.endpoints
+                    );
+                }
+                if current.offline_mode != new_config.offline_mode {
+                    info!(
+                        "Changing offline mode: {} -> {}",
+                        current.offline_mode, new_config.offline_mode
+                    );
+                }
+                if current.stop_grace_period_secs != new_config.stop_grace_period_secs {
+                    info!(
+                        "Changing stop grace period: {}s -> {}s",
+                        current.stop_grace_period_secs, new_config.stop_grace_period_secs
+                    );
+                }
+                if current.jvm_preset != new_config.jvm_preset {
+                    info!(
+                        "Changing JVM preset: {:?} -> {:?}",
+                        current.jvm_preset, new_config.jvm_preset
+                    );
+                }
+                if current.java_vendor != new_config.java_vendor {
+                    info!(
+                        "Changing Java vendor: {} -> {}",
+                        current.java_vendor.get_name(),
+                        new_config.java_vendor.get_name()
+                    );
+                }
+                if current.redaction != new_config.redaction {
+                    info!(
+                        "Changing redaction rules: {:?} -> {:?}",
+                        current.redaction, new_config.redaction
+                    );
+                }
+                if current.curseforge_api_key != new_config.curseforge_api_key {
+                    // Don't log the key itself
+                    info!(
+                        "Changing CurseForge API key (set: {})",
+                        new_config.curseforge_api_key.is_some()
+                    );
+                }
+
                 // Update config while preserving version
                 *config = LauncherConfig {
                     version,
@@ -433,6 +584,13 @@ impl ConfigManager {
                     hide_on_process_start: new_config.hide_on_process_start,
                     global_memory_settings: new_config.global_memory_settings,
                     custom_game_directory: new_config.custom_game_directory.clone(),
+                    endpoints: new_config.endpoints.clone(),
+                    offline_mode: new_config.offline_mode,
+                    stop_grace_period_secs: new_config.stop_grace_period_secs,
+                    jvm_preset: new_config.jvm_preset,
+                    java_vendor: new_config.java_vendor,
+                    redaction: new_config.redaction.clone(),
+                    curseforge_api_key: new_config.curseforge_api_key.clone(),
                 };
 
                 true
@@ -445,6 +603,7 @@ impl ConfigManager {
 
             // Update cache
             update_custom_game_dir(new_config.custom_game_directory.clone());
+            update_endpoint_registry(new_config.endpoints.clone());
 
             // Update Discord status if it changed
             if let Ok(state) = crate::state::State::get().await {
@@ -465,7 +624,7 @@ impl ConfigManager {
 
 #[async_trait]
 impl PostInitializationHandler for ConfigManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("ConfigManager: on_state_ready called. Loading configuration...");
         self.load_config_internal().await?;
         info!("ConfigManager: Successfully loaded configuration in on_state_ready.");
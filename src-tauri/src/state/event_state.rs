@@ -1,10 +1,14 @@
 use crate::error::Result;
+use crate::minecraft::crash_analyzer::CrashDiagnosis;
+use crate::state::process_exit::ProcessExitInfo;
+use crate::state::process_metrics::ResourceSample;
 use crate::state::process_state::ProcessMetadata;
 use dashmap::DashMap;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::Emitter;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -38,9 +42,13 @@ pub enum EventType {
     Error,
     LaunchSuccessful,
     CrashReportContentAvailable,
+    ProcessResourceSample,
+    ProcessStopStage,
 }
 
-#[derive(Serialize, Clone)]
+const EVENT_BROADCAST_CAPACITY: usize = 256;
+
+#[derive(Serialize, Clone, Debug)]
 pub struct EventPayload {
     pub event_id: Uuid,
     pub event_type: EventType,
@@ -58,6 +66,40 @@ pub struct MinecraftProcessExitedPayload {
     pub success: bool,
     pub process_metadata: Option<ProcessMetadata>,
     pub crash_report_content: Option<String>,
+    /// Known crash signatures found in the crash report and latest.log, empty on a clean exit
+    pub crash_diagnoses: Vec<CrashDiagnosis>,
+    /// Exit classification with the exit code/signal and any JVM crash logs
+    pub exit_info: Option<ProcessExitInfo>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessResourceSamplePayload {
+    pub process_id: Uuid,
+    pub profile_id: Uuid,
+    pub sample: ResourceSample,
+}
+
+/// Stages of stopping a game, see `ProcessManager::stop_process`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessStopStage {
+    /// SIGTERM was sent (Unix) or the window was asked to close (Windows)
+    GracefulRequested,
+    /// The game logged "Stopping!" and is saving
+    ShuttingDown,
+    /// The grace period is over, the process tree gets killed
+    ForceKilling,
+    Stopped,
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessStopStagePayload {
+    pub process_id: Uuid,
+    pub profile_id: Uuid,
+    pub stage: ProcessStopStage,
+    pub grace_period_secs: u64,
+    pub message: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -77,14 +119,18 @@ struct EventInfo {
 pub struct EventState {
     app: Option<Arc<tauri::AppHandle>>,
     active_events: DashMap<Uuid, EventInfo>,
+    // In-process listeners (e.g. the headless CLI), independent of the frontend
+    listeners: broadcast::Sender<EventPayload>,
 }
 
 impl EventState {
     pub fn new(app: Option<Arc<tauri::AppHandle>>) -> Self {
         info!("Initializing EventState...");
+        let (listeners, _) = broadcast::channel(EVENT_BROADCAST_CAPACITY);
         let state = Self {
             app,
             active_events: DashMap::new(),
+            listeners,
         };
         info!("Successfully initialized EventState.");
         state
@@ -103,6 +149,9 @@ impl EventState {
             );
         }
 
+        // Forward to in-process listeners; an error only means nobody is subscribed
+        let _ = self.listeners.send(payload.clone());
+
         // Emit the event to the frontend
         if let Some(app) = &self.app {
             app.emit("state_event", payload)
@@ -112,6 +161,11 @@ impl EventState {
         Ok(())
     }
 
+    /// Subscribes to all events emitted from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<EventPayload> {
+        self.listeners.subscribe()
+    }
+
     /// Specific helper to emit a TriggerProfileUpdate event.
     pub async fn trigger_profile_update(&self, profile_id: Uuid) -> Result<()> {
         let payload = EventPayload {
@@ -3,7 +3,10 @@ pub mod discord_state;
 pub mod event_state;
 pub mod norisk_packs_state;
 pub mod norisk_versions_state;
+pub mod playtime_tracker;
 pub mod post_init;
+pub mod process_exit;
+pub mod process_metrics;
 pub mod process_state;
 pub mod profile_state;
 pub mod skin_state;
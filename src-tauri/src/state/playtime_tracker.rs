@@ -0,0 +1,521 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::process_state::ProcessMetadata;
+use crate::utils::json_cache;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::fs;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const PLAYTIME_FILENAME: &str = "playtime.json";
+/// Heartbeats and log events are saved at most this often. Starting and ending a session is
+/// always saved right away, so at most this much of a running session is lost on a crash.
+const SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+/// Sessions kept in `playtime.json`, the oldest finished ones are dropped first
+pub const MAX_PLAY_SESSIONS: usize = 2000;
+const DEFAULT_SERVER_PORT: &str = "25565";
+
+static CONNECTING_TO_SERVER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Connecting to (?P<host>[^\s,]+),\s*(?P<port>\d+)").unwrap());
+static STARTING_INTEGRATED_SERVER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Starting integrated minecraft server version").unwrap());
+static LEFT_WORLD_OR_SERVER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Stopping singleplayer server|Client disconnected with reason|Disconnected from server")
+        .unwrap()
+});
+
+/// Where the time of a session was spent
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlayTarget {
+    /// World folder name in `saves`
+    Singleplayer { world: String },
+    /// Server address, lower case and without the default port
+    Multiplayer { server: String },
+}
+
+impl PlayTarget {
+    pub fn multiplayer(address: &str) -> Self {
+        PlayTarget::Multiplayer {
+            server: normalize_server_address(address),
+        }
+    }
+}
+
+/// Lower case and without the default port, so `MC.example.net:25565` and `mc.example.net`
+/// count as the same server
+pub fn normalize_server_address(address: &str) -> String {
+    let address = address.trim().to_lowercase();
+    match address.rsplit_once(':') {
+        Some((host, DEFAULT_SERVER_PORT)) => host.to_string(),
+        _ => address,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlaytimeSegment {
+    pub target: PlayTarget,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// One run of the game
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlaySession {
+    pub process_id: Uuid,
+    pub profile_id: Uuid,
+    pub profile_name: Option<String>,
+    pub account_uuid: Option<String>,
+    pub account_name: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub modloader: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// Last time the process was seen running. Used as the end of sessions that ended
+    /// while the launcher was closed.
+    pub last_seen_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub segments: Vec<PlaytimeSegment>,
+}
+
+impl PlaySession {
+    fn end(&self) -> DateTime<Utc> {
+        self.ended_at.unwrap_or(self.last_seen_at)
+    }
+
+    pub fn duration_secs(&self) -> u64 {
+        (self.end() - self.started_at).num_seconds().max(0) as u64
+    }
+
+    fn segment_secs(&self, segment: &PlaytimeSegment) -> u64 {
+        let end = segment.ended_at.unwrap_or_else(|| self.end());
+        (end - segment.started_at).num_seconds().max(0) as u64
+    }
+
+    fn open_segment(&mut self) -> Option<&mut PlaytimeSegment> {
+        self.segments.last_mut().filter(|s| s.ended_at.is_none())
+    }
+
+    /// Closes the current segment and starts one for `target` (if any)
+    fn switch_target(&mut self, target: Option<PlayTarget>, at: DateTime<Utc>) -> bool {
+        if let Some(open) = self.open_segment() {
+            if Some(&open.target) == target.as_ref() {
+                return false;
+            }
+            open.ended_at = Some(at);
+        } else if target.is_none() {
+            return false;
+        }
+        if let Some(target) = target {
+            self.segments.push(PlaytimeSegment {
+                target,
+                started_at: at,
+                ended_at: None,
+            });
+        }
+        true
+    }
+
+    fn close(&mut self, at: DateTime<Utc>) {
+        self.switch_target(None, at);
+        self.last_seen_at = at;
+        self.ended_at = Some(at);
+    }
+}
+
+/// What a line of `latest.log` says about where the player is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogPlayEvent {
+    /// The world name isn't logged, it's taken from the `session.lock` of the opened world
+    JoinedSingleplayer,
+    JoinedServer(String),
+    Left,
+}
+
+pub fn parse_log_event(line: &str) -> Option<LogPlayEvent> {
+    // Chat messages can contain anything
+    if line.contains("[CHAT]") {
+        return None;
+    }
+    if let Some(caps) = CONNECTING_TO_SERVER.captures(line) {
+        let address = format!("{}:{}", &caps["host"], &caps["port"]);
+        return Some(LogPlayEvent::JoinedServer(normalize_server_address(&address)));
+    }
+    if STARTING_INTEGRATED_SERVER.is_match(line) {
+        return Some(LogPlayEvent::JoinedSingleplayer);
+    }
+    if LEFT_WORLD_OR_SERVER.is_match(line) {
+        return Some(LogPlayEvent::Left);
+    }
+    None
+}
+
+/// The world that was opened last, going by the `session.lock` Minecraft writes when opening it
+pub async fn find_active_world(instance_dir: &Path) -> Option<String> {
+    let mut entries = fs::read_dir(instance_dir.join("saves")).await.ok()?;
+    let mut newest: Option<(std::time::SystemTime, String)> = None;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(modified) = fs::metadata(entry.path().join("session.lock"))
+            .await
+            .and_then(|m| m.modified())
+        else {
+            continue;
+        };
+        if newest.as_ref().map_or(true, |(time, _)| modified > *time) {
+            newest = Some((modified, entry.file_name().to_string_lossy().into_owned()));
+        }
+    }
+    newest.map(|(_, world)| world)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfilePlaytime {
+    pub profile_id: Uuid,
+    pub profile_name: Option<String>,
+    pub total_secs: u64,
+    pub session_count: u32,
+    pub last_played: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WeeklyPlaytime {
+    /// Monday of the week (UTC)
+    pub week_start: NaiveDate,
+    pub total_secs: u64,
+    pub session_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TargetPlaytime {
+    pub target: PlayTarget,
+    pub total_secs: u64,
+    pub visit_count: u32,
+    pub last_played: DateTime<Utc>,
+}
+
+/// Total playtime per profile, most played first
+pub fn playtime_per_profile(sessions: &[PlaySession]) -> Vec<ProfilePlaytime> {
+    let mut totals: HashMap<Uuid, ProfilePlaytime> = HashMap::new();
+    for session in sessions {
+        let entry = totals
+            .entry(session.profile_id)
+            .or_insert_with(|| ProfilePlaytime {
+                profile_id: session.profile_id,
+                profile_name: None,
+                total_secs: 0,
+                session_count: 0,
+                last_played: session.end(),
+            });
+        entry.total_secs += session.duration_secs();
+        entry.session_count += 1;
+        // Keep the most recent name, profiles can be renamed
+        if session.end() >= entry.last_played && session.profile_name.is_some() {
+            entry.profile_name = session.profile_name.clone();
+        }
+        entry.last_played = entry.last_played.max(session.end());
+    }
+    let mut result: Vec<ProfilePlaytime> = totals.into_values().collect();
+    result.sort_by(|a, b| b.total_secs.cmp(&a.total_secs));
+    result
+}
+
+/// Playtime per week, oldest first. A session running into the next week is split at
+/// midnight on Monday and counts for every week it has time in.
+pub fn playtime_per_week(sessions: &[PlaySession], profile_id: Option<Uuid>) -> Vec<WeeklyPlaytime> {
+    let mut weeks: HashMap<NaiveDate, WeeklyPlaytime> = HashMap::new();
+    for session in sessions
+        .iter()
+        .filter(|s| profile_id.map_or(true, |id| s.profile_id == id))
+    {
+        let end = session.end();
+        let mut from = session.started_at;
+        loop {
+            let day = from.date_naive();
+            let week_start = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+            let next_week = (week_start + Duration::days(7))
+                .and_time(NaiveTime::MIN)
+                .and_utc();
+            let until = end.min(next_week);
+            let entry = weeks.entry(week_start).or_insert(WeeklyPlaytime {
+                week_start,
+                total_secs: 0,
+                session_count: 0,
+            });
+            entry.total_secs += (until - from).num_seconds().max(0) as u64;
+            entry.session_count += 1;
+            if end <= next_week {
+                break;
+            }
+            from = next_week;
+        }
+    }
+    let mut result: Vec<WeeklyPlaytime> = weeks.into_values().collect();
+    result.sort_by_key(|w| w.week_start);
+    result
+}
+
+/// Playtime per server and singleplayer world, most played first
+pub fn playtime_per_target(sessions: &[PlaySession], profile_id: Option<Uuid>) -> Vec<TargetPlaytime> {
+    let mut targets: HashMap<PlayTarget, TargetPlaytime> = HashMap::new();
+    for session in sessions
+        .iter()
+        .filter(|s| profile_id.map_or(true, |id| s.profile_id == id))
+    {
+        for segment in &session.segments {
+            let end = segment.ended_at.unwrap_or_else(|| session.end());
+            let entry = targets
+                .entry(segment.target.clone())
+                .or_insert_with(|| TargetPlaytime {
+                    target: segment.target.clone(),
+                    total_secs: 0,
+                    visit_count: 0,
+                    last_played: end,
+                });
+            entry.total_secs += session.segment_secs(segment);
+            entry.visit_count += 1;
+            entry.last_played = entry.last_played.max(end);
+        }
+    }
+    let mut result: Vec<TargetPlaytime> = targets.into_values().collect();
+    result.sort_by(|a, b| b.total_secs.cmp(&a.total_secs));
+    result
+}
+
+/// Records game sessions and where their time was spent. Persisted to `playtime.json`
+/// so running sessions survive a launcher restart.
+pub struct PlaytimeTracker {
+    path: PathBuf,
+    sessions: Mutex<Vec<PlaySession>>,
+    last_save: Mutex<Option<Instant>>,
+}
+
+impl PlaytimeTracker {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            sessions: Mutex::new(Vec::new()),
+            last_save: Mutex::new(None),
+        }
+    }
+
+    /// Loads the stored sessions. Sessions of `running_processes` continue, all other open
+    /// sessions ended while the launcher was closed and are closed at their last heartbeat.
+    pub async fn load(&self, running_processes: &[ProcessMetadata]) -> Result<()> {
+        let mut sessions = match self.read_sessions().await {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                json_cache::back_up_unreadable(&self.path, e).await?;
+                Vec::new()
+            }
+        };
+
+        let running: HashSet<Uuid> = running_processes.iter().map(|p| p.id).collect();
+        for session in sessions
+            .iter_mut()
+            .filter(|s| s.ended_at.is_none() && !running.contains(&s.process_id))
+        {
+            info!(
+                "Closing playtime session of process {} that ended while the launcher was closed",
+                session.process_id
+            );
+            let last_seen = session.last_seen_at;
+            session.close(last_seen);
+        }
+
+        // Processes started by an older launcher version don't have a session yet
+        for metadata in running_processes {
+            if !sessions.iter().any(|s| s.process_id == metadata.id) {
+                sessions.push(Self::new_session(metadata, None));
+            } else {
+                debug!("Re-attached playtime session of process {}", metadata.id);
+            }
+        }
+
+        drop_oldest_sessions(&mut sessions);
+        let mut guard = self.sessions.lock().await;
+        *guard = sessions;
+        self.save(&guard).await
+    }
+
+    async fn read_sessions(&self) -> Result<Vec<PlaySession>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path).await?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn new_session(metadata: &ProcessMetadata, quick_play: Option<PlayTarget>) -> PlaySession {
+        let now = Utc::now();
+        let mut session = PlaySession {
+            process_id: metadata.id,
+            profile_id: metadata.profile_id,
+            profile_name: metadata.profile_name.clone(),
+            account_uuid: metadata.account_uuid.clone(),
+            account_name: metadata.account_name.clone(),
+            minecraft_version: metadata.minecraft_version.clone(),
+            modloader: metadata.modloader.clone(),
+            started_at: metadata.start_time,
+            last_seen_at: now,
+            ended_at: None,
+            segments: Vec::new(),
+        };
+        session.switch_target(quick_play, metadata.start_time);
+        session
+    }
+
+    pub async fn start_session(&self, metadata: &ProcessMetadata, quick_play: Option<PlayTarget>) {
+        let mut sessions = self.sessions.lock().await;
+        sessions.push(Self::new_session(metadata, quick_play));
+        drop_oldest_sessions(&mut sessions);
+        if let Err(e) = self.save(&sessions).await {
+            warn!("Failed to save playtime after starting process {}: {}", metadata.id, e);
+        }
+    }
+
+    pub async fn end_session(&self, process_id: Uuid) {
+        let mut sessions = self.sessions.lock().await;
+        let Some(session) = sessions
+            .iter_mut()
+            .find(|s| s.process_id == process_id && s.ended_at.is_none())
+        else {
+            return;
+        };
+        session.close(Utc::now());
+        info!(
+            "Playtime session of process {} ended after {}s",
+            process_id,
+            session.duration_secs()
+        );
+        if let Err(e) = self.save(&sessions).await {
+            warn!("Failed to save playtime after process {} ended: {}", process_id, e);
+        }
+    }
+
+    /// Marks the sessions of `process_ids` as still running
+    pub async fn heartbeat(&self, process_ids: &[Uuid]) {
+        if process_ids.is_empty() {
+            return;
+        }
+        let mut sessions = self.sessions.lock().await;
+        let now = Utc::now();
+        for session in sessions
+            .iter_mut()
+            .filter(|s| s.ended_at.is_none() && process_ids.contains(&s.process_id))
+        {
+            session.last_seen_at = now;
+        }
+        if let Err(e) = self.save_debounced(&sessions).await {
+            warn!("Failed to save playtime heartbeat: {}", e);
+        }
+    }
+
+    /// Attributes the following time of a session to the world or server named in a log line
+    pub async fn observe_log_line(&self, process_id: Uuid, line: &str, instance_dir: &Path) {
+        let Some(event) = parse_log_event(line) else {
+            return;
+        };
+        let target = match event {
+            LogPlayEvent::JoinedServer(server) => Some(PlayTarget::Multiplayer { server }),
+            LogPlayEvent::JoinedSingleplayer => match find_active_world(instance_dir).await {
+                Some(world) => Some(PlayTarget::Singleplayer { world }),
+                None => {
+                    debug!("Singleplayer started for process {}, but no world found", process_id);
+                    return;
+                }
+            },
+            LogPlayEvent::Left => None,
+        };
+
+        let mut sessions = self.sessions.lock().await;
+        let Some(session) = sessions
+            .iter_mut()
+            .find(|s| s.process_id == process_id && s.ended_at.is_none())
+        else {
+            return;
+        };
+        if session.switch_target(target.clone(), Utc::now()) {
+            debug!("Process {} is now playing {:?}", process_id, target);
+            if let Err(e) = self.save_debounced(&sessions).await {
+                warn!("Failed to save playtime for process {}: {}", process_id, e);
+            }
+        }
+    }
+
+    /// All sessions, newest first, optionally only those of one profile
+    pub async fn get_sessions(&self, profile_id: Option<Uuid>) -> Vec<PlaySession> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .iter()
+            .rev()
+            .filter(|s| profile_id.map_or(true, |id| s.profile_id == id))
+            .cloned()
+            .collect()
+    }
+
+    pub async fn per_profile(&self) -> Vec<ProfilePlaytime> {
+        playtime_per_profile(&self.sessions.lock().await)
+    }
+
+    pub async fn per_week(&self, profile_id: Option<Uuid>) -> Vec<WeeklyPlaytime> {
+        playtime_per_week(&self.sessions.lock().await, profile_id)
+    }
+
+    pub async fn per_target(&self, profile_id: Option<Uuid>) -> Vec<TargetPlaytime> {
+        playtime_per_target(&self.sessions.lock().await, profile_id)
+    }
+
+    /// Saves unless the last save was less than `SAVE_INTERVAL` ago. Skipped changes are
+    /// written by the next save, heartbeats keep coming while a game runs.
+    async fn save_debounced(&self, sessions: &[PlaySession]) -> Result<()> {
+        let due = self
+            .last_save
+            .lock()
+            .await
+            .map_or(true, |t| t.elapsed() >= SAVE_INTERVAL);
+        if !due {
+            return Ok(());
+        }
+        self.save(sessions).await
+    }
+
+    async fn save(&self, sessions: &[PlaySession]) -> Result<()> {
+        *self.last_save.lock().await = Some(Instant::now());
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let json = serde_json::to_string_pretty(sessions)
+            .map_err(|e| AppError::Other(format!("Failed to serialize playtime: {}", e)))?;
+        fs::write(&self.path, json).await?;
+        Ok(())
+    }
+}
+
+/// Keeps `playtime.json` from growing without bound. Sessions are stored oldest first, running
+/// sessions are never dropped.
+pub fn drop_oldest_sessions(sessions: &mut Vec<PlaySession>) {
+    let mut excess = sessions.len().saturating_sub(MAX_PLAY_SESSIONS);
+    if excess == 0 {
+        return;
+    }
+    info!("Dropping the {} oldest playtime sessions", excess);
+    sessions.retain(|session| {
+        if excess > 0 && session.ended_at.is_some() {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+pub fn default_playtime_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(PLAYTIME_FILENAME)
+}
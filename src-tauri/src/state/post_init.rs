@@ -4,5 +4,6 @@ use std::sync::Arc;
 
 #[async_trait]
 pub trait PostInitializationHandler {
-    async fn on_state_ready(&self, app_handle: Arc<tauri::AppHandle>) -> Result<()>;
+    /// `app_handle` is `None` when the launcher runs headless (CLI mode).
+    async fn on_state_ready(&self, app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()>;
 }
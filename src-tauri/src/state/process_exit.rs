@@ -0,0 +1,309 @@
+use chrono::{DateTime, Utc};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use sysinfo::System;
+use tokio::fs;
+
+/// How a game process ended
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitClassification {
+    /// Exit code 0 or stopped through the launcher
+    Clean,
+    /// Non-zero exit code, usually with a Minecraft crash report
+    Crash,
+    /// Terminated by a signal or another program
+    Killed,
+    /// Killed by the operating system because it ran out of memory
+    OomKilled,
+    /// Killed while the OOM killer was active, but the kernel log couldn't be read to confirm
+    /// that it picked this process
+    PossiblyOomKilled,
+    /// The JVM itself crashed (hs_err_pid file or a fatal signal/NTSTATUS)
+    NativeCrash,
+    /// The process was gone before an exit status could be read (e.g. after a launcher restart)
+    Unknown,
+}
+
+/// Exit status and crash artifacts of a finished game process
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProcessExitInfo {
+    pub classification: ExitClassification,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub signal_name: Option<String>,
+    pub intentionally_stopped: bool,
+    /// `hs_err_pid<pid>.log` (and `replay_pid<pid>.log`) files written by the JVM
+    pub native_crash_logs: Vec<PathBuf>,
+    /// Minecraft crash reports written during this run
+    pub crash_reports: Vec<PathBuf>,
+    pub exited_at: DateTime<Utc>,
+}
+
+/// Everything known about an exit, input for `classify_exit`
+#[derive(Debug, Clone, Default)]
+pub struct ExitFacts {
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub intentionally_stopped: bool,
+    pub has_native_crash_log: bool,
+    /// The kernel log names this process as killed by the OOM killer
+    pub oom_killed: bool,
+    /// The OOM killer killed some process during the run, but the kernel log wasn't readable
+    pub possibly_oom_killed: bool,
+    pub has_crash_report: bool,
+}
+
+// Windows NTSTATUS exit codes of a crashed process
+const NTSTATUS_ACCESS_VIOLATION: u32 = 0xC000_0005;
+const NTSTATUS_ILLEGAL_INSTRUCTION: u32 = 0xC000_001D;
+const NTSTATUS_STACK_OVERFLOW: u32 = 0xC000_00FD;
+const NTSTATUS_STACK_BUFFER_OVERRUN: u32 = 0xC000_0409;
+
+pub fn classify_exit(facts: &ExitFacts) -> ExitClassification {
+    if facts.has_native_crash_log {
+        return ExitClassification::NativeCrash;
+    }
+    if facts.intentionally_stopped {
+        return ExitClassification::Clean;
+    }
+    if facts.oom_killed {
+        return ExitClassification::OomKilled;
+    }
+    if facts.possibly_oom_killed {
+        return ExitClassification::PossiblyOomKilled;
+    }
+
+    if let Some(signal) = facts.signal {
+        return match signal {
+            // SIGILL, SIGABRT, SIGBUS, SIGFPE, SIGSEGV
+            4 | 6 | 7 | 8 | 11 => ExitClassification::NativeCrash,
+            _ => ExitClassification::Killed,
+        };
+    }
+
+    match facts.exit_code {
+        Some(0) => ExitClassification::Clean,
+        Some(code)
+            if matches!(
+                code as u32,
+                NTSTATUS_ACCESS_VIOLATION
+                    | NTSTATUS_ILLEGAL_INSTRUCTION
+                    | NTSTATUS_STACK_OVERFLOW
+                    | NTSTATUS_STACK_BUFFER_OVERRUN
+            ) =>
+        {
+            ExitClassification::NativeCrash
+        }
+        Some(_) => ExitClassification::Crash,
+        None if facts.has_crash_report => ExitClassification::Crash,
+        None => ExitClassification::Unknown,
+    }
+}
+
+pub fn signal_name(signal: i32) -> Option<&'static str> {
+    Some(match signal {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        3 => "SIGQUIT",
+        4 => "SIGILL",
+        6 => "SIGABRT",
+        7 => "SIGBUS",
+        8 => "SIGFPE",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        15 => "SIGTERM",
+        _ => return None,
+    })
+}
+
+/// Number of processes the kernel OOM killer has killed since boot (Linux only).
+/// System-wide, so an increase during a run only means the game was possibly OOM killed.
+pub fn read_oom_kill_count() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let vmstat = std::fs::read_to_string("/proc/vmstat").ok()?;
+        vmstat
+            .lines()
+            .find_map(|line| line.strip_prefix("oom_kill "))
+            .and_then(|value| value.trim().parse().ok())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Whether the kernel log (`/dev/kmsg` records, `prio,seq,usec,flags;message`) has an OOM kill
+/// of `pid` logged at or after `since_boot_usec`. Older records belong to an earlier process
+/// that had the same PID.
+pub fn kernel_log_names_oom_kill(kernel_log: &str, pid: u32, since_boot_usec: u64) -> bool {
+    let killed_process = format!("Killed process {} (", pid);
+    let oom_kill_task = format!(",pid={},", pid);
+    kernel_log
+        .lines()
+        .filter_map(|line| {
+            let (header, message) = line.split_once(';')?;
+            let usec: u64 = header.split(',').nth(2)?.parse().ok()?;
+            Some((usec, message))
+        })
+        .any(|(usec, message)| {
+            usec >= since_boot_usec
+                && (message.contains(&killed_process)
+                    || (message.starts_with("oom-kill:") && message.contains(&oom_kill_task)))
+        })
+}
+
+/// Reads all records of the kernel log (Linux only). `None` if it isn't readable, e.g. because
+/// `kernel.dmesg_restrict` limits it to root.
+fn read_kernel_log() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::io::{ErrorKind, Read};
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open("/dev/kmsg")
+            .ok()?;
+        // Every read returns one record, WouldBlock once all of them were read
+        let mut log = String::new();
+        let mut buffer = vec![0u8; 8192];
+        loop {
+            match file.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => log.push_str(&String::from_utf8_lossy(&buffer[..read])),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                // The record was overwritten while reading, continue with the next one
+                Err(e) if e.raw_os_error() == Some(libc::EPIPE) => continue,
+                Err(e) => {
+                    debug!("Failed to read the kernel log: {}", e);
+                    return None;
+                }
+            }
+        }
+        Some(log)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Looks for the JVM fatal error logs of `pid` written after `since`. The JVM writes them into its
+/// working directory and falls back to the temp directory if that isn't writable. Older files
+/// belong to an earlier process that had the same PID.
+pub async fn find_native_crash_logs(
+    instance_dir: Option<&Path>,
+    pid: u32,
+    since: DateTime<Utc>,
+) -> Vec<PathBuf> {
+    let names = [format!("hs_err_pid{}.log", pid), format!("replay_pid{}.log", pid)];
+    let mut dirs: Vec<PathBuf> = instance_dir.map(Path::to_path_buf).into_iter().collect();
+    dirs.push(std::env::temp_dir());
+
+    let mut found = Vec::new();
+    for dir in dirs {
+        for name in &names {
+            let path = dir.join(name);
+            let Ok(metadata) = fs::metadata(&path).await else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            match metadata.modified() {
+                Ok(modified) if DateTime::<Utc>::from(modified) >= since => {
+                    debug!("Found JVM crash artifact {:?}", path);
+                    found.push(path);
+                }
+                _ => debug!("Ignoring stale JVM crash artifact {:?}", path),
+            }
+        }
+    }
+    found
+}
+
+/// Crash reports in `<instance>/crash-reports` written after `since`
+pub async fn find_crash_reports_since(instance_dir: &Path, since: DateTime<Utc>) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(mut entries) = fs::read_dir(instance_dir.join("crash-reports")).await else {
+        return found;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(modified) = entry.metadata().await.and_then(|m| m.modified()) else {
+            continue;
+        };
+        if DateTime::<Utc>::from(modified) >= since {
+            found.push(entry.path());
+        }
+    }
+    found.sort();
+    found
+}
+
+/// Collects the exit status and crash artifacts of a finished process and classifies the exit
+pub async fn collect_exit_info(
+    instance_dir: Option<&Path>,
+    pid: u32,
+    start_time: DateTime<Utc>,
+    exit_code: Option<i32>,
+    signal: Option<i32>,
+    intentionally_stopped: bool,
+    oom_kills_at_start: Option<u64>,
+) -> ProcessExitInfo {
+    let native_crash_logs = find_native_crash_logs(instance_dir, pid, start_time).await;
+    let crash_reports = match instance_dir {
+        Some(dir) => find_crash_reports_since(dir, start_time).await,
+        None => Vec::new(),
+    };
+    let (mut oom_killed, mut possibly_oom_killed) = (false, false);
+    if signal == Some(9) && !intentionally_stopped {
+        let oom_kill_happened = matches!(
+            (oom_kills_at_start, read_oom_kill_count()),
+            (Some(before), Some(after)) if after > before
+        );
+        if oom_kill_happened {
+            match tokio::task::spawn_blocking(read_kernel_log)
+                .await
+                .ok()
+                .flatten()
+            {
+                Some(kernel_log) => {
+                    let boot_time = DateTime::<Utc>::from_timestamp(System::boot_time() as i64, 0)
+                        .unwrap_or_default();
+                    let since_boot_usec = (start_time - boot_time)
+                        .num_microseconds()
+                        .unwrap_or(0)
+                        .max(0) as u64;
+                    oom_killed = kernel_log_names_oom_kill(&kernel_log, pid, since_boot_usec);
+                }
+                None => possibly_oom_killed = true,
+            }
+        }
+    }
+
+    let classification = classify_exit(&ExitFacts {
+        exit_code,
+        signal,
+        intentionally_stopped,
+        has_native_crash_log: !native_crash_logs.is_empty(),
+        oom_killed,
+        possibly_oom_killed,
+        has_crash_report: !crash_reports.is_empty(),
+    });
+
+    ProcessExitInfo {
+        classification,
+        exit_code,
+        signal,
+        signal_name: signal.and_then(signal_name).map(str::to_string),
+        intentionally_stopped,
+        native_crash_logs,
+        crash_reports,
+        exited_at: Utc::now(),
+    }
+}
@@ -0,0 +1,236 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::process_exit::ExitClassification;
+use crate::state::process_state::ProcessMetadata;
+use crate::utils::json_cache;
+use chrono::{DateTime, Utc};
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, System};
+use tokio::fs;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const SESSION_HISTORY_FILENAME: &str = "session_history.json";
+/// Sessions kept in the history file, oldest are dropped first
+const MAX_SESSION_HISTORY: usize = 500;
+/// Samples kept per process. With the 5 second interval that's the last hour.
+pub const MAX_RESOURCE_SAMPLES: usize = 720;
+/// Memory a JVM uses next to its heap (metaspace, code cache, thread stacks, native memory),
+/// as a share of -Xmx with a lower bound
+const NON_HEAP_ALLOWANCE_PERCENT: u64 = 50;
+const MIN_NON_HEAP_ALLOWANCE_MB: u64 = 1024;
+
+static SESSION_HISTORY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Resource usage of a game process and its children at one point in time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResourceSample {
+    pub timestamp: DateTime<Utc>,
+    /// Summed over all processes, 100 means one fully used core
+    pub cpu_percent: f32,
+    /// Resident set size in bytes
+    pub memory_bytes: u64,
+    /// Only available on Linux
+    pub thread_count: Option<u32>,
+    /// The game process plus wrapper children (e.g. when launched through a wrapper command)
+    pub process_count: u32,
+}
+
+/// Rolling window of samples plus totals over the whole run
+#[derive(Debug, Default)]
+pub struct ResourceHistory {
+    samples: VecDeque<ResourceSample>,
+    peak_memory_bytes: u64,
+    cpu_total: f64,
+    sample_count: u64,
+}
+
+impl ResourceHistory {
+    pub fn push(&mut self, sample: ResourceSample) {
+        self.peak_memory_bytes = self.peak_memory_bytes.max(sample.memory_bytes);
+        self.cpu_total += sample.cpu_percent as f64;
+        self.sample_count += 1;
+        if self.samples.len() >= MAX_RESOURCE_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn samples(&self) -> Vec<ResourceSample> {
+        self.samples.iter().cloned().collect()
+    }
+
+    pub fn peak_memory_bytes(&self) -> Option<u64> {
+        (self.sample_count > 0).then_some(self.peak_memory_bytes)
+    }
+
+    pub fn average_cpu_percent(&self) -> Option<f32> {
+        (self.sample_count > 0).then(|| (self.cpu_total / self.sample_count as f64) as f32)
+    }
+}
+
+/// `root` followed by all of its descendants, e.g. the game started through a wrapper command.
+/// Empty if `root` isn't running.
+pub fn process_tree_pids(sys: &System, root: Pid) -> Vec<Pid> {
+    if sys.process(root).is_none() {
+        return Vec::new();
+    }
+
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for (pid, process) in sys.processes() {
+        // On Linux threads show up as processes as well
+        if process.thread_kind().is_some() {
+            continue;
+        }
+        if let Some(parent) = process.parent() {
+            children.entry(parent).or_default().push(*pid);
+        }
+    }
+
+    let mut tree = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = vec![root];
+    while let Some(pid) = queue.pop() {
+        if !visited.insert(pid) {
+            continue;
+        }
+        tree.push(pid);
+        if let Some(pids) = children.get(&pid) {
+            queue.extend(pids);
+        }
+    }
+    tree
+}
+
+/// Sums CPU, memory and threads of `root` and all of its descendants.
+/// `sys` has to be refreshed with cpu, memory and tasks beforehand.
+pub fn sample_process_tree(sys: &System, root: Pid) -> Option<ResourceSample> {
+    let tree = process_tree_pids(sys, root);
+    if tree.is_empty() {
+        return None;
+    }
+
+    let mut sample = ResourceSample {
+        timestamp: Utc::now(),
+        cpu_percent: 0.0,
+        memory_bytes: 0,
+        thread_count: None,
+        process_count: 0,
+    };
+    for process in tree.iter().filter_map(|pid| sys.process(*pid)) {
+        sample.cpu_percent += process.cpu_usage();
+        sample.memory_bytes += process.memory();
+        sample.process_count += 1;
+        if let Some(tasks) = process.tasks() {
+            *sample.thread_count.get_or_insert(0) += tasks.len() as u32;
+        }
+    }
+    Some(sample)
+}
+
+/// Summary of one game session, stored in the session history
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SessionRecord {
+    pub process_id: Uuid,
+    pub profile_id: Uuid,
+    pub profile_name: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub modloader: Option<String>,
+    pub modloader_version: Option<String>,
+    pub norisk_pack: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub duration_secs: u64,
+    pub peak_memory_bytes: Option<u64>,
+    pub average_cpu_percent: Option<f32>,
+    /// The -Xmx the game was started with
+    pub memory_max_mb: Option<u32>,
+    /// Peak RSS went above `memory_limit_bytes(memory_max_mb)`, so the pack needs more
+    /// memory than its heap limit suggests
+    pub exceeded_memory_max: bool,
+    pub exit_classification: Option<ExitClassification>,
+}
+
+/// RSS a process started with `-Xmx<memory_max_mb>M` can reach before it counts as using
+/// more memory than configured. RSS covers more than the heap, so the expected non-heap
+/// usage is added on top of -Xmx.
+pub fn memory_limit_bytes(memory_max_mb: u32) -> u64 {
+    let heap_mb = memory_max_mb as u64;
+    let allowance_mb = (heap_mb * NON_HEAP_ALLOWANCE_PERCENT / 100).max(MIN_NON_HEAP_ALLOWANCE_MB);
+    (heap_mb + allowance_mb) * 1024 * 1024
+}
+
+pub fn build_session_record(
+    metadata: &ProcessMetadata,
+    history: Option<&ResourceHistory>,
+    ended_at: DateTime<Utc>,
+) -> SessionRecord {
+    let peak_memory_bytes = history.and_then(ResourceHistory::peak_memory_bytes);
+    let exceeded_memory_max = match (peak_memory_bytes, metadata.memory_max_mb) {
+        (Some(peak), Some(max_mb)) => peak > memory_limit_bytes(max_mb),
+        _ => false,
+    };
+
+    SessionRecord {
+        process_id: metadata.id,
+        profile_id: metadata.profile_id,
+        profile_name: metadata.profile_name.clone(),
+        minecraft_version: metadata.minecraft_version.clone(),
+        modloader: metadata.modloader.clone(),
+        modloader_version: metadata.modloader_version.clone(),
+        norisk_pack: metadata.norisk_pack.clone(),
+        started_at: metadata.start_time,
+        ended_at,
+        duration_secs: (ended_at - metadata.start_time).num_seconds().max(0) as u64,
+        peak_memory_bytes,
+        average_cpu_percent: history.and_then(ResourceHistory::average_cpu_percent),
+        memory_max_mb: metadata.memory_max_mb,
+        exceeded_memory_max,
+        exit_classification: metadata.exit_info.as_ref().map(|i| i.classification),
+    }
+}
+
+pub fn default_session_history_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(SESSION_HISTORY_FILENAME)
+}
+
+/// Loads the session history, oldest first. A missing file is an empty history.
+pub async fn load_session_history(path: &Path) -> Result<Vec<SessionRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Appends a session to the history file, dropping the oldest entries above the limit
+pub async fn append_session(path: &Path, record: SessionRecord) -> Result<()> {
+    let _guard = SESSION_HISTORY_LOCK.lock().await;
+
+    let mut sessions = match load_session_history(path).await {
+        Ok(sessions) => sessions,
+        Err(e) => {
+            // A broken history shouldn't stop new sessions from being recorded
+            json_cache::back_up_unreadable(path, e).await?;
+            Vec::new()
+        }
+    };
+    sessions.push(record);
+    if sessions.len() > MAX_SESSION_HISTORY {
+        let excess = sessions.len() - MAX_SESSION_HISTORY;
+        sessions.drain(..excess);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let json = serde_json::to_string_pretty(&sessions)
+        .map_err(|e| AppError::Other(format!("Failed to serialize session history: {}", e)))?;
+    fs::write(path, json).await?;
+    info!("Recorded session in {:?} ({} sessions)", path, sessions.len());
+    Ok(())
+}
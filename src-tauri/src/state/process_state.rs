@@ -1,9 +1,15 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::minecraft::crash_analyzer;
 use crate::state::event_state::{
     EventPayload, EventState, EventType, MinecraftProcessExitedPayload,
+    ProcessResourceSamplePayload, ProcessStopStage, ProcessStopStagePayload,
 };
+use crate::state::playtime_tracker::{self, PlayTarget, PlaytimeTracker};
+use crate::state::process_exit::{self, ExitClassification, ProcessExitInfo};
+use crate::state::process_metrics::{self, ResourceHistory, ResourceSample, SessionRecord};
 use crate::state::{self, post_init::PostInitializationHandler, State};
+use crate::utils::hook_utils::HookContext;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
@@ -14,7 +20,7 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::ExitStatus;
 use std::sync::Arc;
-use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, Signal, System};
 use tauri::Manager;
 use tokio::fs::{self as async_fs, File};
 use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
@@ -33,9 +39,65 @@ use tokio::sync::mpsc; // Für den Channel
 
 const PROCESSES_FILENAME: &str = "processes.json";
 const NOTIFY_EVENT_CHANNEL_BUFFER: usize = 100;
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Extra time a game gets once it logged "Stopping!" during a graceful stop
+pub const SHUTDOWN_SAVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// True for the line a game logs when it starts shutting down (and saving its world)
+pub fn is_shutdown_log_line(line: &str) -> bool {
+    line.ends_with("]: Stopping!")
+}
+
+/// The stage to report once the process tree was killed
+pub fn stage_after_kill(killed: bool) -> ProcessStopStage {
+    if killed {
+        ProcessStopStage::Stopped
+    } else {
+        ProcessStopStage::Failed
+    }
+}
+
+/// Decides when a graceful stop escalates. Fed once per poll with what was observed.
+#[derive(Debug, Clone)]
+pub struct StopEscalation {
+    deadline: std::time::Instant,
+    shutdown_seen: bool,
+}
+
+impl StopEscalation {
+    pub fn new(started: std::time::Instant, grace_period: Duration) -> Self {
+        Self {
+            deadline: started + grace_period,
+            shutdown_seen: false,
+        }
+    }
+
+    /// Returns the stage to report for this poll, if any. `Stopped` and `ForceKilling` end the stop.
+    pub fn poll(
+        &mut self,
+        now: std::time::Instant,
+        running: bool,
+        shutdown_logged: bool,
+    ) -> Option<ProcessStopStage> {
+        if !running {
+            return Some(ProcessStopStage::Stopped);
+        }
+        if !self.shutdown_seen && shutdown_logged {
+            self.shutdown_seen = true;
+            // Saving a big world can take longer than the grace period
+            self.deadline = self.deadline.max(now + SHUTDOWN_SAVE_TIMEOUT);
+            return Some(ProcessStopStage::ShuttingDown);
+        }
+        if now >= self.deadline {
+            return Some(ProcessStopStage::ForceKilling);
+        }
+        None
+    }
+}
 
 pub struct ProcessManager {
-    app_handle: Arc<tauri::AppHandle>,
+    app_handle: Option<Arc<tauri::AppHandle>>, // None in headless (CLI) mode
     processes: Arc<RwLock<HashMap<Uuid, Process>>>,
     processes_file_path: PathBuf,
     save_lock: Mutex<()>,
@@ -44,6 +106,10 @@ pub struct ProcessManager {
     notify_event_tx: mpsc::Sender<CrashReportNotification>,
     active_watchers: Arc<RwLock<HashMap<Uuid, RecommendedWatcher>>>,
     crash_report_contents: Arc<DashMap<Uuid, String>>,
+    resource_history: Arc<DashMap<Uuid, ResourceHistory>>,
+    playtime: Arc<PlaytimeTracker>,
+    /// Processes that logged "Stopping!", watched by a graceful stop
+    shutdown_logged: Arc<DashMap<Uuid, DateTime<Utc>>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,7 +126,47 @@ pub struct ProcessMetadata {
     pub modloader_version: Option<String>,
     pub norisk_pack: Option<String>,
     pub profile_name: Option<String>,
-    pub post_exit_hook: Option<String>,
+    /// Post-exit hooks in execution order, resolved at launch time.
+    /// Processes saved by older versions have a single `post_exit_hook`.
+    #[serde(
+        default,
+        alias = "post_exit_hook",
+        deserialize_with = "deserialize_post_exit_hooks"
+    )]
+    pub post_exit_hooks: Vec<String>,
+    /// Variables the hooks get, see `hook_utils`
+    #[serde(default)]
+    pub hook_context: Option<HookContext>,
+    /// The -Xmx the game was started with
+    #[serde(default)]
+    pub memory_max_mb: Option<u32>,
+    /// Set once the process has exited
+    #[serde(default)]
+    pub exit_info: Option<ProcessExitInfo>,
+}
+
+/// Accepts the hook list as well as the legacy `Option<String>`
+fn deserialize_post_exit_hooks<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PostExitHooks {
+        List(Vec<String>),
+        Legacy(Option<String>),
+    }
+
+    Ok(match PostExitHooks::deserialize(deserializer)? {
+        PostExitHooks::List(hooks) => hooks,
+        PostExitHooks::Legacy(hook) => hook
+            .map(|hook| hook.trim().to_string())
+            .filter(|hook| !hook.is_empty())
+            .into_iter()
+            .collect(),
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -76,6 +182,11 @@ pub enum ProcessState {
 struct Process {
     metadata: ProcessMetadata,
     last_log_position: Arc<Mutex<u64>>,
+    /// OOM killer count when the process was started, see `process_exit::read_oom_kill_count`
+    oom_kills_at_start: Option<u64>,
+    /// False for processes loaded from processes.json after a launcher restart. Nothing waits on
+    /// those, so `periodic_process_check` reports their exit instead of the monitor task.
+    monitored: bool,
 }
 
 // Kapselt die Nachricht, die vom notify event handler zum ProcessManager geschickt wird
@@ -88,7 +199,7 @@ struct CrashReportNotification {
 impl ProcessManager {
     pub async fn new(
         processes_file_path: PathBuf,
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
     ) -> Result<Self> {
         log::info!(
             "Initializing ProcessManager with state file: {:?}",
@@ -99,6 +210,10 @@ impl ProcessManager {
         let launching_processes = Arc::new(DashMap::new());
         let active_watchers = Arc::new(RwLock::new(HashMap::new()));
         let crash_report_contents = Arc::new(DashMap::new());
+        let resource_history = Arc::new(DashMap::new());
+        let playtime = Arc::new(PlaytimeTracker::new(
+            playtime_tracker::default_playtime_path(),
+        ));
 
         // Create the channel. The receiver part (rx) will be handled/stored or recreated
         // appropriately when its consuming task is spawned in on_state_ready.
@@ -106,7 +221,7 @@ impl ProcessManager {
             mpsc::channel::<CrashReportNotification>(NOTIFY_EVENT_CHANNEL_BUFFER);
 
         Ok(Self {
-            app_handle: Arc::clone(&app_handle),
+            app_handle,
             processes,
             processes_file_path,
             save_lock,
@@ -114,12 +229,15 @@ impl ProcessManager {
             notify_event_tx, // Store the sender
             active_watchers,
             crash_report_contents,
+            resource_history,
+            playtime,
+            shutdown_logged: Arc::new(DashMap::new()),
             // notify_event_rx: Mutex::new(Some(notify_event_rx_placeholder)), // Example of how to store rx
         })
     }
 
     async fn process_crash_report_events(
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
         mut receiver: mpsc::Receiver<CrashReportNotification>,
     ) {
         log::info!("Starting crash report event processor task.");
@@ -272,6 +390,8 @@ impl ProcessManager {
                         let process_entry = Process {
                             metadata: metadata.clone(), // metadata hier klonen
                             last_log_position: Arc::new(Mutex::new(0)),
+                            oom_kills_at_start: None,
+                            monitored: false,
                         };
                         processes_map_writer.insert(process_entry.metadata.id, process_entry);
                         log::debug!(
@@ -520,7 +640,10 @@ impl ProcessManager {
         modloader_version: Option<String>,
         norisk_pack: Option<String>,
         profile_name: Option<String>,
-        post_exit_hook: Option<String>,
+        post_exit_hooks: Vec<String>,
+        hook_context: Option<HookContext>,
+        memory_max_mb: Option<u32>,
+        quick_play: Option<PlayTarget>,
     ) -> Result<Uuid> {
         log::info!("Attempting to start process for profile {}", profile_id);
 
@@ -570,7 +693,10 @@ impl ProcessManager {
             modloader_version,
             norisk_pack,
             profile_name: profile_name.clone(),
-            post_exit_hook,
+            post_exit_hooks,
+            hook_context,
+            memory_max_mb,
+            exit_info: None,
         };
 
         log::info!(
@@ -582,12 +708,15 @@ impl ProcessManager {
         let process_entry = Process {
             metadata: metadata.clone(),
             last_log_position: Arc::new(Mutex::new(0)),
+            oom_kills_at_start: process_exit::read_oom_kill_count(),
+            monitored: true,
         };
 
         {
             let mut processes_map = self.processes.write().await;
             processes_map.insert(process_id, process_entry);
         }
+        self.playtime.start_session(&metadata, quick_play).await;
 
         // Watcher für Crash-Reports starten
         // Hier brauchen wir den globalen State für den ProfileManager
@@ -655,7 +784,11 @@ impl ProcessManager {
             let launcher_config = global_state.config_manager.get_config().await;
             if launcher_config.hide_on_process_start {
                 log::info!("Hiding main window as configured (hide_on_process_start = true)");
-                if let Some(main_window) = self.app_handle.get_webview_window("main") {
+                if let Some(main_window) = self
+                    .app_handle
+                    .as_ref()
+                    .and_then(|app| app.get_webview_window("main"))
+                {
                     if let Err(e) = main_window.hide() {
                         log::error!("Failed to hide main window: {}", e);
                     } else {
@@ -688,7 +821,7 @@ impl ProcessManager {
         // Alternative: Der Monitor-Task entfernt nur aus processes, und periodic_process_check räumt Watcher auf.
         // ODER: Der Monitor Task sendet eine "ProcessEnded" Nachricht, auf die der PM reagiert.
 
-        let app_handle_clone_for_monitor = Arc::clone(&self.app_handle);
+        let app_handle_clone_for_monitor = self.app_handle.clone();
 
         tokio::spawn(async move {
             // State holen, um Zugriff auf den ProcessManager für das Stoppen des Watchers zu haben.
@@ -726,6 +859,13 @@ impl ProcessManager {
             };
 
             let exit_code: Option<i32> = exit_status.and_then(|s| s.code());
+            #[cfg(unix)]
+            let exit_signal: Option<i32> = {
+                use std::os::unix::process::ExitStatusExt;
+                exit_status.and_then(|s| s.signal())
+            };
+            #[cfg(not(unix))]
+            let exit_signal: Option<i32> = None;
             let mut success: bool = exit_code == Some(0);
 
             let was_intentionally_stopped = {
@@ -745,6 +885,62 @@ impl ProcessManager {
                 success = true;
             }
 
+            let instance_path: Option<PathBuf> = match &state_for_monitor_res {
+                Ok(state) => state
+                    .profile_manager
+                    .get_profile_instance_path(profile_id)
+                    .await
+                    .map_err(|e| {
+                        log::warn!(
+                            "No instance path for process {} to look for crash artifacts: {}",
+                            process_id,
+                            e
+                        )
+                    })
+                    .ok(),
+                Err(_) => None,
+            };
+
+            // Classify the exit and pick up hs_err_pid logs / crash reports of this run
+            let exit_info = {
+                let (start_time, oom_kills_at_start) = {
+                    let processes_map = processes_arc_clone.read().await;
+                    processes_map
+                        .get(&process_id)
+                        .map(|p| (p.metadata.start_time, p.oom_kills_at_start))
+                        .unwrap_or((Utc::now(), None))
+                };
+                process_exit::collect_exit_info(
+                    instance_path.as_deref(),
+                    pid,
+                    start_time,
+                    exit_code,
+                    exit_signal,
+                    was_intentionally_stopped,
+                    oom_kills_at_start,
+                )
+                .await
+            };
+            log::info!(
+                "Process {} exit classified as {:?} (code: {:?}, signal: {:?}, native crash logs: {:?})",
+                process_id,
+                exit_info.classification,
+                exit_code,
+                exit_info.signal_name,
+                exit_info.native_crash_logs
+            );
+            if matches!(
+                exit_info.classification,
+                ExitClassification::NativeCrash
+                    | ExitClassification::OomKilled
+                    | ExitClassification::PossiblyOomKilled
+            ) {
+                success = false;
+            }
+            if let Some(process_entry) = processes_arc_clone.write().await.get_mut(&process_id) {
+                process_entry.metadata.exit_info = Some(exit_info.clone());
+            }
+
             let exiting_process_metadata_clone: Option<ProcessMetadata> = {
                 let processes_map_reader = processes_arc_clone.read().await;
                 processes_map_reader
@@ -766,6 +962,24 @@ impl ProcessManager {
                 }
             };
 
+            // Match the crash report and the end of latest.log against the known crash signatures
+            let mut crash_diagnoses = Vec::new();
+            if !success {
+                if let Some(instance_path) = &instance_path {
+                    crash_diagnoses = crash_analyzer::analyze_instance_crash(
+                        instance_path,
+                        crash_content_for_payload.as_deref(),
+                    )
+                    .await;
+                }
+            }
+
+            if let (Ok(state), Some(metadata)) =
+                (&state_for_monitor_res, &exiting_process_metadata_clone)
+            {
+                state.process_manager.record_session(metadata).await;
+            }
+
             // Event an UI senden
             if let Ok(state) = &state_for_monitor_res {
                 // Re-access state for this block, or ensure it's still valid
@@ -776,6 +990,8 @@ impl ProcessManager {
                     success,
                     process_metadata: exiting_process_metadata_clone,
                     crash_report_content: crash_content_for_payload,
+                    crash_diagnoses,
+                    exit_info: Some(exit_info.clone()),
                 };
                 let specific_payload_json = serde_json::to_string(&specific_payload)
                     .unwrap_or_else(|e| {
@@ -799,8 +1015,9 @@ impl ProcessManager {
                         None
                     } else {
                         Some(format!(
-                            "Process exited with code {:?}. Intentionally stopped: {}",
+                            "Process exited with code {:?} ({:?}). Intentionally stopped: {}",
                             exit_code.unwrap_or(-1),
+                            exit_info.classification,
                             was_intentionally_stopped
                         ))
                     },
@@ -868,69 +1085,302 @@ impl ProcessManager {
         Ok(process_id)
     }
 
-    pub async fn stop_process(&self, process_id: Uuid) -> Result<()> {
-        log::info!("Attempting to stop process {}", process_id);
-
-        let mut kill_successful = false;
-        let mut pid_for_error: u32 = 0;
+    /// Stops a game in stages so it can save: SIGTERM on Unix or a window close request on
+    /// Windows, then `stop_grace_period_secs` to exit on its own (longer once it logged
+    /// "Stopping!"), then the whole process tree including wrapper children is killed.
+    /// Returns once the first stage is done, the rest is reported via `ProcessStopStage` events.
+    /// `force` skips straight to killing.
+    pub async fn stop_process(&self, process_id: Uuid, force: bool) -> Result<()> {
+        log::info!("Attempting to stop process {} (force: {})", process_id, force);
 
-        let mut processes_map = self.processes.write().await;
-
-        if let Some(process) = processes_map.get_mut(&process_id) {
-            pid_for_error = process.metadata.pid;
+        let (pid, profile_id) = {
+            let mut processes_map = self.processes.write().await;
+            let Some(process) = processes_map.get_mut(&process_id) else {
+                log::warn!("Process {} not found in manager for stopping.", process_id);
+                return Err(AppError::ProcessNotFound(process_id));
+            };
             process.metadata.state = ProcessState::Stopping;
+            (process.metadata.pid, process.metadata.profile_id)
+        };
+        self.shutdown_logged.remove(&process_id);
 
-            let pid_to_kill = process.metadata.pid;
-            log::info!(
-                "Attempting to kill process {} via PID {}",
+        if let Err(e) = self.save_processes().await {
+            log::error!(
+                "Failed to save processes state after initiating stop for {}: {}",
                 process_id,
-                pid_to_kill
+                e
             );
+        }
+
+        let grace_period_secs = if force {
+            0
+        } else {
+            match State::get().await {
+                Ok(state) => state.config_manager.get_config().await.stop_grace_period_secs,
+                Err(_) => 0,
+            }
+        };
+
+        if grace_period_secs > 0 && Self::request_graceful_stop(pid) {
+            Self::emit_stop_stage(
+                process_id,
+                profile_id,
+                ProcessStopStage::GracefulRequested,
+                grace_period_secs,
+                None,
+            )
+            .await;
+            tokio::spawn(Self::escalate_stop(
+                Arc::clone(&self.shutdown_logged),
+                process_id,
+                profile_id,
+                pid,
+                grace_period_secs,
+            ));
+            return Ok(());
+        }
+
+        // Forced, no grace period configured or the graceful request failed
+        Self::emit_stop_stage(
+            process_id,
+            profile_id,
+            ProcessStopStage::ForceKilling,
+            grace_period_secs,
+            None,
+        )
+        .await;
+        let killed = Self::kill_process_tree(pid);
+
+        // Da der Monitor-Task den Watcher beim regulären Exit stoppt, ist es hier nicht zwingend
+        // nötig, aber falls der Monitor nicht schnell genug ist:
+        self.stop_crash_report_watcher(process_id).await;
+
+        Self::emit_kill_result(process_id, profile_id, pid, killed, grace_period_secs).await;
+        if killed {
+            Ok(())
+        } else {
+            Err(AppError::ProcessKillFailed(pid))
+        }
+    }
+
+    /// Asks the game to exit: SIGTERM to the process tree on Unix, a close request to its
+    /// windows on Windows. Returns false if that wasn't possible.
+    fn request_graceful_stop(pid: u32) -> bool {
+        #[cfg(unix)]
+        {
             let mut sys = System::new();
-            let pid_to_refresh = Pid::from(pid_to_kill as usize);
-            sys.refresh_processes(ProcessesToUpdate::Some(&[pid_to_refresh]), false);
+            sys.refresh_processes_specifics(
+                ProcessesToUpdate::All,
+                true,
+                ProcessRefreshKind::nothing(),
+            );
+            let tree = process_metrics::process_tree_pids(&sys, Pid::from(pid as usize));
+            let mut signalled = false;
+            // Children first, a wrapper might not forward the signal
+            for tree_pid in tree.iter().rev() {
+                if let Some(process) = sys.process(*tree_pid) {
+                    let sent = process.kill_with(Signal::Term).unwrap_or(false);
+                    log::debug!("SIGTERM to PID {}: {}", tree_pid, sent);
+                    signalled |= sent;
+                }
+            }
+            signalled
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            // Without /F taskkill sends WM_CLOSE, the same as closing the window
+            match std::process::Command::new("taskkill")
+                .args(["/PID", &pid.to_string(), "/T"])
+                .creation_flags(CREATE_NO_WINDOW)
+                .status()
+            {
+                Ok(status) => status.success(),
+                Err(e) => {
+                    log::warn!("Failed to run taskkill for PID {}: {}", pid, e);
+                    false
+                }
+            }
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = pid;
+            false
+        }
+    }
+
+    /// Kills `pid` and all of its descendants. True if the root process is gone.
+    fn kill_process_tree(pid: u32) -> bool {
+        let root = Pid::from(pid as usize);
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+        let tree = process_metrics::process_tree_pids(&sys, root);
+        if tree.is_empty() {
+            log::warn!(
+                "Process with PID {} not found by sysinfo during stop attempt. Assuming already stopped.",
+                pid
+            );
+            return true;
+        }
 
-            if let Some(sys_process) = sys.process(pid_to_refresh) {
-                if sys_process.kill() {
-                    log::info!("Kill signal sent successfully to PID {}.", pid_to_kill);
-                    kill_successful = true;
+        let mut root_killed = false;
+        for tree_pid in tree.iter().rev() {
+            if let Some(process) = sys.process(*tree_pid) {
+                let killed = process.kill();
+                if *tree_pid == root {
+                    root_killed = killed;
+                }
+                if killed {
+                    log::info!("Kill signal sent successfully to PID {}.", tree_pid);
                 } else {
-                    log::error!("Failed to send kill signal to PID {}.", pid_to_kill);
+                    log::error!("Failed to send kill signal to PID {}.", tree_pid);
                 }
-            } else {
-                log::warn!(
-                    "Process with PID {} not found by sysinfo during stop attempt. Assuming already stopped.",
-                    pid_to_kill
-                );
-                kill_successful = true;
             }
-        } else {
-            drop(processes_map);
-            log::warn!("Process {} not found in manager for stopping.", process_id);
-            return Err(AppError::ProcessNotFound(process_id));
         }
+        root_killed
+    }
 
-        drop(processes_map);
+    /// Waits for the game to exit after a graceful stop request and kills it once the grace
+    /// period is over
+    async fn escalate_stop(
+        shutdown_logged: Arc<DashMap<Uuid, DateTime<Utc>>>,
+        process_id: Uuid,
+        profile_id: Uuid,
+        pid: u32,
+        grace_period_secs: u64,
+    ) {
+        let root = Pid::from(pid as usize);
+        let mut escalation = StopEscalation::new(
+            std::time::Instant::now(),
+            Duration::from_secs(grace_period_secs),
+        );
 
-        // Watcher stoppen, bevor der Prozess tatsächlich beendet wird, oder nachdem der Kill-Befehl gesendet wurde.
-        // Da der Monitor-Task den Watcher beim regulären Exit stoppt, ist es hier vielleicht nicht
-        // zwingend nötig, es sei denn, der Monitor-Task würde nicht korrekt laufen.
-        // Aber für Konsistenz und falls der Kill erfolgreich ist und der Monitor nicht schnell genug ist:
-        self.stop_crash_report_watcher(process_id).await;
+        loop {
+            tokio::time::sleep(STOP_POLL_INTERVAL).await;
 
-        if let Err(e) = self.save_processes().await {
-            log::error!(
-                "Failed to save processes state after initiating stop for {}: {}",
-                process_id,
-                e
+            let mut sys = System::new();
+            sys.refresh_processes(ProcessesToUpdate::Some(&[root]), true);
+            // Until the monitor task reaps it, an exited child is still listed as a zombie
+            let running = sys
+                .process(root)
+                .map_or(false, |p| p.status() != ProcessStatus::Zombie);
+            let stage = escalation.poll(
+                std::time::Instant::now(),
+                running,
+                shutdown_logged.contains_key(&process_id),
             );
+
+            match stage {
+                Some(ProcessStopStage::Stopped) => {
+                    log::info!(
+                        "Process {} exited after the graceful stop request.",
+                        process_id
+                    );
+                    shutdown_logged.remove(&process_id);
+                    Self::emit_stop_stage(
+                        process_id,
+                        profile_id,
+                        ProcessStopStage::Stopped,
+                        grace_period_secs,
+                        None,
+                    )
+                    .await;
+                    return;
+                }
+                Some(ProcessStopStage::ShuttingDown) => {
+                    log::info!(
+                        "Process {} logged its shutdown, waiting for it to finish.",
+                        process_id
+                    );
+                    Self::emit_stop_stage(
+                        process_id,
+                        profile_id,
+                        ProcessStopStage::ShuttingDown,
+                        grace_period_secs,
+                        None,
+                    )
+                    .await;
+                }
+                Some(_) => break,
+                None => {}
+            }
         }
 
-        if kill_successful {
-            Ok(())
-        } else {
-            Err(AppError::ProcessKillFailed(pid_for_error))
+        shutdown_logged.remove(&process_id);
+        log::warn!(
+            "Process {} (PID: {}) did not exit within the grace period, killing it.",
+            process_id,
+            pid
+        );
+        Self::emit_stop_stage(
+            process_id,
+            profile_id,
+            ProcessStopStage::ForceKilling,
+            grace_period_secs,
+            None,
+        )
+        .await;
+        let killed = Self::kill_process_tree(pid);
+        Self::emit_kill_result(process_id, profile_id, pid, killed, grace_period_secs).await;
+    }
+
+    /// Reports the end of a stop after the process tree was killed: `Stopped` or `Failed`
+    async fn emit_kill_result(
+        process_id: Uuid,
+        profile_id: Uuid,
+        pid: u32,
+        killed: bool,
+        grace_period_secs: u64,
+    ) {
+        let stage = stage_after_kill(killed);
+        let message =
+            (stage == ProcessStopStage::Failed).then(|| format!("Failed to kill PID {}", pid));
+        Self::emit_stop_stage(process_id, profile_id, stage, grace_period_secs, message).await;
+    }
+
+    async fn emit_stop_stage(
+        process_id: Uuid,
+        profile_id: Uuid,
+        stage: ProcessStopStage,
+        grace_period_secs: u64,
+        message: Option<String>,
+    ) {
+        let Ok(state) = State::get().await else {
+            return;
+        };
+        let payload = ProcessStopStagePayload {
+            process_id,
+            profile_id,
+            stage,
+            grace_period_secs,
+            message: message.clone(),
+        };
+        let event = EventPayload {
+            event_id: Uuid::new_v4(),
+            event_type: EventType::ProcessStopStage,
+            target_id: Some(process_id),
+            message: serde_json::to_string(&payload).unwrap_or_default(),
+            progress: None,
+            error: if stage == ProcessStopStage::Failed {
+                message
+            } else {
+                None
+            },
+        };
+        if let Err(e) = state.event_state.emit(event).await {
+            log::error!("Failed to emit stop stage {:?} for process {}: {}", stage, process_id, e);
+        }
+    }
+
+    /// Handles a new line of a game's latest.log
+    async fn observe_log_line(&self, process_id: Uuid, line: &str, instance_path: &Path) {
+        if is_shutdown_log_line(line) {
+            self.shutdown_logged.insert(process_id, Utc::now());
         }
+        self.playtime
+            .observe_log_line(process_id, line, instance_path)
+            .await;
     }
 
     pub async fn get_process_metadata(&self, process_id: Uuid) -> Option<ProcessMetadata> {
@@ -958,7 +1408,7 @@ impl ProcessManager {
     }
 
     async fn periodic_process_check(
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
         processes_arc: Arc<RwLock<HashMap<Uuid, Process>>>,
         active_watchers_arc: Arc<RwLock<HashMap<Uuid, RecommendedWatcher>>>,
         notify_tx: mpsc::Sender<CrashReportNotification>,
@@ -992,6 +1442,7 @@ impl ProcessManager {
             let global_state = global_state_res.unwrap();
 
             let mut dead_process_ids_from_map: Vec<Uuid> = Vec::new();
+            let mut running_process_ids: Vec<Uuid> = Vec::new();
             if !pids_to_check_in_map.is_empty() {
                 let mut sys = System::new();
                 let pids_to_refresh: Vec<Pid> = pids_to_check_in_map
@@ -1006,6 +1457,7 @@ impl ProcessManager {
                         log::warn!("Periodic check found managed process {} (PID: {}) no longer running. Marking for removal.", id, pid);
                         dead_process_ids_from_map.push(id);
                     } else {
+                        running_process_ids.push(id);
                         // Prozess läuft noch, stelle sicher, dass ein Watcher existiert, falls er aus irgendeinem Grund fehlt
                         let watchers_map_reader = active_watchers_arc.read().await;
                         let has_watcher = watchers_map_reader.contains_key(&id);
@@ -1044,6 +1496,12 @@ impl ProcessManager {
                 }
             }
 
+            global_state
+                .process_manager
+                .playtime
+                .heartbeat(&running_process_ids)
+                .await;
+
             if !dead_process_ids_from_map.is_empty() {
                 log::warn!(
                     "Periodic check removing {} stale process entries from map: {:?}",
@@ -1051,10 +1509,17 @@ impl ProcessManager {
                     dead_process_ids_from_map
                 );
                 let mut processes_map_writer = processes_arc.write().await;
-                for id in &dead_process_ids_from_map {
-                    processes_map_writer.remove(id);
-                }
+                let removed_processes: Vec<Process> = dead_process_ids_from_map
+                    .iter()
+                    .filter_map(|id| processes_map_writer.remove(id))
+                    .collect();
                 drop(processes_map_writer);
+
+                // Without a monitor task nobody reported these exits yet. The exit status is lost,
+                // but hs_err_pid logs and crash reports still tell what happened.
+                for process in removed_processes.into_iter().filter(|p| !p.monitored) {
+                    Self::report_unmonitored_exit(&global_state, process.metadata).await;
+                }
                 // Speichere Änderungen an der Prozessliste
                 if let Err(e) = global_state.process_manager.save_processes().await {
                     log::error!(
@@ -1094,6 +1559,179 @@ impl ProcessManager {
         }
     }
 
+    /// Emits the exit event for a process that ended while no monitor task was waiting on it
+    async fn report_unmonitored_exit(state: &State, mut metadata: ProcessMetadata) {
+        let instance_path = state
+            .profile_manager
+            .get_profile_instance_path(metadata.profile_id)
+            .await
+            .ok();
+        let exit_info = process_exit::collect_exit_info(
+            instance_path.as_deref(),
+            metadata.pid,
+            metadata.start_time,
+            None,
+            None,
+            metadata.state == ProcessState::Stopping,
+            None,
+        )
+        .await;
+        let success = matches!(
+            exit_info.classification,
+            ExitClassification::Clean | ExitClassification::Unknown
+        );
+        log::info!(
+            "Unmonitored process {} exit classified as {:?}",
+            metadata.id,
+            exit_info.classification
+        );
+
+        let crash_report_content = match exit_info.crash_reports.last() {
+            Some(path) => async_fs::read_to_string(path).await.ok(),
+            None => None,
+        };
+        let crash_diagnoses = match (&instance_path, success) {
+            (Some(instance_path), false) => {
+                crash_analyzer::analyze_instance_crash(
+                    instance_path,
+                    crash_report_content.as_deref(),
+                )
+                .await
+            }
+            _ => Vec::new(),
+        };
+
+        metadata.exit_info = Some(exit_info.clone());
+        state.process_manager.record_session(&metadata).await;
+        let payload = MinecraftProcessExitedPayload {
+            profile_id: metadata.profile_id,
+            process_id: metadata.id,
+            exit_code: None,
+            success,
+            process_metadata: Some(metadata.clone()),
+            crash_report_content,
+            crash_diagnoses,
+            exit_info: Some(exit_info.clone()),
+        };
+        let message = match serde_json::to_string(&payload) {
+            Ok(json) => json,
+            Err(e) => {
+                log::error!(
+                    "Failed to serialize MinecraftProcessExitedPayload for {}: {}",
+                    metadata.id,
+                    e
+                );
+                return;
+            }
+        };
+        let event = EventPayload {
+            event_id: Uuid::new_v4(),
+            event_type: EventType::MinecraftProcessExited,
+            target_id: Some(metadata.id),
+            message,
+            progress: None,
+            error: if success {
+                None
+            } else {
+                Some(format!(
+                    "Process exited unexpectedly ({:?})",
+                    exit_info.classification
+                ))
+            },
+        };
+        if let Err(e) = state.event_state.emit(event).await {
+            log::error!(
+                "Failed to emit MinecraftProcessExited event for process {}: {}",
+                metadata.id,
+                e
+            );
+        }
+    }
+
+    /// Samples CPU, memory and threads of every running game (including wrapper children)
+    /// and emits them as `ProcessResourceSample` events
+    async fn periodic_resource_sampler(
+        processes_arc: Arc<RwLock<HashMap<Uuid, Process>>>,
+        resource_history: Arc<DashMap<Uuid, ResourceHistory>>,
+    ) {
+        let mut interval = interval(RESOURCE_SAMPLE_INTERVAL);
+        // Kept across ticks, CPU usage is calculated from the difference to the last refresh
+        let mut sys = System::new();
+        log::info!("Starting periodic resource sampler task.");
+
+        loop {
+            interval.tick().await;
+
+            let tracked: Vec<(Uuid, Uuid, u32)> = {
+                let processes_map_reader = processes_arc.read().await;
+                processes_map_reader
+                    .values()
+                    .map(|p| (p.metadata.id, p.metadata.profile_id, p.metadata.pid))
+                    .collect()
+            };
+            // Histories of exited processes are normally taken by `record_session`
+            resource_history.retain(|id, _| tracked.iter().any(|(tracked_id, _, _)| tracked_id == id));
+            if tracked.is_empty() {
+                continue;
+            }
+
+            sys.refresh_processes_specifics(
+                ProcessesToUpdate::All,
+                true,
+                ProcessRefreshKind::nothing()
+                    .with_cpu()
+                    .with_memory()
+                    .with_tasks(),
+            );
+
+            let state = match State::get().await {
+                Ok(state) => Some(state),
+                Err(e) => {
+                    log::error!("Resource sampler failed to get global state: {}", e);
+                    None
+                }
+            };
+
+            for (process_id, profile_id, pid) in tracked {
+                let Some(sample) =
+                    process_metrics::sample_process_tree(&sys, Pid::from(pid as usize))
+                else {
+                    continue;
+                };
+                log::trace!("Resource sample for process {}: {:?}", process_id, sample);
+                resource_history
+                    .entry(process_id)
+                    .or_default()
+                    .push(sample.clone());
+
+                let Some(state) = &state else { continue };
+                let payload = ProcessResourceSamplePayload {
+                    process_id,
+                    profile_id,
+                    sample,
+                };
+                let message = match serde_json::to_string(&payload) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        log::error!("Failed to serialize resource sample for {}: {}", process_id, e);
+                        continue;
+                    }
+                };
+                let event = EventPayload {
+                    event_id: Uuid::new_v4(),
+                    event_type: EventType::ProcessResourceSample,
+                    target_id: Some(process_id),
+                    message,
+                    progress: None,
+                    error: None,
+                };
+                if let Err(e) = state.event_state.emit(event).await {
+                    log::error!("Failed to emit resource sample for process {}: {}", process_id, e);
+                }
+            }
+        }
+    }
+
     async fn periodic_log_tailer(processes_arc: Arc<RwLock<HashMap<Uuid, Process>>>) {
         let mut interval = interval(Duration::from_secs(1)); // Log-Tailing kann weiterhin häufig sein
         log::info!("Starting periodic log tailing task (crash reports handled by notify).");
@@ -1123,9 +1761,11 @@ impl ProcessManager {
 
             let processes_to_tail: Vec<(Uuid, Uuid, Arc<Mutex<u64>>)> = processes_map_reader
                 .iter()
+                // Stopping too, a graceful stop waits for the "Stopping!" line
                 .filter(|(_, process_entry)| {
                     process_entry.metadata.state == ProcessState::Running
                         || process_entry.metadata.state == ProcessState::Starting
+                        || process_entry.metadata.state == ProcessState::Stopping
                 })
                 .map(|(id, process_entry)| {
                     (
@@ -1164,6 +1804,8 @@ impl ProcessManager {
                         process_id,
                         &last_pos_mutex,
                         &app_state.event_state, // Verwende app_state Variable
+                        &app_state.process_manager,
+                        &instance_path,
                     )
                     .await
                     {
@@ -1184,6 +1826,8 @@ impl ProcessManager {
         process_id: Uuid,
         last_pos_mutex: &Arc<Mutex<u64>>,
         event_state: &EventState,
+        process_manager: &ProcessManager,
+        instance_path: &Path,
     ) -> Result<()> {
         let current_metadata = tokio::fs::metadata(log_path).await.map_err(AppError::Io)?;
         let current_size = current_metadata.len();
@@ -1259,6 +1903,9 @@ impl ProcessManager {
                             if let Err(e) = event_state.emit(log_event_payload).await {
                                 log::error!("Failed to emit log update via EventState: {}", e);
                             }
+                            process_manager
+                                .observe_log_line(process_id, trimmed_line, instance_path)
+                                .await;
                         }
 
                         bytes_actually_read += bytes_u64;
@@ -1370,6 +2017,55 @@ impl ProcessManager {
         Ok(log_content)
     }
 
+    /// Resource samples of a running process, oldest first
+    pub fn get_resource_samples(&self, process_id: Uuid) -> Vec<ResourceSample> {
+        self.resource_history
+            .get(&process_id)
+            .map(|history| history.samples())
+            .unwrap_or_default()
+    }
+
+    /// Recorded sessions, newest first, optionally only those of one profile
+    pub async fn get_session_history(&self, profile_id: Option<Uuid>) -> Result<Vec<SessionRecord>> {
+        let mut sessions =
+            process_metrics::load_session_history(&process_metrics::default_session_history_path())
+                .await?;
+        if let Some(profile_id) = profile_id {
+            sessions.retain(|s| s.profile_id == profile_id);
+        }
+        sessions.reverse();
+        Ok(sessions)
+    }
+
+    /// Writes the summary of a finished process to the session history
+    async fn record_session(&self, metadata: &ProcessMetadata) {
+        self.playtime.end_session(metadata.id).await;
+
+        let history = self
+            .resource_history
+            .remove(&metadata.id)
+            .map(|(_, history)| history);
+        let record = process_metrics::build_session_record(metadata, history.as_ref(), Utc::now());
+        if record.exceeded_memory_max {
+            log::warn!(
+                "Process {} peaked at {} MB, well above its -Xmx of {:?} MB",
+                metadata.id,
+                record.peak_memory_bytes.unwrap_or(0) / (1024 * 1024),
+                record.memory_max_mb
+            );
+        }
+        if let Err(e) =
+            process_metrics::append_session(&process_metrics::default_session_history_path(), record)
+                .await
+        {
+            log::error!("Failed to record session of process {}: {}", metadata.id, e);
+        }
+    }
+
+    pub fn playtime(&self) -> &PlaytimeTracker {
+        &self.playtime
+    }
+
     /// Adds a task handle to the launching_processes map
     pub fn add_launching_process(&self, profile_id: Uuid, handle: JoinHandle<()>) {
         log::info!("Adding launching task for profile ID: {}", profile_id);
@@ -1409,7 +2105,7 @@ impl ProcessManager {
         self.launching_processes.contains_key(&profile_id)
     }
 
-    // Helper function to execute post-exit hook with flatter structure
+    // Helper function to execute the post-exit hooks with flatter structure
     async fn execute_post_exit_hook_if_needed(
         success: bool,
         state: &State,
@@ -1421,97 +2117,83 @@ impl ProcessManager {
             return;
         }
 
-        // Get hook from process metadata (captured at start time) instead of current config
-        let hook = match removed_process_metadata {
-            Some(process) => match &process.metadata.post_exit_hook {
-                Some(h) => h,
-                None => return, // No hook was configured when process started
-            },
-            None => return, // No process metadata available
+        // Hooks and their context were captured at start time instead of reading the current config
+        let Some(process) = removed_process_metadata else {
+            return; // No process metadata available
         };
-
-        log::info!(
-            "Executing post-exit hook for process {}: {}",
-            process_id,
-            hook
-        );
-
-        let removed_process = match removed_process_metadata {
-            Some(p) => p,
-            None => {
-                log::warn!(
-                    "No process metadata available for post-exit hook for process {}",
-                    process_id
-                );
-                return;
-            }
+        let hooks = process.metadata.post_exit_hooks.clone();
+        if hooks.is_empty() {
+            return; // No hook was configured when process started
+        }
+        let context = match process.metadata.hook_context.clone() {
+            Some(context) => context,
+            // Processes saved by older versions, the context is rebuilt from the profile
+            None => match Self::legacy_hook_context(state, process.metadata.profile_id).await {
+                Some(context) => context,
+                None => {
+                    log::warn!(
+                        "No hook context available for post-exit hooks of process {}",
+                        process_id
+                    );
+                    return;
+                }
+            },
         };
 
-        let profile = match state
-            .profile_manager
-            .get_profile(removed_process.metadata.profile_id)
-            .await
-        {
-            Ok(p) => p,
-            Err(e) => {
-                log::error!(
-                    "Could not get profile for post-exit hook for process {}: {}",
+        // Execute hooks in the background, one after another in chain order
+        tokio::spawn(async move {
+            for hook in hooks {
+                log::info!(
+                    "Executing post-exit hook for process {}: {}",
                     process_id,
-                    e
+                    hook
                 );
-                return;
-            }
-        };
-
-        let game_directory = match state
-            .profile_manager
-            .calculate_instance_path_for_profile(&profile)
-        {
-            Ok(dir) => dir,
-            Err(_) => {
-                log::error!(
-                    "Could not determine game directory for post-exit hook for process {}",
-                    process_id
-                );
-                return;
-            }
-        };
-
-        // Execute hook without waiting for completion (fire and forget)
-        let hook_command = hook.clone();
-        let game_dir_clone = game_directory.clone();
-        tokio::spawn(async move {
-            let mut cmd = hook_command.split(' ');
-            let command = match cmd.next() {
-                Some(c) => c,
-                None => return,
-            };
+                let Some(command) = context.command(&hook) else {
+                    continue;
+                };
 
-            match std::process::Command::new(command)
-                .args(cmd.collect::<Vec<&str>>())
-                .current_dir(&game_dir_clone)
-                .spawn()
-            {
-                Ok(_) => {
-                    log::info!(
-                        "Post-exit hook spawned successfully for process {}",
-                        process_id
-                    );
-                }
-                Err(e) => {
-                    log::error!(
-                        "Failed to spawn post-exit hook for process {}: {}",
-                        process_id,
-                        e
-                    );
+                match tokio::process::Command::from(command).status().await {
+                    Ok(status) if status.success() => {
+                        log::info!(
+                            "Post-exit hook finished successfully for process {}",
+                            process_id
+                        );
+                    }
+                    Ok(status) => {
+                        log::warn!(
+                            "Post-exit hook for process {} exited with {}",
+                            process_id,
+                            status
+                        );
+                    }
+                    Err(e) => {
+                        log::error!(
+                            "Failed to spawn post-exit hook for process {}: {}",
+                            process_id,
+                            e
+                        );
+                    }
                 }
             }
         });
     }
 
+    /// Hook context of a process saved before it was captured at launch. The Java path isn't known.
+    async fn legacy_hook_context(state: &State, profile_id: Uuid) -> Option<HookContext> {
+        let profile = state.profile_manager.get_profile(profile_id).await.ok()?;
+        let instance_dir = state
+            .profile_manager
+            .calculate_instance_path_for_profile(&profile)
+            .ok()?;
+        Some(HookContext::for_profile(&profile, instance_dir, PathBuf::new()))
+    }
+
     // Private helper to schedule the auto-opening of the log window
     fn schedule_auto_open_log_window(&self, process_id: Uuid) {
-        let app_handle_clone = Arc::clone(&self.app_handle);
+        // No log window without a webview (headless mode)
+        let Some(app_handle_clone) = self.app_handle.clone() else {
+            return;
+        };
 
         tokio::spawn(async move {
             match crate::state::State::get().await {
@@ -1556,7 +2238,7 @@ impl ProcessManager {
 
 #[async_trait]
 impl PostInitializationHandler for ProcessManager {
-    async fn on_state_ready(&self, app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         log::info!("ProcessManager: on_state_ready called. Performing post-initialization tasks.");
 
         // For process_crash_report_events: The task requires the receive end of an mpsc channel.
@@ -1569,9 +2251,15 @@ impl PostInitializationHandler for ProcessManager {
         self.load_processes_and_watchers().await?;
         log::info!("ProcessManager: Finished load_processes_and_watchers.");
 
+        // Re-attach playtime sessions of the processes that are still running
+        let running_processes = self.list_processes().await;
+        if let Err(e) = self.playtime.load(&running_processes).await {
+            log::error!("ProcessManager: Failed to load playtime sessions: {}", e);
+        }
+
         let manager_clone_periodic_check_processes = Arc::clone(&self.processes);
         let manager_clone_periodic_check_watchers = Arc::clone(&self.active_watchers);
-        let app_handle_for_periodic_check = Arc::clone(&app_handle);
+        let app_handle_for_periodic_check = app_handle.clone();
         let notify_tx_for_periodic_check = self.notify_event_tx.clone();
 
         tokio::spawn(Self::periodic_process_check(
@@ -1582,6 +2270,12 @@ impl PostInitializationHandler for ProcessManager {
         ));
         log::info!("ProcessManager: Spawned periodic_process_check task.");
 
+        tokio::spawn(Self::periodic_resource_sampler(
+            Arc::clone(&self.processes),
+            Arc::clone(&self.resource_history),
+        ));
+        log::info!("ProcessManager: Spawned periodic_resource_sampler task.");
+
         let tailer_processes_arc = Arc::clone(&self.processes);
         tokio::spawn(Self::periodic_log_tailer(tailer_processes_arc));
         log::info!("ProcessManager: Spawned periodic_log_tailer task.");
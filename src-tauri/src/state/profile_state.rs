@@ -2,8 +2,12 @@ use crate::config::LAUNCHER_DIRECTORY;
 use crate::error::AppError;
 use crate::error::Result;
 use crate::integrations::modrinth::{self, ModrinthDependencyType, ModrinthVersion};
+use crate::integrations::modrinth_resolver::{self, ModrinthInstallPlan};
+use crate::minecraft::dto::JavaDistribution;
+use crate::minecraft::JvmPreset;
 use crate::state::post_init::PostInitializationHandler;
 use crate::utils::hash_utils;
+use crate::utils::jar_inspector::{self, ModMetadata};
 use crate::utils::path_utils;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -11,7 +15,7 @@ use futures::future::BoxFuture;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri_plugin_dialog::FilePath;
@@ -44,6 +48,14 @@ pub enum ModSource {
         download_url: String, // The direct download URL used when adding
         file_hash_sha1: Option<String>, // Optional SHA1 hash for verification
     }, // New variant for Modrinth mods
+    #[serde(rename = "curseforge")]
+    CurseForge {
+        project_id: u32,                // CurseForge project (mod) ID
+        file_id: u32,                   // CurseForge file ID
+        file_name: String,              // The actual filename on disk
+        download_url: Option<String>, // None if the author disabled third-party downloads; resolved lazily
+        file_hash_sha1: Option<String>, // Optional SHA1 hash for verification
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -194,6 +206,34 @@ pub struct ProfileSettings {
     pub extra_game_args: Vec<String>, // Zusätzliche Argumente für das Spiel
     #[serde(default)] // Für Abwärtskompatibilität
     pub custom_jvm_args: Option<String>, // Zusätzliche JVM-Argumente als String
+    #[serde(default)]
+    pub jvm_preset: Option<JvmPreset>, // GC-Preset, None = globale Einstellung. Standard-Profile nutzen immer die globale
+    #[serde(default)]
+    pub java_vendor: Option<JavaDistribution>, // Java-Anbieter für Downloads, None = globale Einstellung
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>, // Umgebungsvariablen für das Spiel
+    #[serde(default)]
+    pub hooks: ProfileHooks, // Hooks, überschreiben oder ergänzen die globalen
+}
+
+/// Per-profile hooks, combined with the global ones by `hook_utils::resolve_hooks`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProfileHooks {
+    pub pre_launch: Option<String>,
+    pub wrapper: Option<String>,
+    pub post_exit: Option<String>,
+    #[serde(default)]
+    pub mode: HookMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookMode {
+    /// A profile hook replaces the global one, an empty one disables it
+    #[default]
+    Override,
+    /// The global hook runs first, then the profile's
+    Chain,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -231,6 +271,7 @@ pub struct CustomModInfo {
     pub filename: String, // Base filename (e.g., OptiFine.jar)
     pub is_enabled: bool, // True if the file doesn't end with .disabled
     pub path: PathBuf,    // Full path to the file in custom_mods directory
+    pub metadata: Option<ModMetadata>, // Metadata read from the jar, if it declares any
 }
 
 // Profile Manager
@@ -837,6 +878,128 @@ impl ProfileManager {
            }
     }
 
+    /// Adds all mods of a confirmed install plan (see `modrinth_resolver`) to a profile.
+    /// Mods that replace an installed version are updated in place. Returns the number of mods written.
+    pub async fn apply_modrinth_install_plan(
+        &self,
+        profile_id: Uuid,
+        plan: &ModrinthInstallPlan,
+    ) -> Result<usize> {
+        if plan.profile_id != profile_id {
+            return Err(AppError::InvalidOperation(format!(
+                "Install plan was resolved for profile {}, not {}",
+                plan.profile_id, profile_id
+            )));
+        }
+        if !plan.can_apply() {
+            return Err(AppError::InvalidOperation(format!(
+                "Install plan has {} conflict(s) and {} unresolved dependencies",
+                plan.conflicts.len(),
+                plan.unresolved.len()
+            )));
+        }
+
+        let profile = self.get_profile(profile_id).await?;
+        if profile.game_version != plan.game_version || profile.loader != plan.loader {
+            return Err(AppError::InvalidOperation(
+                "Profile version or loader changed since the plan was resolved".to_string(),
+            ));
+        }
+
+        // Standard versions have no mod list, their mods are downloaded directly
+        if profile.is_standard_version {
+            for planned in &plan.mods {
+                self.add_modrinth_mod(
+                    profile_id,
+                    planned.project_id.clone(),
+                    planned.version_id.clone(),
+                    planned.file_name.clone(),
+                    planned.download_url.clone(),
+                    planned.file_hash_sha1.clone(),
+                    Some(planned.name.clone()),
+                    Some(planned.version_number.clone()),
+                    Some(planned.loaders.clone()),
+                    Some(planned.game_versions.clone()),
+                    false,
+                )
+                .await?;
+            }
+            return Ok(plan.mods.len());
+        }
+
+        {
+            let mut profiles = self.profiles.write().await;
+            let profile = profiles
+                .get_mut(&profile_id)
+                .ok_or(AppError::ProfileNotFound(profile_id))?;
+
+            // Every planned mod either replaces the installed copy of its project or is new.
+            // Anything else means the mods changed since the plan was resolved.
+            for planned in &plan.mods {
+                let installed = profile.mods.iter().any(|m| match &m.source {
+                    ModSource::Modrinth { project_id, .. } => project_id == &planned.project_id,
+                    _ => false,
+                });
+                if installed != planned.replaces_version_id.is_some() {
+                    return Err(AppError::InvalidOperation(format!(
+                        "Mods of profile {} changed since the plan was resolved ({}), resolve it again",
+                        profile_id, planned.name
+                    )));
+                }
+            }
+
+            for planned in &plan.mods {
+                let source = ModSource::Modrinth {
+                    project_id: planned.project_id.clone(),
+                    version_id: planned.version_id.clone(),
+                    file_name: planned.file_name.clone(),
+                    download_url: planned.download_url.clone(),
+                    file_hash_sha1: planned.file_hash_sha1.clone(),
+                };
+                let associated_loader =
+                    modrinth_resolver::associated_loader(&profile.loader, &planned.loaders);
+
+                let existing = profile.mods.iter_mut().find(|m| match &m.source {
+                    ModSource::Modrinth { project_id, .. } => project_id == &planned.project_id,
+                    _ => false,
+                });
+                match existing {
+                    Some(existing) => {
+                        info!(
+                            "Replacing {} with version {} in profile {}",
+                            planned.name, planned.version_number, profile_id
+                        );
+                        existing.source = source;
+                        existing.version = Some(planned.version_number.clone());
+                        existing.game_versions = Some(planned.game_versions.clone());
+                        existing.associated_loader = associated_loader;
+                        // A disabled copy gets replaced because the plan needs it
+                        existing.enabled = true;
+                    }
+                    None => {
+                        info!(
+                            "Adding {} ({}) to profile {}",
+                            planned.name, planned.version_number, profile_id
+                        );
+                        profile.mods.push(Mod {
+                            id: Uuid::new_v4(),
+                            source,
+                            enabled: true,
+                            display_name: Some(planned.name.clone()),
+                            version: Some(planned.version_number.clone()),
+                            game_versions: Some(planned.game_versions.clone()),
+                            file_name_override: None,
+                            associated_loader,
+                        });
+                    }
+                }
+            }
+        }
+
+        self.save_profiles().await?;
+        Ok(plan.mods.len())
+    }
+
     // Set the enabled status of a specific mod within a profile
     pub async fn set_mod_enabled(
         &self,
@@ -1434,6 +1597,7 @@ impl ProfileManager {
                             filename: base_filename,
                             is_enabled,
                             path: path.clone(),
+                            metadata: None,
                         });
                     } else {
                         log::trace!(
@@ -1445,6 +1609,18 @@ impl ProfileManager {
             }
         }
 
+        let jars = custom_mods
+            .iter()
+            .map(|custom_mod| (custom_mod.path.clone(), None))
+            .collect();
+        let jar_metadata = jar_inspector::get_jars_metadata(jars).await;
+        for custom_mod in custom_mods.iter_mut() {
+            custom_mod.metadata = jar_metadata
+                .get(&custom_mod.path)
+                .and_then(|metadata| metadata.for_loader(profile.loader))
+                .cloned();
+        }
+
         log::info!(
             "Found {} relevant custom mod file(s) in {:?}",
             custom_mods.len(),
@@ -1800,7 +1976,7 @@ impl ProfileManager {
 
 #[async_trait]
 impl PostInitializationHandler for ProfileManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("ProfileManager: on_state_ready called. Loading profiles...");
         let mut loaded_profiles = self
             .load_profiles_internal(&self.profiles_path.clone())
@@ -1840,6 +2016,7 @@ impl PostInitializationHandler for ProfileManager {
 pub fn get_profile_mod_filename(source: &ModSource) -> crate::error::Result<String> {
     match source {
         ModSource::Modrinth { file_name, .. } => Ok(file_name.clone()),
+        ModSource::CurseForge { file_name, .. } => Ok(file_name.clone()),
         ModSource::Local { file_name } => Ok(file_name.clone()),
         ModSource::Url { file_name, url } => file_name.clone().ok_or_else(|| {
             crate::error::AppError::Other(format!("Filename missing for URL mod source: {}", url))
@@ -1879,6 +2056,10 @@ impl Default for ProfileSettings {
             fullscreen: false,
             extra_game_args: Vec::new(),
             custom_jvm_args: None, // Standardmäßig keine benutzerdefinierten JVM-Args
+            jvm_preset: None,
+            java_vendor: None,
+            env_vars: BTreeMap::new(),
+            hooks: ProfileHooks::default(),
         }
     }
 }
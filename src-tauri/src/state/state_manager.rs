@@ -34,13 +34,23 @@ pub struct State {
 impl State {
     // Initialize the global state
     pub async fn init(app: Arc<tauri::AppHandle>) -> Result<()> {
+        Self::init_with_app(Some(app)).await
+    }
+
+    // Initialize the global state without a Tauri app/window (headless CLI mode).
+    // Events are not sent to a frontend, use `event_state.subscribe()` to receive them.
+    pub async fn init_headless() -> Result<()> {
+        Self::init_with_app(None).await
+    }
+
+    async fn init_with_app(app: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         let initial_state_arc = LAUNCHER_STATE
             .get_or_try_init(|| async {
                 log::info!("State::init - Starting primary initialization of managers (Phase 1 - Lightweight Instantiation)...");
                 let config_manager = ConfigManager::new()?;
                 let discord_manager = DiscordManager::new(false).await?;
                 let io_semaphore = Arc::new(Semaphore::new(10));
-                let event_state = EventState::new(Some(app.clone()));
+                let event_state = EventState::new(app.clone());
                 let minecraft_account_manager_v2 = MinecraftAuthStore::new().await?;
                 let norisk_pack_manager = NoriskPackManager::new(default_norisk_packs_path())?;
                 let norisk_version_manager = NoriskVersionManager::new(default_norisk_versions_path())?;
@@ -0,0 +1,723 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::integrations::modrinth::{
+    self, ModrinthBulkUpdateRequestBody, ModrinthVersion, ModrinthVersionType,
+};
+use crate::integrations::modrinth_resolver;
+use crate::state::profile_state::{Mod, ModLoader, ModSource, Profile};
+use crate::state::state_manager::State;
+use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::utils::snapshot_utils::{self, SnapshotRecord};
+use crate::utils::{datapack_utils, hash_utils, resourcepack_utils, shaderpack_utils};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+const MOD_CACHE_DIR_NAME: &str = "mod_cache";
+const CONCURRENT_DOWNLOADS: usize = 8;
+/// Older snapshots are pruned after a successful update
+const MAX_SNAPSHOTS_PER_PROFILE: usize = 5;
+
+/// Kind of profile content an update applies to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateContentKind {
+    Mod,
+    ResourcePack,
+    ShaderPack,
+    DataPack,
+}
+
+impl UpdateContentKind {
+    /// Loader filter for the Modrinth bulk update check
+    pub fn loaders(&self, profile: &Profile) -> Vec<String> {
+        match self {
+            // Quilt profiles run Fabric mods as well
+            UpdateContentKind::Mod => modrinth_resolver::compatible_loaders(&profile.loader),
+            // Same (empty) filter the content tabs use for packs
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// An installed item whose file hash is known and that may have a newer Modrinth version
+#[derive(Debug, Clone)]
+pub struct UpdateCandidate {
+    pub kind: UpdateContentKind,
+    /// Profile mod entry, only set for mods
+    pub mod_id: Option<Uuid>,
+    pub name: String,
+    pub file_name: String,
+    /// Location on disk, only set for packs (mods live in the shared mod cache)
+    pub path: Option<PathBuf>,
+    pub sha1: String,
+    pub is_disabled: bool,
+    pub current_version_id: Option<String>,
+}
+
+/// A single pending update shown in the "update all" preview
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContentUpdate {
+    pub kind: UpdateContentKind,
+    pub mod_id: Option<Uuid>,
+    pub project_id: String,
+    pub name: String,
+    pub current_version_id: Option<String>,
+    pub current_version_number: Option<String>,
+    pub current_file_name: String,
+    pub current_path: Option<PathBuf>,
+    pub is_disabled: bool,
+    pub new_version_id: String,
+    pub new_version_number: String,
+    pub new_file_name: String,
+    pub download_url: String,
+    pub new_file_hash_sha1: Option<String>,
+    pub new_game_versions: Vec<String>,
+    pub new_loaders: Vec<String>,
+    pub version_type: ModrinthVersionType,
+    pub date_published: String,
+    pub changelog: Option<String>,
+}
+
+/// Every available update for a profile, returned by `check_profile_updates`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileUpdatePlan {
+    pub profile_id: Uuid,
+    pub game_version: String,
+    pub loader: String,
+    pub updates: Vec<ContentUpdate>,
+}
+
+/// A file that was replaced by an update and is kept in the snapshot store
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotFile {
+    pub kind: UpdateContentKind,
+    /// Where the file lived before the update
+    pub original_path: PathBuf,
+    /// Hash of the object in the snapshot store that holds the old file
+    pub sha1: String,
+    /// The new file that took its place, removed again on rollback
+    pub installed_path: Option<PathBuf>,
+}
+
+/// State of a profile before an "update all", used to roll the whole update back
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateSnapshot {
+    pub id: Uuid,
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    pub created_at: DateTime<Utc>,
+    pub previous_mods: Vec<Mod>,
+    pub updates: Vec<ContentUpdate>,
+    pub files: Vec<SnapshotFile>,
+}
+
+impl SnapshotRecord for UpdateSnapshot {
+    const DIR: &'static str = "updates";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn objects(&self) -> Vec<String> {
+        self.files.iter().map(|f| f.sha1.clone()).collect()
+    }
+}
+
+fn mod_cache_dir() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME)
+}
+
+/// Turns the current and latest versions (both keyed by file hash) into the list of updates.
+/// Items that are already up to date, or where Modrinth only knows an older version, are skipped.
+pub fn diff_updates(
+    candidates: &[UpdateCandidate],
+    current_versions: &HashMap<String, ModrinthVersion>,
+    latest_versions: &HashMap<String, ModrinthVersion>,
+) -> Vec<ContentUpdate> {
+    let mut updates = Vec::new();
+
+    for candidate in candidates {
+        let Some(latest) = latest_versions.get(&candidate.sha1) else {
+            continue;
+        };
+        let current = current_versions.get(&candidate.sha1);
+        let current_version_id = candidate
+            .current_version_id
+            .clone()
+            .or_else(|| current.map(|v| v.id.clone()));
+
+        if current_version_id.as_deref() == Some(latest.id.as_str()) {
+            continue;
+        }
+        if let Some(current) = current {
+            if current.project_id != latest.project_id
+                || current.date_published >= latest.date_published
+            {
+                debug!(
+                    "Skipping '{}': latest version {} is not newer than {}",
+                    candidate.name, latest.id, current.id
+                );
+                continue;
+            }
+        }
+
+        let Some(primary_file) = latest
+            .files
+            .iter()
+            .find(|f| f.primary)
+            .or_else(|| latest.files.first())
+        else {
+            warn!(
+                "No file found for Modrinth version {} of '{}', skipping update",
+                latest.id, candidate.name
+            );
+            continue;
+        };
+        if primary_file.hashes.sha1.as_deref() == Some(candidate.sha1.as_str()) {
+            continue;
+        }
+
+        updates.push(ContentUpdate {
+            kind: candidate.kind,
+            mod_id: candidate.mod_id,
+            project_id: latest.project_id.clone(),
+            name: candidate.name.clone(),
+            current_version_id,
+            current_version_number: current.map(|v| v.version_number.clone()),
+            current_file_name: candidate.file_name.clone(),
+            current_path: candidate.path.clone(),
+            is_disabled: candidate.is_disabled,
+            new_version_id: latest.id.clone(),
+            new_version_number: latest.version_number.clone(),
+            new_file_name: primary_file.filename.clone(),
+            download_url: primary_file.url.clone(),
+            new_file_hash_sha1: primary_file.hashes.sha1.clone(),
+            new_game_versions: latest.game_versions.clone(),
+            new_loaders: latest.loaders.clone(),
+            version_type: latest.version_type.clone(),
+            date_published: latest.date_published.clone(),
+            changelog: latest.changelog.clone(),
+        });
+    }
+
+    updates
+}
+
+/// Collects every Modrinth mod and every resource, shader and data pack of a profile
+async fn collect_candidates(profile: &Profile) -> Result<Vec<UpdateCandidate>> {
+    let mut candidates = Vec::new();
+    let cache_dir = mod_cache_dir();
+
+    for mod_entry in &profile.mods {
+        let ModSource::Modrinth {
+            version_id,
+            file_name,
+            file_hash_sha1,
+            ..
+        } = &mod_entry.source
+        else {
+            continue;
+        };
+
+        let sha1 = match file_hash_sha1 {
+            Some(hash) => hash.clone(),
+            None => match hash_utils::calculate_sha1_from_file(cache_dir.join(file_name)).await {
+                Ok(hash) => hash,
+                Err(e) => {
+                    debug!(
+                        "No hash for mod '{}' and it is not cached ({}), skipping",
+                        file_name, e
+                    );
+                    continue;
+                }
+            },
+        };
+
+        candidates.push(UpdateCandidate {
+            kind: UpdateContentKind::Mod,
+            mod_id: Some(mod_entry.id),
+            name: mod_entry.display_name.clone().unwrap_or_else(|| file_name.clone()),
+            file_name: file_name.clone(),
+            path: None,
+            sha1,
+            is_disabled: !mod_entry.enabled,
+            current_version_id: Some(version_id.clone()),
+        });
+    }
+
+    let resourcepacks = resourcepack_utils::get_resourcepacks_for_profile(profile, true, false)
+        .await?
+        .into_iter()
+        .map(|p| (p.filename, p.path, p.sha1_hash, p.is_disabled));
+    push_pack_candidates(&mut candidates, UpdateContentKind::ResourcePack, resourcepacks);
+
+    let shaderpacks = shaderpack_utils::get_shaderpacks_for_profile(profile, true, false)
+        .await?
+        .into_iter()
+        .map(|p| (p.filename, p.path, p.sha1_hash, p.is_disabled));
+    push_pack_candidates(&mut candidates, UpdateContentKind::ShaderPack, shaderpacks);
+
+    let datapacks = datapack_utils::get_datapacks_for_profile(profile, true, false)
+        .await?
+        .into_iter()
+        .map(|p| (p.filename, p.path, p.sha1_hash, p.is_disabled));
+    push_pack_candidates(&mut candidates, UpdateContentKind::DataPack, datapacks);
+
+    Ok(candidates)
+}
+
+/// `packs` are (filename, path, sha1, is_disabled) tuples from the content listings
+fn push_pack_candidates(
+    candidates: &mut Vec<UpdateCandidate>,
+    kind: UpdateContentKind,
+    packs: impl Iterator<Item = (String, String, Option<String>, bool)>,
+) {
+    for (filename, path, sha1, is_disabled) in packs {
+        let Some(sha1) = sha1 else {
+            continue;
+        };
+        candidates.push(UpdateCandidate {
+            kind,
+            mod_id: None,
+            name: filename.clone(),
+            file_name: filename,
+            path: Some(PathBuf::from(path)),
+            sha1,
+            is_disabled,
+            current_version_id: None,
+        });
+    }
+}
+
+/// Checks Modrinth for updates to all mods, resource packs, shader packs and data packs of a profile
+pub async fn check_profile_updates(profile: &Profile) -> Result<ProfileUpdatePlan> {
+    info!(
+        "Checking for updates to all content of profile '{}' ({})",
+        profile.name, profile.id
+    );
+
+    let candidates = collect_candidates(profile).await?;
+    let mut latest_versions = HashMap::new();
+
+    for kind in [
+        UpdateContentKind::Mod,
+        UpdateContentKind::ResourcePack,
+        UpdateContentKind::ShaderPack,
+        UpdateContentKind::DataPack,
+    ] {
+        let hashes: Vec<String> = candidates
+            .iter()
+            .filter(|c| c.kind == kind)
+            .map(|c| c.sha1.clone())
+            .collect();
+        if hashes.is_empty() {
+            continue;
+        }
+
+        let request = ModrinthBulkUpdateRequestBody::new(
+            hashes,
+            "sha1".to_string(),
+            kind.loaders(profile),
+            vec![profile.game_version.clone()],
+        )?;
+        latest_versions.extend(modrinth::check_bulk_updates(request).await?);
+    }
+
+    // Packs don't store which version they are, so look that up by hash as well
+    let pack_hashes: Vec<String> = candidates
+        .iter()
+        .filter(|c| c.kind != UpdateContentKind::Mod)
+        .map(|c| c.sha1.clone())
+        .collect();
+    let current_versions = modrinth::get_versions_by_hashes(pack_hashes, "sha1").await?;
+
+    let mut updates = diff_updates(&candidates, &current_versions, &latest_versions);
+    // Mods know their version number without a lookup
+    for update in updates.iter_mut().filter(|u| u.kind == UpdateContentKind::Mod) {
+        if update.current_version_number.is_none() {
+            update.current_version_number = update.mod_id.and_then(|id| {
+                profile
+                    .mods
+                    .iter()
+                    .find(|m| m.id == id)
+                    .and_then(|m| m.version.clone())
+            });
+        }
+    }
+    info!(
+        "Found {} update(s) among {} item(s) in profile '{}'",
+        updates.len(),
+        candidates.len(),
+        profile.name
+    );
+
+    Ok(ProfileUpdatePlan {
+        profile_id: profile.id,
+        game_version: profile.game_version.clone(),
+        loader: profile.loader.as_str().to_string(),
+        updates,
+    })
+}
+
+/// Makes sure the profile still has the file the update was planned against
+fn ensure_still_current(profile: &Profile, update: &ContentUpdate) -> Result<()> {
+    let unchanged = match update.kind {
+        UpdateContentKind::Mod => profile.mods.iter().any(|m| {
+            Some(m.id) == update.mod_id
+                && matches!(&m.source, ModSource::Modrinth { version_id, .. }
+                    if Some(version_id) == update.current_version_id.as_ref())
+        }),
+        _ => update.current_path.as_ref().map_or(false, |p| p.exists()),
+    };
+
+    if unchanged {
+        Ok(())
+    } else {
+        Err(AppError::InvalidOperation(format!(
+            "'{}' changed since the update check, please check for updates again",
+            update.name
+        )))
+    }
+}
+
+/// Downloads the new file of an update. Mods go straight into the shared mod cache (nothing
+/// references the new file yet), packs into a staging directory.
+async fn stage_update(update: &ContentUpdate, staging_dir: &Path, index: usize) -> Result<PathBuf> {
+    let target_path = match update.kind {
+        UpdateContentKind::Mod => mod_cache_dir().join(&update.new_file_name),
+        _ => staging_dir.join(format!("{}_{}", index, update.new_file_name)),
+    };
+
+    let mut config = DownloadConfig::new();
+    if let Some(sha1) = &update.new_file_hash_sha1 {
+        config = config.with_sha1(sha1);
+    }
+
+    debug!(
+        "Staging update of '{}' to {} at {:?}",
+        update.name, update.new_version_number, target_path
+    );
+    DownloadUtils::download_file(&update.download_url, &target_path, config).await?;
+    Ok(target_path)
+}
+
+/// Renames a file, falling back to copy + remove across volumes
+async fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    if fs::rename(from, to).await.is_err() {
+        fs::copy(from, to).await?;
+        fs::remove_file(from).await?;
+    }
+    Ok(())
+}
+
+/// Stores the old file of an update in the snapshot store and moves the staged file into its place
+pub async fn swap_in(
+    snapshot: &mut UpdateSnapshot,
+    store_root: &Path,
+    update: &ContentUpdate,
+    staged_path: &Path,
+) -> Result<()> {
+    if update.kind == UpdateContentKind::Mod {
+        // Other profiles may still use the old jar, so it stays in the cache and only gets copied
+        let old_path = mod_cache_dir().join(&update.current_file_name);
+        if old_path.exists() {
+            let sha1 = snapshot_utils::store_object(store_root, &old_path).await?;
+            snapshot.files.push(SnapshotFile {
+                kind: update.kind,
+                original_path: old_path,
+                sha1,
+                installed_path: None,
+            });
+        }
+        return Ok(());
+    }
+
+    let old_path = update.current_path.clone().ok_or_else(|| {
+        AppError::InvalidOperation(format!("No file path for '{}'", update.name))
+    })?;
+    let target_dir = old_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| AppError::InvalidOperation(format!("Invalid path {:?}", old_path)))?;
+    let installed_path = if update.is_disabled {
+        target_dir.join(format!("{}.disabled", update.new_file_name))
+    } else {
+        target_dir.join(&update.new_file_name)
+    };
+    if installed_path != old_path && installed_path.exists() {
+        return Err(AppError::InvalidOperation(format!(
+            "{:?} already exists, not overwriting it",
+            installed_path
+        )));
+    }
+
+    let sha1 = snapshot_utils::store_object(store_root, &old_path).await?;
+    fs::remove_file(&old_path).await?;
+    snapshot.files.push(SnapshotFile {
+        kind: update.kind,
+        original_path: old_path,
+        sha1,
+        installed_path: Some(installed_path.clone()),
+    });
+    move_file(staged_path, &installed_path).await
+}
+
+/// Puts every file of a snapshot back, newest first. Keeps going on errors and returns the first one.
+pub async fn restore_files(snapshot: &UpdateSnapshot, store_root: &Path) -> Result<()> {
+    let mut first_error = None;
+
+    for file in snapshot.files.iter().rev() {
+        let result: Result<()> = async {
+            if let Some(installed_path) = &file.installed_path {
+                if installed_path.exists() {
+                    fs::remove_file(installed_path).await?;
+                }
+            }
+            if file.kind == UpdateContentKind::Mod && file.original_path.exists() {
+                return Ok(());
+            }
+            let stored_path = snapshot_utils::object_path(store_root, &file.sha1);
+            if !stored_path.is_file() {
+                return Err(AppError::NotFound(format!(
+                    "Stored copy of {:?} is missing ({:?})",
+                    file.original_path, stored_path
+                )));
+            }
+            if let Some(parent) = file.original_path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::copy(&stored_path, &file.original_path).await?;
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!("Failed to restore {:?}: {}", file.original_path, e);
+            first_error.get_or_insert(e);
+        }
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Points a profile mod entry at the new Modrinth version
+pub fn apply_mod_update(
+    mod_entry: &mut Mod,
+    update: &ContentUpdate,
+    profile_loader: &ModLoader,
+) {
+    mod_entry.source = ModSource::Modrinth {
+        project_id: update.project_id.clone(),
+        version_id: update.new_version_id.clone(),
+        file_name: update.new_file_name.clone(),
+        download_url: update.download_url.clone(),
+        file_hash_sha1: update.new_file_hash_sha1.clone(),
+    };
+    mod_entry.version = Some(update.new_version_number.clone());
+    mod_entry.game_versions = Some(update.new_game_versions.clone());
+    mod_entry.associated_loader =
+        modrinth_resolver::associated_loader(profile_loader, &update.new_loaders);
+}
+
+/// Applies all updates of a plan at once. Every new file is downloaded before anything in the
+/// profile changes; the previous mod list and the replaced files are kept in a snapshot that
+/// `rollback_profile_update` restores.
+pub async fn apply_profile_updates(plan: &ProfileUpdatePlan) -> Result<UpdateSnapshot> {
+    let state = State::get().await?;
+    let mut profile = state.profile_manager.get_profile(plan.profile_id).await?;
+
+    if profile.is_standard_version {
+        return Err(AppError::InvalidOperation(
+            "Standard profiles cannot be updated".to_string(),
+        ));
+    }
+    if plan.updates.is_empty() {
+        return Err(AppError::InvalidOperation("No updates selected".to_string()));
+    }
+    for update in &plan.updates {
+        ensure_still_current(&profile, update)?;
+    }
+
+    info!(
+        "Applying {} update(s) to profile '{}' ({})",
+        plan.updates.len(),
+        profile.name,
+        profile.id
+    );
+
+    let store_root = snapshot_utils::get_snapshot_store_root();
+    let snapshot_id = Uuid::new_v4();
+    let staging_dir = snapshot_utils::profile_store_dir(&store_root, profile.id)
+        .join("staging")
+        .join(snapshot_id.to_string());
+    fs::create_dir_all(&staging_dir).await?;
+
+    // 1. Download everything first, a failed download leaves the profile untouched
+    let staged: Vec<Result<PathBuf>> = stream::iter(plan.updates.iter().enumerate())
+        .map(|(index, update)| stage_update(update, &staging_dir, index))
+        .buffered(CONCURRENT_DOWNLOADS)
+        .collect()
+        .await;
+    let staged = match staged.into_iter().collect::<Result<Vec<_>>>() {
+        Ok(staged) => staged,
+        Err(e) => {
+            error!("Update of profile '{}' aborted, download failed: {}", profile.name, e);
+            let _ = fs::remove_dir_all(&staging_dir).await;
+            return Err(e);
+        }
+    };
+
+    // 2. Record the previous state before touching any file
+    let _guard = snapshot_utils::lock_store().await;
+    let mut snapshot = UpdateSnapshot {
+        id: snapshot_id,
+        profile_id: profile.id,
+        profile_name: profile.name.clone(),
+        created_at: Utc::now(),
+        previous_mods: profile.mods.clone(),
+        updates: plan.updates.clone(),
+        files: Vec::new(),
+    };
+    snapshot_utils::write_record(&store_root, profile.id, &snapshot).await?;
+
+    // 3. Swap the files and the mod list, undoing everything on the first failure
+    let mut result = Ok(());
+    for (update, staged_path) in plan.updates.iter().zip(&staged) {
+        result = swap_in(&mut snapshot, &store_root, update, staged_path).await;
+        if result.is_err() {
+            break;
+        }
+    }
+    if result.is_ok() {
+        let loader = profile.loader;
+        for update in &plan.updates {
+            if let Some(mod_entry) = profile.mods.iter_mut().find(|m| Some(m.id) == update.mod_id) {
+                apply_mod_update(mod_entry, update, &loader);
+            }
+        }
+        result = state
+            .profile_manager
+            .update_profile(profile.id, profile.clone())
+            .await;
+    }
+    if let Err(e) = result {
+        error!(
+            "Update of profile '{}' failed, restoring previous files: {}",
+            profile.name, e
+        );
+        if let Err(restore_err) = restore_files(&snapshot, &store_root).await {
+            error!("Restoring profile '{}' was incomplete: {}", profile.name, restore_err);
+        }
+        let _ = fs::remove_dir_all(&staging_dir).await;
+        let _ =
+            snapshot_utils::delete_record::<UpdateSnapshot>(&store_root, profile.id, snapshot.id)
+                .await;
+        let _ = snapshot_utils::remove_unreferenced_objects(&store_root).await;
+        return Err(e);
+    }
+
+    if let Err(e) = fs::remove_dir_all(&staging_dir).await {
+        warn!("Failed to remove staging directory {:?}: {}", staging_dir, e);
+    }
+    snapshot_utils::write_record(&store_root, profile.id, &snapshot).await?;
+    if let Err(e) = snapshot_utils::prune_records::<UpdateSnapshot>(
+        &store_root,
+        profile.id,
+        MAX_SNAPSHOTS_PER_PROFILE,
+        |_| true,
+    )
+    .await
+    {
+        warn!(
+            "Failed to prune update snapshots of profile '{}': {}",
+            profile.name, e
+        );
+    }
+
+    info!(
+        "Updated {} item(s) in profile '{}', snapshot {}",
+        plan.updates.len(),
+        profile.name,
+        snapshot.id
+    );
+    Ok(snapshot)
+}
+
+/// Lists the update snapshots of a profile, newest first
+pub async fn list_update_snapshots(profile_id: Uuid) -> Result<Vec<UpdateSnapshot>> {
+    snapshot_utils::list_records(&snapshot_utils::get_snapshot_store_root(), profile_id).await
+}
+
+/// Rolls a profile back to the state before an "update all". Uses the latest snapshot
+/// unless `snapshot_id` is given. Mods added or removed after the update are left alone.
+pub async fn rollback_profile_update(
+    profile_id: Uuid,
+    snapshot_id: Option<Uuid>,
+) -> Result<UpdateSnapshot> {
+    let _guard = snapshot_utils::lock_store().await;
+    let snapshots = list_update_snapshots(profile_id).await?;
+    let snapshot = match snapshot_id {
+        Some(id) => snapshots.into_iter().find(|s| s.id == id),
+        None => snapshots.into_iter().next(),
+    }
+    .ok_or_else(|| {
+        AppError::NotFound(format!("No update snapshot found for profile {}", profile_id))
+    })?;
+
+    info!(
+        "Rolling back update {} of profile '{}' ({} item(s))",
+        snapshot.id,
+        snapshot.profile_name,
+        snapshot.updates.len()
+    );
+
+    let store_root = snapshot_utils::get_snapshot_store_root();
+    restore_files(&snapshot, &store_root).await?;
+
+    let state = State::get().await?;
+    let mut profile = state.profile_manager.get_profile(profile_id).await?;
+    for update in &snapshot.updates {
+        let Some(previous) = snapshot.previous_mods.iter().find(|m| Some(m.id) == update.mod_id)
+        else {
+            continue;
+        };
+        match profile.mods.iter_mut().find(|m| m.id == previous.id) {
+            Some(mod_entry) => {
+                // Keep whatever the user toggled since the update
+                let enabled = mod_entry.enabled;
+                *mod_entry = previous.clone();
+                mod_entry.enabled = enabled;
+            }
+            None => debug!(
+                "Mod {} was removed after the update, not restoring it",
+                previous.id
+            ),
+        }
+    }
+    state.profile_manager.update_profile(profile_id, profile).await?;
+
+    let cleanup = async {
+        snapshot_utils::delete_record::<UpdateSnapshot>(&store_root, profile_id, snapshot.id)
+            .await?;
+        snapshot_utils::remove_unreferenced_objects(&store_root).await
+    };
+    if let Err(e) = cleanup.await {
+        warn!("Failed to remove update snapshot {}: {}", snapshot.id, e);
+    }
+
+    info!("Rolled back update {} of profile {}", snapshot.id, profile_id);
+    Ok(snapshot)
+}
@@ -1,13 +1,23 @@
 use crate::config::HTTP_CLIENT;
 use crate::error::{AppError, Result};
+use crate::minecraft::offline;
 use crate::utils::hash_utils;
 use crate::utils::disk_space_utils::DiskSpaceUtils;
+use crate::utils::endpoint_registry;
 use futures::stream::StreamExt;
 use log::{debug, error, info, warn};
-use reqwest::Response;
+use rand::Rng;
+use reqwest::header::{HeaderName, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+/// How often (in bytes) the offset of a resumable download is persisted to disk
+const RESUME_CHECKPOINT_BYTES: u64 = 4 * 1024 * 1024;
 
 /// Configuration for file downloads
 pub struct DownloadConfig {
@@ -31,6 +41,12 @@ pub struct DownloadConfig {
     pub check_disk_space: bool,
     /// Buffer percentage for disk space check (default: 0.25 = 25%)
     pub disk_space_buffer: f64,
+    /// Keep partial downloads in a `.part` file and continue them with HTTP Range requests (default: true)
+    pub resume: bool,
+    /// Base delay for the exponential backoff between retry attempts
+    pub retry_base_delay: Duration,
+    /// Upper bound for the backoff delay between retry attempts
+    pub retry_max_delay: Duration,
 }
 
 impl std::fmt::Debug for DownloadConfig {
@@ -46,6 +62,9 @@ impl std::fmt::Debug for DownloadConfig {
             .field("progress_callback", &"<callback function>")
             .field("check_disk_space", &self.check_disk_space)
             .field("disk_space_buffer", &self.disk_space_buffer)
+            .field("resume", &self.resume)
+            .field("retry_base_delay", &self.retry_base_delay)
+            .field("retry_max_delay", &self.retry_max_delay)
             .finish()
     }
 }
@@ -65,6 +84,9 @@ impl Clone for DownloadConfig {
             progress_callback: None,
             check_disk_space: self.check_disk_space,
             disk_space_buffer: self.disk_space_buffer,
+            resume: self.resume,
+            retry_base_delay: self.retry_base_delay,
+            retry_max_delay: self.retry_max_delay,
         }
     }
 }
@@ -85,6 +107,9 @@ impl Default for DownloadConfig {
             progress_callback: None,
             check_disk_space: true,
             disk_space_buffer: 0.25, // 25% buffer by default
+            resume: true,
+            retry_base_delay: Duration::from_millis(500),
+            retry_max_delay: Duration::from_secs(30),
         }
     }
 }
@@ -146,6 +171,34 @@ impl DownloadConfig {
         self.disk_space_buffer = buffer_percentage;
         self
     }
+
+    /// Enables or disables resuming partial downloads (only used for streaming downloads)
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Sets the base and maximum delay for the exponential backoff between retries
+    pub fn with_retry_delay(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.retry_base_delay = base_delay;
+        self.retry_max_delay = max_delay;
+        self
+    }
+}
+
+/// Persisted state of a partial download, stored next to the `.part` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PartialDownloadState {
+    /// URL the partial file was downloaded from
+    url: String,
+    /// Expected SHA1 or SHA256 of the finished file. With a hash the partial file can be
+    /// continued from any mirror, since the result is verified anyway.
+    #[serde(default)]
+    expected_hash: Option<String>,
+    /// Number of bytes in the `.part` file that are known to be on disk
+    offset: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
 }
 
 /// Central download utility for robust file downloads
@@ -161,6 +214,19 @@ impl DownloadUtils {
         let target_path = target_path.as_ref();
         debug!("Starting download: {} -> {:?}", url, target_path);
 
+        // In offline mode only an existing, verified file counts - never touch the network
+        if offline::is_active() {
+            if Self::verify_existing_file(target_path, &config).await? {
+                debug!("[Offline] Using cached file: {:?}", target_path);
+                return Ok(());
+            }
+            let file_name = target_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| url.to_string());
+            return Err(offline::missing_artifact(file_name, target_path, Some(url)));
+        }
+
         // Check if file already exists and is valid
         if !config.force_overwrite && Self::verify_existing_file(target_path, &config).await? {
             info!("File already exists and passes verification: {:?}", target_path);
@@ -182,41 +248,53 @@ impl DownloadUtils {
             }
         }
 
+        // Configured base URL override and mirrors for this URL (just the URL itself if there are none).
+        // Attempts rotate through the candidates; a partial file with a known hash is resumed from any of them.
+        let candidate_urls = endpoint_registry::resolve_url(url);
+
         let mut attempt = 0;
         let mut last_error = None;
 
         while attempt <= config.max_retries {
+            let attempt_url = &candidate_urls[attempt as usize % candidate_urls.len()];
             if attempt > 0 {
-                warn!("Retry attempt {}/{} for: {}", attempt, config.max_retries, url);
+                let delay = Self::retry_delay(&config, attempt);
+                warn!(
+                    "Retry attempt {}/{} for: {} (waiting {:?})",
+                    attempt, config.max_retries, attempt_url, delay
+                );
+                tokio::time::sleep(delay).await;
             }
 
-            match Self::download_attempt(url, target_path, &config).await {
+            match Self::download_attempt(attempt_url, target_path, &config).await {
                 Ok(()) => {
-                    info!("Successfully downloaded: {} -> {:?}", url, target_path);
+                    info!("Successfully downloaded: {} -> {:?}", attempt_url, target_path);
                     return Ok(());
                 }
                 Err(e) => {
-                    error!("Download attempt {} failed for {}: {}", attempt + 1, url, e);
+                    error!("Download attempt {} failed for {}: {}", attempt + 1, attempt_url, e);
                     last_error = Some(e);
                     attempt += 1;
-                    
-                    if attempt <= config.max_retries {
-                        // Clean up partially downloaded file
-                        if target_path.exists() {
-                            debug!("Cleaning up partially downloaded file: {:?}", target_path);
-                            if let Err(cleanup_err) = fs::remove_file(target_path).await {
-                                warn!("Failed to clean up partial file {:?}: {}", target_path, cleanup_err);
-                            }
-                        }
-                        
-                        if attempt < config.max_retries {
-                            info!("Retrying download in next attempt...");
+
+                    // Clean up partially downloaded file
+                    if target_path.exists() {
+                        debug!("Cleaning up partially downloaded file: {:?}", target_path);
+                        if let Err(cleanup_err) = fs::remove_file(target_path).await {
+                            warn!("Failed to clean up partial file {:?}: {}", target_path, cleanup_err);
                         }
                     }
+
+                    // The .part file is kept between attempts so the next one can resume it
+                    if !config.resume {
+                        Self::discard_partial(target_path).await;
+                    }
                 }
             }
         }
 
+        // Giving up, a later download starts from zero instead of picking up a stale partial file
+        Self::discard_partial(target_path).await;
+
         let final_error = last_error.unwrap_or_else(|| {
             AppError::Download("Unknown download error".to_string())
         });
@@ -254,35 +332,139 @@ impl DownloadUtils {
         Self::download_file(url, target_path, config).await
     }
 
-    /// Single download attempt
-    async fn download_attempt(
+    /// Delay before the given retry attempt: exponential backoff with jitter.
+    /// The jitter picks a random value between 50% and 100% of the backoff so that
+    /// many parallel downloads don't hammer the server at the same moment.
+    fn retry_delay(config: &DownloadConfig, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = config
+            .retry_base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(config.retry_max_delay);
+        let jitter = rand::thread_rng().gen_range(0.5..=1.0);
+        backoff.mul_f64(jitter)
+    }
+
+    /// Path of the partial file for a download target (`<target>.part`)
+    pub fn part_path(target_path: &Path) -> PathBuf {
+        let mut file_name = target_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".part");
+        target_path.with_file_name(file_name)
+    }
+
+    /// Path of the persisted resume state for a download target (`<target>.part.json`)
+    fn part_state_path(target_path: &Path) -> PathBuf {
+        let mut file_name = target_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".part.json");
+        target_path.with_file_name(file_name)
+    }
+
+    /// Whether a partial file is still worth keeping after a response with this status.
+    /// Client errors won't go away on a retry, except for timeouts and rate limits.
+    fn is_resumable_status(status: StatusCode) -> bool {
+        !status.is_client_error()
+            || status == StatusCode::REQUEST_TIMEOUT
+            || status == StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// Removes the `.part` file and its resume state
+    async fn discard_partial(target_path: &Path) {
+        for path in [Self::part_path(target_path), Self::part_state_path(target_path)] {
+            if path.exists() {
+                debug!("Removing partial download file: {:?}", path);
+                if let Err(e) = fs::remove_file(&path).await {
+                    warn!("Failed to remove partial download file {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    /// Hash the finished file is verified against, identifies the file independent of its URL
+    fn expected_hash(config: &DownloadConfig) -> Option<String> {
+        config
+            .expected_sha1
+            .as_ref()
+            .or(config.expected_sha256.as_ref())
+            .map(|hash| hash.to_lowercase())
+    }
+
+    /// Loads the resume state for a download, if the `.part` file belongs to the same file.
+    /// Without an expected hash only a partial file from the same URL counts as the same file.
+    async fn load_partial_state(
         url: &str,
         target_path: &Path,
         config: &DownloadConfig,
-    ) -> Result<()> {
-        // Create parent directories
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).await.map_err(|e| {
-                AppError::Download(format!(
-                    "Failed to create parent directory for {:?}: {}",
-                    target_path, e
-                ))
-            })?;
+    ) -> Option<PartialDownloadState> {
+        let state_path = Self::part_state_path(target_path);
+        let part_path = Self::part_path(target_path);
+
+        let content = fs::read_to_string(&state_path).await.ok()?;
+        let mut state: PartialDownloadState = match serde_json::from_str(&content) {
+            Ok(state) => state,
+            Err(e) => {
+                warn!("Ignoring unreadable resume state {:?}: {}", state_path, e);
+                return None;
+            }
+        };
+
+        let same_file = match (Self::expected_hash(config), &state.expected_hash) {
+            (Some(expected), Some(stored)) => expected == *stored,
+            (None, None) => state.url == url,
+            _ => false,
+        };
+        if !same_file {
+            debug!(
+                "Partial download {:?} belongs to a different file ({}), starting over",
+                part_path, state.url
+            );
+            return None;
+        }
+
+        // Never resume past what is actually on disk
+        let part_len = fs::metadata(&part_path).await.ok()?.len();
+        state.offset = state.offset.min(part_len);
+        if state.offset == 0 {
+            return None;
+        }
+        Some(state)
+    }
+
+    async fn save_partial_state(target_path: &Path, state: &PartialDownloadState) {
+        let state_path = Self::part_state_path(target_path);
+        match serde_json::to_vec(state) {
+            Ok(content) => {
+                if let Err(e) = fs::write(&state_path, content).await {
+                    warn!("Failed to persist resume state {:?}: {}", state_path, e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize resume state for {:?}: {}", target_path, e),
         }
+    }
 
-        // Make HTTP request
+    fn build_request(url: &str, config: &DownloadConfig) -> RequestBuilder {
         let mut request = HTTP_CLIENT.get(url);
-        
+
         if let Some(user_agent) = &config.user_agent {
             request = request.header("User-Agent", user_agent);
         }
+        request
+    }
+
+    async fn send_request(url: &str, request: RequestBuilder) -> Result<Response> {
+        let response = Self::send_unchecked(url, request).await?;
+        Self::check_status(url, response).await
+    }
 
-        let response = request.send().await.map_err(|e| {
+    /// Sends the request without turning error statuses into errors
+    async fn send_unchecked(url: &str, request: RequestBuilder) -> Result<Response> {
+        request.send().await.map_err(|e| {
             let error_msg = format!("HTTP request failed for {}: {}", url, e);
             error!("{}", error_msg);
             AppError::Download(error_msg)
-        })?;
+        })
+    }
 
+    async fn check_status(url: &str, response: Response) -> Result<Response> {
         if !response.status().is_success() {
             let status = response.status();
             let error_msg = format!(
@@ -304,45 +486,166 @@ impl DownloadUtils {
             return Err(AppError::Download(error_msg));
         }
 
-        // Get content length for progress tracking
-        let content_length = response.content_length();
+        Ok(response)
+    }
+
+    /// Single download attempt
+    async fn download_attempt(
+        url: &str,
+        target_path: &Path,
+        config: &DownloadConfig,
+    ) -> Result<()> {
+        // Create parent directories
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                AppError::Download(format!(
+                    "Failed to create parent directory for {:?}: {}",
+                    target_path, e
+                ))
+            })?;
+        }
 
         if config.use_streaming {
-            Self::download_streaming(response, target_path, config, content_length).await
+            Self::download_streaming(url, target_path, config).await
         } else {
+            let response = Self::send_request(url, Self::build_request(url, config)).await?;
             Self::download_in_memory(response, target_path, config, url).await
         }
     }
 
-    /// Download using streaming (recommended for large files)
+    /// Download using streaming (recommended for large files).
+    /// Data is written to `<target>.part` and only renamed to the target after it was verified.
+    /// If a previous attempt left a partial file behind, it is continued with a Range request.
     async fn download_streaming(
-        response: Response,
+        url: &str,
         target_path: &Path,
         config: &DownloadConfig,
-        content_length: Option<u64>,
     ) -> Result<()> {
-        debug!("Creating file for streaming download: {:?}", target_path);
-        let mut file = fs::File::create(target_path).await.map_err(|e| {
-            let error_msg = format!("Failed to create file {:?}: {}", target_path, e);
-            error!("{}", error_msg);
-            AppError::Download(error_msg)
-        })?;
+        let part_path = Self::part_path(target_path);
+        let resume_state = if config.resume {
+            Self::load_partial_state(url, target_path, config).await
+        } else {
+            None
+        };
+
+        let mut request = Self::build_request(url, config);
+        if let Some(state) = &resume_state {
+            info!(
+                "Resuming download of {} at byte {} ({:?})",
+                url, state.offset, part_path
+            );
+            request = request.header(RANGE, format!("bytes={}-", state.offset));
+            // If the file changed on the server, If-Range makes it send the whole file again.
+            // Validators of another mirror never match, the hash check covers those.
+            if state.url == url {
+                if let Some(validator) = state.etag.as_ref().or(state.last_modified.as_ref()) {
+                    request = request.header(IF_RANGE, validator);
+                }
+            }
+        }
+
+        // Connection errors, timeouts and 5xx keep the partial file for the next attempt
+        let response = Self::send_unchecked(url, request).await?;
+        if !Self::is_resumable_status(response.status()) {
+            // e.g. 404 or 416 (the partial file doesn't match what the server has anymore)
+            Self::discard_partial(target_path).await;
+        }
+        let response = Self::check_status(url, response).await?;
+
+        let offset = match &resume_state {
+            Some(state) if response.status() == StatusCode::PARTIAL_CONTENT => {
+                let range_start = response
+                    .headers()
+                    .get(CONTENT_RANGE)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_content_range_start);
+                if range_start != Some(state.offset) {
+                    Self::discard_partial(target_path).await;
+                    return Err(AppError::Download(format!(
+                        "Server answered resume request for {} with unexpected range {:?}",
+                        url, range_start
+                    )));
+                }
+                state.offset
+            }
+            Some(_) => {
+                info!("Server did not honor the range request for {}, starting from zero", url);
+                0
+            }
+            None => 0,
+        };
+
+        let header_value = |name: HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let mut state = PartialDownloadState {
+            url: url.to_string(),
+            expected_hash: Self::expected_hash(config),
+            offset,
+            etag: header_value(ETAG),
+            last_modified: header_value(LAST_MODIFIED),
+        };
+
+        // Get content length for progress tracking
+        let content_length = response.content_length().map(|len| len + offset);
+
+        let mut file = if offset > 0 {
+            debug!("Opening partial file for resumed download: {:?}", part_path);
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .open(&part_path)
+                .await
+                .map_err(|e| {
+                    AppError::Download(format!("Failed to open partial file {:?}: {}", part_path, e))
+                })?;
+            // Drop anything behind the persisted offset, it may not have been written completely
+            file.set_len(offset).await?;
+            file.seek(SeekFrom::Start(offset)).await?;
+            file
+        } else {
+            debug!("Creating file for streaming download: {:?}", part_path);
+            fs::File::create(&part_path).await.map_err(|e| {
+                let error_msg = format!("Failed to create file {:?}: {}", part_path, e);
+                error!("{}", error_msg);
+                AppError::Download(error_msg)
+            })?
+        };
+
+        if config.resume {
+            Self::save_partial_state(target_path, &state).await;
+        }
 
         let mut stream = response.bytes_stream();
-        let mut downloaded = 0u64;
+        let mut downloaded = offset;
+        let mut last_checkpoint = offset;
         let mut chunk_count = 0u64;
 
-        debug!("Starting streaming download (content_length: {:?})", content_length);
+        debug!("Starting streaming download (content_length: {:?}, offset: {})", content_length, offset);
         
         while let Some(chunk_result) = stream.next().await {
-            let chunk = chunk_result.map_err(|e| {
-                let error_msg = format!("Stream error during download: {}", e);
-                error!("{}", error_msg);
-                AppError::Download(error_msg)
-            })?;
+            let chunk = match chunk_result {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    // Persist what we have so the next attempt can continue from here
+                    if config.resume && file.sync_data().await.is_ok() {
+                        state.offset = downloaded;
+                        Self::save_partial_state(target_path, &state).await;
+                    }
+                    let error_msg = format!(
+                        "Stream error during download after {} bytes: {}",
+                        downloaded, e
+                    );
+                    error!("{}", error_msg);
+                    return Err(AppError::Download(error_msg));
+                }
+            };
 
             file.write_all(&chunk).await.map_err(|e| {
-                let error_msg = format!("Write error for {:?}: {}", target_path, e);
+                let error_msg = format!("Write error for {:?}: {}", part_path, e);
                 error!("{}", error_msg);
                 AppError::Download(error_msg)
             })?;
@@ -350,6 +653,13 @@ impl DownloadUtils {
             downloaded += chunk.len() as u64;
             chunk_count += 1;
 
+            if config.resume && downloaded - last_checkpoint >= RESUME_CHECKPOINT_BYTES {
+                file.sync_data().await?;
+                state.offset = downloaded;
+                Self::save_partial_state(target_path, &state).await;
+                last_checkpoint = downloaded;
+            }
+
             // Log progress every 1000 chunks or every 10MB for large downloads
             if chunk_count % 1000 == 0 || downloaded % (10 * 1024 * 1024) == 0 {
                 debug!("Downloaded {} bytes in {} chunks", downloaded, chunk_count);
@@ -365,13 +675,21 @@ impl DownloadUtils {
 
         // Ensure file is fully written to disk - CRITICAL for preventing corruption
         file.sync_all().await.map_err(|e| {
-            AppError::Download(format!("Failed to sync file {:?}: {}", target_path, e))
+            AppError::Download(format!("Failed to sync file {:?}: {}", part_path, e))
         })?;
 
         // Explicitly close the file handle
         drop(file);
 
-        // Verify the downloaded file
+        fs::rename(&part_path, target_path).await.map_err(|e| {
+            AppError::Download(format!(
+                "Failed to move {:?} to {:?}: {}",
+                part_path, target_path, e
+            ))
+        })?;
+        Self::discard_partial(target_path).await;
+
+        // Verify the downloaded file - on failure the retry loop removes it and starts from zero
         Self::verify_downloaded_file(target_path, config).await
     }
 
@@ -645,4 +963,11 @@ impl DownloadUtils {
         debug!("EOCD signature not found - ZIP file incomplete: {:?}", file_path);
         false
     }
-}
\ No newline at end of file
+}
+
+/// Extracts the start offset from a `Content-Range: bytes <start>-<end>/<total>` header
+fn parse_content_range_start(value: &str) -> Option<u64> {
+    let range = value.trim().strip_prefix("bytes")?.trim_start();
+    let (start, _) = range.split_once('-')?;
+    start.trim().parse().ok()
+}
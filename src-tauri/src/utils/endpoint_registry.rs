@@ -0,0 +1,271 @@
+//! Central registry of all remote endpoints the launcher talks to.
+//!
+//! Every service has a default base URL which can be overridden in the `LauncherConfig`
+//! (`endpoints`), together with an ordered list of fallback mirrors (e.g. BMCLAPI for
+//! piston-meta, libraries and assets). API services build their URLs through this module,
+//! so the launcher can be pointed at an internal mirror or a local mock server.
+
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Endpoint {
+    /// Version manifest and version JSONs (piston-meta / launchermeta)
+    PistonMeta,
+    /// Client jars, server jars and logging configs (piston-data / launcher.mojang.com)
+    PistonData,
+    /// Vanilla libraries
+    Libraries,
+    /// Vanilla asset objects
+    Assets,
+    MojangApi,
+    MojangSession,
+    Modrinth,
+    CurseForge,
+    CurseForgeCdn,
+    FabricMeta,
+    FabricMaven,
+    QuiltMeta,
+    QuiltMaven,
+    ForgeMaven,
+    NeoForgeMaven,
+    Zulu,
+    Adoptium,
+    Corretto,
+    /// Oracle GraalVM downloads
+    GraalVm,
+    Mclogs,
+    NoriskApi,
+    NoriskApiStaging,
+    NoriskCdn,
+    Starlight,
+    Blog,
+}
+
+impl Endpoint {
+    pub const ALL: [Endpoint; 25] = [
+        Endpoint::PistonMeta,
+        Endpoint::PistonData,
+        Endpoint::Libraries,
+        Endpoint::Assets,
+        Endpoint::MojangApi,
+        Endpoint::MojangSession,
+        Endpoint::Modrinth,
+        Endpoint::CurseForge,
+        Endpoint::CurseForgeCdn,
+        Endpoint::FabricMeta,
+        Endpoint::FabricMaven,
+        Endpoint::QuiltMeta,
+        Endpoint::QuiltMaven,
+        Endpoint::ForgeMaven,
+        Endpoint::NeoForgeMaven,
+        Endpoint::Zulu,
+        Endpoint::Adoptium,
+        Endpoint::Corretto,
+        Endpoint::GraalVm,
+        Endpoint::Mclogs,
+        Endpoint::NoriskApi,
+        Endpoint::NoriskApiStaging,
+        Endpoint::NoriskCdn,
+        Endpoint::Starlight,
+        Endpoint::Blog,
+    ];
+
+    /// The built-in base URL (without trailing slash)
+    pub fn default_base_url(&self) -> &'static str {
+        match self {
+            Endpoint::PistonMeta => "https://piston-meta.mojang.com",
+            Endpoint::PistonData => "https://piston-data.mojang.com",
+            Endpoint::Libraries => "https://libraries.minecraft.net",
+            Endpoint::Assets => "https://resources.download.minecraft.net",
+            Endpoint::MojangApi => "https://api.mojang.com",
+            Endpoint::MojangSession => "https://sessionserver.mojang.com",
+            Endpoint::Modrinth => "https://api.modrinth.com/v2",
+            Endpoint::CurseForge => "https://api.curseforge.com/v1",
+            Endpoint::CurseForgeCdn => "https://edge.forgecdn.net/files",
+            Endpoint::FabricMeta => "https://meta.fabricmc.net/v2",
+            Endpoint::FabricMaven => "https://maven.fabricmc.net",
+            Endpoint::QuiltMeta => "https://meta.quiltmc.org/v3",
+            Endpoint::QuiltMaven => "https://maven.quiltmc.org/repository/release",
+            Endpoint::ForgeMaven => "https://maven.minecraftforge.net",
+            Endpoint::NeoForgeMaven => "https://maven.neoforged.net",
+            Endpoint::Zulu => "https://api.azul.com/zulu/download/community/v1.0",
+            Endpoint::Adoptium => "https://api.adoptium.net/v3",
+            Endpoint::Corretto => "https://corretto.aws",
+            Endpoint::GraalVm => "https://download.oracle.com/graalvm",
+            Endpoint::Mclogs => "https://api.mclo.gs/1",
+            Endpoint::NoriskApi => "https://api.norisk.gg/api/v1",
+            Endpoint::NoriskApiStaging => "https://api-staging.norisk.gg/api/v1",
+            Endpoint::NoriskCdn => "https://cdn.norisk.gg",
+            Endpoint::Starlight => "https://starlightskins.lunareclipse.studio",
+            Endpoint::Blog => "https://blog.norisk.gg/wp-json/wp/v2",
+        }
+    }
+
+    /// Additional hosts that serve the same content as the default base URL.
+    /// Absolute URLs (e.g. from version JSONs) starting with one of these are rewritten as well.
+    fn legacy_base_urls(&self) -> &'static [&'static str] {
+        match self {
+            Endpoint::PistonMeta => &["https://launchermeta.mojang.com"],
+            Endpoint::PistonData => &["https://launcher.mojang.com"],
+            _ => &[],
+        }
+    }
+}
+
+/// Override for a single endpoint
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EndpointOverride {
+    /// Replaces the default base URL
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Mirrors tried in order if the primary base URL fails
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct EndpointRegistry {
+    pub overrides: BTreeMap<Endpoint, EndpointOverride>,
+}
+
+impl EndpointRegistry {
+    /// The primary base URL for an endpoint (without trailing slash)
+    pub fn base_url(&self, endpoint: Endpoint) -> String {
+        self.overrides
+            .get(&endpoint)
+            .and_then(|o| o.base_url.as_deref())
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| endpoint.default_base_url())
+            .trim()
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// The primary base URL followed by all configured mirrors
+    pub fn base_urls(&self, endpoint: Endpoint) -> Vec<String> {
+        let mut urls = vec![self.base_url(endpoint)];
+        if let Some(endpoint_override) = self.overrides.get(&endpoint) {
+            for mirror in &endpoint_override.mirrors {
+                let mirror = mirror.trim().trim_end_matches('/').to_string();
+                if !mirror.is_empty() && !urls.contains(&mirror) {
+                    urls.push(mirror);
+                }
+            }
+        }
+        urls
+    }
+
+    /// Candidate URLs for a path below an endpoint, in the order they should be tried
+    pub fn urls_for(&self, endpoint: Endpoint, path: &str) -> Vec<String> {
+        let path = path.trim_start_matches('/');
+        self.base_urls(endpoint)
+            .into_iter()
+            .map(|base| format!("{}/{}", base, path))
+            .collect()
+    }
+
+    /// Rewrites an absolute URL that points to a known endpoint (e.g. a library URL from a
+    /// version JSON, or a URL built from `base_url`) to the configured base URL followed by
+    /// the mirrors. Unknown URLs are returned as-is.
+    pub fn resolve_url(&self, url: &str) -> Vec<String> {
+        for endpoint in Endpoint::ALL {
+            if !self.overrides.contains_key(&endpoint) {
+                continue;
+            }
+            let primary = self.base_url(endpoint);
+            let known = std::iter::once(primary.as_str())
+                .chain(std::iter::once(endpoint.default_base_url()))
+                .chain(endpoint.legacy_base_urls().iter().copied());
+            for prefix in known {
+                if let Some(rest) = url.strip_prefix(prefix) {
+                    if rest.is_empty() || rest.starts_with('/') || rest.starts_with('?') {
+                        return self
+                            .base_urls(endpoint)
+                            .into_iter()
+                            .map(|base| format!("{}{}", base, rest))
+                            .collect();
+                    }
+                }
+            }
+        }
+        vec![url.to_string()]
+    }
+}
+
+static ENDPOINT_REGISTRY_CACHE: Lazy<RwLock<EndpointRegistry>> =
+    Lazy::new(|| RwLock::new(EndpointRegistry::default()));
+
+/// Update the cached endpoint registry (called whenever the launcher config is loaded or changed)
+pub fn update_endpoint_registry(registry: EndpointRegistry) {
+    if let Ok(mut guard) = ENDPOINT_REGISTRY_CACHE.write() {
+        *guard = registry;
+    }
+}
+
+/// Snapshot of the currently active endpoint registry
+pub fn current() -> EndpointRegistry {
+    ENDPOINT_REGISTRY_CACHE
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+/// The primary base URL for an endpoint using the active registry
+pub fn base_url(endpoint: Endpoint) -> String {
+    current().base_url(endpoint)
+}
+
+/// Candidate URLs for a path below an endpoint using the active registry
+pub fn urls_for(endpoint: Endpoint, path: &str) -> Vec<String> {
+    current().urls_for(endpoint, path)
+}
+
+/// Rewrites an absolute URL using the active registry
+pub fn resolve_url(url: &str) -> Vec<String> {
+    current().resolve_url(url)
+}
+
+/// Sends a request to each candidate URL in order until one answers successfully.
+/// Connection errors, 404 and server errors fall through to the next mirror; other
+/// client errors (e.g. 401) are returned directly. The result of the last candidate is
+/// returned if all of them fail.
+pub async fn send_with_fallback<F>(urls: &[String], build_request: F) -> reqwest::Result<Response>
+where
+    F: Fn(&str) -> RequestBuilder,
+{
+    for (index, url) in urls.iter().enumerate() {
+        let is_last = index + 1 == urls.len();
+        match build_request(url).send().await {
+            Ok(response) => {
+                let status = response.status();
+                let try_next = status == StatusCode::NOT_FOUND
+                    || status == StatusCode::TOO_MANY_REQUESTS
+                    || status.is_server_error();
+                if !try_next || is_last {
+                    return Ok(response);
+                }
+                warn!(
+                    "[Endpoints] {} returned status {}, trying next mirror",
+                    url, status
+                );
+            }
+            Err(e) => {
+                if is_last {
+                    return Err(e);
+                }
+                warn!("[Endpoints] Request to {} failed: {}, trying next mirror", url, e);
+            }
+        }
+    }
+
+    // No candidates at all - let reqwest produce a proper error for the empty URL
+    debug!("[Endpoints] send_with_fallback called without candidate URLs");
+    build_request("").send().await
+}
@@ -4,6 +4,7 @@ use async_zip::tokio::read::seek::ZipFileReader;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use futures::AsyncReadExt;
 use log::debug;
+use std::collections::HashMap;
 use std::path::Path;
 use tokio::fs::File;
 
@@ -143,6 +144,57 @@ pub async fn find_first_png_in_archive_as_base64(archive_path: &Path) -> Result<
     Err(AppError::PngNotFoundInArchive(archive_path.to_path_buf()))
 }
 
+/// Reads the given entries from an archive, entries the archive doesn't contain are left out.
+pub async fn read_archive_entries(
+    archive_path: &Path,
+    entry_names: &[&str],
+) -> Result<HashMap<String, Vec<u8>>> {
+    if !archive_path.exists() {
+        return Err(AppError::FileNotFound(archive_path.to_path_buf()));
+    }
+
+    let file = File::open(archive_path).await?;
+    let mut zip = ZipFileReader::with_tokio(tokio::io::BufReader::new(file))
+        .await
+        .map_err(|e| {
+            AppError::ArchiveReadError(format!(
+                "Failed to read archive {}: {}",
+                archive_path.display(),
+                e
+            ))
+        })?;
+
+    let wanted: Vec<(usize, String)> = zip
+        .file()
+        .entries()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let filename = entry.filename().as_str().ok()?;
+            entry_names
+                .contains(&filename)
+                .then(|| (index, filename.to_string()))
+        })
+        .collect();
+
+    let mut contents = HashMap::new();
+    for (index, filename) in wanted {
+        let mut entry_reader = zip.reader_with_entry(index).await.map_err(|e| {
+            AppError::ArchiveReadError(format!(
+                "Failed to create reader for entry {}: {}",
+                filename, e
+            ))
+        })?;
+        let mut buffer = Vec::new();
+        entry_reader.read_to_end(&mut buffer).await.map_err(|e| {
+            AppError::ArchiveReadError(format!("Failed to read content of {}: {}", filename, e))
+        })?;
+        contents.insert(filename, buffer);
+    }
+
+    Ok(contents)
+}
+
 pub async fn get_jar_icon_test() {
     // Verwende einen Raw-String für den Windows-Pfad
     let path_str = r"C:\Users\sheesh\AppData\Roaming\norisk\NoRiskClientV3\meta\mod_cache\§fAbsolute §7[§f16x§7]§8.zip";
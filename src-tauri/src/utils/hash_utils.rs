@@ -1,5 +1,5 @@
 use sha1::{Digest, Sha1};
-use sha2::Sha256;
+use sha2::{Sha256, Sha512};
 use std::io;
 use std::path::{Path, PathBuf};
 use tokio::fs::File;
@@ -54,6 +54,24 @@ pub async fn calculate_sha256_from_file<P: AsRef<Path>>(path: P) -> Result<Strin
     Ok(format!("{:x}", hash_bytes)) // Format as hex string
 }
 
+/// Asynchronously calculates the SHA512 hash of a file (Modrinth uses it next to SHA1).
+pub async fn calculate_sha512_from_file<P: AsRef<Path>>(path: P) -> Result<String, io::Error> {
+    let mut file = File::open(path).await?;
+    let mut hasher = Sha512::new();
+    let mut buffer = [0; 8192]; // Read in chunks
+
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    let hash_bytes = hasher.finalize();
+    Ok(format!("{:x}", hash_bytes)) // Format as hex string
+}
+
 /// Calculates the SHA256 hash of a byte slice.
 pub fn calculate_sha256_from_bytes(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
@@ -0,0 +1,138 @@
+//! Launch hooks (pre-launch, wrapper, post-exit) and the variables they get.
+//!
+//! The global hooks live in `LauncherConfig::hooks`. A profile can override them or chain its
+//! own hooks after them (`ProfileSettings::hooks`). Every hook gets these variables in its
+//! environment, and `${NAME}` placeholders in the hook string are replaced with the same values:
+//!
+//! | Variable               | Value                                   |
+//! |------------------------|-----------------------------------------|
+//! | `NORISK_INSTANCE_DIR`  | Game directory of the profile           |
+//! | `NORISK_PROFILE_ID`    | Profile UUID                            |
+//! | `NORISK_PROFILE_NAME`  | Profile name                            |
+//! | `NORISK_JAVA_PATH`     | Java executable the game is started with |
+//! | `NORISK_GAME_VERSION`  | Minecraft version, e.g. `1.21.1`        |
+//! | `NORISK_LOADER`        | `vanilla`, `fabric`, `forge`, ...       |
+
+use crate::state::config_state::Hooks;
+use crate::state::profile_state::{HookMode, Profile, ProfileHooks};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::Command;
+use uuid::Uuid;
+
+/// Everything a hook is told about the launch
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HookContext {
+    pub instance_dir: PathBuf,
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    pub java_path: PathBuf,
+    pub game_version: String,
+    pub loader: String,
+}
+
+impl HookContext {
+    pub fn for_profile(profile: &Profile, instance_dir: PathBuf, java_path: PathBuf) -> Self {
+        Self {
+            instance_dir,
+            profile_id: profile.id,
+            profile_name: profile.name.clone(),
+            java_path,
+            game_version: profile.game_version.clone(),
+            loader: profile.loader.as_str().to_string(),
+        }
+    }
+
+    pub fn variables(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                "NORISK_INSTANCE_DIR",
+                self.instance_dir.to_string_lossy().into_owned(),
+            ),
+            ("NORISK_PROFILE_ID", self.profile_id.to_string()),
+            ("NORISK_PROFILE_NAME", self.profile_name.clone()),
+            (
+                "NORISK_JAVA_PATH",
+                self.java_path.to_string_lossy().into_owned(),
+            ),
+            ("NORISK_GAME_VERSION", self.game_version.clone()),
+            ("NORISK_LOADER", self.loader.clone()),
+        ]
+    }
+
+    /// Replaces `${NAME}` placeholders of the hook variables, unknown ones are kept as they are
+    pub fn substitute(&self, value: &str) -> String {
+        let mut result = value.to_string();
+        for (name, variable) in self.variables() {
+            result = result.replace(&format!("${{{}}}", name), &variable);
+        }
+        result
+    }
+
+    pub fn apply_env(&self, command: &mut Command) {
+        for (name, value) in self.variables() {
+            command.env(name, value);
+        }
+    }
+
+    /// Builds the command of a pre-launch or post-exit hook. The hook is split on whitespace,
+    /// placeholders are replaced per argument so paths with spaces stay one argument.
+    pub fn command(&self, hook: &str) -> Option<Command> {
+        let mut parts = hook.split_whitespace().map(|part| self.substitute(part));
+        let mut command = Command::new(parts.next()?);
+        command.args(parts).current_dir(&self.instance_dir);
+        self.apply_env(&mut command);
+        Some(command)
+    }
+}
+
+/// Program and arguments of chained wrappers, outermost first. Every wrapper is split like the
+/// other hooks, so `mangohud --dlsym` inside `gamemoderun` stays two arguments.
+pub fn wrapper_args(wrappers: &[String], context: Option<&HookContext>) -> Vec<String> {
+    wrappers
+        .iter()
+        .flat_map(|wrapper| wrapper.split_whitespace())
+        .map(|part| match context {
+            Some(context) => context.substitute(part),
+            None => part.to_string(),
+        })
+        .collect()
+}
+
+/// The hooks to run for one launch, in execution order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedHooks {
+    pub pre_launch: Vec<String>,
+    /// Outermost wrapper first, java is appended after the last one
+    pub wrapper: Vec<String>,
+    pub post_exit: Vec<String>,
+}
+
+/// Combines the global hooks with the ones of a profile (if any). With `HookMode::Override` a
+/// profile hook replaces the global one and an empty profile hook disables it. With
+/// `HookMode::Chain` the global hook runs first (or wraps the profile's wrapper), then the profile's.
+pub fn resolve_hooks(global: &Hooks, profile: Option<&ProfileHooks>) -> ResolvedHooks {
+    let default_hooks = ProfileHooks::default();
+    let profile = profile.unwrap_or(&default_hooks);
+    ResolvedHooks {
+        pre_launch: combine(&global.pre_launch, &profile.pre_launch, profile.mode),
+        wrapper: combine(&global.wrapper, &profile.wrapper, profile.mode),
+        post_exit: combine(&global.post_exit, &profile.post_exit, profile.mode),
+    }
+}
+
+fn combine(global: &Option<String>, profile: &Option<String>, mode: HookMode) -> Vec<String> {
+    let global = global.as_deref().map(str::trim).filter(|h| !h.is_empty());
+    let profile = profile.as_deref().map(str::trim);
+
+    let hooks: Vec<&str> = match (mode, profile) {
+        (_, None) => global.into_iter().collect(),
+        (HookMode::Override, Some(hook)) => vec![hook],
+        (HookMode::Chain, Some(hook)) => global.into_iter().chain(Some(hook)).collect(),
+    };
+    hooks
+        .into_iter()
+        .filter(|h| !h.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,732 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::profile_state::ModLoader;
+use crate::utils::json_cache::JsonCache;
+use crate::utils::{file_utils, hash_utils};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use futures::stream::{self, StreamExt};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tokio::sync::RwLock;
+
+const CACHE_FILENAME: &str = "jar_metadata_cache.json";
+const MANIFEST: &str = "META-INF/MANIFEST.MF";
+
+/// How many jars are inspected at the same time
+const CONCURRENT_JAR_READS: usize = 8;
+
+lazy_static! {
+    static ref JAR_METADATA_CACHE: Arc<RwLock<Option<JarMetadataCache>>> =
+        Arc::new(RwLock::new(None));
+    // Base64 icons by jar sha1, only kept in memory as they would bloat the cache file
+    static ref JAR_ICONS: Arc<RwLock<HashMap<String, Option<String>>>> =
+        Arc::new(RwLock::new(HashMap::new()));
+}
+
+/// Metadata file a mod is described in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModMetadataFormat {
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+    LegacyForge,
+}
+
+impl ModMetadataFormat {
+    /// All formats, in the order they are preferred when a jar declares several
+    pub const ALL: [ModMetadataFormat; 5] = [
+        ModMetadataFormat::Quilt,
+        ModMetadataFormat::Fabric,
+        ModMetadataFormat::NeoForge,
+        ModMetadataFormat::Forge,
+        ModMetadataFormat::LegacyForge,
+    ];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ModMetadataFormat::Fabric => "fabric.mod.json",
+            ModMetadataFormat::Quilt => "quilt.mod.json",
+            ModMetadataFormat::Forge => "META-INF/mods.toml",
+            ModMetadataFormat::NeoForge => "META-INF/neoforge.mods.toml",
+            ModMetadataFormat::LegacyForge => "mcmod.info",
+        }
+    }
+
+    /// Whether the loader reads this format
+    pub fn is_read_by(self, loader: ModLoader) -> bool {
+        match self {
+            ModMetadataFormat::Fabric => matches!(loader, ModLoader::Fabric | ModLoader::Quilt),
+            ModMetadataFormat::Quilt => loader == ModLoader::Quilt,
+            ModMetadataFormat::Forge => matches!(loader, ModLoader::Forge | ModLoader::NeoForge),
+            ModMetadataFormat::NeoForge => loader == ModLoader::NeoForge,
+            ModMetadataFormat::LegacyForge => loader == ModLoader::Forge,
+        }
+    }
+}
+
+/// Side a mod has to be installed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModEnvironment {
+    #[default]
+    Both,
+    Client,
+    Server,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModDependencyKind {
+    Required,
+    Optional,
+    Incompatible,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModDependency {
+    pub mod_id: String,
+    /// Version range in the syntax of the metadata format, alternatives are joined by ` || `
+    pub version_range: Option<String>,
+    pub kind: ModDependencyKind,
+}
+
+/// What a mod declares about itself in its jar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModMetadata {
+    pub format: ModMetadataFormat,
+    pub mod_id: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    pub dependencies: Vec<ModDependency>,
+    pub environment: ModEnvironment,
+    /// Path of the icon inside the jar
+    pub icon: Option<String>,
+}
+
+impl ModMetadata {
+    fn new(format: ModMetadataFormat, mod_id: String) -> Self {
+        Self {
+            format,
+            mod_id,
+            name: None,
+            version: None,
+            description: None,
+            authors: Vec::new(),
+            dependencies: Vec::new(),
+            environment: ModEnvironment::Both,
+            icon: None,
+        }
+    }
+
+    pub fn required_dependency(&self, mod_id: &str) -> Option<&ModDependency> {
+        self.dependencies
+            .iter()
+            .find(|dep| dep.kind == ModDependencyKind::Required && dep.mod_id == mod_id)
+    }
+}
+
+/// Metadata of all formats a jar declares
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JarMetadata {
+    pub sha1: String,
+    pub mods: Vec<ModMetadata>,
+}
+
+impl JarMetadata {
+    pub fn primary(&self) -> Option<&ModMetadata> {
+        self.mods.first()
+    }
+
+    /// Metadata the given loader reads, falls back to the primary one
+    pub fn for_loader(&self, loader: ModLoader) -> Option<&ModMetadata> {
+        self.mods
+            .iter()
+            .find(|metadata| metadata.format.is_read_by(loader))
+            .or_else(|| self.primary())
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// A version requirement that is either a string or a list of alternatives
+fn json_version_range(value: &Value) -> Option<String> {
+    match value {
+        Value::String(range) => Some(range.clone()),
+        Value::Array(ranges) => Some(
+            ranges
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" || "),
+        ),
+        _ => None,
+    }
+}
+
+/// An icon path, or a map of sizes to paths of which the largest is used
+fn json_icon(value: &Value) -> Option<String> {
+    let icon = match value {
+        Value::String(path) => Some(path.as_str()),
+        Value::Object(sizes) => sizes
+            .iter()
+            .filter_map(|(size, path)| Some((size.parse::<u32>().unwrap_or(0), path.as_str()?)))
+            .max_by_key(|(size, _)| *size)
+            .map(|(_, path)| path),
+        _ => None,
+    };
+    non_empty(icon.map(|path| path.trim_start_matches('/')))
+}
+
+fn json_string(value: &Value, key: &str) -> Option<String> {
+    non_empty(value.get(key).and_then(Value::as_str))
+}
+
+/// Parses a `fabric.mod.json`
+pub fn parse_fabric_mod_json(content: &str) -> Result<ModMetadata> {
+    let json: Value = serde_json::from_str(content)?;
+    let mut metadata = ModMetadata::new(
+        ModMetadataFormat::Fabric,
+        json_string(&json, "id").unwrap_or_default(),
+    );
+    metadata.name = json_string(&json, "name");
+    metadata.version = json_string(&json, "version");
+    metadata.description = json_string(&json, "description");
+    metadata.icon = json.get("icon").and_then(json_icon);
+    metadata.environment = match json.get("environment").and_then(Value::as_str) {
+        Some("client") => ModEnvironment::Client,
+        Some("server") => ModEnvironment::Server,
+        _ => ModEnvironment::Both,
+    };
+    if let Some(authors) = json.get("authors").and_then(Value::as_array) {
+        metadata.authors = authors
+            .iter()
+            .filter_map(|author| match author {
+                Value::String(name) => non_empty(Some(name.as_str())),
+                person => json_string(person, "name"),
+            })
+            .collect();
+    }
+
+    for (key, kind) in [
+        ("depends", ModDependencyKind::Required),
+        ("recommends", ModDependencyKind::Optional),
+        ("suggests", ModDependencyKind::Optional),
+        ("breaks", ModDependencyKind::Incompatible),
+        ("conflicts", ModDependencyKind::Incompatible),
+    ] {
+        let Some(dependencies) = json.get(key).and_then(Value::as_object) else {
+            continue;
+        };
+        let mut dependencies: Vec<_> = dependencies
+            .iter()
+            .map(|(mod_id, range)| ModDependency {
+                mod_id: mod_id.clone(),
+                version_range: json_version_range(range),
+                kind,
+            })
+            .collect();
+        dependencies.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+        metadata.dependencies.extend(dependencies);
+    }
+
+    Ok(metadata)
+}
+
+/// Parses a `quilt.mod.json`
+pub fn parse_quilt_mod_json(content: &str) -> Result<ModMetadata> {
+    let json: Value = serde_json::from_str(content)?;
+    let loader = json
+        .get("quilt_loader")
+        .ok_or_else(|| AppError::ParseError("quilt.mod.json has no quilt_loader".to_string()))?;
+    let mut metadata = ModMetadata::new(
+        ModMetadataFormat::Quilt,
+        json_string(loader, "id").unwrap_or_default(),
+    );
+    metadata.version = json_string(loader, "version");
+    if let Some(info) = loader.get("metadata") {
+        metadata.name = json_string(info, "name");
+        metadata.description = json_string(info, "description");
+        metadata.icon = info.get("icon").and_then(json_icon);
+        if let Some(contributors) = info.get("contributors").and_then(Value::as_object) {
+            metadata.authors = contributors.keys().cloned().collect();
+            metadata.authors.sort();
+        }
+    }
+    metadata.environment = match json
+        .pointer("/minecraft/environment")
+        .and_then(Value::as_str)
+    {
+        Some("client") => ModEnvironment::Client,
+        Some("dedicated_server") => ModEnvironment::Server,
+        _ => ModEnvironment::Both,
+    };
+
+    for (key, kind) in [
+        ("depends", ModDependencyKind::Required),
+        ("breaks", ModDependencyKind::Incompatible),
+    ] {
+        let Some(dependencies) = loader.get(key).and_then(Value::as_array) else {
+            continue;
+        };
+        for dependency in dependencies {
+            let dependency = match dependency {
+                Value::String(mod_id) => ModDependency {
+                    mod_id: mod_id.clone(),
+                    version_range: None,
+                    kind,
+                },
+                object => {
+                    let Some(mod_id) = json_string(object, "id") else {
+                        continue;
+                    };
+                    let optional = object.get("optional").and_then(Value::as_bool) == Some(true);
+                    ModDependency {
+                        mod_id,
+                        version_range: object.get("versions").and_then(json_version_range),
+                        kind: if optional && kind == ModDependencyKind::Required {
+                            ModDependencyKind::Optional
+                        } else {
+                            kind
+                        },
+                    }
+                }
+            };
+            metadata.dependencies.push(dependency);
+        }
+    }
+
+    Ok(metadata)
+}
+
+/// Parses a Forge `mods.toml` or NeoForge `neoforge.mods.toml`. Only the first declared mod is
+/// described, the dependencies of all mods in the file are collected.
+pub fn parse_mods_toml(content: &str, format: ModMetadataFormat) -> Result<ModMetadata> {
+    let toml: toml::Table = content
+        .parse()
+        .map_err(|e| AppError::ParseError(format!("Invalid {}: {}", format.file_name(), e)))?;
+    let first_mod = toml
+        .get("mods")
+        .and_then(toml::Value::as_array)
+        .and_then(|mods| mods.first())
+        .and_then(toml::Value::as_table);
+    let mod_string = |key: &str| {
+        non_empty(
+            first_mod
+                .and_then(|table| table.get(key))
+                .or_else(|| toml.get(key))
+                .and_then(toml::Value::as_str),
+        )
+    };
+
+    let mut metadata = ModMetadata::new(format, mod_string("modId").unwrap_or_default());
+    metadata.name = mod_string("displayName");
+    metadata.version = mod_string("version");
+    metadata.description = mod_string("description");
+    metadata.icon = mod_string("logoFile").map(|path| path.trim_start_matches('/').to_string());
+    metadata.authors = match first_mod.and_then(|table| table.get("authors")) {
+        Some(toml::Value::String(authors)) => authors
+            .split(',')
+            .filter_map(|author| non_empty(Some(author)))
+            .collect(),
+        Some(toml::Value::Array(authors)) => authors
+            .iter()
+            .filter_map(|author| non_empty(author.as_str()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let client_side_only = toml.get("clientSideOnly").and_then(toml::Value::as_bool) == Some(true);
+    metadata.environment = match mod_string("displayTest").as_deref() {
+        _ if client_side_only => ModEnvironment::Client,
+        Some("IGNORE_ALL_VERSION") => ModEnvironment::Client,
+        Some("IGNORE_SERVER_VERSION") => ModEnvironment::Server,
+        _ => ModEnvironment::Both,
+    };
+
+    let dependency_tables = toml
+        .get("dependencies")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|dependencies| dependencies.values())
+        .filter_map(toml::Value::as_array)
+        .flatten()
+        .filter_map(toml::Value::as_table);
+    for dependency in dependency_tables {
+        let Some(mod_id) = non_empty(dependency.get("modId").and_then(toml::Value::as_str)) else {
+            continue;
+        };
+        // Forge uses `mandatory`, NeoForge `type`
+        let kind = match dependency.get("type").and_then(toml::Value::as_str) {
+            Some(kind) if kind.eq_ignore_ascii_case("required") => ModDependencyKind::Required,
+            Some(kind) if kind.eq_ignore_ascii_case("optional") => ModDependencyKind::Optional,
+            Some(_) => ModDependencyKind::Incompatible,
+            None => match dependency.get("mandatory") {
+                Some(toml::Value::Boolean(false)) => ModDependencyKind::Optional,
+                Some(toml::Value::String(mandatory)) if mandatory == "false" => {
+                    ModDependencyKind::Optional
+                }
+                _ => ModDependencyKind::Required,
+            },
+        };
+        metadata.dependencies.push(ModDependency {
+            mod_id,
+            version_range: non_empty(dependency.get("versionRange").and_then(toml::Value::as_str)),
+            kind,
+        });
+    }
+
+    Ok(metadata)
+}
+
+/// Parses a legacy Forge `mcmod.info`, either a plain list of mods or a `modList` object
+pub fn parse_mcmod_info(content: &str) -> Result<ModMetadata> {
+    let json: Value = serde_json::from_str(content)?;
+    let first_mod = match &json {
+        Value::Array(mods) => mods.first(),
+        object => object
+            .get("modList")
+            .and_then(Value::as_array)
+            .and_then(|mods| mods.first()),
+    }
+    .ok_or_else(|| AppError::ParseError("mcmod.info declares no mod".to_string()))?;
+
+    let mut metadata = ModMetadata::new(
+        ModMetadataFormat::LegacyForge,
+        json_string(first_mod, "modid").unwrap_or_default(),
+    );
+    metadata.name = json_string(first_mod, "name");
+    metadata.version = json_string(first_mod, "version");
+    metadata.description = json_string(first_mod, "description");
+    metadata.icon = json_string(first_mod, "logoFile")
+        .map(|path| path.trim_start_matches('/').to_string())
+        .filter(|path| !path.is_empty());
+    metadata.authors = first_mod
+        .get("authorList")
+        .or_else(|| first_mod.get("authors"))
+        .and_then(Value::as_array)
+        .map(|authors| {
+            authors
+                .iter()
+                .filter_map(|author| non_empty(author.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Entries look like `modid` or `modid@[1.0,)`, `dependencies` only affects the load order
+    let mut seen = HashSet::new();
+    for (key, kind) in [
+        ("requiredMods", ModDependencyKind::Required),
+        ("dependencies", ModDependencyKind::Optional),
+    ] {
+        let Some(dependencies) = first_mod.get(key).and_then(Value::as_array) else {
+            continue;
+        };
+        for dependency in dependencies.iter().filter_map(Value::as_str) {
+            let (mod_id, version_range) = match dependency.split_once('@') {
+                Some((mod_id, range)) => (mod_id.trim(), non_empty(Some(range))),
+                None => (dependency.trim(), None),
+            };
+            if !mod_id.is_empty() && seen.insert(mod_id.to_string()) {
+                metadata.dependencies.push(ModDependency {
+                    mod_id: mod_id.to_string(),
+                    version_range,
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok(metadata)
+}
+
+fn parse_metadata(format: ModMetadataFormat, content: &str) -> Result<ModMetadata> {
+    match format {
+        ModMetadataFormat::Fabric => parse_fabric_mod_json(content),
+        ModMetadataFormat::Quilt => parse_quilt_mod_json(content),
+        ModMetadataFormat::Forge | ModMetadataFormat::NeoForge => parse_mods_toml(content, format),
+        ModMetadataFormat::LegacyForge => parse_mcmod_info(content),
+    }
+}
+
+/// Value of a main section attribute in a jar manifest
+fn manifest_attribute(manifest: &str, name: &str) -> Option<String> {
+    manifest
+        .lines()
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == name)
+        .and_then(|(_, value)| non_empty(Some(value)))
+}
+
+/// Reads the metadata of every format a jar declares, in order of preference. Files that
+/// can't be parsed are skipped. Doesn't use the cache.
+pub async fn inspect_jar(jar_path: &Path) -> Result<Vec<ModMetadata>> {
+    let mut file_names: Vec<&str> = ModMetadataFormat::ALL
+        .iter()
+        .map(|format| format.file_name())
+        .collect();
+    file_names.push(MANIFEST);
+    let entries = file_utils::read_archive_entries(jar_path, &file_names).await?;
+
+    // Forge mods usually take their version from the manifest
+    let manifest_version = entries.get(MANIFEST).and_then(|manifest| {
+        manifest_attribute(&String::from_utf8_lossy(manifest), "Implementation-Version")
+    });
+
+    let mut mods = Vec::new();
+    for format in ModMetadataFormat::ALL {
+        let Some(content) = entries.get(format.file_name()) else {
+            continue;
+        };
+        match parse_metadata(format, &String::from_utf8_lossy(content)) {
+            Ok(mut metadata) => {
+                if metadata
+                    .version
+                    .as_deref()
+                    .map_or(false, |version| version.contains("${"))
+                {
+                    metadata.version = manifest_version.clone();
+                }
+                mods.push(metadata);
+            }
+            Err(e) => warn!(
+                "Ignoring invalid {} in {}: {}",
+                format.file_name(),
+                jar_path.display(),
+                e
+            ),
+        }
+    }
+    Ok(mods)
+}
+
+pub fn is_jar(path: &Path) -> bool {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    file_name.ends_with(".jar") || file_name.ends_with(".jar.disabled")
+}
+
+/// Size and modification time a jar had when it was hashed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JarFileStamp {
+    pub size: u64,
+    pub modified_ms: u64,
+    pub sha1: String,
+}
+
+impl JarFileStamp {
+    pub fn of(path: &Path, sha1: String) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            modified_ms: modified.as_millis() as u64,
+            sha1,
+        })
+    }
+}
+
+/// Jar metadata keyed by the jar's sha1, so renamed or copied jars aren't inspected again.
+/// The hashes of known files are kept as well to not hash unchanged jars on every call.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JarMetadataCache {
+    #[serde(default)]
+    pub entries: HashMap<String, Vec<ModMetadata>>,
+    #[serde(default)]
+    pub files: HashMap<PathBuf, JarFileStamp>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl JsonCache for JarMetadataCache {
+    const NAME: &'static str = "jar metadata cache";
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn set_dirty(&mut self, dirty: bool) {
+        self.dirty = dirty;
+    }
+
+    /// Drops files that no longer exist and metadata no remaining file refers to
+    fn prune(&mut self) {
+        let (files, entries) = (self.files.len(), self.entries.len());
+        self.files.retain(|path, _| path.exists());
+        let referenced: HashSet<&str> = self.files.values().map(|s| s.sha1.as_str()).collect();
+        self.entries
+            .retain(|sha1, _| referenced.contains(sha1.as_str()));
+        self.dirty |= self.files.len() != files || self.entries.len() != entries;
+    }
+}
+
+impl JarMetadataCache {
+    pub fn get(&self, sha1: &str) -> Option<&Vec<ModMetadata>> {
+        self.entries.get(sha1)
+    }
+
+    pub fn insert(&mut self, sha1: String, mods: Vec<ModMetadata>) {
+        self.entries.insert(sha1, mods);
+        self.dirty = true;
+    }
+
+    /// Hash of the file if it is unchanged since it was last hashed
+    pub fn known_sha1(&self, path: &Path) -> Option<&str> {
+        let stamp = self.files.get(path)?;
+        let current = JarFileStamp::of(path, String::new())?;
+        (stamp.size == current.size && stamp.modified_ms == current.modified_ms)
+            .then_some(stamp.sha1.as_str())
+    }
+
+    pub fn remember_file(&mut self, path: PathBuf, stamp: JarFileStamp) {
+        if self.files.get(&path) != Some(&stamp) {
+            self.files.insert(path, stamp);
+            self.dirty = true;
+        }
+    }
+}
+
+fn cache_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join(CACHE_FILENAME)
+}
+
+async fn ensure_cache_loaded() {
+    let mut cache = JAR_METADATA_CACHE.write().await;
+    if cache.is_none() {
+        let mut loaded = JarMetadataCache::load(&cache_path()).await;
+        loaded.prune();
+        *cache = Some(loaded);
+    }
+}
+
+async fn save_cache() {
+    if let Some(cache) = JAR_METADATA_CACHE.write().await.as_mut() {
+        cache.save_if_dirty(&cache_path()).await;
+    }
+}
+
+async fn lookup_jar_metadata(jar_path: &Path, known_sha1: Option<String>) -> Result<JarMetadata> {
+    if !jar_path.exists() {
+        return Err(AppError::FileNotFound(jar_path.to_path_buf()));
+    }
+    ensure_cache_loaded().await;
+
+    let cached_sha1 = {
+        let cache = JAR_METADATA_CACHE.read().await;
+        cache
+            .as_ref()
+            .and_then(|cache| cache.known_sha1(jar_path))
+            .map(str::to_string)
+    };
+    let sha1 = match known_sha1.or(cached_sha1) {
+        Some(sha1) => sha1.to_lowercase(),
+        None => hash_utils::calculate_sha1_from_file(jar_path).await?,
+    };
+
+    let cached_mods = {
+        let cache = JAR_METADATA_CACHE.read().await;
+        cache.as_ref().and_then(|cache| cache.get(&sha1)).cloned()
+    };
+    let mods = match &cached_mods {
+        Some(mods) => mods.clone(),
+        None => {
+            debug!("Inspecting jar {}", jar_path.display());
+            inspect_jar(jar_path).await?
+        }
+    };
+
+    let mut cache = JAR_METADATA_CACHE.write().await;
+    let cache = cache.get_or_insert_with(JarMetadataCache::default);
+    if let Some(stamp) = JarFileStamp::of(jar_path, sha1.clone()) {
+        cache.remember_file(jar_path.to_path_buf(), stamp);
+    }
+    if cached_mods.is_none() {
+        cache.insert(sha1.clone(), mods.clone());
+    }
+    Ok(JarMetadata { sha1, mods })
+}
+
+/// Metadata of a jar, from the cache unless the jar changed. A known sha1 (e.g. from Modrinth)
+/// saves hashing the jar.
+pub async fn get_jar_metadata(jar_path: &Path, known_sha1: Option<String>) -> Result<JarMetadata> {
+    let metadata = lookup_jar_metadata(jar_path, known_sha1).await;
+    save_cache().await;
+    metadata
+}
+
+/// Metadata of several jars, keyed by path. Jars that can't be read are left out.
+pub async fn get_jars_metadata(
+    jars: Vec<(PathBuf, Option<String>)>,
+) -> HashMap<PathBuf, JarMetadata> {
+    let results: Vec<(PathBuf, Result<JarMetadata>)> = stream::iter(jars)
+        .map(|(path, known_sha1)| async move {
+            let metadata = lookup_jar_metadata(&path, known_sha1).await;
+            (path, metadata)
+        })
+        .buffer_unordered(CONCURRENT_JAR_READS)
+        .collect()
+        .await;
+    save_cache().await;
+
+    results
+        .into_iter()
+        .filter_map(|(path, metadata)| match metadata {
+            Ok(metadata) => Some((path, metadata)),
+            Err(e) => {
+                warn!("Failed to read metadata of {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Base64 encoded icon of a jar: the icon its metadata declares, otherwise the first PNG in it
+pub async fn get_jar_icon(jar_path: &Path) -> Result<Option<String>> {
+    let metadata = get_jar_metadata(jar_path, None).await?;
+    if let Some(icon) = JAR_ICONS.read().await.get(&metadata.sha1) {
+        return Ok(icon.clone());
+    }
+
+    let mut icon = None;
+    if let Some(icon_path) = metadata.mods.iter().find_map(|m| m.icon.as_deref()) {
+        let entries = file_utils::read_archive_entries(jar_path, &[icon_path]).await?;
+        icon = entries.get(icon_path).map(|bytes| STANDARD.encode(bytes));
+    }
+    if icon.is_none() {
+        icon = match file_utils::find_first_png_in_archive_as_base64(jar_path).await {
+            Ok(icon) => Some(icon),
+            Err(AppError::PngNotFoundInArchive(_)) => None,
+            Err(e) => return Err(e),
+        };
+    }
+
+    JAR_ICONS.write().await.insert(metadata.sha1, icon.clone());
+    Ok(icon)
+}
+
+/// Like `file_utils::find_first_png_in_archive_as_base64`, but jars use their declared icon
+/// and are cached by sha1
+pub async fn find_archive_icon_as_base64(archive_path: &Path) -> Result<String> {
+    if !is_jar(archive_path) {
+        return file_utils::find_first_png_in_archive_as_base64(archive_path).await;
+    }
+    get_jar_icon(archive_path)
+        .await?
+        .ok_or_else(|| AppError::PngNotFoundInArchive(archive_path.to_path_buf()))
+}
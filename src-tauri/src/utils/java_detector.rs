@@ -1,24 +1,33 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::minecraft::java_requirements::JavaRequirement;
+use crate::utils::json_cache::JsonCache;
 use crate::utils::system_info::{Architecture, OperatingSystem, OS};
 use lazy_static::lazy_static;
 use log::{info, warn};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 use std::process::Stdio;
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 use std::sync::Arc;
+use std::time::UNIX_EPOCH;
 use tokio::fs;
 use tokio::sync::RwLock;
 
+const PROBE_CACHE_FILENAME: &str = "java_detection_cache.json";
+
 // Global cache of detected Java installations
 lazy_static! {
     static ref JAVA_INSTALLATIONS: Arc<RwLock<Option<Vec<JavaInstallation>>>> =
         Arc::new(RwLock::new(None));
+    // `java -version` results, loaded from disk on the first scan
+    static ref JAVA_PROBE_CACHE: Arc<RwLock<Option<JavaProbeCache>>> =
+        Arc::new(RwLock::new(None));
 }
 
 /// Represents a detected Java installation
@@ -100,6 +109,110 @@ impl JavaInstallation {
     }
 }
 
+/// `java -version` results of earlier scans, keyed by the resolved executable path. An entry
+/// is only used while the executable's modification time is unchanged, so an updated JDK is
+/// probed again.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JavaProbeCache {
+    #[serde(default)]
+    pub entries: HashMap<PathBuf, JavaProbeEntry>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaProbeEntry {
+    /// Modification time of the executable in milliseconds since the epoch
+    pub modified_ms: u64,
+    pub installation: JavaInstallation,
+}
+
+impl JsonCache for JavaProbeCache {
+    const NAME: &'static str = "Java detection cache";
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn set_dirty(&mut self, dirty: bool) {
+        self.dirty = dirty;
+    }
+
+    /// Drops executables that no longer exist
+    fn prune(&mut self) {
+        let before = self.entries.len();
+        self.entries.retain(|path, _| path.exists());
+        self.dirty |= self.entries.len() != before;
+    }
+}
+
+impl JavaProbeCache {
+    pub fn get(&self, path: &Path, modified_ms: u64) -> Option<&JavaInstallation> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.modified_ms == modified_ms)
+            .map(|entry| &entry.installation)
+    }
+
+    pub fn insert(&mut self, path: PathBuf, modified_ms: u64, installation: JavaInstallation) {
+        self.entries.insert(
+            path,
+            JavaProbeEntry {
+                modified_ms,
+                installation,
+            },
+        );
+        self.dirty = true;
+    }
+}
+
+fn probe_cache_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(PROBE_CACHE_FILENAME)
+}
+
+/// Modification time of a file in milliseconds since the epoch
+pub fn executable_modified_ms(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+/// Like `get_java_info`, but reuses the result of an earlier scan if the executable (after
+/// resolving symlinks) hasn't changed since
+async fn probe_java(java_path: &Path) -> Result<JavaInstallation> {
+    let resolved = fs::canonicalize(java_path)
+        .await
+        .unwrap_or_else(|_| java_path.to_path_buf());
+    let Some(modified_ms) = executable_modified_ms(&resolved) else {
+        return get_java_info(java_path).await;
+    };
+
+    {
+        let mut cache = JAVA_PROBE_CACHE.write().await;
+        if cache.is_none() {
+            *cache = Some(JavaProbeCache::load(&probe_cache_path()).await);
+        }
+        if let Some(installation) = cache.as_ref().and_then(|c| c.get(&resolved, modified_ms)) {
+            let mut installation = installation.clone();
+            installation.path = java_path.to_path_buf();
+            return Ok(installation);
+        }
+    }
+
+    let installation = get_java_info(java_path).await?;
+    if let Some(cache) = JAVA_PROBE_CACHE.write().await.as_mut() {
+        cache.insert(resolved, modified_ms, installation.clone());
+    }
+    Ok(installation)
+}
+
+/// Writes new probe results to disk
+async fn save_probe_cache() {
+    if let Some(cache) = JAVA_PROBE_CACHE.write().await.as_mut() {
+        cache.prune();
+        cache.save_if_dirty(&probe_cache_path()).await;
+    }
+}
+
 /// Detects Java installations in the launcher's meta/java directory
 async fn detect_java_in_launcher_dir() -> Result<Vec<JavaInstallation>> {
     info!("Detecting Java installations in launcher directory");
@@ -138,7 +251,7 @@ async fn detect_java_in_launcher_dir() -> Result<Vec<JavaInstallation>> {
         };
 
         if java_exe.exists() {
-            match get_java_info(&java_exe).await {
+            match probe_java(&java_exe).await {
                 Ok(mut info) => {
                     let version_clone = info.version.clone(); // Clone version before move
                     info.source = "Launcher Directory".to_string();
@@ -162,7 +275,7 @@ async fn detect_java_in_launcher_dir() -> Result<Vec<JavaInstallation>> {
             };
 
             if direct_java_exe.exists() {
-                match get_java_info(&direct_java_exe).await {
+                match probe_java(&direct_java_exe).await {
                     Ok(mut info) => {
                         let version_clone = info.version.clone(); // Clone version before move
                         info.source = "Launcher Directory".to_string();
@@ -236,6 +349,20 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>> {
         Err(e) => warn!("Failed to detect Java in PATH: {}", e),
     }
 
+    // JAVA_HOME
+    match detect_java_in_java_home().await {
+        Ok(Some(installation)) => {
+            info!(
+                "Found Java in JAVA_HOME: {} ({})",
+                installation.path.display(),
+                installation.version
+            );
+            installations.push(installation);
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Failed to detect Java in JAVA_HOME: {}", e),
+    }
+
     // OS-specific paths
     match OS {
         OperatingSystem::WINDOWS => {
@@ -289,6 +416,21 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>> {
         _ => warn!("Unsupported OS for Java detection"),
     }
 
+    // The same JDK is often reachable through several symlinks (PATH, alternatives, SDKMAN's
+    // `current`), keep the first one found
+    let mut seen = HashSet::new();
+    let mut unique = Vec::with_capacity(installations.len());
+    for installation in installations {
+        let resolved = fs::canonicalize(&installation.path)
+            .await
+            .unwrap_or_else(|_| installation.path.clone());
+        if seen.insert(resolved) {
+            unique.push(installation);
+        }
+    }
+    let mut installations = unique;
+    save_probe_cache().await;
+
     // Remove duplicates based on path
     installations.sort_by(|a, b| {
         let path_cmp = a.path.to_string_lossy().cmp(&b.path.to_string_lossy());
@@ -391,6 +533,12 @@ pub async fn get_java_info(java_path: &Path) -> Result<JavaInstallation> {
         .captures(&version_output)
         .and_then(|caps| caps.get(1))
         .map(|m| m.as_str().to_string())
+        // Oracle's own builds only call themselves "Java(TM)"
+        .or_else(|| {
+            version_output
+                .contains("Java(TM)")
+                .then(|| "Oracle".to_string())
+        })
         .unwrap_or_else(|| "Unknown".to_string());
 
     // Determine VM
@@ -421,9 +569,17 @@ pub async fn get_java_info(java_path: &Path) -> Result<JavaInstallation> {
     })
 }
 
-/// Finds the best Java installation for the given Minecraft version
-pub async fn find_best_java_for_minecraft(mc_version: &str) -> Result<Option<JavaInstallation>> {
-    let installations = detect_java_installations().await?;
+/// Finds the best Java installation for the given Minecraft version,
+/// limited to installations that satisfy the mods' Java requirements if given
+pub async fn find_best_java_for_minecraft(
+    mc_version: &str,
+    mod_requirement: Option<&JavaRequirement>,
+) -> Result<Option<JavaInstallation>> {
+    let installations: Vec<JavaInstallation> = detect_java_installations()
+        .await?
+        .into_iter()
+        .filter(|java| mod_requirement.map_or(true, |r| r.is_satisfied_by(java.major_version)))
+        .collect();
 
     if installations.is_empty() {
         return Ok(None);
@@ -497,7 +653,7 @@ async fn detect_java_in_system_path() -> Result<Vec<JavaInstallation>> {
 
         let java_path = path.join(java_exe);
         if java_path.exists() {
-            match get_java_info(&java_path).await {
+            match probe_java(&java_path).await {
                 Ok(mut info) => {
                     info.source = "PATH".to_string();
                     installations.push(info);
@@ -514,6 +670,23 @@ async fn detect_java_in_system_path() -> Result<Vec<JavaInstallation>> {
     Ok(installations)
 }
 
+/// Detects the Java installation `JAVA_HOME` points to
+async fn detect_java_in_java_home() -> Result<Option<JavaInstallation>> {
+    let Some(java_home) = std::env::var_os("JAVA_HOME").filter(|home| !home.is_empty()) else {
+        return Ok(None);
+    };
+    let java_exe = Path::new(&java_home)
+        .join("bin")
+        .join(get_java_executable_name());
+    if !java_exe.exists() {
+        warn!("JAVA_HOME has no Java executable: {}", java_exe.display());
+        return Ok(None);
+    }
+    let mut info = probe_java(&java_exe).await?;
+    info.source = "JAVA_HOME".to_string();
+    Ok(Some(info))
+}
+
 /// Detects Java installations on Windows
 async fn detect_java_on_windows() -> Result<Vec<JavaInstallation>> {
     info!("Detecting Java installations on Windows");
@@ -557,7 +730,7 @@ async fn detect_java_on_windows() -> Result<Vec<JavaInstallation>> {
                         // Check if there's a bin/java.exe
                         let java_exe = path.join("bin").join("java.exe");
                         if java_exe.exists() {
-                            match get_java_info(&java_exe).await {
+                            match probe_java(&java_exe).await {
                                 Ok(mut info) => {
                                     info.source = format!("Windows ({})", location.display());
                                     installations.push(info);
@@ -614,7 +787,7 @@ async fn detect_java_on_macos() -> Result<Vec<JavaInstallation>> {
                         let java_home = path.join("Contents").join("Home");
                         let java_exe = java_home.join("bin").join("java");
                         if java_exe.exists() {
-                            match get_java_info(&java_exe).await {
+                            match probe_java(&java_exe).await {
                                 Ok(mut info) => {
                                     info.source = format!("macOS ({})", location.display());
                                     installations.push(info);
@@ -651,7 +824,7 @@ async fn detect_java_on_macos() -> Result<Vec<JavaInstallation>> {
                                 .to_string();
                             let java_exe = Path::new(&path_str).join("bin").join("java");
                             if java_exe.exists() {
-                                match get_java_info(&java_exe).await {
+                                match probe_java(&java_exe).await {
                                     Ok(mut info) => {
                                         info.source = "java_home command".to_string();
                                         installations.push(info);
@@ -674,51 +847,165 @@ async fn detect_java_on_macos() -> Result<Vec<JavaInstallation>> {
     Ok(installations)
 }
 
-/// Detects Java installations on Linux
-async fn detect_java_on_linux() -> Result<Vec<JavaInstallation>> {
-    info!("Detecting Java installations on Linux");
-    let mut installations = Vec::new();
+/// Where Java lives on Linux, as (source, pattern) pairs. Patterns point at the `java`
+/// executable; a `*` matches any part of a single path segment.
+pub fn linux_java_patterns(
+    home: Option<&Path>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Vec<(String, PathBuf)> {
+    let mut patterns: Vec<(String, PathBuf)> = Vec::new();
+    let mut add = |source: &str, pattern: PathBuf| patterns.push((source.to_string(), pattern));
+
+    // Distribution packages and manually extracted JDKs
+    add(
+        "Linux (/usr/lib/jvm)",
+        PathBuf::from("/usr/lib/jvm/*/bin/java"),
+    );
+    add(
+        "Linux (/usr/lib64/jvm)",
+        PathBuf::from("/usr/lib64/jvm/*/bin/java"),
+    );
+    add("Linux (/opt)", PathBuf::from("/opt/*/bin/java"));
+    add("Linux (/opt)", PathBuf::from("/opt/*/*/bin/java"));
+
+    // Version managers, which honor their own directory variables
+    let home_dir = |var: &str, default: &str| {
+        env(var)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home.map(|home| home.join(default)))
+    };
+    if let Some(sdkman) = home_dir("SDKMAN_DIR", ".sdkman") {
+        add("SDKMAN", sdkman.join("candidates/java/*/bin/java"));
+    }
+    if let Some(asdf) = home_dir("ASDF_DATA_DIR", ".asdf") {
+        add("asdf", asdf.join("installs/java/*/bin/java"));
+    }
+    if let Some(jenv) = home_dir("JENV_ROOT", ".jenv") {
+        add("jenv", jenv.join("versions/*/bin/java"));
+    }
 
-    // Common locations to check on Linux
-    let locations = vec![
-        // System packages
-        PathBuf::from("/usr/lib/jvm"),
-        // User installations
-        PathBuf::from("/opt/java"),
-        PathBuf::from("/opt/jdk"),
-        PathBuf::from("/opt/openjdk"),
-    ];
+    // Flatpak OpenJDK extensions, from the host and from inside the sandbox
+    let flatpak_runtime =
+        "runtime/org.freedesktop.Sdk.Extension.openjdk*/*/*/active/files/jvm/*/bin/java";
+    add(
+        "Flatpak",
+        Path::new("/var/lib/flatpak").join(flatpak_runtime),
+    );
+    if let Some(home) = home {
+        add(
+            "Flatpak",
+            home.join(".local/share/flatpak").join(flatpak_runtime),
+        );
+    }
+    add(
+        "Flatpak",
+        PathBuf::from("/usr/lib/sdk/openjdk*/jvm/*/bin/java"),
+    );
 
-    for location in locations {
-        if !location.exists() {
+    // Nix profiles and store paths
+    if let Some(home) = home {
+        add("Nix", home.join(".nix-profile/bin/java"));
+    }
+    add("Nix", PathBuf::from("/run/current-system/sw/bin/java"));
+    if let Some(user) = env("USER").filter(|user| !user.is_empty()) {
+        add(
+            "Nix",
+            Path::new("/etc/profiles/per-user")
+                .join(user)
+                .join("bin/java"),
+        );
+    }
+    add(
+        "Nix",
+        PathBuf::from("/nix/store/*-openjdk-*/lib/openjdk/bin/java"),
+    );
+    add("Nix", PathBuf::from("/nix/store/*-zulu*/bin/java"));
+    add("Nix", PathBuf::from("/nix/store/*-temurin-*/bin/java"));
+
+    patterns
+}
+
+/// Matches one path segment against a pattern where `*` stands for any text
+pub fn matches_segment(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (index, part) in parts.iter().enumerate() {
+        if index + 1 == parts.len() {
+            return rest.len() >= part.len() && rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    // No `*` at all
+    rest.is_empty()
+}
+
+/// All existing files matching a pattern from `linux_java_patterns`
+pub async fn expand_java_pattern(pattern: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::new()];
+    for component in pattern.components() {
+        let segment = match component {
+            Component::Normal(segment) => segment.to_string_lossy(),
+            other => {
+                candidates
+                    .iter_mut()
+                    .for_each(|c| c.push(other.as_os_str()));
+                continue;
+            }
+        };
+        if !segment.contains('*') {
+            candidates.iter_mut().for_each(|c| c.push(&*segment));
             continue;
         }
 
-        // Try to list all directories in this location
-        match fs::read_dir(&location).await {
-            Ok(mut read_dir) => {
-                while let Ok(Some(entry)) = read_dir.next_entry().await {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        // Check for bin/java
-                        let java_exe = path.join("bin").join("java");
-                        if java_exe.exists() {
-                            match get_java_info(&java_exe).await {
-                                Ok(mut info) => {
-                                    info.source = format!("Linux ({})", location.display());
-                                    installations.push(info);
-                                }
-                                Err(e) => warn!(
-                                    "Failed to get info for Java at {}: {}",
-                                    java_exe.display(),
-                                    e
-                                ),
-                            }
-                        }
-                    }
+        let mut expanded = Vec::new();
+        for candidate in &candidates {
+            let Ok(mut read_dir) = fs::read_dir(candidate).await else {
+                continue;
+            };
+            while let Ok(Some(entry)) = read_dir.next_entry().await {
+                let name = entry.file_name();
+                if matches_segment(&segment, &name.to_string_lossy()) {
+                    expanded.push(candidate.join(name));
                 }
             }
-            Err(e) => warn!("Failed to read directory {}: {}", location.display(), e),
+        }
+        expanded.sort();
+        candidates = expanded;
+        if candidates.is_empty() {
+            break;
+        }
+    }
+    candidates.retain(|candidate| candidate.is_file());
+    candidates
+}
+
+/// Detects Java installations on Linux
+async fn detect_java_on_linux() -> Result<Vec<JavaInstallation>> {
+    info!("Detecting Java installations on Linux");
+    let mut installations = Vec::new();
+
+    let home = dirs::home_dir();
+    for (source, pattern) in linux_java_patterns(home.as_deref(), |key| std::env::var(key).ok()) {
+        for java_exe in expand_java_pattern(&pattern).await {
+            match probe_java(&java_exe).await {
+                Ok(mut info) => {
+                    info.source = source.clone();
+                    installations.push(info);
+                }
+                Err(e) => warn!(
+                    "Failed to get info for Java at {}: {}",
+                    java_exe.display(),
+                    e
+                ),
+            }
         }
     }
 
@@ -736,7 +1023,7 @@ async fn detect_java_on_linux() -> Result<Vec<JavaInstallation>> {
                 if !java_path.is_empty() {
                     let java_exe = PathBuf::from(java_path);
                     if java_exe.exists() {
-                        match get_java_info(&java_exe).await {
+                        match probe_java(&java_exe).await {
                             Ok(mut info) => {
                                 info.source = "update-alternatives".to_string();
                                 installations.push(info);
@@ -763,7 +1050,7 @@ async fn detect_java_on_linux() -> Result<Vec<JavaInstallation>> {
                 if !java_path.is_empty() {
                     let java_exe = PathBuf::from(java_path);
                     if java_exe.exists() {
-                        match get_java_info(&java_exe).await {
+                        match probe_java(&java_exe).await {
                             Ok(mut info) => {
                                 info.source = "which command".to_string();
                                 installations.push(info);
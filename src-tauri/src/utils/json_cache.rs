@@ -0,0 +1,69 @@
+use crate::error::Result;
+use async_trait::async_trait;
+use log::warn;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Display;
+use std::path::Path;
+use tokio::fs;
+
+/// Copies a JSON file that couldn't be read to `<file>.bak`, so starting over with an empty
+/// file doesn't overwrite what was in it
+pub async fn back_up_unreadable(path: &Path, error: impl Display) -> Result<()> {
+    let backup = path.with_extension("json.bak");
+    warn!(
+        "Failed to read {:?} ({}), backing it up to {:?}",
+        path, error, backup
+    );
+    fs::copy(path, &backup).await?;
+    Ok(())
+}
+
+/// A cache that is kept in memory and stored as a JSON file. Implementors track whether they
+/// changed since they were loaded or saved, so an unchanged cache isn't written again.
+#[async_trait]
+pub trait JsonCache: Default + Serialize + DeserializeOwned + Send + Sync {
+    /// What the cache holds, used in log messages
+    const NAME: &'static str;
+
+    fn is_dirty(&self) -> bool;
+
+    fn set_dirty(&mut self, dirty: bool);
+
+    /// Drops entries that refer to files which no longer exist
+    fn prune(&mut self) {}
+
+    /// A missing or unreadable cache file is an empty cache
+    async fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path).await {
+            Ok(content) => content,
+            Err(_) => return Self::default(),
+        };
+        match serde_json::from_str(&content) {
+            Ok(cache) => cache,
+            Err(e) => {
+                warn!("Ignoring corrupt {} {}: {}", Self::NAME, path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    async fn save(&mut self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, serde_json::to_string(&*self)?).await?;
+        self.set_dirty(false);
+        Ok(())
+    }
+
+    /// Writes the cache if it changed. Failures are only logged, the cache stays dirty.
+    async fn save_if_dirty(&mut self, path: &Path) {
+        if !self.is_dirty() {
+            return;
+        }
+        if let Err(e) = self.save(path).await {
+            warn!("Failed to save {}: {}", Self::NAME, e);
+        }
+    }
+}
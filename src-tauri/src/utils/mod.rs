@@ -1,18 +1,25 @@
+pub mod bulk_update_utils; // Profile-wide "update all" for Modrinth content with rollback snapshots
 pub mod datapack_utils; // DataPack-Utils für das Scannen und Verwalten von DataPacks
 pub mod debug_utils;
 pub mod disk_space_utils; // Disk space utility for checking available space before downloads
 pub mod download_utils; // Central download utility for robust file downloads
+pub mod endpoint_registry; // Configurable base URLs and fallback mirrors for all remote endpoints
 pub mod file_utils; // Utilities for file operations like reading archives
 pub mod hash_utils;
+pub mod hook_utils; // Global and per-profile launch hooks and their variables
+pub mod jar_inspector; // Reads mod metadata from jars, cached by sha1
 pub mod java_detector; // Java detector to find Java installations
+pub mod json_cache; // Load/save helpers for caches stored as JSON files
 pub mod mc_utils; // Utilities for Minecraft-related operations
 pub mod migration_utils; // Migration utilities for profile and config updates
 pub mod path_utils; // Deklariert das neue Modul und macht seinen Inhalt (wenn `pub`) nutzbar
 pub mod profile_utils; // Utility-Funktionen für Profilinhalte wie das Installieren von Modrinth-Content
+pub mod redaction_utils; // Masks tokens, paths, IPs and emails in logs before uploading them
 pub mod repair_utils; // Repair utilities for fixing profile issues
 pub mod resourcepack_utils; // ResourcePack-Utils für das Scannen und Verwalten von ResourcePacks
 pub mod server_ping; // Server-Ping-Modul für die Kommunikation mit Minecraft-Servern
 pub mod shaderpack_utils; // ShaderPack-Utils für das Scannen und Verwalten von ShaderPacks
+pub mod snapshot_utils; // Profile snapshots with a content-addressed file store
 pub mod system_info; // <-- Hinzufügen
 pub mod updater_utils;
 pub mod world_utils; // <-- Hinzugefügt
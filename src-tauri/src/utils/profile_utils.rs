@@ -6,6 +6,7 @@ use crate::state::profile_state::ModSource;
 use crate::state::profile_state::Profile;
 use crate::state::state_manager::State;
 use crate::utils::file_utils;
+use crate::utils::jar_inspector::{self, ModMetadata};
 use crate::utils::{datapack_utils, hash_utils, resourcepack_utils, shaderpack_utils};
 use async_zip::tokio::write::ZipFileWriter;
 use async_zip::{Compression, ZipEntryBuilder};
@@ -914,6 +915,24 @@ pub async fn list_log_files(profile_id: Uuid) -> Result<Vec<PathBuf>> {
     Ok(log_files)
 }
 
+/// Archive format used when exporting a profile
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileExportFormat {
+    #[default]
+    NoriskPack,
+    Mrpack,
+}
+
+impl ProfileExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProfileExportFormat::NoriskPack => "noriskpack",
+            ProfileExportFormat::Mrpack => "mrpack",
+        }
+    }
+}
+
 /// Exports a profile to a `.noriskpack` file
 ///
 /// This creates a zip archive with the .noriskpack extension that contains:
@@ -1099,7 +1118,7 @@ fn sanitize_profile_for_export(profile: &Profile) -> Profile {
 }
 
 /// Collect all files recursively (like Modrinth's add_all_recursive_folder_paths)
-fn collect_all_files_recursive<'a>(
+pub(crate) fn collect_all_files_recursive<'a>(
     dir_path: &'a Path,
     file_list: &'a mut Vec<PathBuf>,
 ) -> BoxFuture<'a, Result<()>> {
@@ -1941,6 +1960,7 @@ pub struct LocalContentItem {
     pub fallback_version: Option<String>, // Fallback Version aus dem compatibility target
     pub id: Option<String>,               // Added optional ID field
     pub associated_loader: Option<crate::state::profile_state::ModLoader>, // Added associated_loader
+    pub metadata: Option<ModMetadata>, // Metadata read from the mod jar (name, version, authors, ...)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)] // Ensure Serialize and Deserialize are here
@@ -2093,6 +2113,7 @@ impl LocalContentLoader {
                                 fallback_version: fallback_version,
                                 id: None,
                                 associated_loader: None,
+                                metadata: None,
                             });
                         }
                     }
@@ -2110,6 +2131,9 @@ impl LocalContentLoader {
                         crate::state::profile_state::ModSource::Modrinth {
                             ref file_name, ..
                         } => filename = Some(file_name.clone()),
+                        crate::state::profile_state::ModSource::CurseForge {
+                            ref file_name, ..
+                        } => filename = Some(file_name.clone()),
                         crate::state::profile_state::ModSource::Local { ref file_name, .. } => {
                             filename = Some(file_name.clone())
                         }
@@ -2149,6 +2173,7 @@ impl LocalContentLoader {
                     // Smarter fallback: if this profile mod comes from Modrinth/Url/Maven, point to mod_cache
                     match &mod_item.source {
                         crate::state::profile_state::ModSource::Modrinth { .. }
+                        | crate::state::profile_state::ModSource::CurseForge { .. }
                         | crate::state::profile_state::ModSource::Url { .. }
                         | crate::state::profile_state::ModSource::Maven { .. } => {
                             crate::config::ProjectDirsExt::meta_dir(&*crate::config::LAUNCHER_DIRECTORY)
@@ -2165,6 +2190,9 @@ impl LocalContentLoader {
                 let sha1_hash = match mod_item.source {
                     crate::state::profile_state::ModSource::Modrinth {
                         ref file_hash_sha1, ..
+                    }
+                    | crate::state::profile_state::ModSource::CurseForge {
+                        ref file_hash_sha1, ..
                     } => file_hash_sha1.clone(),
                     _ => None,
                 };
@@ -2204,6 +2232,7 @@ impl LocalContentLoader {
                     fallback_version: mod_item.version.clone(),
                     id: Some(mod_item.id.to_string()), // Set the ID from ModProfileEntry
                     associated_loader: mod_item.associated_loader.clone(), // Populate associated_loader
+                    metadata: None,
                 });
             }
         }
@@ -2322,6 +2351,7 @@ impl LocalContentLoader {
                     fallback_version: None,
                     id: None,
                     associated_loader: None,
+                    metadata: None,
                 });
             }
         }
@@ -2427,6 +2457,25 @@ impl LocalContentLoader {
             }
         }
 
+        // Read the metadata of mod jars, unchanged jars come from the cache
+        if matches!(
+            params.content_type,
+            ContentType::Mod | ContentType::NoRiskMod
+        ) {
+            let jars: Vec<(PathBuf, Option<String>)> = final_items
+                .iter()
+                .map(|item| (PathBuf::from(&item.path_str), item.sha1_hash.clone()))
+                .filter(|(path, _)| jar_inspector::is_jar(path) && path.exists())
+                .collect();
+            let jar_metadata = jar_inspector::get_jars_metadata(jars).await;
+            for item in final_items.iter_mut() {
+                item.metadata = jar_metadata
+                    .get(Path::new(&item.path_str))
+                    .and_then(|metadata| metadata.for_loader(profile.loader))
+                    .cloned();
+            }
+        }
+
         if params.fetch_modrinth_data {
             // Use params.fetch_modrinth_data
             let mut hashes_for_modrinth_lookup: HashMap<String, Vec<usize>> = HashMap::new(); // sha1 -> Vec of indices in final_items
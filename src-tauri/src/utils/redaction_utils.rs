@@ -0,0 +1,319 @@
+//! Masks sensitive data in logs and crash reports before they leave the machine.
+//!
+//! Every upload (mclo.gs, NoRisk crash reports) goes through a `Redactor` built from
+//! `LauncherConfig::redaction`. The built-in rules cover launcher/session tokens, access keys,
+//! home-directory paths, IPv4/IPv6 addresses and email-like strings; each can be switched off and
+//! users can add their own regex rules. `Redactor::preview` shows the changed lines before sharing.
+
+use crate::error::{AppError, Result};
+use crate::state::state_manager::State;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinRedaction {
+    /// `-Dnorisk.token=`, `--accessToken`, session IDs and JWTs
+    Tokens,
+    /// `api_key=..`, `password: ..`, `Bearer ..` and the like
+    AccessKeys,
+    /// The user's home directory and `/home/<user>`, `C:\Users\<user>` style paths
+    HomePaths,
+    Ipv4,
+    Ipv6,
+    Emails,
+}
+
+impl BuiltinRedaction {
+    pub const ALL: [BuiltinRedaction; 6] = [
+        BuiltinRedaction::Tokens,
+        BuiltinRedaction::AccessKeys,
+        BuiltinRedaction::HomePaths,
+        BuiltinRedaction::Ipv4,
+        BuiltinRedaction::Ipv6,
+        BuiltinRedaction::Emails,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuiltinRedaction::Tokens => "tokens",
+            BuiltinRedaction::AccessKeys => "access_keys",
+            BuiltinRedaction::HomePaths => "home_paths",
+            BuiltinRedaction::Ipv4 => "ipv4",
+            BuiltinRedaction::Ipv6 => "ipv6",
+            BuiltinRedaction::Emails => "emails",
+        }
+    }
+
+    /// (pattern, replacement) pairs, applied in order
+    fn patterns(&self) -> &'static [(Regex, &'static str)] {
+        match self {
+            BuiltinRedaction::Tokens => &TOKEN_PATTERNS,
+            BuiltinRedaction::AccessKeys => &ACCESS_KEY_PATTERNS,
+            BuiltinRedaction::HomePaths => &HOME_PATH_PATTERNS,
+            BuiltinRedaction::Ipv4 => &IPV4_PATTERNS,
+            BuiltinRedaction::Ipv6 => &IPV6_PATTERNS,
+            BuiltinRedaction::Emails => &EMAIL_PATTERNS,
+        }
+    }
+}
+
+fn compile(patterns: &[(&str, &'static str)]) -> Vec<(Regex, &'static str)> {
+    patterns
+        .iter()
+        .map(|(pattern, replacement)| {
+            (
+                Regex::new(pattern).expect("Invalid redaction regex"),
+                *replacement,
+            )
+        })
+        .collect()
+}
+
+static TOKEN_PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    compile(&[
+        (r"(-Dnorisk\.token=)[^\s]+", "${1}<token>"),
+        (r"(--accessToken[ \t]+)[^\s]+", "${1}<token>"),
+        // "(Session ID is token:<access token>:<uuid>)"
+        (r"\btoken:[^\s:)]+:[0-9a-fA-F-]+", "token:<token>"),
+        (r"\beyJ[\w-]+\.[\w-]+\.[\w-]+", "<token>"),
+    ])
+});
+
+static ACCESS_KEY_PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    compile(&[
+        (
+            r#"(?i)\b((?:access|api|secret|client)[_-]?(?:token|key|secret)|password|passwd|authorization)(["']?[ \t]*[:=][ \t]*["']?)(?:Bearer[ \t]+)?[^\s"',;&]+"#,
+            "${1}${2}<secret>",
+        ),
+        (r"(?i)\b(Bearer[ \t]+)[\w.~+/-]+=*", "${1}<secret>"),
+    ])
+});
+
+static HOME_PATH_PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    let mut patterns = Vec::new();
+    // The actual home directory first, so `~` keeps custom locations like /var/home/<user> readable
+    if let Some(home) = dirs::home_dir()
+        .map(|home| {
+            home.to_string_lossy()
+                .trim_end_matches(['/', '\\'])
+                .to_string()
+        })
+        .filter(|home| home.len() > 2)
+    {
+        let case_insensitive = if cfg!(windows) { "(?i)" } else { "" };
+        let pattern = format!(
+            r#"{}{}([\\/\s"':;,)\]]|$)"#,
+            case_insensitive,
+            regex::escape(&home)
+        );
+        if let Ok(regex) = Regex::new(&pattern) {
+            patterns.push((regex, "~${1}"));
+        }
+    }
+    patterns.extend(compile(&[
+        (
+            r#"(?i)\b([A-Z]:[\\/]+(?:Users|Documents and Settings)[\\/]+)[^\\/\s"']+"#,
+            "${1}<user>",
+        ),
+        (r#"(/(?:home|Users)/)[^/\s"']+"#, "${1}<user>"),
+    ]));
+    patterns
+});
+
+static IPV4_PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    compile(&[(
+        // Not right after a name, `-`, `_` or `.`, so four-part versions like `sodium-1.2.3.4` or
+        // `v1.2.3.4` are left alone (the regex crate has no lookbehind, hence the captured prefix)
+        r"(^|[^\w.+-])(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\b",
+        "${1}<ip>",
+    )])
+});
+
+static IPV6_PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    compile(&[(
+        // Full form, or compressed with hex groups on both sides of `::` (so `12:00:01` and
+        // `Foo::bar` are left alone)
+        r"(?i)\b(?:(?:[0-9a-f]{1,4}:){7}[0-9a-f]{1,4}|(?:[0-9a-f]{1,4}:){1,6}:(?:[0-9a-f]{1,4}:){0,5}[0-9a-f]{1,4})\b",
+        "<ip>",
+    )])
+});
+
+static EMAIL_PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    compile(&[(
+        r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}\b",
+        "<email>",
+    )])
+});
+
+/// A user-defined rule. `replacement` may use `$1`-style capture groups.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomRedactionRule {
+    pub name: String,
+    pub pattern: String,
+    #[serde(default)]
+    pub replacement: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RedactionSettings {
+    /// Off means logs are uploaded exactly as they are
+    #[serde(default = "default_redaction_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub disabled_rules: Vec<BuiltinRedaction>,
+    #[serde(default)]
+    pub custom_rules: Vec<CustomRedactionRule>,
+}
+
+fn default_redaction_enabled() -> bool {
+    true
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_redaction_enabled(),
+            disabled_rules: Vec::new(),
+            custom_rules: Vec::new(),
+        }
+    }
+}
+
+struct RedactionRule {
+    name: String,
+    patterns: Vec<(Regex, String)>,
+}
+
+/// One line that differs after redaction
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RedactedLine {
+    /// 1-based
+    pub line: usize,
+    pub original: String,
+    pub redacted: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RedactionPreview {
+    /// Exactly what would be uploaded
+    pub redacted: String,
+    pub changed_lines: Vec<RedactedLine>,
+    /// Number of replacements per rule name
+    pub matches: BTreeMap<String, usize>,
+}
+
+pub struct Redactor {
+    rules: Vec<RedactionRule>,
+}
+
+impl Redactor {
+    pub fn new(settings: &RedactionSettings) -> Result<Self> {
+        if !settings.enabled {
+            return Ok(Self { rules: Vec::new() });
+        }
+
+        let mut rules: Vec<RedactionRule> = BuiltinRedaction::ALL
+            .iter()
+            .filter(|rule| !settings.disabled_rules.contains(rule))
+            .map(|rule| RedactionRule {
+                name: rule.as_str().to_string(),
+                patterns: rule
+                    .patterns()
+                    .iter()
+                    .map(|(regex, replacement)| (regex.clone(), replacement.to_string()))
+                    .collect(),
+            })
+            .collect();
+
+        for custom in &settings.custom_rules {
+            let regex = Regex::new(&custom.pattern).map_err(|e| {
+                AppError::Config(format!("Invalid redaction rule '{}': {}", custom.name, e))
+            })?;
+            rules.push(RedactionRule {
+                name: custom.name.clone(),
+                patterns: vec![(
+                    regex,
+                    custom
+                        .replacement
+                        .clone()
+                        .unwrap_or_else(|| "<redacted>".to_string()),
+                )],
+            });
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Built from the current launcher config
+    pub async fn from_config() -> Result<Self> {
+        let state = State::get().await?;
+        let config = state.config_manager.get_config().await;
+        Self::new(&config.redaction)
+    }
+
+    pub fn redact(&self, content: &str) -> String {
+        self.preview(content).redacted
+    }
+
+    /// Redacts line by line (no rule spans lines) and records what changed
+    pub fn preview(&self, content: &str) -> RedactionPreview {
+        let mut redacted = String::with_capacity(content.len());
+        let mut changed_lines = Vec::new();
+        let mut matches = BTreeMap::new();
+
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            let result = self.redact_line(line, &mut matches);
+            if result != line {
+                changed_lines.push(RedactedLine {
+                    line: index + 1,
+                    original: line.trim_end_matches(['\r', '\n']).to_string(),
+                    redacted: result.trim_end_matches(['\r', '\n']).to_string(),
+                });
+            }
+            redacted.push_str(&result);
+        }
+
+        RedactionPreview {
+            redacted,
+            changed_lines,
+            matches,
+        }
+    }
+
+    fn redact_line(&self, line: &str, matches: &mut BTreeMap<String, usize>) -> String {
+        let mut result = line.to_string();
+        for rule in &self.rules {
+            for (regex, replacement) in &rule.patterns {
+                let count = regex.find_iter(&result).count();
+                if count == 0 {
+                    continue;
+                }
+                *matches.entry(rule.name.clone()).or_insert(0) += count;
+                result = regex
+                    .replace_all(&result, replacement.as_str())
+                    .into_owned();
+            }
+        }
+        result
+    }
+
+    /// Redacts every string inside a serializable value, e.g. the process metadata of a crash report.
+    /// The JSON isn't parsed back into `T`: a rule that rewrites a UUID or a date would break that.
+    pub fn redact_value<T: Serialize>(&self, value: &T) -> Result<serde_json::Value> {
+        let mut json = serde_json::to_value(value)?;
+        self.redact_json(&mut json);
+        Ok(json)
+    }
+
+    fn redact_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => *s = self.redact(s),
+            serde_json::Value::Array(values) => values.iter_mut().for_each(|v| self.redact_json(v)),
+            serde_json::Value::Object(map) => map.values_mut().for_each(|v| self.redact_json(v)),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,854 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::profile_state::{self, Mod, Profile};
+use crate::state::state_manager::State;
+use crate::utils::bulk_update_utils::UpdateSnapshot;
+use crate::utils::hash_utils;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+const MOD_CACHE_DIR_NAME: &str = "mod_cache";
+/// Instance directories that are part of every snapshot
+const SNAPSHOT_DIRS: &[&str] = &["mods", "config", "resourcepacks", "shaderpacks"];
+const SAVES_DIR: &str = "saves";
+const CONCURRENT_HASHES: usize = 8;
+/// Automatic snapshots beyond this count are pruned, manual ones are kept until deleted
+const MAX_AUTOMATIC_SNAPSHOTS: usize = 10;
+/// Profile fields that change on every launch and don't count as a config change
+const VOLATILE_PROFILE_FIELDS: &[&str] = &["last_played", "state"];
+
+/// Serializes every write to the snapshot store and the object cleanup, so the cleanup never
+/// removes objects of a snapshot that is still being written
+static SNAPSHOT_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Held by everything that writes records or objects to the snapshot store
+pub async fn lock_store() -> MutexGuard<'static, ()> {
+    SNAPSHOT_LOCK.lock().await
+}
+
+/// A record in the snapshot store. Profile snapshots and "update all" snapshots share one store:
+/// records live in `<store>/<profile_id>/<DIR>/<id>.json`, file contents in `<store>/objects`.
+pub trait SnapshotRecord: Serialize + DeserializeOwned {
+    /// Directory of the records below the profile's store directory
+    const DIR: &'static str;
+
+    fn id(&self) -> Uuid;
+
+    fn created_at(&self) -> DateTime<Utc>;
+
+    /// Hashes of the store objects the record needs
+    fn objects(&self) -> Vec<String>;
+}
+
+/// Why a snapshot was taken
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotReason {
+    Manual,
+    BeforeLaunch,
+    BeforeRestore,
+}
+
+/// Where the content of a snapshot entry is stored
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum BlobLocation {
+    /// Content-addressed object in the snapshot store
+    Store,
+    /// Identical file in the shared mod cache, not copied again
+    ModCache { file_name: String },
+}
+
+/// One file of the instance directory
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    /// Path relative to the instance directory, always with `/` separators
+    pub path: String,
+    pub sha1: String,
+    pub size: u64,
+    pub location: BlobLocation,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileSnapshot {
+    pub id: Uuid,
+    pub profile_id: Uuid,
+    pub label: Option<String>,
+    pub reason: SnapshotReason,
+    pub created_at: DateTime<Utc>,
+    pub include_saves: bool,
+    /// Hash of the profile JSON without volatile fields, used to detect config changes
+    pub profile_fingerprint: String,
+    pub profile: Profile,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+/// Snapshot without its file manifest, for listings
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileSnapshotSummary {
+    pub id: Uuid,
+    pub profile_id: Uuid,
+    pub label: Option<String>,
+    pub reason: SnapshotReason,
+    pub created_at: DateTime<Utc>,
+    pub include_saves: bool,
+    pub file_count: usize,
+    pub total_size: u64,
+}
+
+impl SnapshotRecord for ProfileSnapshot {
+    const DIR: &'static str = "snapshots";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn objects(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.location == BlobLocation::Store)
+            .map(|e| e.sha1.clone())
+            .collect()
+    }
+}
+
+impl From<&ProfileSnapshot> for ProfileSnapshotSummary {
+    fn from(snapshot: &ProfileSnapshot) -> Self {
+        Self {
+            id: snapshot.id,
+            profile_id: snapshot.profile_id,
+            label: snapshot.label.clone(),
+            reason: snapshot.reason,
+            created_at: snapshot.created_at,
+            include_saves: snapshot.include_saves,
+            file_count: snapshot.entries.len(),
+            total_size: snapshot.entries.iter().map(|e| e.size).sum(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A change to `Profile.mods` between two snapshots
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModChange {
+    pub mod_id: Uuid,
+    pub name: String,
+    pub kind: ModChangeKind,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FileDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotDiff {
+    pub from: Uuid,
+    /// `None` when compared against the current state of the profile
+    pub to: Option<Uuid>,
+    /// Top-level profile fields that differ, besides `mods`
+    pub changed_profile_fields: Vec<String>,
+    pub mod_changes: Vec<ModChange>,
+    pub files: FileDiff,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotRestoreReport {
+    pub restored_from: Uuid,
+    /// Snapshot of the state right before the restore, so the restore itself can be undone
+    pub safety_snapshot: Uuid,
+    pub files_written: usize,
+    pub files_removed: usize,
+}
+
+/// Returns the snapshot root directory path: <meta_dir>/profile_snapshots
+pub fn get_snapshot_store_root() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join("profile_snapshots")
+}
+
+fn mod_cache_dir() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME)
+}
+
+pub fn object_path(store_root: &Path, sha1: &str) -> PathBuf {
+    store_root.join("objects").join(&sha1[..2]).join(sha1)
+}
+
+/// Directory of a profile inside the store, also used for staging downloads
+pub fn profile_store_dir(store_root: &Path, profile_id: Uuid) -> PathBuf {
+    store_root.join(profile_id.to_string())
+}
+
+fn records_dir<T: SnapshotRecord>(store_root: &Path, profile_id: Uuid) -> PathBuf {
+    profile_store_dir(store_root, profile_id).join(T::DIR)
+}
+
+fn record_path<T: SnapshotRecord>(store_root: &Path, profile_id: Uuid, id: Uuid) -> PathBuf {
+    records_dir::<T>(store_root, profile_id).join(format!("{}.json", id))
+}
+
+/// Copies a file into the store unless an object with the same hash exists already
+async fn put_object(store_root: &Path, source: &Path, sha1: &str) -> Result<()> {
+    let object = object_path(store_root, sha1);
+    if object.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = object.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    // Copy under a temporary name so a crash never leaves a truncated object
+    let tmp = object.with_extension(format!("tmp-{}", Uuid::new_v4().simple()));
+    fs::copy(source, &tmp).await?;
+    fs::rename(&tmp, &object).await?;
+    Ok(())
+}
+
+/// Hashes a file and stores it as an object, returns the hash
+pub async fn store_object(store_root: &Path, source: &Path) -> Result<String> {
+    let sha1 = hash_utils::calculate_sha1_from_file(source).await?;
+    put_object(store_root, source, &sha1).await?;
+    Ok(sha1)
+}
+
+pub async fn write_record<T: SnapshotRecord>(
+    store_root: &Path,
+    profile_id: Uuid,
+    record: &T,
+) -> Result<()> {
+    let dir = records_dir::<T>(store_root, profile_id);
+    fs::create_dir_all(&dir).await?;
+    let json = serde_json::to_string_pretty(record)?;
+    fs::write(dir.join(format!("{}.json", record.id())), json).await?;
+    Ok(())
+}
+
+/// Lists the records of one kind of a profile, newest first
+pub async fn list_records<T: SnapshotRecord>(
+    store_root: &Path,
+    profile_id: Uuid,
+) -> Result<Vec<T>> {
+    let dir = records_dir::<T>(store_root, profile_id);
+    let mut records: Vec<T> = Vec::new();
+    if !dir.exists() {
+        return Ok(records);
+    }
+
+    let mut entries = fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let record = fs::read_to_string(&path)
+            .await
+            .map_err(AppError::from)
+            .and_then(|content| Ok(serde_json::from_str(&content)?));
+        match record {
+            Ok(record) => records.push(record),
+            Err(e) => warn!("Ignoring unreadable snapshot {:?}: {}", path, e),
+        }
+    }
+    records.sort_by(|a, b| b.created_at().cmp(&a.created_at()));
+    Ok(records)
+}
+
+pub async fn load_record<T: SnapshotRecord>(
+    store_root: &Path,
+    profile_id: Uuid,
+    id: Uuid,
+) -> Result<T> {
+    let path = record_path::<T>(store_root, profile_id, id);
+    if !path.exists() {
+        return Err(AppError::NotFound(format!(
+            "Snapshot {} of profile {} not found",
+            id, profile_id
+        )));
+    }
+    let content = fs::read_to_string(&path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Removes a record. Its objects stay until `remove_unreferenced_objects` runs.
+pub async fn delete_record<T: SnapshotRecord>(
+    store_root: &Path,
+    profile_id: Uuid,
+    id: Uuid,
+) -> Result<()> {
+    let path = record_path::<T>(store_root, profile_id, id);
+    if !path.exists() {
+        return Err(AppError::NotFound(format!(
+            "Snapshot {} of profile {} not found",
+            id, profile_id
+        )));
+    }
+    fs::remove_file(&path).await?;
+    Ok(())
+}
+
+/// Removes all but the `keep` newest records for which `prunable` is true, then the objects
+/// nothing references anymore. Returns the number of removed records.
+pub async fn prune_records<T: SnapshotRecord>(
+    store_root: &Path,
+    profile_id: Uuid,
+    keep: usize,
+    prunable: impl Fn(&T) -> bool,
+) -> Result<usize> {
+    let records = list_records::<T>(store_root, profile_id).await?;
+    let mut removed = 0;
+    for old in records.iter().filter(|r| prunable(r)).skip(keep) {
+        delete_record::<T>(store_root, profile_id, old.id()).await?;
+        removed += 1;
+    }
+    if removed > 0 {
+        debug!(
+            "Pruned {} old snapshot(s) of profile {} from {:?}",
+            removed, profile_id, store_root
+        );
+        remove_unreferenced_objects(store_root).await?;
+    }
+    Ok(removed)
+}
+
+fn tracked_dirs(include_saves: bool) -> Vec<&'static str> {
+    let mut dirs = SNAPSHOT_DIRS.to_vec();
+    if include_saves {
+        dirs.push(SAVES_DIR);
+    }
+    dirs
+}
+
+/// Hash of the profile JSON without volatile fields
+pub fn profile_fingerprint(profile: &Profile) -> Result<String> {
+    let value = normalized_profile_value(profile)?;
+    Ok(hash_utils::calculate_sha1_from_bytes(
+        serde_json::to_string(&value)?.as_bytes(),
+    ))
+}
+
+fn normalized_profile_value(profile: &Profile) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(profile)?;
+    if let Some(object) = value.as_object_mut() {
+        for field in VOLATILE_PROFILE_FIELDS {
+            object.remove(*field);
+        }
+        // Serialized from a HashSet, so the order is random
+        if let Some(serde_json::Value::Array(items)) =
+            object.get_mut("disabled_norisk_mods_detailed")
+        {
+            items.sort_by_key(|item| item.to_string());
+        }
+    }
+    Ok(value)
+}
+
+/// Recursively lists all files below `dir` as paths relative to `base`
+async fn collect_files(base: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_type = entry.file_type().await?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            Box::pin(collect_files(base, &path, files)).await?;
+        } else if file_type.is_file() {
+            if let Ok(relative) = path.strip_prefix(base) {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn relative_key(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Hashes the tracked directories of an instance. With `store_root` set, every file that isn't
+/// in the mod cache already is copied into the content-addressed store.
+pub async fn capture_instance_files(
+    instance_dir: &Path,
+    include_saves: bool,
+    store_root: Option<&Path>,
+    mod_cache_dir: &Path,
+) -> Result<Vec<SnapshotEntry>> {
+    let mut files = Vec::new();
+    for dir in tracked_dirs(include_saves) {
+        collect_files(instance_dir, &instance_dir.join(dir), &mut files).await?;
+    }
+
+    let results: Vec<Result<SnapshotEntry>> = stream::iter(files)
+        .map(|relative| async move {
+            let path = instance_dir.join(&relative);
+            let sha1 = hash_utils::calculate_sha1_from_file(&path).await?;
+            let size = fs::metadata(&path).await?.len();
+            let key = relative_key(&relative);
+
+            // Jars under mods/ usually have an identical copy in the mod cache
+            let mut location = BlobLocation::Store;
+            if key.starts_with("mods/") {
+                if let Some(file_name) = relative.file_name().map(|n| n.to_string_lossy().to_string()) {
+                    let cached = mod_cache_dir.join(&file_name);
+                    if cached.is_file()
+                        && hash_utils::calculate_sha1_from_file(&cached).await.ok().as_deref()
+                            == Some(sha1.as_str())
+                    {
+                        location = BlobLocation::ModCache { file_name };
+                    }
+                }
+            }
+
+            if let (Some(store_root), BlobLocation::Store) = (store_root, &location) {
+                put_object(store_root, &path, &sha1).await?;
+            }
+
+            Ok(SnapshotEntry {
+                path: key,
+                sha1,
+                size,
+                location,
+            })
+        })
+        .buffer_unordered(CONCURRENT_HASHES)
+        .collect()
+        .await;
+
+    let mut entries = results.into_iter().collect::<Result<Vec<_>>>()?;
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Brings the tracked directories of an instance to the state of `entries`: changed and missing
+/// files are written back, files that weren't part of the snapshot are removed.
+/// Returns (files written, files removed).
+pub async fn restore_instance_files(
+    instance_dir: &Path,
+    entries: &[SnapshotEntry],
+    include_saves: bool,
+    store_root: &Path,
+    mod_cache_dir: &Path,
+) -> Result<(usize, usize)> {
+    let current = capture_instance_files(instance_dir, include_saves, None, mod_cache_dir).await?;
+    let current_hashes: HashMap<&str, &str> = current
+        .iter()
+        .map(|e| (e.path.as_str(), e.sha1.as_str()))
+        .collect();
+    let wanted: HashSet<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+
+    // Check every source first so a broken snapshot doesn't leave a half restored instance
+    let mut sources = Vec::new();
+    for entry in entries {
+        if current_hashes.get(entry.path.as_str()) == Some(&entry.sha1.as_str()) {
+            continue;
+        }
+        let source = match &entry.location {
+            BlobLocation::Store => object_path(store_root, &entry.sha1),
+            BlobLocation::ModCache { file_name } => mod_cache_dir.join(file_name),
+        };
+        if !source.is_file() {
+            return Err(AppError::NotFound(format!(
+                "Snapshot content for '{}' is missing ({})",
+                entry.path,
+                source.display()
+            )));
+        }
+        sources.push((entry, source));
+    }
+
+    let mut removed = 0;
+    for entry in &current {
+        if !wanted.contains(entry.path.as_str()) {
+            debug!("Removing '{}', it is not part of the snapshot", entry.path);
+            fs::remove_file(instance_dir.join(&entry.path)).await?;
+            removed += 1;
+        }
+    }
+
+    for (entry, source) in &sources {
+        let target = instance_dir.join(&entry.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::copy(source, &target).await?;
+    }
+
+    Ok((sources.len(), removed))
+}
+
+/// Compares two file manifests
+pub fn diff_entries(from: &[SnapshotEntry], to: &[SnapshotEntry]) -> FileDiff {
+    let from_map: HashMap<&str, &str> =
+        from.iter().map(|e| (e.path.as_str(), e.sha1.as_str())).collect();
+    let to_map: HashMap<&str, &str> =
+        to.iter().map(|e| (e.path.as_str(), e.sha1.as_str())).collect();
+
+    let mut diff = FileDiff::default();
+    for (path, sha1) in &to_map {
+        match from_map.get(path) {
+            None => diff.added.push(path.to_string()),
+            Some(old) if old != sha1 => diff.modified.push(path.to_string()),
+            _ => {}
+        }
+    }
+    diff.removed = from_map
+        .keys()
+        .filter(|path| !to_map.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect();
+
+    diff.added.sort();
+    diff.removed.sort();
+    diff.modified.sort();
+    diff
+}
+
+/// Compares two versions of a profile, returns the changed top-level fields and mod changes
+pub fn diff_profiles(from: &Profile, to: &Profile) -> Result<(Vec<String>, Vec<ModChange>)> {
+    let from_value = normalized_profile_value(from)?;
+    let to_value = normalized_profile_value(to)?;
+
+    let mut changed_fields = Vec::new();
+    if let (Some(from_object), Some(to_object)) = (from_value.as_object(), to_value.as_object()) {
+        let keys: HashSet<&String> = from_object.keys().chain(to_object.keys()).collect();
+        for key in keys {
+            if key != "mods" && from_object.get(key) != to_object.get(key) {
+                changed_fields.push(key.clone());
+            }
+        }
+    }
+    changed_fields.sort();
+
+    let mod_name = |m: &Mod| {
+        m.display_name
+            .clone()
+            .or_else(|| profile_state::get_profile_mod_filename(&m.source).ok())
+            .unwrap_or_else(|| m.id.to_string())
+    };
+    let mut mod_changes = Vec::new();
+    for new_mod in &to.mods {
+        match from.mods.iter().find(|m| m.id == new_mod.id) {
+            None => mod_changes.push(ModChange {
+                mod_id: new_mod.id,
+                name: mod_name(new_mod),
+                kind: ModChangeKind::Added,
+                from_version: None,
+                to_version: new_mod.version.clone(),
+            }),
+            Some(old_mod) => {
+                if serde_json::to_value(old_mod)? != serde_json::to_value(new_mod)? {
+                    mod_changes.push(ModChange {
+                        mod_id: new_mod.id,
+                        name: mod_name(new_mod),
+                        kind: ModChangeKind::Changed,
+                        from_version: old_mod.version.clone(),
+                        to_version: new_mod.version.clone(),
+                    });
+                }
+            }
+        }
+    }
+    for old_mod in &from.mods {
+        if !to.mods.iter().any(|m| m.id == old_mod.id) {
+            mod_changes.push(ModChange {
+                mod_id: old_mod.id,
+                name: mod_name(old_mod),
+                kind: ModChangeKind::Removed,
+                from_version: old_mod.version.clone(),
+                to_version: None,
+            });
+        }
+    }
+
+    Ok((changed_fields, mod_changes))
+}
+
+/// Loads a single snapshot including its file manifest
+pub async fn load_snapshot(profile_id: Uuid, snapshot_id: Uuid) -> Result<ProfileSnapshot> {
+    load_record(&get_snapshot_store_root(), profile_id, snapshot_id).await
+}
+
+/// Lists the snapshots of a profile, newest first
+pub async fn list_snapshots(profile_id: Uuid) -> Result<Vec<ProfileSnapshotSummary>> {
+    Ok(
+        list_records::<ProfileSnapshot>(&get_snapshot_store_root(), profile_id)
+            .await?
+            .iter()
+            .map(ProfileSnapshotSummary::from)
+            .collect(),
+    )
+}
+
+async fn create_snapshot_locked(
+    profile: &Profile,
+    label: Option<String>,
+    reason: SnapshotReason,
+    include_saves: bool,
+) -> Result<ProfileSnapshot> {
+    let state = State::get().await?;
+    let instance_dir = state
+        .profile_manager
+        .calculate_instance_path_for_profile(profile)?;
+    let store_root = get_snapshot_store_root();
+
+    let entries =
+        capture_instance_files(&instance_dir, include_saves, Some(&store_root), &mod_cache_dir())
+            .await?;
+    let snapshot = ProfileSnapshot {
+        id: Uuid::new_v4(),
+        profile_id: profile.id,
+        label,
+        reason,
+        created_at: Utc::now(),
+        include_saves,
+        profile_fingerprint: profile_fingerprint(profile)?,
+        profile: profile.clone(),
+        entries,
+    };
+    write_record(&store_root, profile.id, &snapshot).await?;
+
+    info!(
+        "Created {:?} snapshot {} of profile '{}' ({} files)",
+        reason,
+        snapshot.id,
+        profile.name,
+        snapshot.entries.len()
+    );
+    Ok(snapshot)
+}
+
+/// Stores the profile JSON and the tracked instance directories as a new snapshot
+pub async fn create_snapshot(
+    profile_id: Uuid,
+    label: Option<String>,
+    reason: SnapshotReason,
+    include_saves: bool,
+) -> Result<ProfileSnapshotSummary> {
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    if profile.is_standard_version {
+        return Err(AppError::InvalidOperation(
+            "Snapshots are not supported for standard profiles".to_string(),
+        ));
+    }
+
+    let _guard = lock_store().await;
+    let snapshot = create_snapshot_locked(&profile, label, reason, include_saves).await?;
+    Ok(ProfileSnapshotSummary::from(&snapshot))
+}
+
+/// Takes an automatic snapshot before a launch, but only if the profile config changed since
+/// the latest snapshot (or there is none yet). Runs in the launch task, not the command.
+pub async fn snapshot_before_launch(profile: &Profile) -> Result<Option<Uuid>> {
+    if profile.is_standard_version {
+        return Ok(None);
+    }
+
+    let _guard = lock_store().await;
+    let fingerprint = profile_fingerprint(profile)?;
+    let snapshots = list_records::<ProfileSnapshot>(&get_snapshot_store_root(), profile.id).await?;
+    if snapshots
+        .first()
+        .map_or(false, |latest| latest.profile_fingerprint == fingerprint)
+    {
+        debug!(
+            "Profile '{}' unchanged since the last snapshot, skipping automatic snapshot",
+            profile.name
+        );
+        return Ok(None);
+    }
+
+    let snapshot =
+        create_snapshot_locked(profile, None, SnapshotReason::BeforeLaunch, false).await?;
+    prune_automatic_snapshots(profile.id).await?;
+    Ok(Some(snapshot.id))
+}
+
+async fn prune_automatic_snapshots(profile_id: Uuid) -> Result<()> {
+    prune_records::<ProfileSnapshot>(
+        &get_snapshot_store_root(),
+        profile_id,
+        MAX_AUTOMATIC_SNAPSHOTS,
+        |s| s.reason != SnapshotReason::Manual,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Deletes objects from the store that no record of any profile references anymore.
+/// Call with the store lock held.
+pub async fn remove_unreferenced_objects(store_root: &Path) -> Result<()> {
+    let mut referenced = HashSet::new();
+
+    let mut profile_dirs = fs::read_dir(store_root).await?;
+    while let Some(entry) = profile_dirs.next_entry().await? {
+        let Ok(profile_id) = Uuid::parse_str(&entry.file_name().to_string_lossy()) else {
+            continue;
+        };
+        for snapshot in list_records::<ProfileSnapshot>(store_root, profile_id).await? {
+            referenced.extend(snapshot.objects());
+        }
+        for snapshot in list_records::<UpdateSnapshot>(store_root, profile_id).await? {
+            referenced.extend(snapshot.objects());
+        }
+    }
+
+    let mut objects = Vec::new();
+    collect_files(store_root, &store_root.join("objects"), &mut objects).await?;
+    let mut removed = 0;
+    for object in objects {
+        let name = object
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if !referenced.contains(&name) {
+            fs::remove_file(store_root.join(&object)).await?;
+            removed += 1;
+        }
+    }
+    debug!("Removed {} unreferenced snapshot object(s)", removed);
+    Ok(())
+}
+
+/// Deletes a snapshot and the stored files only it referenced
+pub async fn delete_snapshot(profile_id: Uuid, snapshot_id: Uuid) -> Result<()> {
+    let _guard = lock_store().await;
+    let store_root = get_snapshot_store_root();
+    delete_record::<ProfileSnapshot>(&store_root, profile_id, snapshot_id).await?;
+    remove_unreferenced_objects(&store_root).await
+}
+
+/// Fails while the game of the profile is starting or running, its files must not change then
+pub async fn ensure_profile_not_running(state: &State, profile_id: Uuid) -> Result<()> {
+    let running = state.process_manager.has_launching_process(profile_id)
+        || !state
+            .process_manager
+            .get_process_metadata_by_profile(profile_id)
+            .await
+            .is_empty();
+    if running {
+        return Err(AppError::InvalidOperation(
+            "The game of this profile is running, close it first".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Compares a snapshot with another one, or with the current state of the profile when `to` is `None`
+pub async fn diff_snapshots(
+    profile_id: Uuid,
+    from: Uuid,
+    to: Option<Uuid>,
+) -> Result<SnapshotDiff> {
+    let from_snapshot = load_snapshot(profile_id, from).await?;
+    let (to_profile, to_entries) = match to {
+        Some(to_id) => {
+            let to_snapshot = load_snapshot(profile_id, to_id).await?;
+            (to_snapshot.profile, to_snapshot.entries)
+        }
+        None => {
+            let state = State::get().await?;
+            let profile = state.profile_manager.get_profile(profile_id).await?;
+            let instance_dir = state
+                .profile_manager
+                .calculate_instance_path_for_profile(&profile)?;
+            let entries = capture_instance_files(
+                &instance_dir,
+                from_snapshot.include_saves,
+                None,
+                &mod_cache_dir(),
+            )
+            .await?;
+            (profile, entries)
+        }
+    };
+
+    let (changed_profile_fields, mod_changes) = diff_profiles(&from_snapshot.profile, &to_profile)?;
+    Ok(SnapshotDiff {
+        from,
+        to,
+        changed_profile_fields,
+        mod_changes,
+        files: diff_entries(&from_snapshot.entries, &to_entries),
+    })
+}
+
+/// Restores the profile JSON and instance files of a snapshot. The current state is
+/// snapshotted first, so a restore can itself be undone.
+pub async fn restore_snapshot(profile_id: Uuid, snapshot_id: Uuid) -> Result<SnapshotRestoreReport> {
+    let state = State::get().await?;
+    ensure_profile_not_running(&state, profile_id).await?;
+
+    let _guard = lock_store().await;
+    let snapshot = load_snapshot(profile_id, snapshot_id).await?;
+    let current = state.profile_manager.get_profile(profile_id).await?;
+    let safety = create_snapshot_locked(
+        &current,
+        Some(format!("Before restoring {}", snapshot_id)),
+        SnapshotReason::BeforeRestore,
+        snapshot.include_saves,
+    )
+    .await?;
+
+    let instance_dir = state
+        .profile_manager
+        .calculate_instance_path_for_profile(&current)?;
+    let (files_written, files_removed) = restore_instance_files(
+        &instance_dir,
+        &snapshot.entries,
+        snapshot.include_saves,
+        &get_snapshot_store_root(),
+        &mod_cache_dir(),
+    )
+    .await?;
+
+    // The instance directory stays where it is, only the configuration goes back
+    let mut restored = snapshot.profile.clone();
+    restored.id = current.id;
+    restored.path = current.path.clone();
+    restored.last_played = current.last_played;
+    restored.state = current.state.clone();
+    state
+        .profile_manager
+        .update_profile(profile_id, restored)
+        .await?;
+    prune_automatic_snapshots(profile_id).await?;
+
+    info!(
+        "Restored snapshot {} of profile '{}': {} file(s) written, {} removed",
+        snapshot_id, current.name, files_written, files_removed
+    );
+    Ok(SnapshotRestoreReport {
+        restored_from: snapshot_id,
+        safety_snapshot: safety.id,
+        files_written,
+        files_removed,
+    })
+}
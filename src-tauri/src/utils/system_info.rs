@@ -2,7 +2,7 @@ use crate::error::{AppError, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Deserialize, PartialEq, Eq, Hash, Debug)]
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum OperatingSystem {
     #[serde(rename = "windows")]
     WINDOWS,
@@ -14,7 +14,7 @@ pub enum OperatingSystem {
     UNKNOWN,
 }
 
-#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Architecture {
     #[serde(rename = "x86")]
     X86,
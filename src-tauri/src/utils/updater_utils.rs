@@ -1,4 +1,5 @@
 use crate::error::{AppError, Result as AppResult};
+use crate::utils::endpoint_registry::{self, Endpoint};
 use log::{error, info, warn};
 use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
@@ -229,11 +230,15 @@ pub async fn check_for_updates(
     );
 
     // Determine the base part of the URL and the platform-specific segment template
-    let base_repo_url = if is_beta_channel {
-        "https://api-staging.norisk.gg/api/v1/launcher/releases-v2"
+    let api_endpoint = if is_beta_channel {
+        Endpoint::NoriskApiStaging
     } else {
-        "https://api.norisk.gg/api/v1/launcher/releases-v2"
+        Endpoint::NoriskApi
     };
+    let base_repo_url = format!(
+        "{}/launcher/releases-v2",
+        endpoint_registry::base_url(api_endpoint)
+    );
 
     let mut platform_specific_target = "{{target}}".to_string(); // Default: Tauri replaces {{target}}
 
@@ -0,0 +1,349 @@
+// tests/bulk_update_tests.rs
+
+use chrono::{Duration, Utc};
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::integrations::modrinth::{ModrinthVersion, ModrinthVersionType};
+use noriskclient_launcher_v3_lib::state::profile_state::{Mod, ModLoader, Profile};
+use noriskclient_launcher_v3_lib::utils::bulk_update_utils::{
+    apply_mod_update, diff_updates, restore_files, swap_in, ContentUpdate, SnapshotFile,
+    UpdateCandidate, UpdateContentKind, UpdateSnapshot,
+};
+use noriskclient_launcher_v3_lib::utils::snapshot_utils::{
+    list_records, object_path, prune_records, store_object, write_record,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+fn version(id: &str, project_id: &str, date: &str, sha1: &str) -> ModrinthVersion {
+    serde_json::from_value(json!({
+        "id": id,
+        "project_id": project_id,
+        "author_id": null,
+        "featured": false,
+        "name": id,
+        "version_number": format!("{}.0", id),
+        "changelog": format!("Changes in {}", id),
+        "dependencies": [],
+        "game_versions": ["1.21.1"],
+        "version_type": "release",
+        "loaders": ["fabric"],
+        "files": [{
+            "hashes": { "sha512": null, "sha1": sha1 },
+            "url": format!("https://cdn.modrinth.com/{}.jar", id),
+            "filename": format!("{}.jar", id),
+            "primary": true,
+            "size": 1,
+            "file_type": null,
+        }],
+        "date_published": date,
+    }))
+    .unwrap()
+}
+
+fn candidate(kind: UpdateContentKind, sha1: &str, current_version_id: Option<&str>) -> UpdateCandidate {
+    UpdateCandidate {
+        kind,
+        mod_id: (kind == UpdateContentKind::Mod).then(Uuid::new_v4),
+        name: sha1.to_string(),
+        file_name: format!("{}.zip", sha1),
+        path: (kind != UpdateContentKind::Mod).then(|| PathBuf::from(format!("{}.zip", sha1))),
+        sha1: sha1.to_string(),
+        is_disabled: false,
+        current_version_id: current_version_id.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_diff_updates() {
+    let candidates = vec![
+        // Mod with a newer version
+        candidate(UpdateContentKind::Mod, "mod-old", Some("m1")),
+        // Mod that is already up to date
+        candidate(UpdateContentKind::Mod, "mod-latest", Some("m3")),
+        // Pack with a newer version, current version only known by hash
+        candidate(UpdateContentKind::ResourcePack, "pack-old", None),
+        // Pack that is newer than anything Modrinth returns for the filters
+        candidate(UpdateContentKind::ShaderPack, "shader-new", None),
+    ];
+
+    let current: HashMap<String, ModrinthVersion> = [
+        ("pack-old".to_string(), version("p1", "pack", "2024-01-01T00:00:00Z", "pack-old")),
+        ("shader-new".to_string(), version("s2", "shader", "2024-06-01T00:00:00Z", "shader-new")),
+    ]
+    .into();
+    let latest: HashMap<String, ModrinthVersion> = [
+        ("mod-old".to_string(), version("m2", "mod", "2024-05-01T00:00:00Z", "mod-new")),
+        ("mod-latest".to_string(), version("m3", "mod2", "2024-05-01T00:00:00Z", "mod-latest")),
+        ("pack-old".to_string(), version("p2", "pack", "2024-03-01T00:00:00Z", "pack-new")),
+        ("shader-new".to_string(), version("s1", "shader", "2024-02-01T00:00:00Z", "shader-old")),
+    ]
+    .into();
+
+    let updates = diff_updates(&candidates, &current, &latest);
+
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates[0].kind, UpdateContentKind::Mod);
+    assert_eq!(updates[0].current_version_id.as_deref(), Some("m1"));
+    assert_eq!(updates[0].new_version_id, "m2");
+    assert_eq!(updates[0].new_file_hash_sha1.as_deref(), Some("mod-new"));
+    assert_eq!(updates[0].changelog.as_deref(), Some("Changes in m2"));
+
+    assert_eq!(updates[1].kind, UpdateContentKind::ResourcePack);
+    assert_eq!(updates[1].current_version_id.as_deref(), Some("p1"));
+    assert_eq!(updates[1].current_version_number.as_deref(), Some("p1.0"));
+    assert_eq!(updates[1].new_file_name, "p2.jar");
+    assert_eq!(updates[1].current_path, Some(PathBuf::from("pack-old.zip")));
+}
+
+fn content_update(
+    kind: UpdateContentKind,
+    current_path: Option<PathBuf>,
+    new_file_name: &str,
+) -> ContentUpdate {
+    let current_file_name = current_path
+        .as_ref()
+        .and_then(|p| p.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "old.jar".to_string());
+    ContentUpdate {
+        kind,
+        mod_id: None,
+        project_id: "project".to_string(),
+        name: current_file_name.clone(),
+        current_version_id: Some("v1".to_string()),
+        current_version_number: Some("1.0".to_string()),
+        current_file_name,
+        current_path,
+        is_disabled: false,
+        new_version_id: "v2".to_string(),
+        new_version_number: "2.0".to_string(),
+        new_file_name: new_file_name.to_string(),
+        download_url: format!("https://cdn.modrinth.com/{}", new_file_name),
+        new_file_hash_sha1: None,
+        new_game_versions: vec!["1.21.1".to_string()],
+        new_loaders: vec!["fabric".to_string()],
+        version_type: ModrinthVersionType::Release,
+        date_published: "2024-05-01T00:00:00Z".to_string(),
+        changelog: None,
+    }
+}
+
+fn snapshot(created_at: chrono::DateTime<Utc>) -> UpdateSnapshot {
+    UpdateSnapshot {
+        id: Uuid::new_v4(),
+        profile_id: Uuid::nil(),
+        profile_name: "Update Test".to_string(),
+        created_at,
+        previous_mods: Vec::new(),
+        updates: Vec::new(),
+        files: Vec::new(),
+    }
+}
+
+fn write_file(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+#[tokio::test]
+async fn test_swap_in_and_restore_files() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let packs_dir = dir.path().join("resourcepacks");
+    let store = dir.path().join("store");
+    let staging = dir.path().join("staging");
+    write_file(&packs_dir.join("faithful-1.zip"), "old pack");
+    write_file(&packs_dir.join("shaders-1.zip.disabled"), "old shader");
+    write_file(&staging.join("0_faithful-2.zip"), "new pack");
+    write_file(&staging.join("1_shaders-2.zip"), "new shader");
+
+    let pack = content_update(
+        UpdateContentKind::ResourcePack,
+        Some(packs_dir.join("faithful-1.zip")),
+        "faithful-2.zip",
+    );
+    let mut shader = content_update(
+        UpdateContentKind::ShaderPack,
+        Some(packs_dir.join("shaders-1.zip.disabled")),
+        "shaders-2.zip",
+    );
+    shader.is_disabled = true;
+
+    let mut snapshot = snapshot(Utc::now());
+    swap_in(
+        &mut snapshot,
+        &store,
+        &pack,
+        &staging.join("0_faithful-2.zip"),
+    )
+    .await?;
+    swap_in(
+        &mut snapshot,
+        &store,
+        &shader,
+        &staging.join("1_shaders-2.zip"),
+    )
+    .await?;
+
+    assert_eq!(
+        std::fs::read_to_string(packs_dir.join("faithful-2.zip"))?,
+        "new pack"
+    );
+    // Disabled packs stay disabled
+    assert_eq!(
+        std::fs::read_to_string(packs_dir.join("shaders-2.zip.disabled"))?,
+        "new shader"
+    );
+    assert!(!packs_dir.join("faithful-1.zip").exists());
+    assert_eq!(snapshot.files.len(), 2);
+    // The old files are kept as objects of the snapshot store
+    assert_eq!(
+        std::fs::read_to_string(object_path(&store, &snapshot.files[0].sha1))?,
+        "old pack"
+    );
+
+    restore_files(&snapshot, &store).await?;
+
+    assert_eq!(
+        std::fs::read_to_string(packs_dir.join("faithful-1.zip"))?,
+        "old pack"
+    );
+    assert_eq!(
+        std::fs::read_to_string(packs_dir.join("shaders-1.zip.disabled"))?,
+        "old shader"
+    );
+    assert!(!packs_dir.join("faithful-2.zip").exists());
+    assert!(!packs_dir.join("shaders-2.zip.disabled").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_files_keeps_going_after_errors() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let packs_dir = dir.path().join("resourcepacks");
+    let store = dir.path().join("store");
+    let staging = dir.path().join("staging");
+    write_file(&packs_dir.join("a-1.zip"), "a");
+    write_file(&packs_dir.join("b-1.zip"), "b");
+    write_file(&staging.join("a-2.zip"), "a2");
+    write_file(&staging.join("b-2.zip"), "b2");
+
+    let mut snapshot = snapshot(Utc::now());
+    for name in ["a", "b"] {
+        let update = content_update(
+            UpdateContentKind::ResourcePack,
+            Some(packs_dir.join(format!("{}-1.zip", name))),
+            &format!("{}-2.zip", name),
+        );
+        let staged = staging.join(format!("{}-2.zip", name));
+        swap_in(&mut snapshot, &store, &update, &staged).await?;
+    }
+    // The stored copy of "b" got lost
+    std::fs::remove_file(object_path(&store, &snapshot.files[1].sha1))?;
+
+    assert!(restore_files(&snapshot, &store).await.is_err());
+    assert_eq!(std::fs::read_to_string(packs_dir.join("a-1.zip"))?, "a");
+    assert!(!packs_dir.join("a-2.zip").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_snapshots_keeps_newest() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let root = dir.path();
+    let now = Utc::now();
+    let mut ids = Vec::new();
+    for age_days in [3, 0, 2, 1] {
+        let snapshot = snapshot(now - Duration::days(age_days));
+        write_record(root, Uuid::nil(), &snapshot).await?;
+        ids.push((age_days, snapshot.id));
+    }
+
+    let removed = prune_records::<UpdateSnapshot>(root, Uuid::nil(), 2, |_| true).await?;
+    assert_eq!(removed, 2);
+
+    let remaining: Vec<Uuid> = list_records::<UpdateSnapshot>(root, Uuid::nil())
+        .await?
+        .iter()
+        .map(|s| s.id)
+        .collect();
+    let newest: Vec<Uuid> = [0, 1]
+        .iter()
+        .map(|age| ids.iter().find(|(a, _)| a == age).unwrap().1)
+        .collect();
+    assert_eq!(remaining, newest);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pruning_removes_unreferenced_objects() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let store = dir.path().join("store");
+    write_file(&dir.path().join("old.zip"), "old");
+    write_file(&dir.path().join("new.zip"), "new");
+
+    let mut records = Vec::new();
+    for (name, age_days) in [("old.zip", 1), ("new.zip", 0)] {
+        let mut snapshot = snapshot(Utc::now() - Duration::days(age_days));
+        snapshot.files.push(SnapshotFile {
+            kind: UpdateContentKind::ResourcePack,
+            original_path: dir.path().join(name),
+            sha1: store_object(&store, &dir.path().join(name)).await?,
+            installed_path: None,
+        });
+        write_record(&store, Uuid::nil(), &snapshot).await?;
+        records.push(snapshot);
+    }
+
+    prune_records::<UpdateSnapshot>(&store, Uuid::nil(), 1, |_| true).await?;
+
+    assert!(!object_path(&store, &records[0].files[0].sha1).exists());
+    assert!(object_path(&store, &records[1].files[0].sha1).exists());
+    Ok(())
+}
+
+#[test]
+fn test_quilt_profiles_update_fabric_mods() {
+    let profile: Profile = serde_json::from_value(json!({
+        "name": "Quilt",
+        "path": "quilt",
+        "game_version": "1.21.1",
+        "loader": "quilt",
+        "loader_version": null,
+        "last_played": null,
+        "description": null,
+        "norisk_information": null,
+        "mods": [],
+    }))
+    .unwrap();
+    assert_eq!(
+        UpdateContentKind::Mod.loaders(&profile),
+        vec!["quilt".to_string(), "fabric".to_string()]
+    );
+    assert!(UpdateContentKind::ResourcePack.loaders(&profile).is_empty());
+
+    let mut mod_entry: Mod = serde_json::from_value(json!({
+        "source": {
+            "type": "modrinth",
+            "project_id": "project",
+            "version_id": "v1",
+            "file_name": "old.jar",
+            "download_url": "https://cdn.modrinth.com/old.jar",
+            "file_hash_sha1": null,
+        },
+        "enabled": true,
+        "display_name": "Fabric API",
+        "version": "1.0",
+        "game_versions": null,
+        "file_name_override": null,
+        "associated_loader": "quilt",
+    }))
+    .unwrap();
+    let update = content_update(UpdateContentKind::Mod, None, "new.jar");
+    apply_mod_update(&mut mod_entry, &update, &profile.loader);
+
+    assert_eq!(mod_entry.version.as_deref(), Some("2.0"));
+    // Recorded as a Quilt mod, otherwise the launch skips it
+    assert_eq!(mod_entry.associated_loader, Some(ModLoader::Quilt));
+}
@@ -0,0 +1,16 @@
+// tests/common/mod.rs
+// Helpers shared by the integration tests, include with `mod common;`
+
+use std::io::Write;
+use std::path::Path;
+
+/// Writes a jar (zip) with the given text files
+pub fn write_jar(path: &Path, files: &[(&str, &str)]) {
+    let mut jar = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+    for (name, content) in files {
+        jar.start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        jar.write_all(content.as_bytes()).unwrap();
+    }
+    jar.finish().unwrap();
+}
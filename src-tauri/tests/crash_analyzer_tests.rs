@@ -0,0 +1,92 @@
+// tests/crash_analyzer_tests.rs
+
+use noriskclient_launcher_v3_lib::minecraft::crash_analyzer::{
+    parse_rules, CrashRuleSet, CrashSource,
+};
+
+const CRASH_REPORT: &str = r#"---- Minecraft Crash Report ----
+Description: Initializing game
+
+java.lang.RuntimeException: Mixin transformation of net.minecraft.class_310 failed
+Caused by: org.spongepowered.asm.mixin.transformer.throwables.MixinTransformerError: An unexpected critical error was encountered
+Caused by: org.spongepowered.asm.mixin.throwables.MixinApplyError: Mixin [betterf3.mixins.json:DebugMixin] from phase [DEFAULT] in config [betterf3.mixins.json] FAILED during APPLY
+"#;
+
+const LATEST_LOG: &str = r#"[12:00:01] [main/INFO]: Loading Minecraft 1.21.1 with Fabric Loader 0.16.5
+[12:00:02] [main/ERROR]: Incompatible mods found!
+	 - Mod 'Sodium Extra' (sodium-extra) 0.6.0 requires any version of sodium, which is missing!
+	 - Mod 'Iris' (iris) 1.8.0 requires any version of sodium, which is missing!
+[12:00:03] [Render thread/ERROR]: GLFW error 65542: WGL: The driver does not appear to support OpenGL
+"#;
+
+#[test]
+fn test_builtin_rules_match_known_signatures() {
+    let diagnoses = CrashRuleSet::builtin().analyze(Some(CRASH_REPORT), Some(LATEST_LOG));
+    let found: Vec<(&str, Option<&str>)> = diagnoses
+        .iter()
+        .map(|d| (d.rule_id.as_str(), d.offending_mod.as_deref()))
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![
+            ("fabric_missing_dependency", Some("sodium-extra")),
+            ("fabric_missing_dependency", Some("iris")),
+            ("opengl_init_failure", None),
+            ("mixin_apply_failure", Some("betterf3")),
+        ]
+    );
+    assert_eq!(
+        diagnoses[0].cause,
+        "Sodium Extra (sodium-extra) needs sodium, which is not installed."
+    );
+    assert_eq!(diagnoses[2].source, CrashSource::LatestLog);
+    assert_eq!(diagnoses[3].source, CrashSource::CrashReport);
+}
+
+#[test]
+fn test_java_version_from_class_file_version() {
+    let log = "java.lang.UnsupportedClassVersionError: net/fabricmc/loader/impl/launch/knot/KnotClient has been compiled by a more recent version of the Java Runtime (class file version 65.0), this version of the Java Runtime only recognizes class file versions up to 61.0";
+
+    let diagnoses = CrashRuleSet::builtin().analyze(None, Some(log));
+
+    assert_eq!(diagnoses.len(), 1);
+    assert_eq!(diagnoses[0].rule_id, "wrong_java_version");
+    assert!(diagnoses[0].suggested_action.starts_with("Select Java 21 or newer"));
+}
+
+#[test]
+fn test_custom_rules() {
+    let rules = parse_rules(
+        r#"[
+            {
+                "id": "my_mod_crash",
+                "title": "My mod crashed",
+                "category": "mod",
+                "severity": "error",
+                "priority": 1000,
+                "patterns": ["MyModException in (?P<mod>\\w+): (?P<reason>.+)"],
+                "cause": "{mod} failed: {reason}",
+                "suggestion": "Contact the author of {mod}."
+            },
+            {
+                "id": "disabled_rule",
+                "title": "Disabled",
+                "category": "mod",
+                "severity": "warning",
+                "patterns": ["MyModException"],
+                "cause": "-",
+                "suggestion": "-",
+                "enabled": false
+            }
+        ]"#,
+    )
+    .unwrap();
+
+    let diagnoses = CrashRuleSet::from_rules(rules)
+        .analyze(Some("MyModException in coolmod: broken state"), None);
+
+    assert_eq!(diagnoses.len(), 1);
+    assert_eq!(diagnoses[0].offending_mod.as_deref(), Some("coolmod"));
+    assert_eq!(diagnoses[0].cause, "coolmod failed: broken state");
+}
@@ -0,0 +1,167 @@
+// tests/curseforge_tests.rs
+
+use async_trait::async_trait;
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::integrations::curseforge::{
+    cdn_download_url, content_dir_for_class, determine_loader_from_mod_loaders,
+    resolve_manifest_files, CurseForgeFile, CurseForgeFileHash, CurseForgeFileResolver,
+    CurseForgeManifest, CURSEFORGE_CLASS_DATA_PACKS, CURSEFORGE_CLASS_RESOURCE_PACKS,
+    CURSEFORGE_CLASS_SHADERS,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::{ModLoader, ModSource};
+use std::collections::HashMap;
+
+const MANIFEST: &str = r#"{
+    "minecraft": {
+        "version": "1.20.1",
+        "modLoaders": [{ "id": "forge-47.2.0", "primary": true }]
+    },
+    "manifestType": "minecraftModpack",
+    "manifestVersion": 1,
+    "name": "Test Pack",
+    "version": "1.0.0",
+    "author": "someone",
+    "files": [
+        { "projectID": 238222, "fileID": 4712345, "required": true },
+        { "projectID": 111111, "fileID": 4700001, "required": true },
+        { "projectID": 222222, "fileID": 4700002, "required": false }
+    ],
+    "overrides": "overrides"
+}"#;
+
+/// Serves file metadata from memory instead of the CurseForge API.
+struct MockResolver {
+    files: HashMap<u32, CurseForgeFile>,
+}
+
+#[async_trait]
+impl CurseForgeFileResolver for MockResolver {
+    async fn get_files(&self, file_ids: &[u32]) -> Result<HashMap<u32, CurseForgeFile>> {
+        Ok(file_ids
+            .iter()
+            .filter_map(|id| self.files.get(id).map(|f| (*id, f.clone())))
+            .collect())
+    }
+}
+
+fn mock_file(id: u32, mod_id: u32, file_name: &str, download_url: Option<&str>) -> CurseForgeFile {
+    CurseForgeFile {
+        id,
+        mod_id,
+        display_name: Some(file_name.to_string()),
+        file_name: file_name.to_string(),
+        download_url: download_url.map(str::to_string),
+        hashes: vec![CurseForgeFileHash {
+            value: "ABCDEF".to_string(),
+            algo: 1,
+        }],
+        file_length: 1,
+    }
+}
+
+#[test]
+fn test_loader_mapping() {
+    let manifest: CurseForgeManifest = serde_json::from_str(MANIFEST).unwrap();
+    assert_eq!(
+        determine_loader_from_mod_loaders(&manifest.minecraft),
+        (ModLoader::Forge, Some("1.20.1-47.2.0".to_string()))
+    );
+
+    let mut minecraft = manifest.minecraft.clone();
+    minecraft.mod_loaders[0].id = "neoforge-20.4.80".to_string();
+    assert_eq!(
+        determine_loader_from_mod_loaders(&minecraft),
+        (ModLoader::NeoForge, Some("20.4.80".to_string()))
+    );
+
+    minecraft.mod_loaders[0].id = "fabric-0.15.7".to_string();
+    assert_eq!(
+        determine_loader_from_mod_loaders(&minecraft),
+        (ModLoader::Fabric, Some("0.15.7".to_string()))
+    );
+
+    minecraft.mod_loaders.clear();
+    assert_eq!(
+        determine_loader_from_mod_loaders(&minecraft),
+        (ModLoader::Vanilla, None)
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_manifest_files_with_mock() -> Result<()> {
+    let manifest: CurseForgeManifest = serde_json::from_str(MANIFEST).unwrap();
+    let resolver = MockResolver {
+        files: HashMap::from([
+            (
+                4712345,
+                mock_file(4712345, 238222, "jei-1.20.1.jar", Some("http://localhost/jei.jar")),
+            ),
+            (4700001, mock_file(4700001, 111111, "some pack.zip", None)),
+        ]),
+    };
+
+    // The optional file is missing from the mock and must be skipped
+    let (mods, other_files) = resolve_manifest_files(&manifest, &resolver).await?;
+    assert_eq!(mods.len(), 1);
+    assert_eq!(other_files.len(), 1);
+
+    let jei = &mods[0];
+    assert_eq!(jei.associated_loader, Some(ModLoader::Forge));
+    match &jei.source {
+        ModSource::CurseForge {
+            project_id,
+            download_url,
+            file_hash_sha1,
+            ..
+        } => {
+            assert_eq!(*project_id, 238222);
+            assert_eq!(download_url.as_deref(), Some("http://localhost/jei.jar"));
+            assert_eq!(file_hash_sha1.as_deref(), Some("abcdef"));
+        }
+        other => panic!("Unexpected mod source: {:?}", other),
+    }
+
+    assert_eq!(
+        resolver.download_url(&other_files[0]),
+        cdn_download_url(4700001, "some pack.zip")
+    );
+    assert_eq!(
+        cdn_download_url(4700001, "some pack.zip"),
+        "https://edge.forgecdn.net/files/4700/1/some%20pack.zip"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_required_file_fails() {
+    let manifest: CurseForgeManifest = serde_json::from_str(MANIFEST).unwrap();
+    let resolver = MockResolver {
+        files: HashMap::new(),
+    };
+    assert!(resolve_manifest_files(&manifest, &resolver).await.is_err());
+}
+
+#[tokio::test]
+async fn test_non_mod_files_are_routed_by_class() -> Result<()> {
+    assert_eq!(
+        content_dir_for_class(Some(CURSEFORGE_CLASS_SHADERS)),
+        "shaderpacks"
+    );
+    assert_eq!(
+        content_dir_for_class(Some(CURSEFORGE_CLASS_DATA_PACKS)),
+        "datapacks"
+    );
+    assert_eq!(
+        content_dir_for_class(Some(CURSEFORGE_CLASS_RESOURCE_PACKS)),
+        "resourcepacks"
+    );
+    // Unknown projects (or a resolver that can't look up classes) keep the old behaviour
+    assert_eq!(content_dir_for_class(Some(17)), "resourcepacks");
+    assert_eq!(content_dir_for_class(None), "resourcepacks");
+
+    let resolver = MockResolver {
+        files: HashMap::new(),
+    };
+    assert!(resolver.get_project_classes(&[111111]).await?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,276 @@
+// tests/download_utils_tests.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::utils::download_utils::{DownloadConfig, DownloadUtils};
+use noriskclient_launcher_v3_lib::utils::hash_utils;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const PAYLOAD_SIZE: usize = 300_000;
+
+/// Minimal HTTP/1.1 stand-in that can cut connections after a number of body bytes.
+struct FlakyServer {
+    addr: SocketAddr,
+    /// Range start offsets of all requests received so far (`None` = no Range header)
+    requests: Arc<Mutex<Vec<Option<u64>>>>,
+}
+
+impl FlakyServer {
+    /// `drop_after[i]` limits the body bytes sent for the i-th request; later requests get everything.
+    async fn start(payload: Arc<Vec<u8>>, drop_after: Vec<usize>, honor_range: bool) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let requests_clone = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((socket, _)) = listener.accept().await else {
+                    break;
+                };
+                let request_index = requests_clone.lock().unwrap().len();
+                let limit = drop_after.get(request_index).copied();
+                serve(socket, &payload, limit, honor_range, &requests_clone).await;
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    fn url(&self) -> String {
+        format!("http://{}/files/runtime.bin", self.addr)
+    }
+
+    fn requests(&self) -> Vec<Option<u64>> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve(
+    mut socket: TcpStream,
+    payload: &[u8],
+    limit: Option<usize>,
+    honor_range: bool,
+    requests: &Mutex<Vec<Option<u64>>>,
+) {
+    let mut raw = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !raw.windows(4).any(|w| w == b"\r\n\r\n") {
+        match socket.read(&mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => raw.extend_from_slice(&buf[..n]),
+        }
+    }
+
+    let request = String::from_utf8_lossy(&raw).to_string();
+    let range_start = request.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if !name.eq_ignore_ascii_case("range") {
+            return None;
+        }
+        value.trim().strip_prefix("bytes=")?.trim_end_matches('-').parse::<u64>().ok()
+    });
+    requests.lock().unwrap().push(range_start);
+
+    let (head, body) = match range_start {
+        Some(start) if honor_range && (start as usize) < payload.len() => (
+            format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\n",
+                payload.len() - start as usize,
+                start,
+                payload.len() - 1,
+                payload.len()
+            ),
+            &payload[start as usize..],
+        ),
+        _ => (
+            format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n", payload.len()),
+            payload,
+        ),
+    };
+    let head = format!(
+        "{}ETag: \"test-etag\"\r\nAccept-Ranges: bytes\r\nConnection: close\r\n\r\n",
+        head
+    );
+
+    let body = match limit {
+        Some(limit) => &body[..limit.min(body.len())],
+        None => body,
+    };
+    let _ = socket.write_all(head.as_bytes()).await;
+    let _ = socket.write_all(body).await;
+    let _ = socket.flush().await;
+    let _ = socket.shutdown().await;
+}
+
+fn test_payload() -> Arc<Vec<u8>> {
+    Arc::new((0..PAYLOAD_SIZE).map(|i| (i * 31 % 251) as u8).collect())
+}
+
+fn test_config() -> DownloadConfig {
+    DownloadConfig::new()
+        .with_disk_space_check(false)
+        .with_retry_delay(Duration::from_millis(1), Duration::from_millis(10))
+}
+
+#[tokio::test]
+async fn test_resumes_after_dropped_connections() -> Result<()> {
+    let payload = test_payload();
+    let server = FlakyServer::start(payload.clone(), vec![100_000, 100_000], true).await;
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("runtime.bin");
+
+    let config = test_config()
+        .with_retries(3)
+        .with_sha1(hash_utils::calculate_sha1_from_bytes(&payload));
+    DownloadUtils::download_file(&server.url(), &target, config).await?;
+
+    assert_eq!(std::fs::read(&target)?, *payload);
+    assert_eq!(server.requests(), vec![None, Some(100_000), Some(200_000)]);
+    assert!(!DownloadUtils::part_path(&target).exists());
+    assert!(!dir.path().join("runtime.bin.part.json").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restarts_when_server_ignores_range() -> Result<()> {
+    let payload = test_payload();
+    let server = FlakyServer::start(payload.clone(), vec![100_000], false).await;
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("runtime.bin");
+
+    let config = test_config()
+        .with_retries(2)
+        .with_sha256(hash_utils::calculate_sha256_from_bytes(&payload));
+    DownloadUtils::download_file(&server.url(), &target, config).await?;
+
+    assert_eq!(std::fs::read(&target)?, *payload);
+    assert_eq!(server.requests(), vec![None, Some(100_000)]);
+    Ok(())
+}
+
+/// Leaves the first `offset` bytes and their resume state behind, like a launcher that was
+/// closed in the middle of a download
+fn seed_partial_download(target: &std::path::Path, payload: &[u8], offset: usize, sha1: &str) {
+    std::fs::write(DownloadUtils::part_path(target), &payload[..offset]).unwrap();
+    let state = serde_json::json!({
+        "url": "http://127.0.0.1:9/files/runtime.bin",
+        "expected_hash": sha1,
+        "offset": offset,
+        "etag": null,
+        "last_modified": null,
+    });
+    std::fs::write(
+        target.with_file_name("runtime.bin.part.json"),
+        state.to_string(),
+    )
+    .unwrap();
+}
+
+/// Answers every request with 404
+async fn not_found_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket
+                .write_all(
+                    b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                )
+                .await;
+            let _ = socket.shutdown().await;
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn test_removes_partial_file_after_last_attempt() -> Result<()> {
+    let payload = test_payload();
+    let server = FlakyServer::start(payload.clone(), vec![120_000], true).await;
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("runtime.bin");
+    let sha1 = hash_utils::calculate_sha1_from_bytes(&payload);
+
+    let result = DownloadUtils::download_file(
+        &server.url(),
+        &target,
+        test_config().with_retries(0).with_sha1(&sha1),
+    )
+    .await;
+    assert!(result.is_err());
+    assert!(!target.exists());
+    assert!(!DownloadUtils::part_path(&target).exists());
+    assert!(!dir.path().join("runtime.bin.part.json").exists());
+
+    DownloadUtils::download_file(&server.url(), &target, test_config().with_sha1(&sha1)).await?;
+
+    assert_eq!(std::fs::read(&target)?, *payload);
+    assert_eq!(server.requests(), vec![None, None]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resumes_interrupted_download_from_another_mirror() -> Result<()> {
+    let payload = test_payload();
+    let mirror = FlakyServer::start(payload.clone(), vec![], true).await;
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("runtime.bin");
+    let sha1 = hash_utils::calculate_sha1_from_bytes(&payload);
+    seed_partial_download(&target, &payload, 120_000, &sha1);
+
+    // Same file by hash, so the mirror continues where the other server stopped
+    DownloadUtils::download_file(&mirror.url(), &target, test_config().with_sha1(&sha1)).await?;
+
+    assert_eq!(std::fs::read(&target)?, *payload);
+    assert_eq!(mirror.requests(), vec![Some(120_000)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_error_removes_partial_file() -> Result<()> {
+    let payload = test_payload();
+    let addr = not_found_server().await;
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("runtime.bin");
+    let sha1 = hash_utils::calculate_sha1_from_bytes(&payload);
+    seed_partial_download(&target, &payload, 120_000, &sha1);
+
+    let url = format!("http://{}/files/runtime.bin", addr);
+    let result = DownloadUtils::download_file(
+        &url,
+        &target,
+        test_config().with_retries(0).with_sha1(&sha1),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(!DownloadUtils::part_path(&target).exists());
+    assert!(!dir.path().join("runtime.bin.part.json").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hash_mismatch_fails_and_cleans_up() -> Result<()> {
+    let payload = test_payload();
+    let server = FlakyServer::start(payload, vec![50_000], true).await;
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("runtime.bin");
+
+    let config = test_config()
+        .with_retries(2)
+        .with_sha1("0000000000000000000000000000000000000000");
+    let result = DownloadUtils::download_file(&server.url(), &target, config).await;
+
+    assert!(result.is_err());
+    assert!(!target.exists());
+    assert!(!DownloadUtils::part_path(&target).exists());
+    // A failed verification must not be resumed, the last attempt starts from zero again
+    assert_eq!(server.requests(), vec![None, Some(50_000), None]);
+    Ok(())
+}
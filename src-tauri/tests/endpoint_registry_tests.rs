@@ -0,0 +1,100 @@
+// tests/endpoint_registry_tests.rs
+
+use noriskclient_launcher_v3_lib::utils::endpoint_registry::{
+    Endpoint, EndpointOverride, EndpointRegistry,
+};
+
+fn registry_with_mirrors() -> EndpointRegistry {
+    serde_json::from_str(
+        r#"{
+            "libraries": {
+                "mirrors": ["https://bmclapi2.bangbang93.com/maven/"]
+            },
+            "piston_meta": {
+                "base_url": "http://localhost:8080/meta/",
+                "mirrors": ["https://bmclapi2.bangbang93.com"]
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_defaults_without_overrides() {
+    let registry = EndpointRegistry::default();
+    assert_eq!(
+        registry.base_url(Endpoint::Modrinth),
+        "https://api.modrinth.com/v2"
+    );
+    assert_eq!(
+        registry.urls_for(Endpoint::FabricMeta, "/versions/loader/1.21"),
+        vec!["https://meta.fabricmc.net/v2/versions/loader/1.21"]
+    );
+
+    // Without overrides, absolute URLs are used as they are
+    let url = "https://launchermeta.mojang.com/v1/packages/abc/1.8.9.json";
+    assert_eq!(registry.resolve_url(url), vec![url]);
+}
+
+#[test]
+fn test_base_url_and_mirrors_order() {
+    let registry = registry_with_mirrors();
+    assert_eq!(
+        registry.urls_for(Endpoint::PistonMeta, "mc/game/version_manifest.json"),
+        vec![
+            "http://localhost:8080/meta/mc/game/version_manifest.json",
+            "https://bmclapi2.bangbang93.com/mc/game/version_manifest.json",
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_absolute_urls() {
+    let registry = registry_with_mirrors();
+
+    // Library URLs from a version JSON keep the original host first, then the mirror
+    assert_eq!(
+        registry.resolve_url("https://libraries.minecraft.net/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar"),
+        vec![
+            "https://libraries.minecraft.net/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar",
+            "https://bmclapi2.bangbang93.com/maven/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar",
+        ]
+    );
+
+    // Legacy launchermeta URLs and URLs built from the overridden base are both rewritten
+    let expected = vec![
+        "http://localhost:8080/meta/v1/packages/abc/1.21.json",
+        "https://bmclapi2.bangbang93.com/v1/packages/abc/1.21.json",
+    ];
+    assert_eq!(
+        registry.resolve_url("https://launchermeta.mojang.com/v1/packages/abc/1.21.json"),
+        expected
+    );
+    assert_eq!(
+        registry.resolve_url("http://localhost:8080/meta/v1/packages/abc/1.21.json"),
+        expected
+    );
+
+    // Hosts that only share a prefix are not touched
+    let url = "https://libraries.minecraft.net.evil.example/a.jar";
+    assert_eq!(registry.resolve_url(url), vec![url]);
+}
+
+#[test]
+fn test_registry_roundtrip() {
+    let mut registry = EndpointRegistry::default();
+    registry.overrides.insert(
+        Endpoint::NoriskApi,
+        EndpointOverride {
+            base_url: Some("http://127.0.0.1:3000/api/v1".to_string()),
+            mirrors: Vec::new(),
+        },
+    );
+    let json = serde_json::to_string(&registry).unwrap();
+    assert_eq!(
+        json,
+        r#"{"norisk_api":{"base_url":"http://127.0.0.1:3000/api/v1","mirrors":[]}}"#
+    );
+    let parsed: EndpointRegistry = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, registry);
+}
@@ -0,0 +1,112 @@
+// tests/hook_utils_tests.rs
+
+use noriskclient_launcher_v3_lib::state::config_state::Hooks;
+use noriskclient_launcher_v3_lib::state::profile_state::{HookMode, ProfileHooks};
+use noriskclient_launcher_v3_lib::utils::hook_utils::{resolve_hooks, wrapper_args, HookContext};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+fn global_hooks() -> Hooks {
+    Hooks {
+        pre_launch: Some("backup.sh".to_string()),
+        wrapper: Some("gamemoderun".to_string()),
+        post_exit: Some("notify.sh".to_string()),
+    }
+}
+
+#[test]
+fn test_override_and_chain() {
+    let overridden = resolve_hooks(
+        &global_hooks(),
+        Some(&ProfileHooks {
+            pre_launch: Some("sync.sh".to_string()),
+            wrapper: Some(String::new()),
+            post_exit: None,
+            mode: HookMode::Override,
+        }),
+    );
+    assert_eq!(overridden.pre_launch, vec!["sync.sh"]);
+    // An empty profile hook disables the global one
+    assert!(overridden.wrapper.is_empty());
+    assert_eq!(overridden.post_exit, vec!["notify.sh"]);
+
+    let chained = resolve_hooks(
+        &global_hooks(),
+        Some(&ProfileHooks {
+            pre_launch: Some("sync.sh".to_string()),
+            wrapper: Some("mangohud".to_string()),
+            post_exit: None,
+            mode: HookMode::Chain,
+        }),
+    );
+    assert_eq!(chained.pre_launch, vec!["backup.sh", "sync.sh"]);
+    assert_eq!(chained.wrapper, vec!["gamemoderun", "mangohud"]);
+    assert_eq!(chained.post_exit, vec!["notify.sh"]);
+
+    assert_eq!(
+        resolve_hooks(&global_hooks(), None).wrapper,
+        vec!["gamemoderun"]
+    );
+}
+
+#[test]
+fn test_placeholders_and_env() {
+    let context = HookContext {
+        instance_dir: PathBuf::from("/games/My Pack"),
+        profile_id: Uuid::nil(),
+        profile_name: "My Pack".to_string(),
+        java_path: PathBuf::from("/opt/java/bin/java"),
+        game_version: "1.21.1".to_string(),
+        loader: "fabric".to_string(),
+    };
+
+    let command = context
+        .command("rsync -a ${NORISK_INSTANCE_DIR}/saves /backup/${NORISK_GAME_VERSION} ${UNKNOWN}")
+        .unwrap();
+    let args: Vec<String> = command
+        .get_args()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(command.get_program(), "rsync");
+    assert_eq!(
+        args,
+        vec!["-a", "/games/My Pack/saves", "/backup/1.21.1", "${UNKNOWN}"]
+    );
+    assert_eq!(
+        command.get_current_dir(),
+        Some(PathBuf::from("/games/My Pack").as_path())
+    );
+    assert!(command
+        .get_envs()
+        .any(|(name, value)| name == "NORISK_LOADER" && value == Some("fabric".as_ref())));
+}
+
+#[test]
+fn test_chained_wrapper_args() {
+    let context = HookContext {
+        instance_dir: PathBuf::from("/games/My Pack"),
+        profile_id: Uuid::nil(),
+        profile_name: "My Pack".to_string(),
+        java_path: PathBuf::from("/opt/java/bin/java"),
+        game_version: "1.21.1".to_string(),
+        loader: "fabric".to_string(),
+    };
+    let wrappers = vec![
+        "gamemoderun".to_string(),
+        "mangohud --dlsym".to_string(),
+        "strace -o ${NORISK_INSTANCE_DIR}/trace.log".to_string(),
+    ];
+
+    assert_eq!(
+        wrapper_args(&wrappers, Some(&context)),
+        vec![
+            "gamemoderun",
+            "mangohud",
+            "--dlsym",
+            "strace",
+            "-o",
+            "/games/My Pack/trace.log"
+        ]
+    );
+    assert!(wrapper_args(&[], None).is_empty());
+}
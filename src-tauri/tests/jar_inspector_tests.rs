@@ -0,0 +1,250 @@
+// tests/jar_inspector_tests.rs
+
+mod common;
+
+use common::write_jar;
+use noriskclient_launcher_v3_lib::state::profile_state::ModLoader;
+use noriskclient_launcher_v3_lib::utils::jar_inspector::{
+    inspect_jar, parse_fabric_mod_json, parse_mcmod_info, parse_mods_toml, parse_quilt_mod_json,
+    JarFileStamp, JarMetadata, JarMetadataCache, ModDependency, ModDependencyKind, ModEnvironment,
+    ModMetadataFormat,
+};
+use noriskclient_launcher_v3_lib::utils::json_cache::JsonCache;
+
+fn dependency(mod_id: &str, version_range: Option<&str>, kind: ModDependencyKind) -> ModDependency {
+    ModDependency {
+        mod_id: mod_id.to_string(),
+        version_range: version_range.map(str::to_string),
+        kind,
+    }
+}
+
+#[test]
+fn test_fabric_and_quilt() {
+    let fabric = parse_fabric_mod_json(
+        r#"{
+            "schemaVersion": 1,
+            "id": "sodium",
+            "version": "0.5.8",
+            "name": "Sodium",
+            "authors": ["JellySquid", {"name": "IMS"}],
+            "environment": "client",
+            "icon": {"16": "assets/sodium/icon16.png", "128": "assets/sodium/icon.png"},
+            "depends": {"minecraft": ["1.20.1", "1.20.2"], "java": ">=17"},
+            "breaks": {"optifabric": "*"}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(fabric.format, ModMetadataFormat::Fabric);
+    assert_eq!(fabric.mod_id, "sodium");
+    assert_eq!(fabric.name.as_deref(), Some("Sodium"));
+    assert_eq!(fabric.authors, vec!["JellySquid", "IMS"]);
+    assert_eq!(fabric.environment, ModEnvironment::Client);
+    assert_eq!(fabric.icon.as_deref(), Some("assets/sodium/icon.png"));
+    assert_eq!(
+        fabric.dependencies,
+        vec![
+            dependency("java", Some(">=17"), ModDependencyKind::Required),
+            dependency(
+                "minecraft",
+                Some("1.20.1 || 1.20.2"),
+                ModDependencyKind::Required
+            ),
+            dependency("optifabric", Some("*"), ModDependencyKind::Incompatible),
+        ]
+    );
+    assert!(parse_fabric_mod_json("{").is_err());
+
+    let quilt = parse_quilt_mod_json(
+        r#"{
+            "schema_version": 1,
+            "quilt_loader": {
+                "id": "qsl",
+                "version": "7.0.0",
+                "metadata": {"name": "QSL", "contributors": {"QuiltMC": "Owner"}},
+                "depends": ["quilt_loader", {"id": "minecraft", "versions": ">=1.20"},
+                    {"id": "modmenu", "optional": true}]
+            },
+            "minecraft": {"environment": "dedicated_server"}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(quilt.mod_id, "qsl");
+    assert_eq!(quilt.authors, vec!["QuiltMC"]);
+    assert_eq!(quilt.environment, ModEnvironment::Server);
+    assert_eq!(
+        quilt.dependencies,
+        vec![
+            dependency("quilt_loader", None, ModDependencyKind::Required),
+            dependency("minecraft", Some(">=1.20"), ModDependencyKind::Required),
+            dependency("modmenu", None, ModDependencyKind::Optional),
+        ]
+    );
+    assert!(parse_quilt_mod_json(r#"{"id": "qsl"}"#).is_err());
+}
+
+#[test]
+fn test_forge() {
+    let mods_toml = r#"
+modLoader="javafml"
+loaderVersion="[47,)"
+
+[[mods]]
+modId="jei"
+version="${file.jarVersion}"
+displayName="Just Enough Items"
+authors="mezz, Ranged"
+logoFile="/jei.png"
+displayTest="IGNORE_ALL_VERSION"
+
+[[dependencies.jei]]
+    modId="forge"
+    mandatory=true
+    versionRange="[47,)"
+
+[[dependencies.jei]]
+    modId="jade"
+    mandatory=false
+"#;
+    let forge = parse_mods_toml(mods_toml, ModMetadataFormat::Forge).unwrap();
+    assert_eq!(forge.mod_id, "jei");
+    assert_eq!(forge.name.as_deref(), Some("Just Enough Items"));
+    assert_eq!(forge.authors, vec!["mezz", "Ranged"]);
+    assert_eq!(forge.icon.as_deref(), Some("jei.png"));
+    assert_eq!(forge.environment, ModEnvironment::Client);
+    assert_eq!(
+        forge.dependencies,
+        vec![
+            dependency("forge", Some("[47,)"), ModDependencyKind::Required),
+            dependency("jade", None, ModDependencyKind::Optional),
+        ]
+    );
+
+    let neoforge = r#"
+[[mods]]
+modId = "create"
+[[dependencies.create]]
+modId = "flywheel"
+type = "incompatible"
+"#;
+    let neoforge = parse_mods_toml(neoforge, ModMetadataFormat::NeoForge).unwrap();
+    assert_eq!(neoforge.format, ModMetadataFormat::NeoForge);
+    assert_eq!(
+        neoforge.dependencies,
+        vec![dependency(
+            "flywheel",
+            None,
+            ModDependencyKind::Incompatible
+        )]
+    );
+    assert!(parse_mods_toml("[[mods]", ModMetadataFormat::Forge).is_err());
+
+    let mcmod_info = r#"[{
+        "modid": "journeymap",
+        "name": "JourneyMap",
+        "version": "5.7.1",
+        "authorList": ["techbrew", "mysticdrew"],
+        "requiredMods": ["Forge@[14.23,)"],
+        "dependencies": ["Forge", "baubles"]
+    }]"#;
+    let legacy = parse_mcmod_info(mcmod_info).unwrap();
+    assert_eq!(legacy.format, ModMetadataFormat::LegacyForge);
+    assert_eq!(legacy.mod_id, "journeymap");
+    assert_eq!(legacy.authors, vec!["techbrew", "mysticdrew"]);
+    assert_eq!(
+        legacy.dependencies,
+        vec![
+            dependency("Forge", Some("[14.23,)"), ModDependencyKind::Required),
+            dependency("baubles", None, ModDependencyKind::Optional),
+        ]
+    );
+    assert!(parse_mcmod_info(r#"{"modList": []}"#).is_err());
+}
+
+#[tokio::test]
+async fn test_inspect_jar() {
+    let dir = tempfile::tempdir().unwrap();
+    let jar = dir.path().join("jei.jar");
+    write_jar(
+        &jar,
+        &[
+            (
+                "META-INF/MANIFEST.MF",
+                "Manifest-Version: 1.0\nImplementation-Version: 15.2.0.27\n",
+            ),
+            (
+                "META-INF/mods.toml",
+                "[[mods]]\nmodId=\"jei\"\nversion=\"${file.jarVersion}\"\n",
+            ),
+            (
+                "fabric.mod.json",
+                r#"{"id": "jei", "version": "15.2.0.27"}"#,
+            ),
+            ("mcmod.info", "not json"),
+        ],
+    );
+
+    let mods = inspect_jar(&jar).await.unwrap();
+    let formats: Vec<_> = mods.iter().map(|m| m.format).collect();
+    assert_eq!(
+        formats,
+        vec![ModMetadataFormat::Fabric, ModMetadataFormat::Forge]
+    );
+    // The placeholder is replaced by the manifest version
+    assert_eq!(mods[1].version.as_deref(), Some("15.2.0.27"));
+
+    let metadata = JarMetadata {
+        sha1: "abc".to_string(),
+        mods,
+    };
+    assert_eq!(
+        metadata.for_loader(ModLoader::NeoForge).map(|m| m.format),
+        Some(ModMetadataFormat::Forge)
+    );
+    assert_eq!(
+        metadata.for_loader(ModLoader::Vanilla).map(|m| m.format),
+        Some(ModMetadataFormat::Fabric)
+    );
+
+    assert!(inspect_jar(&dir.path().join("missing.jar")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_metadata_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let jar = dir.path().join("sodium.jar");
+    write_jar(&jar, &[("fabric.mod.json", r#"{"id": "sodium"}"#)]);
+    let mods = inspect_jar(&jar).await.unwrap();
+
+    let mut cache = JarMetadataCache::default();
+    cache.insert("abc".to_string(), mods.clone());
+    cache.remember_file(
+        jar.clone(),
+        JarFileStamp::of(&jar, "abc".to_string()).unwrap(),
+    );
+    assert!(cache.is_dirty());
+
+    let cache_path = dir.path().join("jar_metadata_cache.json");
+    cache.save(&cache_path).await.unwrap();
+    assert!(!cache.is_dirty());
+
+    let mut loaded = JarMetadataCache::load(&cache_path).await;
+    assert_eq!(loaded.known_sha1(&jar), Some("abc"));
+    assert_eq!(loaded.get("abc"), Some(&mods));
+
+    // A changed jar has to be hashed again
+    write_jar(
+        &jar,
+        &[("fabric.mod.json", r#"{"id": "sodium", "name": "Sodium"}"#)],
+    );
+    assert_eq!(loaded.known_sha1(&jar), None);
+
+    std::fs::remove_file(&jar).unwrap();
+    loaded.prune();
+    assert!(loaded.files.is_empty());
+    assert!(loaded.entries.is_empty());
+    assert!(loaded.is_dirty());
+
+    std::fs::write(&cache_path, "{").unwrap();
+    assert!(JarMetadataCache::load(&cache_path).await.entries.is_empty());
+}
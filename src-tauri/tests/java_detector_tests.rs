@@ -0,0 +1,115 @@
+// tests/java_detector_tests.rs
+
+use noriskclient_launcher_v3_lib::utils::java_detector::{
+    executable_modified_ms, expand_java_pattern, linux_java_patterns, matches_segment,
+    JavaInstallation, JavaProbeCache,
+};
+use noriskclient_launcher_v3_lib::utils::json_cache::JsonCache;
+use noriskclient_launcher_v3_lib::utils::system_info::Architecture;
+use std::path::{Path, PathBuf};
+
+fn touch(path: &Path) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, "").unwrap();
+}
+
+#[test]
+fn test_patterns() {
+    assert!(matches_segment("*-openjdk-*", "abc123-openjdk-17.0.8+7"));
+    assert!(!matches_segment("*-openjdk-*", "abc123-openjfx-17"));
+    assert!(matches_segment("openjdk*", "openjdk21"));
+    assert!(matches_segment("java", "java"));
+    assert!(!matches_segment("java", "javaw"));
+
+    let home = Path::new("/home/steve");
+    let patterns = linux_java_patterns(Some(home), |key| match key {
+        "SDKMAN_DIR" => Some("/data/sdkman".to_string()),
+        "USER" => Some("steve".to_string()),
+        _ => None,
+    });
+    let pattern = |source: &str| -> Vec<PathBuf> {
+        patterns
+            .iter()
+            .filter(|(s, _)| s == source)
+            .map(|(_, p)| p.clone())
+            .collect()
+    };
+    assert_eq!(
+        pattern("SDKMAN"),
+        vec![PathBuf::from("/data/sdkman/candidates/java/*/bin/java")]
+    );
+    assert_eq!(
+        pattern("asdf"),
+        vec![PathBuf::from("/home/steve/.asdf/installs/java/*/bin/java")]
+    );
+    assert!(pattern("Nix").contains(&PathBuf::from("/etc/profiles/per-user/steve/bin/java")));
+    assert!(pattern("Flatpak").contains(&PathBuf::from(
+        "/home/steve/.local/share/flatpak/runtime/org.freedesktop.Sdk.Extension.openjdk*/*/*/active/files/jvm/*/bin/java"
+    )));
+}
+
+#[tokio::test]
+async fn test_expand_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("nix/store");
+    touch(&store.join("aaa-openjdk-17.0.8+7/lib/openjdk/bin/java"));
+    touch(&store.join("bbb-openjdk-21.0.1+12/lib/openjdk/bin/java"));
+    // Source and documentation outputs don't ship an executable
+    std::fs::create_dir_all(store.join("ccc-openjdk-17.0.8+7-doc/share")).unwrap();
+    std::fs::create_dir_all(store.join("ddd-glibc-2.38/lib")).unwrap();
+
+    let found = expand_java_pattern(&store.join("*-openjdk-*/lib/openjdk/bin/java")).await;
+    assert_eq!(
+        found,
+        vec![
+            store.join("aaa-openjdk-17.0.8+7/lib/openjdk/bin/java"),
+            store.join("bbb-openjdk-21.0.1+12/lib/openjdk/bin/java"),
+        ]
+    );
+    assert!(expand_java_pattern(&dir.path().join("missing/*/bin/java"))
+        .await
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_probe_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let java = dir.path().join("jdk-17/bin/java");
+    touch(&java);
+    let modified_ms = executable_modified_ms(&java).unwrap();
+
+    let installation = JavaInstallation {
+        path: java.clone(),
+        version: "17.0.9".to_string(),
+        major_version: 17,
+        is_64bit: true,
+        vendor: "OpenJDK".to_string(),
+        vm_name: Some("HotSpot".to_string()),
+        source: "SDKMAN".to_string(),
+        architecture: Architecture::X64,
+    };
+    let mut cache = JavaProbeCache::default();
+    cache.insert(java.clone(), modified_ms, installation);
+    assert!(cache.is_dirty());
+
+    let cache_path = dir.path().join("java_detection_cache.json");
+    cache.save(&cache_path).await.unwrap();
+    assert!(!cache.is_dirty());
+
+    let mut loaded = JavaProbeCache::load(&cache_path).await;
+    assert_eq!(
+        loaded.get(&java, modified_ms).map(|i| i.version.as_str()),
+        Some("17.0.9")
+    );
+    // A replaced executable is probed again
+    assert!(loaded.get(&java, modified_ms + 1).is_none());
+
+    std::fs::remove_file(&java).unwrap();
+    loaded.prune();
+    assert!(loaded.entries.is_empty());
+    assert!(loaded.is_dirty());
+
+    // A corrupt file is an empty cache
+    std::fs::write(&cache_path, "{").unwrap();
+    assert!(JavaProbeCache::load(&cache_path).await.entries.is_empty());
+}
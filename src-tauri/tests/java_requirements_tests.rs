@@ -0,0 +1,190 @@
+// tests/java_requirements_tests.rs
+
+mod common;
+
+use common::write_jar;
+use noriskclient_launcher_v3_lib::minecraft::java_requirements::{
+    parse_fabric_java_requirement, parse_mods_toml_java_requirement, read_jar_java_requirement,
+    read_mod_java_constraints, JavaRequirement, JavaVersionRange,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::ModLoader;
+
+fn range(min: Option<u32>, max: Option<u32>) -> Option<JavaVersionRange> {
+    Some(JavaVersionRange::new(min, max))
+}
+
+fn fabric(java: &str) -> Option<JavaVersionRange> {
+    let json = format!(r#"{{"id": "test", "depends": {{"java": {}}}}}"#, java);
+    parse_fabric_java_requirement(&json).unwrap()
+}
+
+#[test]
+fn test_fabric_requirements() {
+    assert_eq!(fabric(r#"">=21""#), range(Some(21), None));
+    assert_eq!(fabric(r#"">17""#), range(Some(18), None));
+    assert_eq!(fabric(r#"">=17 <22""#), range(Some(17), Some(21)));
+    assert_eq!(fabric(r#""<=1.8""#), range(None, Some(8)));
+    assert_eq!(fabric(r#""17.x""#), range(Some(17), Some(17)));
+    // An array means any of them
+    assert_eq!(fabric(r#"["17", ">=21"]"#), range(Some(17), None));
+    assert_eq!(fabric(r#"">=17 <21 || >=21""#), range(Some(17), None));
+    assert_eq!(fabric(r#""*""#), None);
+
+    let without_java = r#"{"id": "test", "depends": {"fabricloader": ">=0.15"}}"#;
+    assert_eq!(parse_fabric_java_requirement(without_java).unwrap(), None);
+    assert_eq!(
+        parse_fabric_java_requirement(r#"{"id": "test"}"#).unwrap(),
+        None
+    );
+    assert!(parse_fabric_java_requirement("{").is_err());
+}
+
+#[test]
+fn test_mods_toml_requirements() {
+    let mods_toml = r#"
+modLoader="javafml"
+loaderVersion="[47,)"
+
+[[mods]]
+modId="example"
+version="1.0.0" # not a dependency
+
+[[dependencies.example]]
+    modId="minecraft"
+    mandatory=true
+    versionRange="[1.20.1,1.21)"
+
+[[dependencies.example]]
+    modId="java"
+    mandatory=true
+    versionRange="[17,22)" # LTS only
+"#;
+    assert_eq!(
+        parse_mods_toml_java_requirement(mods_toml).unwrap(),
+        range(Some(17), Some(21))
+    );
+
+    let neoforge = r#"
+[[dependencies.example]]
+modId = 'java'
+type = "required"
+versionRange = "[21,)"
+"#;
+    assert_eq!(
+        parse_mods_toml_java_requirement(neoforge).unwrap(),
+        range(Some(21), None)
+    );
+
+    // Optional dependencies don't restrict the runtime
+    let optional = r#"
+[[dependencies.example]]
+modId="java"
+type="optional"
+versionRange="[21,)"
+"#;
+    assert_eq!(parse_mods_toml_java_requirement(optional).unwrap(), None);
+
+    let soft = "[[dependencies.example]]\nmodId=\"java\"\nmandatory=true\nversionRange=\"17\"\n";
+    assert_eq!(
+        parse_mods_toml_java_requirement(soft).unwrap(),
+        range(Some(17), None)
+    );
+    assert_eq!(
+        parse_mods_toml_java_requirement("[[mods]]\nmodId=\"java\"\n").unwrap(),
+        None
+    );
+
+    // Real TOML: inline tables, literal strings and multi-line values
+    let inline = r#"
+description = '''
+versionRange="[8,)" is not a dependency
+'''
+dependencies.example = [
+    { modId = "minecraft", mandatory = true, versionRange = "[1.20.1,1.21)" },
+    { modId = 'java', mandatory = true, versionRange = '[17,)' },
+]
+"#;
+    assert_eq!(
+        parse_mods_toml_java_requirement(inline).unwrap(),
+        range(Some(17), None)
+    );
+
+    assert!(parse_mods_toml_java_requirement("[[dependencies.example]\nmodId=").is_err());
+}
+
+#[test]
+fn test_requirement() {
+    let mut requirement = JavaRequirement::for_minecraft("1.20.1", 17);
+    assert!(requirement.is_satisfied_by(17));
+    assert_eq!(requirement.lowest_satisfying(), Some(17));
+
+    requirement.add("sodium.jar", JavaVersionRange::at_least(21));
+    requirement.add("anything.jar", JavaVersionRange::default());
+    assert_eq!(requirement.constraints.len(), 2);
+    assert!(!requirement.is_satisfied_by(17));
+    assert_eq!(requirement.lowest_satisfying(), Some(21));
+    assert_eq!(
+        JavaRequirement::describe(&requirement.unmet_by(17)),
+        "sodium.jar (Java 21 or newer)"
+    );
+
+    requirement.add("legacy.jar", JavaVersionRange::new(None, Some(17)));
+    assert_eq!(requirement.lowest_satisfying(), None);
+}
+
+#[tokio::test]
+async fn test_read_jars() {
+    let dir = tempfile::tempdir().unwrap();
+    let fabric_jar = dir.path().join("sodium.jar");
+    write_jar(
+        &fabric_jar,
+        &[
+            ("fabric.mod.json", r#"{"depends": {"java": ">=21"}}"#),
+            (
+                "META-INF/mods.toml",
+                "[[dependencies.sodium]]\nmodId=\"java\"\nmandatory=true\nversionRange=\"[17,)\"\n",
+            ),
+        ],
+    );
+    let plain_jar = dir.path().join("library.jar");
+    write_jar(
+        &plain_jar,
+        &[("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\n")],
+    );
+
+    // Each loader reads its own metadata
+    assert_eq!(
+        read_jar_java_requirement(&fabric_jar, ModLoader::Fabric)
+            .await
+            .unwrap(),
+        range(Some(21), None)
+    );
+    assert_eq!(
+        read_jar_java_requirement(&fabric_jar, ModLoader::NeoForge)
+            .await
+            .unwrap(),
+        range(Some(17), None)
+    );
+    assert_eq!(
+        read_jar_java_requirement(&plain_jar, ModLoader::Fabric)
+            .await
+            .unwrap(),
+        None
+    );
+
+    assert!(
+        read_jar_java_requirement(&dir.path().join("missing.jar"), ModLoader::Quilt)
+            .await
+            .is_err()
+    );
+
+    // Missing jars are skipped
+    let mods = vec![
+        ("sodium.jar".to_string(), fabric_jar),
+        ("library.jar".to_string(), plain_jar),
+        ("missing.jar".to_string(), dir.path().join("missing.jar")),
+    ];
+    let constraints = read_mod_java_constraints(&mods, ModLoader::Quilt).await;
+    assert_eq!(constraints.len(), 1);
+    assert_eq!(constraints[0].source, "sodium.jar");
+}
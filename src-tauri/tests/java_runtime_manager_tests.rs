@@ -0,0 +1,107 @@
+// tests/java_runtime_manager_tests.rs
+
+use noriskclient_launcher_v3_lib::minecraft::downloads::java_runtime_manager::{
+    compare_java_versions, parse_release_file, parse_runtime_dir_name, removable_runtimes,
+    InstalledJavaRuntime, JavaRuntimeReference, JavaRuntimeUsage,
+};
+use noriskclient_launcher_v3_lib::minecraft::dto::{JavaDistribution, ZuluApiResponse};
+use std::cmp::Ordering;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+#[test]
+fn test_runtime_dir_names() {
+    let (distribution, major, force_x86_64) = parse_runtime_dir_name("zulu_17").unwrap();
+    assert!(distribution == JavaDistribution::Zulu);
+    assert_eq!((major, force_x86_64), (17, false));
+
+    let (_, major, force_x86_64) = parse_runtime_dir_name("zulu_8_x86_64").unwrap();
+    assert_eq!((major, force_x86_64), (8, true));
+
+    // Upgrade leftovers and foreign directories aren't runtimes
+    assert!(parse_runtime_dir_name("zulu_17.pending").is_none());
+    assert!(parse_runtime_dir_name("zulu_17.old").is_none());
+    assert!(parse_runtime_dir_name("openjdk_17").is_none());
+}
+
+#[test]
+fn test_versions() {
+    assert_eq!(
+        compare_java_versions("17.0.10", "17.0.9"),
+        Ordering::Greater
+    );
+    assert_eq!(
+        compare_java_versions("1.8.0_392", "1.8.0_402"),
+        Ordering::Less
+    );
+    assert_eq!(compare_java_versions("21.0.1", "21.0.1"), Ordering::Equal);
+
+    let zulu = |jdk_version: Vec<u32>| ZuluApiResponse {
+        url: String::new(),
+        jdk_version,
+        sha256_hash: None,
+    };
+    assert_eq!(
+        zulu(vec![17, 0, 9, 8]).version_string().as_deref(),
+        Some("17.0.9")
+    );
+    assert_eq!(
+        zulu(vec![8, 0, 392, 8]).version_string().as_deref(),
+        Some("1.8.0_392")
+    );
+    assert_eq!(zulu(vec![]).version_string(), None);
+
+    let release = parse_release_file(
+        "IMPLEMENTOR=\"Azul Systems, Inc.\"\nJAVA_VERSION=\"17.0.9\"\nOS_ARCH=\"aarch64\"\n",
+    );
+    assert_eq!(
+        release.get("JAVA_VERSION").map(String::as_str),
+        Some("17.0.9")
+    );
+    assert_eq!(
+        release.get("IMPLEMENTOR").map(String::as_str),
+        Some("Azul Systems, Inc.")
+    );
+}
+
+#[test]
+fn test_removable_runtimes() {
+    let runtime = |id: &str, in_use: bool, referenced: bool| InstalledJavaRuntime {
+        id: id.to_string(),
+        path: PathBuf::from(id),
+        java_binary: None,
+        distribution: "zulu".to_string(),
+        vendor: None,
+        major_version: 17,
+        version: None,
+        architecture: None,
+        size_bytes: 0,
+        referenced_by: if referenced {
+            vec![JavaRuntimeReference {
+                profile_id: Uuid::nil(),
+                profile_name: "Fabric".to_string(),
+                usage: JavaRuntimeUsage::AutoSelected,
+            }]
+        } else {
+            Vec::new()
+        },
+        in_use,
+        pending_version: None,
+    };
+    let runtimes = vec![
+        runtime("zulu_8", false, false),
+        runtime("zulu_17", false, true),
+        runtime("zulu_21", true, false),
+    ];
+
+    let ids: Vec<&str> = removable_runtimes(&runtimes, &[])
+        .unwrap()
+        .into_iter()
+        .map(|runtime| runtime.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["zulu_8"]);
+
+    // Offline, the auto-selected runtime of a profile can't be worked out. It might be any of
+    // them, so nothing is removable.
+    assert!(removable_runtimes(&runtimes, &["Vanilla 1.21".to_string()]).is_err());
+}
@@ -0,0 +1,157 @@
+// tests/java_vendors_tests.rs
+
+use noriskclient_launcher_v3_lib::minecraft::downloads::java_vendors::{
+    parse_adoptium_package, parse_corretto_package, parse_graalvm_package,
+    parse_mojang_runtime_index, parse_mojang_runtime_manifest, parse_zulu_package,
+    JavaPackageSource, JavaPlatform, MojangRuntimeEntry,
+};
+use noriskclient_launcher_v3_lib::minecraft::dto::JavaDistribution;
+use noriskclient_launcher_v3_lib::utils::system_info::{Architecture, OperatingSystem};
+
+const ZULU: &str = include_str!("fixtures/java_vendors/zulu_17_linux_x64.json");
+const ADOPTIUM: &str = include_str!("fixtures/java_vendors/adoptium_8_windows_x64.json");
+const CORRETTO_SHA256: &str = include_str!("fixtures/java_vendors/corretto_17_linux_x64.sha256");
+const GRAALVM_SHA256: &str = include_str!("fixtures/java_vendors/graalvm_17_linux_x64.sha256");
+const MOJANG_INDEX: &str = include_str!("fixtures/java_vendors/mojang_all.json");
+const MOJANG_MANIFEST: &str = include_str!("fixtures/java_vendors/mojang_manifest.json");
+
+fn archive(source: &JavaPackageSource) -> (&str, &str) {
+    match source {
+        JavaPackageSource::Archive { url, sha256 } => (url, sha256),
+        other => panic!("Expected an archive, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_archive_vendors() {
+    let zulu = parse_zulu_package(ZULU, 17).unwrap();
+    assert_eq!(zulu.distribution, JavaDistribution::Zulu);
+    assert_eq!(zulu.version.as_deref(), Some("17.0.9"));
+    assert_eq!(
+        archive(&zulu.source),
+        (
+            "https://cdn.azul.com/zulu/bin/zulu17.46.19-ca-jre17.0.9-linux_x64.tar.gz",
+            "e3e0dc8f4c5c2b4e0c4e3b69f9b2f1ad8a05af7d5d1b1b5f5d1a4a2b6f86b2b9"
+        )
+    );
+    // Without a published checksum the archive can't be verified
+    assert!(parse_zulu_package(r#"{"url": "https://cdn.azul.com/x.tar.gz"}"#, 17).is_err());
+
+    let temurin = parse_adoptium_package(ADOPTIUM, 8).unwrap().unwrap();
+    assert_eq!(temurin.version.as_deref(), Some("1.8.0_392"));
+    assert!(archive(&temurin.source)
+        .0
+        .ends_with("OpenJDK8U-jre_x64_windows_hotspot_8u392b08.zip"));
+    assert!(parse_adoptium_package("[]", 8).unwrap().is_none());
+
+    let corretto = parse_corretto_package(
+        17,
+        "https://corretto.aws/downloads/resources/17.0.9.8.1/amazon-corretto-17.0.9.8.1-linux-x64.tar.gz",
+        CORRETTO_SHA256,
+    )
+    .unwrap();
+    assert_eq!(corretto.version.as_deref(), Some("17.0.9"));
+    assert_eq!(
+        archive(&corretto.source).1,
+        "a3f1b4c2d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f80"
+    );
+    let corretto_8 = parse_corretto_package(
+        8,
+        "https://corretto.aws/downloads/resources/8.392.08.1/amazon-corretto-8.392.08.1-linux-x64.tar.gz",
+        CORRETTO_SHA256,
+    )
+    .unwrap();
+    assert_eq!(corretto_8.version.as_deref(), Some("1.8.0_392"));
+    assert!(parse_corretto_package(17, "https://corretto.aws/x", "<html>").is_err());
+
+    let graalvm = parse_graalvm_package(
+        17,
+        "https://download.oracle.com/graalvm/17/archive/graalvm-jdk-17.0.12_linux-x64_bin.tar.gz",
+        GRAALVM_SHA256,
+    )
+    .unwrap();
+    assert_eq!(graalvm.version.as_deref(), Some("17.0.12"));
+    assert_eq!(
+        archive(&graalvm.source).1,
+        "9d8c7b6a5f4e3d2c1b0a99887766554433221100ffeeddccbbaa998877665544"
+    );
+    let latest = parse_graalvm_package(
+        21,
+        "https://download.oracle.com/graalvm/21/latest/graalvm-jdk-21_linux-x64_bin.tar.gz",
+        GRAALVM_SHA256,
+    )
+    .unwrap();
+    assert_eq!(latest.version, None);
+}
+
+#[test]
+fn test_mojang_runtimes() {
+    let platform = JavaPlatform {
+        os: OperatingSystem::OSX,
+        arch: Architecture::AARCH64,
+    };
+    assert_eq!(platform.mojang_key(), Some("mac-os-arm64"));
+
+    let gamma = parse_mojang_runtime_index(MOJANG_INDEX, "linux", Some("java-runtime-gamma"), 17)
+        .unwrap()
+        .unwrap();
+    assert_eq!(gamma.distribution, JavaDistribution::Mojang);
+    assert_eq!(gamma.version.as_deref(), Some("17.0.8"));
+    assert_eq!(
+        gamma.source,
+        JavaPackageSource::MojangManifest {
+            url: "https://piston-meta.mojang.com/v1/packages/1b8d4bf2d4ef1c3d1e1c2c6d8ae3d7c0ef0b7a5e/manifest.json".to_string(),
+            sha1: "1b8d4bf2d4ef1c3d1e1c2c6d8ae3d7c0ef0b7a5e".to_string(),
+        }
+    );
+
+    let legacy = parse_mojang_runtime_index(MOJANG_INDEX, "linux", Some("jre-legacy"), 8)
+        .unwrap()
+        .unwrap();
+    assert_eq!(legacy.version.as_deref(), Some("1.8.0_51"));
+
+    // Without a component the newest runtime of the major version wins
+    let newest = parse_mojang_runtime_index(MOJANG_INDEX, "linux", None, 17)
+        .unwrap()
+        .unwrap();
+    assert_eq!(newest.version.as_deref(), Some("17.0.8"));
+
+    // No legacy runtime for Apple Silicon, that's what the x86_64 override is for
+    assert!(
+        parse_mojang_runtime_index(MOJANG_INDEX, "mac-os-arm64", Some("jre-legacy"), 8)
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        parse_mojang_runtime_index(MOJANG_INDEX, "linux", Some("java-runtime-gamma"), 21)
+            .unwrap()
+            .is_none()
+    );
+
+    let entries = parse_mojang_runtime_manifest(MOJANG_MANIFEST).unwrap();
+    assert_eq!(entries.len(), 6);
+    assert_eq!(
+        entries[0],
+        MojangRuntimeEntry::Directory {
+            path: "bin".to_string()
+        }
+    );
+    assert_eq!(
+        entries[1],
+        MojangRuntimeEntry::File {
+            path: "bin/java".to_string(),
+            url: "https://piston-data.mojang.com/v1/objects/3a8e0d4b6c9f2e1d7a5b4c3e2f1a0b9c8d7e6f5a/java".to_string(),
+            sha1: "3a8e0d4b6c9f2e1d7a5b4c3e2f1a0b9c8d7e6f5a".to_string(),
+            size: 12328,
+            executable: true,
+        }
+    );
+    assert!(entries.contains(&MojangRuntimeEntry::Link {
+        path: "legal/java.desktop/LICENSE".to_string(),
+        target: "../java.base/LICENSE".to_string(),
+    }));
+
+    // Files may not escape the runtime directory
+    let escaping = r#"{"files": {"../../evil": {"type": "directory"}}}"#;
+    assert!(parse_mojang_runtime_manifest(escaping).is_err());
+}
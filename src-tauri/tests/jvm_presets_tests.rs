@@ -0,0 +1,79 @@
+// tests/jvm_presets_tests.rs
+
+use noriskclient_launcher_v3_lib::minecraft::launch::jvm_presets::{
+    merge_with_custom_args, JvmPreset,
+};
+
+#[test]
+fn test_version_guards() {
+    let java21 = JvmPreset::GenerationalZgc.flags(21, None, 4096);
+    assert!(java21.contains(&"-XX:+UseZGC".to_string()));
+    assert!(java21.contains(&"-XX:+ZGenerational".to_string()));
+
+    // Generational is the default on 23+, the flag is dropped
+    let java23 = JvmPreset::GenerationalZgc.flags(23, None, 4096);
+    assert!(java23.contains(&"-XX:+UseZGC".to_string()));
+    assert!(!java23.contains(&"-XX:+ZGenerational".to_string()));
+
+    // ZGC isn't generational before 21, so the preset falls back to vanilla G1
+    assert_eq!(
+        JvmPreset::GenerationalZgc.flags(17, None, 4096),
+        JvmPreset::VanillaG1.flags(17, None, 4096)
+    );
+
+    let aikar_large = JvmPreset::AikarG1.flags(21, None, 16384);
+    assert!(aikar_large.contains(&"-XX:G1HeapRegionSize=16M".to_string()));
+    assert!(!aikar_large.contains(&"-XX:G1RSetUpdatingPauseTimePercent=5".to_string()));
+
+    assert!(JvmPreset::None.flags(21, None, 4096).is_empty());
+}
+
+#[test]
+fn test_shenandoah_needs_a_build_that_ships_it() {
+    let temurin = JvmPreset::Shenandoah.flags(21, Some("temurin"), 4096);
+    assert!(temurin.contains(&"-XX:+UseShenandoahGC".to_string()));
+    assert!(JvmPreset::Shenandoah.is_available(21, None));
+
+    // Oracle, GraalVM and OpenJ9 builds leave Shenandoah out, the preset falls back to vanilla G1
+    for vendor in [
+        "Oracle HotSpot",
+        "graalvm",
+        "OpenJDK GraalVM",
+        "Eclipse OpenJ9",
+    ] {
+        assert!(!JvmPreset::Shenandoah.is_available(21, Some(vendor)));
+        assert_eq!(
+            JvmPreset::Shenandoah.flags(21, Some(vendor), 4096),
+            JvmPreset::VanillaG1.flags(21, Some(vendor), 4096)
+        );
+    }
+}
+
+#[test]
+fn test_custom_args_override_preset() {
+    let preset = JvmPreset::VanillaG1.flags(21, None, 4096);
+
+    let merged = merge_with_custom_args(
+        preset.clone(),
+        &[
+            "-XX:MaxGCPauseMillis=100".to_string(),
+            "-Dfoo=bar".to_string(),
+        ],
+    );
+    assert_eq!(
+        merged,
+        vec![
+            "-XX:+UnlockExperimentalVMOptions",
+            "-XX:+UseG1GC",
+            "-XX:G1NewSizePercent=20",
+            "-XX:G1ReservePercent=20",
+            "-XX:G1HeapRegionSize=32M",
+            "-XX:MaxGCPauseMillis=100",
+            "-Dfoo=bar",
+        ]
+    );
+
+    // Choosing a collector replaces the whole preset
+    let merged = merge_with_custom_args(preset, &["-XX:+UseParallelGC".to_string()]);
+    assert_eq!(merged, vec!["-XX:+UseParallelGC"]);
+}
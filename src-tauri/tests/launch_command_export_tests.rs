@@ -0,0 +1,56 @@
+// tests/launch_command_export_tests.rs
+
+use noriskclient_launcher_v3_lib::minecraft::launch::command_export::LaunchCommand;
+use std::process::Command;
+
+fn sample_command() -> LaunchCommand {
+    let mut command = Command::new("/opt/java/bin/java");
+    command
+        .current_dir("/home/steve/NoRisk Launcher/profiles/my pack")
+        .arg("-Xmx4096M")
+        .arg("-Dnorisk.token=secret-norisk")
+        .arg("-cp")
+        .arg("/libs/a.jar:/libs/b.jar")
+        .arg("net.fabricmc.loader.impl.launch.knot.KnotClient")
+        .arg("--username")
+        .arg("Steve")
+        .arg("--accessToken")
+        .arg("secret-access")
+        .arg("--quickPlaySingleplayer")
+        .arg("Steve's World");
+    LaunchCommand::from_command(&command)
+}
+
+#[test]
+fn test_tokens_are_redacted() {
+    let command = sample_command().redacted();
+
+    assert!(!command.args.iter().any(|arg| arg.contains("secret")));
+    assert!(command
+        .args
+        .contains(&"-Dnorisk.token=${NORISK_TOKEN}".to_string()));
+    assert!(command.args.contains(&"${ACCESS_TOKEN}".to_string()));
+    assert_eq!(command.redacted, vec!["NORISK_TOKEN", "ACCESS_TOKEN"]);
+
+    let json: serde_json::Value = serde_json::from_str(&command.to_json().unwrap()).unwrap();
+    assert_eq!(json["program"], "/opt/java/bin/java");
+    assert_eq!(json["args"][1], "-Dnorisk.token=${NORISK_TOKEN}");
+}
+
+#[test]
+fn test_shell_script_quoting() {
+    let script = sample_command().redacted().to_shell_script();
+
+    assert!(script.starts_with("#!/bin/sh\n"));
+    assert!(script.contains(": \"${ACCESS_TOKEN:?"));
+    assert!(script.contains("cd '/home/steve/NoRisk Launcher/profiles/my pack'\n"));
+    assert!(script.contains("exec /opt/java/bin/java \\\n"));
+    assert!(script.contains("  -Dnorisk.token=\"${NORISK_TOKEN}\" \\\n"));
+    assert!(script.contains("  \"${ACCESS_TOKEN}\" \\\n"));
+    assert!(script.contains("  'Steve'\\''s World'\n"));
+
+    // Without redaction the tokens are written as plain arguments
+    let script = sample_command().to_shell_script();
+    assert!(script.contains("  secret-access \\\n"));
+    assert!(!script.contains(":?"));
+}
@@ -0,0 +1,123 @@
+// tests/log_parser_tests.rs
+
+use noriskclient_launcher_v3_lib::minecraft::log_parser::{
+    filter_records, parse_log, LogFilter, LogLevel,
+};
+
+const FABRIC_LOG: &str = "[12:00:01] [main/INFO] (FabricLoader/GameProvider) Loading Minecraft 1.21.1 with Fabric Loader 0.16.5
+[12:00:02] [Render thread/INFO] (Minecraft) Setting user: Steve
+[12:00:03] [Worker-Main-2/ERROR] (Minecraft) Failed to load texture: minecraft:textures/foo.png
+java.io.FileNotFoundException: minecraft:textures/foo.png
+\tat net.minecraft.class_3300.method_14486(class_3300.java:42)
+\tat net.minecraft.class_1060.method_4616(class_1060.java:60)
+[12:00:04] [Render thread/WARN] (sodium) Something looks off
+";
+
+const FORGE_LOG: &str = "[16Jun2023 22:21:44.123] [main/INFO] [cpw.mods.modlauncher.Launcher/MODLAUNCHER]: ModLauncher running: args [--username, Steve]
+[16Jun2023 22:21:45.456] [main/DEBUG] [net.minecraftforge.fml.loading.FMLLoader/CORE]: FML found ForgeSPI package implementation version 7.0.1
+";
+
+const XML_LOG: &str = r#"<log4j:Event logger="net.minecraft.client.Minecraft" timestamp="1718568104123" level="INFO" thread="Render thread">
+  <log4j:Message><![CDATA[Setting user: Steve]]></log4j:Message>
+</log4j:Event>
+<log4j:Event logger="net.minecraft.server.MinecraftServer" timestamp="1718568105000" level="ERROR" thread="Server thread">
+  <log4j:Message><![CDATA[Encountered an unexpected exception]]></log4j:Message>
+  <log4j:Throwable><![CDATA[java.lang.NullPointerException: Cannot invoke "Object.toString()"
+	at net.minecraft.server.MinecraftServer.tick(MinecraftServer.java:812)
+]]></log4j:Throwable>
+</log4j:Event>
+<log4j:Event logger="net.minecraft.client.Minecraft" timestamp="1718568106000" level="WARN" thread="Render thread">
+  <log4j:Message><![CDATA[Reloading Resour"#;
+
+#[test]
+fn test_plain_layouts() {
+    let records = parse_log(FABRIC_LOG);
+    assert_eq!(records.len(), 4);
+    assert_eq!(
+        records[0].logger.as_deref(),
+        Some("FabricLoader/GameProvider")
+    );
+    assert_eq!(records[2].thread.as_deref(), Some("Worker-Main-2"));
+    assert_eq!(records[2].level, Some(LogLevel::Error));
+    assert_eq!(records[2].stack_trace.len(), 3);
+    assert_eq!(records[3].index, 3);
+
+    let records = parse_log(FORGE_LOG);
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0].timestamp.as_deref(),
+        Some("16Jun2023 22:21:44.123")
+    );
+    assert_eq!(
+        records[1].logger.as_deref(),
+        Some("net.minecraftforge.fml.loading.FMLLoader/CORE")
+    );
+    assert!(records[1].message.starts_with("FML found ForgeSPI"));
+}
+
+#[test]
+fn test_xml_layout() {
+    let records = parse_log(XML_LOG);
+    // The last event is cut off, like a log of a running or killed game
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].message, "Setting user: Steve");
+    assert_eq!(
+        records[0].timestamp.as_deref(),
+        Some("2024-06-16T20:01:44.123+00:00")
+    );
+    assert_eq!(records[1].level, Some(LogLevel::Error));
+    assert_eq!(records[1].stack_trace.len(), 2);
+    assert_eq!(records[2].level, Some(LogLevel::Warn));
+}
+
+#[test]
+fn test_filter_and_paging() {
+    let records = parse_log(FABRIC_LOG);
+
+    let page = filter_records(
+        records.clone(),
+        &LogFilter {
+            levels: vec![LogLevel::Error, LogLevel::Warn],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(page.total, 2);
+
+    // Regex and text search look into stack traces too
+    let page = filter_records(
+        records.clone(),
+        &LogFilter {
+            pattern: Some(r"class_1060\.java:\d+".to_string()),
+            logger: Some("minecraft".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.records[0].index, 2);
+
+    let page = filter_records(
+        records.clone(),
+        &LogFilter {
+            offset: 1,
+            limit: Some(2),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(page.total, 4);
+    assert_eq!(
+        page.records.iter().map(|r| r.index).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    assert!(filter_records(
+        records,
+        &LogFilter {
+            pattern: Some("(unclosed".to_string()),
+            ..Default::default()
+        }
+    )
+    .is_err());
+}
@@ -0,0 +1,292 @@
+// tests/modrinth_resolver_tests.rs
+
+use async_trait::async_trait;
+use noriskclient_launcher_v3_lib::error::{AppError, Result};
+use noriskclient_launcher_v3_lib::integrations::modrinth::ModrinthVersion;
+use noriskclient_launcher_v3_lib::integrations::modrinth_resolver::{
+    associated_loader, resolve_install_plan, ConflictKind, ModrinthVersionProvider,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::{ModLoader, Profile};
+use serde_json::json;
+
+/// Serves version metadata from memory instead of the Modrinth API.
+struct MockProvider {
+    versions: Vec<ModrinthVersion>,
+}
+
+#[async_trait]
+impl ModrinthVersionProvider for MockProvider {
+    async fn get_version(&self, version_id: &str) -> Result<ModrinthVersion> {
+        self.versions
+            .iter()
+            .find(|v| v.id == version_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(version_id.to_string()))
+    }
+
+    async fn get_versions(&self, version_ids: &[String]) -> Result<Vec<ModrinthVersion>> {
+        Ok(self
+            .versions
+            .iter()
+            .filter(|v| version_ids.contains(&v.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_project_versions(
+        &self,
+        project_id: &str,
+        _loaders: &[String],
+        _game_version: &str,
+    ) -> Result<Vec<ModrinthVersion>> {
+        // Unfiltered on purpose, the resolver has to check compatibility itself
+        Ok(self
+            .versions
+            .iter()
+            .filter(|v| v.project_id == project_id)
+            .cloned()
+            .collect())
+    }
+}
+
+/// `dependencies` are (type, project_id, version_id) tuples
+fn version(
+    id: &str,
+    project_id: &str,
+    version_type: &str,
+    date: &str,
+    game_version: &str,
+    dependencies: &[(&str, Option<&str>, Option<&str>)],
+) -> ModrinthVersion {
+    let dependencies: Vec<_> = dependencies
+        .iter()
+        .map(|(dependency_type, project_id, version_id)| {
+            json!({
+                "version_id": version_id,
+                "project_id": project_id,
+                "file_name": null,
+                "dependency_type": dependency_type,
+            })
+        })
+        .collect();
+    serde_json::from_value(json!({
+        "id": id,
+        "project_id": project_id,
+        "author_id": null,
+        "featured": false,
+        "name": format!("{} {}", project_id, id),
+        "version_number": id,
+        "changelog": null,
+        "dependencies": dependencies,
+        "game_versions": [game_version],
+        "version_type": version_type,
+        "loaders": ["fabric"],
+        "files": [{
+            "hashes": { "sha512": null, "sha1": format!("sha1-{}", id) },
+            "url": format!("https://cdn.modrinth.com/{}.jar", id),
+            "filename": format!("{}.jar", id),
+            "primary": true,
+            "size": 1,
+            "file_type": null,
+        }],
+        "date_published": date,
+    }))
+    .unwrap()
+}
+
+/// Fabric 1.21.1 profile with the given (project_id, version_id) Modrinth mods installed
+fn profile(installed: &[(&str, &str)]) -> Profile {
+    let mods: Vec<_> = installed
+        .iter()
+        .map(|(project_id, version_id)| {
+            json!({
+                "source": {
+                    "type": "modrinth",
+                    "project_id": project_id,
+                    "version_id": version_id,
+                    "file_name": format!("{}.jar", version_id),
+                    "download_url": format!("https://cdn.modrinth.com/{}.jar", version_id),
+                    "file_hash_sha1": null,
+                },
+                "enabled": true,
+                "display_name": project_id,
+                "version": null,
+                "game_versions": null,
+                "file_name_override": null,
+                "associated_loader": null,
+            })
+        })
+        .collect();
+    serde_json::from_value(json!({
+        "name": "Resolver Test",
+        "path": "resolver-test",
+        "game_version": "1.21.1",
+        "loader": "fabric",
+        "loader_version": null,
+        "last_played": null,
+        "description": null,
+        "norisk_information": null,
+        "mods": mods,
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_transitive_closure_picks_compatible_versions() -> Result<()> {
+    let provider = MockProvider {
+        versions: vec![
+            version("a1", "aaaa", "release", "2024-08-01", "1.21.1", &[("required", Some("bbbb"), None)]),
+            // Newer beta and a release for another game version must both lose against b1
+            version("b1", "bbbb", "release", "2024-07-01", "1.21.1", &[("required", None, Some("c1"))]),
+            version("b2", "bbbb", "beta", "2024-09-01", "1.21.1", &[]),
+            version("b3", "bbbb", "release", "2024-10-01", "1.20.1", &[]),
+            version("c1", "cccc", "release", "2024-01-01", "1.21.1", &[("optional", Some("dddd"), None)]),
+            version("c2", "cccc", "release", "2024-06-01", "1.21.1", &[]),
+        ],
+    };
+
+    let plan = resolve_install_plan(&provider, &profile(&[]), "a1").await?;
+
+    assert!(plan.can_apply(), "unexpected problems: {:?}", plan);
+    let resolved: Vec<(&str, Option<&str>)> = plan
+        .mods
+        .iter()
+        .map(|m| (m.version_id.as_str(), m.required_by.as_deref()))
+        .collect();
+    assert_eq!(
+        resolved,
+        vec![("a1", None), ("b1", Some("aaaa")), ("c1", Some("bbbb"))]
+    );
+    assert_eq!(plan.mods[1].file_hash_sha1.as_deref(), Some("sha1-b1"));
+    assert_eq!(plan.optional.len(), 1);
+    assert_eq!(plan.optional[0].project_id, "dddd");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_conflicts_with_installed_mods() -> Result<()> {
+    let provider = MockProvider {
+        versions: vec![
+            version(
+                "a1",
+                "aaaa",
+                "release",
+                "2024-08-01",
+                "1.21.1",
+                &[
+                    ("required", Some("fapi"), None),
+                    ("required", Some("missing"), None),
+                    ("incompatible", Some("optifine"), None),
+                ],
+            ),
+            version("f1", "fapi", "release", "2024-01-01", "1.21.1", &[]),
+            // Installed mod that declares the new one incompatible
+            version("s1", "sodium", "release", "2024-01-01", "1.21.1", &[("incompatible", Some("aaaa"), None)]),
+            version("o1", "optifine", "release", "2024-01-01", "1.21.1", &[]),
+        ],
+    };
+    let profile = profile(&[("fapi", "f1"), ("sodium", "s1"), ("optifine", "o1")]);
+
+    let plan = resolve_install_plan(&provider, &profile, "a1").await?;
+
+    assert!(!plan.can_apply());
+    assert_eq!(plan.mods.len(), 1);
+    assert_eq!(plan.satisfied.len(), 1);
+    assert_eq!(plan.satisfied[0].project_id, "fapi");
+    assert_eq!(plan.unresolved.len(), 1);
+    assert_eq!(plan.unresolved[0].project_id.as_deref(), Some("missing"));
+
+    let mut conflicts: Vec<(ConflictKind, Option<&str>)> = plan
+        .conflicts
+        .iter()
+        .map(|c| (c.kind, c.other_project_id.as_deref()))
+        .collect();
+    conflicts.sort_by_key(|(_, other)| other.map(str::to_string));
+    assert_eq!(
+        conflicts,
+        vec![
+            (ConflictKind::Incompatible, Some("optifine")),
+            (ConflictKind::Incompatible, Some("sodium")),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unsupported_root_version() -> Result<()> {
+    let provider = MockProvider {
+        versions: vec![version("a1", "aaaa", "release", "2024-08-01", "1.20.1", &[])],
+    };
+
+    let plan = resolve_install_plan(&provider, &profile(&[]), "a1").await?;
+
+    assert_eq!(plan.conflicts.len(), 1);
+    assert_eq!(plan.conflicts[0].kind, ConflictKind::UnsupportedByProfile);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_disabled_mods_are_replaced() -> Result<()> {
+    let provider = MockProvider {
+        versions: vec![
+            version("a1", "aaaa", "release", "2024-08-01", "1.21.1", &[("required", Some("fapi"), None)]),
+            version("f1", "fapi", "release", "2024-01-01", "1.21.1", &[]),
+            version("o1", "optifine", "release", "2024-01-01", "1.21.1", &[("incompatible", Some("aaaa"), None)]),
+        ],
+    };
+    let mut profile = profile(&[("aaaa", "a1"), ("fapi", "f1"), ("optifine", "o1")]);
+    for installed in &mut profile.mods {
+        installed.enabled = false;
+    }
+
+    let plan = resolve_install_plan(&provider, &profile, "a1").await?;
+
+    // A disabled dependency isn't satisfied and a disabled mod doesn't conflict
+    assert!(plan.can_apply(), "unexpected problems: {:?}", plan);
+    assert!(plan.satisfied.is_empty());
+    let replaced: Vec<(&str, Option<&str>)> = plan
+        .mods
+        .iter()
+        .map(|m| (m.version_id.as_str(), m.replaces_version_id.as_deref()))
+        .collect();
+    assert_eq!(replaced, vec![("a1", Some("a1")), ("f1", Some("f1"))]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_quilt_profile_installs_fabric_mods() -> Result<()> {
+    let provider = MockProvider {
+        versions: vec![
+            version("a1", "aaaa", "release", "2024-08-01", "1.21.1", &[("required", Some("fapi"), None)]),
+            version("f1", "fapi", "release", "2024-01-01", "1.21.1", &[]),
+        ],
+    };
+    let mut profile = profile(&[]);
+    profile.loader = ModLoader::Quilt;
+
+    let plan = resolve_install_plan(&provider, &profile, "a1").await?;
+
+    assert!(plan.can_apply(), "unexpected problems: {:?}", plan);
+    assert_eq!(plan.mods.len(), 2);
+    // Recorded as Quilt mods, otherwise the launch skips them
+    for planned in &plan.mods {
+        assert_eq!(planned.loaders, vec!["fabric".to_string()]);
+        assert_eq!(
+            associated_loader(&profile.loader, &planned.loaders),
+            Some(ModLoader::Quilt)
+        );
+    }
+    assert_eq!(
+        associated_loader(&ModLoader::Fabric, &planned_loaders(&["fabric", "quilt"])),
+        Some(ModLoader::Fabric)
+    );
+    assert_eq!(
+        associated_loader(&ModLoader::Forge, &planned_loaders(&["fabric"])),
+        Some(ModLoader::Fabric)
+    );
+    Ok(())
+}
+
+fn planned_loaders(loaders: &[&str]) -> Vec<String> {
+    loaders.iter().map(|l| l.to_string()).collect()
+}
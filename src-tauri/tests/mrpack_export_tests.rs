@@ -0,0 +1,152 @@
+// tests/mrpack_export_tests.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::integrations::mrpack::{
+    collect_override_files, dependencies_for_profile, env_for_sides, process_mrpack, write_mrpack,
+    ModrinthIndex, ModrinthIndexFile, FABRIC_LOADER_DEPENDENCY, FORGE_DEPENDENCY,
+    MINECRAFT_DEPENDENCY,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::{ModLoader, Profile};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+fn profile(loader: &str, loader_version: Option<&str>) -> Profile {
+    serde_json::from_value(json!({
+        "name": "Export Test",
+        "path": "export-test",
+        "game_version": "1.20.1",
+        "loader": loader,
+        "loader_version": loader_version,
+        "last_played": null,
+        "description": "Exported by a test",
+        "norisk_information": null,
+        "mods": [],
+    }))
+    .unwrap()
+}
+
+fn write_file(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_dependencies_for_profile() -> Result<()> {
+    let dependencies = dependencies_for_profile(&profile("fabric", Some("0.15.7")))?;
+    assert_eq!(dependencies.len(), 2);
+    assert_eq!(dependencies[MINECRAFT_DEPENDENCY], "1.20.1");
+    assert_eq!(dependencies[FABRIC_LOADER_DEPENDENCY], "0.15.7");
+
+    // Forge versions are stored maven-style, the mrpack only wants the loader part
+    let dependencies = dependencies_for_profile(&profile("forge", Some("1.20.1-47.2.0")))?;
+    assert_eq!(dependencies[FORGE_DEPENDENCY], "47.2.0");
+
+    let dependencies = dependencies_for_profile(&profile("vanilla", None))?;
+    assert_eq!(
+        dependencies,
+        HashMap::from([(MINECRAFT_DEPENDENCY.to_string(), "1.20.1".to_string())])
+    );
+
+    assert!(dependencies_for_profile(&profile("quilt", None)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_env_for_project() {
+    let client_only = env_for_sides("required", "unsupported").unwrap();
+    assert_eq!(client_only["client"], "required");
+    assert_eq!(client_only["server"], "unsupported");
+
+    let server_only = env_for_sides("unsupported", "optional").unwrap();
+    assert_eq!(server_only["client"], "unsupported");
+    assert_eq!(server_only["server"], "required");
+
+    assert!(env_for_sides("required", "required").is_none());
+    assert!(env_for_sides("optional", "unknown").is_none());
+}
+
+#[tokio::test]
+async fn test_export_round_trip() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let instance = dir.path().join("instance");
+    write_file(&instance.join("options.txt"), "fov:90");
+    write_file(&instance.join("config/sodium.json"), "{}");
+    write_file(&instance.join("config_backup/sodium.json"), "old");
+    write_file(&instance.join("mods/sodium.jar"), "managed");
+    write_file(&instance.join("saves/world/level.dat"), "not selected");
+
+    // Selecting mods/ must not ship the managed jars twice, selecting config/ must not pull in
+    // config_backup/
+    let overrides = collect_override_files(
+        &instance,
+        &[
+            instance.join("options.txt"),
+            instance.join("config"),
+            instance.join("mods"),
+        ],
+    )
+    .await?;
+    let mut zip_paths: Vec<&str> = overrides.iter().map(|(path, _)| path.as_str()).collect();
+    zip_paths.sort();
+    assert_eq!(
+        zip_paths,
+        vec!["overrides/config/sodium.json", "overrides/options.txt"]
+    );
+
+    let source = profile("fabric", Some("0.15.7"));
+    let index = ModrinthIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: "2024.05.01".to_string(),
+        name: source.name.clone(),
+        summary: source.description.clone(),
+        files: vec![ModrinthIndexFile {
+            path: "mods/sodium.jar".to_string(),
+            hashes: HashMap::from([
+                ("sha1".to_string(), "abc".to_string()),
+                ("sha512".to_string(), "def".to_string()),
+            ]),
+            env: env_for_sides("required", "unsupported"),
+            downloads: vec!["https://cdn.modrinth.com/sodium.jar".to_string()],
+            file_size: 7,
+        }],
+        dependencies: dependencies_for_profile(&source)?,
+    };
+    let pack_path = dir.path().join("out/export-test.mrpack");
+    // The same zip path twice (e.g. a custom mod and a cached jar) is only written once
+    let mut overrides = overrides;
+    overrides.push((
+        "overrides/options.txt".to_string(),
+        instance.join("config_backup/sodium.json"),
+    ));
+    write_mrpack(&pack_path, &index, &overrides).await?;
+
+    let (imported, imported_index) = process_mrpack(pack_path.clone()).await?;
+    assert_eq!(imported.name, "Export Test");
+    assert_eq!(imported.game_version, "1.20.1");
+    assert_eq!(imported.loader, ModLoader::Fabric);
+    assert_eq!(imported.loader_version.as_deref(), Some("0.15.7"));
+    assert_eq!(imported_index.files.len(), 1);
+    assert_eq!(imported_index.files[0].env, index.files[0].env);
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&pack_path)?).unwrap();
+    let mut names: Vec<&str> = archive.file_names().collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "modrinth.index.json",
+            "overrides/config/sodium.json",
+            "overrides/options.txt"
+        ]
+    );
+    let mut options = String::new();
+    archive
+        .by_name("overrides/options.txt")
+        .unwrap()
+        .read_to_string(&mut options)?;
+    assert_eq!(options, "fov:90");
+    Ok(())
+}
@@ -0,0 +1,96 @@
+// tests/offline_tests.rs
+
+use noriskclient_launcher_v3_lib::error::AppError;
+use noriskclient_launcher_v3_lib::minecraft::offline;
+use noriskclient_launcher_v3_lib::state::profile_state::Profile;
+use noriskclient_launcher_v3_lib::utils::download_utils::{DownloadConfig, DownloadUtils};
+use noriskclient_launcher_v3_lib::utils::hash_utils;
+
+// Nothing listens on this port, any request would fail
+const UNREACHABLE_URL: &str = "http://127.0.0.1:9/libraries/example-1.0.jar";
+
+fn test_profile() -> Profile {
+    serde_json::from_str(
+        r#"{
+            "name": "Offline Test",
+            "path": "offline-test",
+            "game_version": "1.21.1",
+            "loader": "vanilla"
+        }"#,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_offline_uses_verified_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("example-1.0.txt");
+    let content = b"cached library content";
+    tokio::fs::write(&target, content).await.unwrap();
+
+    let config = DownloadConfig::new()
+        .with_sha1(&hash_utils::calculate_sha1_from_bytes(content))
+        .with_disk_space_check(false);
+
+    let profile = test_profile();
+    let (result, report) = offline::run(&profile, async {
+        offline::set_step("libraries");
+        DownloadUtils::download_file(UNREACHABLE_URL, &target, config).await
+    })
+    .await;
+
+    assert!(result.is_ok());
+    assert!(report.is_ready());
+}
+
+#[tokio::test]
+async fn test_offline_reports_missing_and_corrupt_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing.txt");
+    let corrupt = dir.path().join("corrupt.txt");
+    tokio::fs::write(&corrupt, b"truncated").await.unwrap();
+
+    let profile = test_profile();
+    let (_, report) = offline::run(&profile, async {
+        offline::set_step("libraries");
+        let result = DownloadUtils::download_file(
+            UNREACHABLE_URL,
+            &missing,
+            DownloadConfig::new().with_disk_space_check(false),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::OfflineArtifactMissing(_))));
+
+        offline::set_step("client");
+        let config = DownloadConfig::new()
+            .with_sha1("0000000000000000000000000000000000000000")
+            .with_disk_space_check(false);
+        let result = DownloadUtils::download_file(UNREACHABLE_URL, &corrupt, config).await;
+        assert!(matches!(result, Err(AppError::OfflineArtifactMissing(_))));
+    })
+    .await;
+
+    assert_eq!(report.profile_name, "Offline Test");
+    assert_eq!(report.missing.len(), 2);
+    assert_eq!(report.missing[0].step, "libraries");
+    assert_eq!(report.missing[0].path, missing);
+    assert_eq!(report.missing[0].url.as_deref(), Some(UNREACHABLE_URL));
+    assert_eq!(report.missing[1].step, "client");
+    assert_eq!(report.missing[1].path, corrupt);
+
+    // The corrupt file is left untouched, nothing is deleted without a replacement
+    assert!(corrupt.exists());
+    assert!(!offline::is_active());
+}
+
+#[tokio::test]
+async fn test_readiness_check_only_inspects() {
+    let profile = test_profile();
+    let (check_only, _) = offline::run_check(&profile, async { offline::is_check_only() }).await;
+    assert!(check_only);
+
+    // A regular offline install still writes what it can derive from the cache
+    let (check_only, _) = offline::run(&profile, async { offline::is_check_only() }).await;
+    assert!(!check_only);
+    assert!(!offline::is_check_only());
+}
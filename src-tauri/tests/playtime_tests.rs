@@ -0,0 +1,131 @@
+// tests/playtime_tests.rs
+
+use noriskclient_launcher_v3_lib::state::playtime_tracker::{
+    drop_oldest_sessions, parse_log_event, playtime_per_target, playtime_per_week, LogPlayEvent,
+    PlaySession, PlayTarget, MAX_PLAY_SESSIONS,
+};
+
+#[test]
+fn test_parse_log_event() {
+    assert_eq!(
+        parse_log_event("[18:02:11] [Render thread/INFO]: Connecting to MC.Example.net, 25565"),
+        Some(LogPlayEvent::JoinedServer("mc.example.net".to_string()))
+    );
+    assert_eq!(
+        parse_log_event("[18:02:11] [Render thread/INFO]: Connecting to 127.0.0.1, 25566"),
+        Some(LogPlayEvent::JoinedServer("127.0.0.1:25566".to_string()))
+    );
+    assert_eq!(
+        parse_log_event("[18:05:40] [Server thread/INFO]: Starting integrated minecraft server version 1.21.1"),
+        Some(LogPlayEvent::JoinedSingleplayer)
+    );
+    assert_eq!(
+        parse_log_event("[18:30:02] [Server thread/INFO]: Stopping singleplayer server as player logged out"),
+        Some(LogPlayEvent::Left)
+    );
+    assert_eq!(
+        parse_log_event("[18:31:00] [Render thread/INFO]: [CHAT] Connecting to evil.example, 25565"),
+        None
+    );
+}
+
+#[test]
+fn test_playtime_aggregation() {
+    let sessions: Vec<PlaySession> = serde_json::from_value(serde_json::json!([
+        {
+            "process_id": "6a1f3c7e-8d4b-4f2a-9b1e-2c3d4e5f6a7b",
+            "profile_id": "0b9c8d7e-6f5a-4b3c-2d1e-0f9e8d7c6b5a",
+            "started_at": "2026-03-02T18:00:00Z",
+            "last_seen_at": "2026-03-02T20:00:00Z",
+            "ended_at": "2026-03-02T20:00:00Z",
+            "segments": [
+                { "target": { "type": "multiplayer", "server": "mc.example.net" },
+                  "started_at": "2026-03-02T18:05:00Z", "ended_at": "2026-03-02T19:05:00Z" },
+                { "target": { "type": "singleplayer", "world": "New World" },
+                  "started_at": "2026-03-02T19:10:00Z", "ended_at": null }
+            ]
+        },
+        {
+            "process_id": "7b2f3c7e-8d4b-4f2a-9b1e-2c3d4e5f6a7b",
+            "profile_id": "0b9c8d7e-6f5a-4b3c-2d1e-0f9e8d7c6b5a",
+            "started_at": "2026-03-08T10:00:00Z",
+            "last_seen_at": "2026-03-08T10:30:00Z",
+            "ended_at": null,
+            "segments": [
+                { "target": { "type": "multiplayer", "server": "mc.example.net" },
+                  "started_at": "2026-03-08T10:00:00Z", "ended_at": null }
+            ]
+        }
+    ]))
+    .unwrap();
+
+    // Monday the 2nd and Sunday the 8th are the same week
+    let weeks = playtime_per_week(&sessions, None);
+    assert_eq!(weeks.len(), 1);
+    assert_eq!(weeks[0].week_start.to_string(), "2026-03-02");
+    assert_eq!(weeks[0].total_secs, 2 * 3600 + 30 * 60);
+
+    let targets = playtime_per_target(&sessions, None);
+    assert_eq!(
+        targets[0].target,
+        PlayTarget::Multiplayer { server: "mc.example.net".to_string() }
+    );
+    assert_eq!(targets[0].total_secs, 3600 + 30 * 60);
+    assert_eq!(targets[0].visit_count, 2);
+    // The open segment ends with its session
+    assert_eq!(targets[1].total_secs, 50 * 60);
+}
+
+fn session(started_at: &str, ended_at: Option<&str>, last_seen_at: &str) -> PlaySession {
+    serde_json::from_value(serde_json::json!({
+        "process_id": uuid::Uuid::new_v4(),
+        "profile_id": "0b9c8d7e-6f5a-4b3c-2d1e-0f9e8d7c6b5a",
+        "started_at": started_at,
+        "last_seen_at": last_seen_at,
+        "ended_at": ended_at,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_weekly_playtime_splits_sessions_at_the_week_boundary() {
+    // Sunday 23:00 until Monday 01:30
+    let sessions = vec![session(
+        "2026-03-08T23:00:00Z",
+        Some("2026-03-09T01:30:00Z"),
+        "2026-03-09T01:30:00Z",
+    )];
+
+    let weeks = playtime_per_week(&sessions, None);
+    assert_eq!(weeks.len(), 2);
+    assert_eq!(weeks[0].week_start.to_string(), "2026-03-02");
+    assert_eq!(weeks[0].total_secs, 3600);
+    assert_eq!(weeks[1].week_start.to_string(), "2026-03-09");
+    assert_eq!(weeks[1].total_secs, 90 * 60);
+    assert!(weeks.iter().all(|w| w.session_count == 1));
+}
+
+#[test]
+fn test_oldest_finished_sessions_are_dropped() {
+    let running = session("2026-01-01T10:00:00Z", None, "2026-01-01T11:00:00Z");
+    let running_id = running.process_id;
+    let mut sessions = vec![running];
+    for _ in 0..MAX_PLAY_SESSIONS + 1 {
+        sessions.push(session(
+            "2026-01-02T10:00:00Z",
+            Some("2026-01-02T11:00:00Z"),
+            "2026-01-02T11:00:00Z",
+        ));
+    }
+    let newest_id = sessions.last().unwrap().process_id;
+    let oldest_finished_ids = [sessions[1].process_id, sessions[2].process_id];
+
+    drop_oldest_sessions(&mut sessions);
+    assert_eq!(sessions.len(), MAX_PLAY_SESSIONS);
+    // The running session stays even though it's the oldest
+    assert_eq!(sessions[0].process_id, running_id);
+    assert_eq!(sessions.last().unwrap().process_id, newest_id);
+    assert!(sessions
+        .iter()
+        .all(|s| !oldest_finished_ids.contains(&s.process_id)));
+}
@@ -0,0 +1,120 @@
+// tests/prism_tests.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::integrations::prism::{
+    determine_versions_from_components, hash_mod_jars, parse_instance_cfg, MmcPack,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::ModLoader;
+use serde_json::json;
+
+fn pack(components: serde_json::Value) -> MmcPack {
+    serde_json::from_value(json!({ "formatVersion": 1, "components": components })).unwrap()
+}
+
+#[test]
+fn test_parse_instance_cfg() {
+    let prism = "[General]\n\
+                 ConfigVersion=1.2\n\
+                 name=\"Better Survival\"\n\
+                 # a comment\n\
+                 \n\
+                 JvmArgs=-XX:+UseG1GC -Dfoo=bar\n\
+                 notes=\n\
+                 OverrideMemory = true\n";
+    let cfg = parse_instance_cfg(prism);
+    assert_eq!(cfg["name"], "Better Survival");
+    // Only the first '=' separates key and value
+    assert_eq!(cfg["JvmArgs"], "-XX:+UseG1GC -Dfoo=bar");
+    assert_eq!(cfg["notes"], "");
+    assert_eq!(cfg["OverrideMemory"], "true");
+    assert!(!cfg.contains_key("[General]"));
+    assert_eq!(cfg.len(), 5);
+
+    // Older MultiMC instances have no section header
+    let multimc = parse_instance_cfg("InstanceType=OneSix\r\nname=Old Pack\r\n");
+    assert_eq!(multimc["name"], "Old Pack");
+    assert_eq!(multimc["InstanceType"], "OneSix");
+}
+
+#[test]
+fn test_determine_versions_from_components() -> Result<()> {
+    let fabric = pack(json!([
+        { "uid": "org.lwjgl3", "version": "3.3.3" },
+        { "uid": "net.minecraft", "version": "1.21.1" },
+        { "uid": "net.fabricmc.intermediary", "version": "1.21.1" },
+        { "uid": "net.fabricmc.fabric-loader", "version": "0.16.5" },
+    ]));
+    assert_eq!(
+        determine_versions_from_components(&fabric)?,
+        (
+            "1.21.1".to_string(),
+            ModLoader::Fabric,
+            Some("0.16.5".to_string())
+        )
+    );
+
+    let quilt = pack(json!([
+        { "uid": "net.minecraft", "version": "1.20.1" },
+        { "uid": "org.quiltmc.quilt-loader", "version": "0.26.0" },
+    ]));
+    assert_eq!(
+        determine_versions_from_components(&quilt)?.1,
+        ModLoader::Quilt
+    );
+
+    // Forge versions are stored maven-style
+    let forge = pack(json!([
+        { "uid": "net.minecraft", "version": "1.20.1" },
+        { "uid": "net.minecraftforge", "version": "47.2.0" },
+    ]));
+    assert_eq!(
+        determine_versions_from_components(&forge)?,
+        (
+            "1.20.1".to_string(),
+            ModLoader::Forge,
+            Some("1.20.1-47.2.0".to_string())
+        )
+    );
+
+    let neoforge = pack(json!([
+        { "uid": "net.minecraft", "version": "1.21.1" },
+        { "uid": "net.neoforged", "version": "21.1.65" },
+    ]));
+    assert_eq!(
+        determine_versions_from_components(&neoforge)?.2.as_deref(),
+        Some("21.1.65")
+    );
+
+    let vanilla = pack(json!([{ "uid": "net.minecraft", "version": "1.8.9" }]));
+    assert_eq!(
+        determine_versions_from_components(&vanilla)?,
+        ("1.8.9".to_string(), ModLoader::Vanilla, None)
+    );
+
+    let no_minecraft = pack(json!([{ "uid": "net.fabricmc.fabric-loader", "version": "0.16.5" }]));
+    assert!(determine_versions_from_components(&no_minecraft).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_identical_jars_are_all_kept() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mods_dir = dir.path();
+    std::fs::write(mods_dir.join("sodium.jar"), "same content")?;
+    std::fs::write(mods_dir.join("sodium-copy.jar.disabled"), "same content")?;
+    std::fs::write(mods_dir.join("lithium.jar"), "other content")?;
+    std::fs::write(mods_dir.join("readme.txt"), "not a mod")?;
+
+    let (hash_to_paths, unhashed) = hash_mod_jars(mods_dir).await?;
+    assert!(unhashed.is_empty());
+    assert_eq!(hash_to_paths.len(), 2);
+
+    let duplicates = hash_to_paths
+        .values()
+        .find(|paths| paths.len() == 2)
+        .expect("identical jars should share one hash entry");
+    // The enabled copy comes first and becomes the Modrinth mod
+    assert_eq!(duplicates[0], mods_dir.join("sodium.jar"));
+    assert_eq!(duplicates[1], mods_dir.join("sodium-copy.jar.disabled"));
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// tests/process_exit_tests.rs
+
+use chrono::{Duration, Utc};
+use noriskclient_launcher_v3_lib::state::process_exit::{
+    classify_exit, find_native_crash_logs, kernel_log_names_oom_kill, ExitClassification, ExitFacts,
+};
+
+#[test]
+fn test_classify_exit() {
+    let classify = |facts: ExitFacts| classify_exit(&facts);
+
+    assert_eq!(
+        classify(ExitFacts { exit_code: Some(0), ..Default::default() }),
+        ExitClassification::Clean
+    );
+    assert_eq!(
+        classify(ExitFacts { exit_code: Some(1), has_crash_report: true, ..Default::default() }),
+        ExitClassification::Crash
+    );
+    // SIGKILL from the launcher's stop button is still a clean exit
+    assert_eq!(
+        classify(ExitFacts { signal: Some(9), intentionally_stopped: true, ..Default::default() }),
+        ExitClassification::Clean
+    );
+    assert_eq!(
+        classify(ExitFacts { signal: Some(9), ..Default::default() }),
+        ExitClassification::Killed
+    );
+    assert_eq!(
+        classify(ExitFacts { signal: Some(9), oom_killed: true, ..Default::default() }),
+        ExitClassification::OomKilled
+    );
+    assert_eq!(
+        classify(ExitFacts { signal: Some(9), possibly_oom_killed: true, ..Default::default() }),
+        ExitClassification::PossiblyOomKilled
+    );
+    assert_eq!(
+        classify(ExitFacts { signal: Some(11), ..Default::default() }),
+        ExitClassification::NativeCrash
+    );
+    // EXCEPTION_ACCESS_VIOLATION on Windows
+    assert_eq!(
+        classify(ExitFacts { exit_code: Some(0xC000_0005_u32 as i32), ..Default::default() }),
+        ExitClassification::NativeCrash
+    );
+    // An hs_err_pid log wins over the exit code
+    assert_eq!(
+        classify(ExitFacts { exit_code: Some(1), has_native_crash_log: true, ..Default::default() }),
+        ExitClassification::NativeCrash
+    );
+    assert_eq!(classify(ExitFacts::default()), ExitClassification::Unknown);
+}
+
+#[test]
+fn test_kernel_log_names_oom_kill() {
+    let kernel_log = "\
+6,1201,5000000,-;oom-kill:constraint=CONSTRAINT_NONE,nodemask=(null),task=java,pid=4242,uid=1000
+3,1202,5000100,-;Out of memory: Killed process 4242 (java) total-vm:12000000kB, anon-rss:8000000kB
+6,1301,9000000,-;oom-kill:constraint=CONSTRAINT_NONE,nodemask=(null),task=firefox,pid=5151,uid=1000
+3,1302,9000100,-;Out of memory: Killed process 5151 (firefox) total-vm:9000000kB
+";
+    assert!(kernel_log_names_oom_kill(kernel_log, 4242, 1_000_000));
+    assert!(kernel_log_names_oom_kill(kernel_log, 5151, 1_000_000));
+    // Another process was killed, not the game
+    assert!(!kernel_log_names_oom_kill(kernel_log, 424, 1_000_000));
+    assert!(!kernel_log_names_oom_kill(kernel_log, 7777, 1_000_000));
+    // Logged before the game started, so it was an earlier process with the same PID
+    assert!(!kernel_log_names_oom_kill(kernel_log, 4242, 6_000_000));
+}
+
+#[tokio::test]
+async fn test_native_crash_logs_of_reused_pid_are_ignored() {
+    let dir = tempfile::tempdir().unwrap();
+    // Unlikely to exist in the shared temp directory
+    let pid = 3_999_999_937;
+    let hs_err = dir.path().join(format!("hs_err_pid{}.log", pid));
+    std::fs::write(&hs_err, "# A fatal error has been detected").unwrap();
+
+    let started_before = Utc::now() - Duration::minutes(5);
+    assert_eq!(
+        find_native_crash_logs(Some(dir.path()), pid, started_before).await,
+        vec![hs_err]
+    );
+
+    // Left behind by an earlier process with the same PID
+    let started_after = Utc::now() + Duration::minutes(5);
+    assert!(find_native_crash_logs(Some(dir.path()), pid, started_after)
+        .await
+        .is_empty());
+}
@@ -0,0 +1,79 @@
+// tests/process_metrics_tests.rs
+
+use chrono::{Duration, Utc};
+use noriskclient_launcher_v3_lib::state::process_metrics::{
+    append_session, build_session_record, load_session_history, memory_limit_bytes,
+    ResourceHistory, ResourceSample, MAX_RESOURCE_SAMPLES,
+};
+use noriskclient_launcher_v3_lib::state::process_state::ProcessMetadata;
+
+const MB: u64 = 1024 * 1024;
+
+fn sample(cpu_percent: f32, memory_bytes: u64) -> ResourceSample {
+    ResourceSample {
+        timestamp: Utc::now(),
+        cpu_percent,
+        memory_bytes,
+        thread_count: Some(40),
+        process_count: 1,
+    }
+}
+
+#[tokio::test]
+async fn test_session_record_from_history() {
+    let mut history = ResourceHistory::default();
+    history.push(sample(150.0, 5000 * MB));
+    for _ in 0..MAX_RESOURCE_SAMPLES {
+        history.push(sample(50.0, 3000 * MB));
+    }
+    // The window rolls, the peak is kept for the whole run
+    assert_eq!(history.samples().len(), MAX_RESOURCE_SAMPLES);
+    assert_eq!(history.peak_memory_bytes(), Some(5000 * MB));
+
+    let start_time = Utc::now() - Duration::minutes(90);
+    let metadata: ProcessMetadata = serde_json::from_value(serde_json::json!({
+        "id": "6a1f3c7e-8d4b-4f2a-9b1e-2c3d4e5f6a7b",
+        "profile_id": "0b9c8d7e-6f5a-4b3c-2d1e-0f9e8d7c6b5a",
+        "start_time": start_time,
+        "state": "Running",
+        "pid": 4242,
+        "minecraft_version": "1.21.1",
+        "memory_max_mb": 4096
+    }))
+    .unwrap();
+
+    // 5000 MB RSS is normal non-heap overhead for a 4096 MB heap
+    let record = build_session_record(&metadata, Some(&history), Utc::now());
+    assert!(!record.exceeded_memory_max);
+    assert_eq!(record.duration_secs / 60, 90);
+    assert_eq!(record.minecraft_version.as_deref(), Some("1.21.1"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session_history.json");
+    assert!(load_session_history(&path).await.unwrap().is_empty());
+    append_session(&path, record.clone()).await.unwrap();
+    let sessions = load_session_history(&path).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].peak_memory_bytes, Some(5000 * MB));
+
+    // An unreadable history is backed up before a new one is started
+    std::fs::write(&path, "{ not json").unwrap();
+    append_session(&path, record).await.unwrap();
+    assert_eq!(load_session_history(&path).await.unwrap().len(), 1);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("session_history.json.bak")).unwrap(),
+        "{ not json"
+    );
+
+    history.push(sample(50.0, 6500 * MB));
+    let record = build_session_record(&metadata, Some(&history), Utc::now());
+    assert!(record.exceeded_memory_max);
+}
+
+#[test]
+fn test_memory_limit_allows_non_heap_usage() {
+    // At least 1 GB on top of small heaps, half the heap on top of large ones
+    assert_eq!(memory_limit_bytes(2048), 3072 * MB);
+    assert_eq!(memory_limit_bytes(4096), 6144 * MB);
+    assert_eq!(memory_limit_bytes(8192), 12288 * MB);
+}
@@ -0,0 +1,164 @@
+// tests/process_state_tests.rs
+
+use noriskclient_launcher_v3_lib::state::event_state::ProcessStopStage;
+use noriskclient_launcher_v3_lib::state::process_state::{
+    is_shutdown_log_line, stage_after_kill, ProcessMetadata, StopEscalation, SHUTDOWN_SAVE_TIMEOUT,
+};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+fn metadata(hooks: serde_json::Value) -> ProcessMetadata {
+    let mut value = json!({
+        "id": "00000000-0000-0000-0000-000000000001",
+        "profile_id": "00000000-0000-0000-0000-000000000002",
+        "start_time": "2025-01-01T12:00:00Z",
+        "state": "Running",
+        "pid": 4242,
+    });
+    if let serde_json::Value::Object(hooks) = hooks {
+        value.as_object_mut().unwrap().extend(hooks);
+    }
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_post_exit_hook_migration() {
+    // processes.json written before hooks could be chained
+    assert_eq!(
+        metadata(json!({ "post_exit_hook": "notify-send done" })).post_exit_hooks,
+        vec!["notify-send done"]
+    );
+    assert!(metadata(json!({ "post_exit_hook": null }))
+        .post_exit_hooks
+        .is_empty());
+    assert!(metadata(json!({})).post_exit_hooks.is_empty());
+
+    let current = metadata(json!({ "post_exit_hooks": ["backup.sh", "notify.sh"] }));
+    assert_eq!(current.post_exit_hooks, vec!["backup.sh", "notify.sh"]);
+    // Saved again in the new format
+    let saved = serde_json::to_value(&current).unwrap();
+    assert_eq!(saved["post_exit_hooks"], json!(["backup.sh", "notify.sh"]));
+    assert!(saved.get("post_exit_hook").is_none());
+}
+
+/// Feeds `escalation` one poll per `(seconds since start, running, shutdown logged)` entry
+fn run_stop(
+    escalation: &mut StopEscalation,
+    start: Instant,
+    polls: &[(u64, bool, bool)],
+) -> Vec<Option<ProcessStopStage>> {
+    polls
+        .iter()
+        .map(|&(secs, running, shutdown_logged)| {
+            escalation.poll(start + Duration::from_secs(secs), running, shutdown_logged)
+        })
+        .collect()
+}
+
+#[test]
+fn test_stop_escalation_stages() {
+    let start = Instant::now();
+
+    // Exits on its own within the grace period
+    let mut escalation = StopEscalation::new(start, Duration::from_secs(10));
+    assert_eq!(
+        run_stop(
+            &mut escalation,
+            start,
+            &[(1, true, false), (2, false, false)]
+        ),
+        vec![None, Some(ProcessStopStage::Stopped)]
+    );
+
+    // Ignores the request, killed once the grace period is over
+    let mut escalation = StopEscalation::new(start, Duration::from_secs(10));
+    assert_eq!(
+        run_stop(
+            &mut escalation,
+            start,
+            &[(9, true, false), (10, true, false)]
+        ),
+        vec![None, Some(ProcessStopStage::ForceKilling)]
+    );
+
+    // Logs "Stopping!" and finishes saving after the grace period
+    let mut escalation = StopEscalation::new(start, Duration::from_secs(10));
+    assert_eq!(
+        run_stop(
+            &mut escalation,
+            start,
+            &[
+                (5, true, true),
+                (6, true, true),
+                (30, true, true),
+                (40, false, true)
+            ],
+        ),
+        vec![
+            Some(ProcessStopStage::ShuttingDown),
+            None,
+            None,
+            Some(ProcessStopStage::Stopped)
+        ]
+    );
+}
+
+#[test]
+fn test_stopping_extends_the_grace_period() {
+    let start = Instant::now();
+    let saving_until = 5 + SHUTDOWN_SAVE_TIMEOUT.as_secs();
+
+    let mut escalation = StopEscalation::new(start, Duration::from_secs(10));
+    assert_eq!(
+        run_stop(
+            &mut escalation,
+            start,
+            &[
+                (5, true, true),
+                (saving_until - 1, true, true),
+                (saving_until, true, true)
+            ],
+        ),
+        vec![
+            Some(ProcessStopStage::ShuttingDown),
+            None,
+            Some(ProcessStopStage::ForceKilling)
+        ]
+    );
+
+    // A grace period longer than the save timeout is never shortened
+    let grace_secs = saving_until + 60;
+    let mut escalation = StopEscalation::new(start, Duration::from_secs(grace_secs));
+    assert_eq!(
+        run_stop(
+            &mut escalation,
+            start,
+            &[
+                (5, true, true),
+                (saving_until, true, true),
+                (grace_secs, true, true)
+            ],
+        ),
+        vec![
+            Some(ProcessStopStage::ShuttingDown),
+            None,
+            Some(ProcessStopStage::ForceKilling)
+        ]
+    );
+
+    assert!(is_shutdown_log_line(
+        "[12:00:00] [Server thread/INFO]: Stopping!"
+    ));
+    assert!(!is_shutdown_log_line(
+        "[12:00:00] [Server thread/INFO]: Stopping server"
+    ));
+    assert!(!is_shutdown_log_line(
+        "[12:00:00] [Render thread/INFO]: Stopping! (not really)"
+    ));
+}
+
+#[test]
+fn test_force_kill_reports_stopped() {
+    assert_eq!(stage_after_kill(true), ProcessStopStage::Stopped);
+    assert_eq!(stage_after_kill(false), ProcessStopStage::Failed);
+}
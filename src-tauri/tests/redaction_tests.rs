@@ -0,0 +1,127 @@
+// tests/redaction_tests.rs
+
+use noriskclient_launcher_v3_lib::state::process_state::ProcessMetadata;
+use noriskclient_launcher_v3_lib::utils::redaction_utils::{
+    BuiltinRedaction, CustomRedactionRule, RedactionSettings, Redactor,
+};
+
+const LOG: &str = "[12:00:01] [main/INFO] (Minecraft) Setting user: Steve
+[12:00:01] [main/INFO] (Launcher) Args: -Dnorisk.token=abc.def-123 --accessToken eyJhbGciOi.eyJzdWIiOi.c2lnbmF0dXJl --username Steve
+[12:00:02] [main/INFO] (Minecraft) Loading pack from /home/steve/.minecraft/resourcepacks and C:\\Users\\Steve\\AppData
+[12:00:03] [Render thread/INFO] (Minecraft) Connecting to 192.168.178.20, 25565 (2001:db8:85a3::8a2e:370:7334)
+[12:00:04] [Render thread/WARN] (sodium) Contact steve.miner@example.org, api_key=\"s3cr3t\" Authorization: Bearer xyz
+";
+
+#[test]
+fn test_builtin_rules() {
+    let redactor = Redactor::new(&RedactionSettings::default()).unwrap();
+    let preview = redactor.preview(LOG);
+    let lines: Vec<&str> = preview.redacted.lines().collect();
+
+    assert_eq!(
+        lines[0],
+        "[12:00:01] [main/INFO] (Minecraft) Setting user: Steve"
+    );
+    assert_eq!(
+        lines[1],
+        "[12:00:01] [main/INFO] (Launcher) Args: -Dnorisk.token=<token> --accessToken <token> --username Steve"
+    );
+    assert_eq!(
+        lines[2],
+        "[12:00:02] [main/INFO] (Minecraft) Loading pack from /home/<user>/.minecraft/resourcepacks and C:\\Users\\<user>\\AppData"
+    );
+    assert_eq!(
+        lines[3],
+        "[12:00:03] [Render thread/INFO] (Minecraft) Connecting to <ip>, 25565 (<ip>)"
+    );
+    assert_eq!(
+        lines[4],
+        "[12:00:04] [Render thread/WARN] (sodium) Contact <email>, api_key=\"<secret>\" Authorization: <secret>"
+    );
+
+    // Timestamps aren't IPv6 addresses and untouched lines aren't part of the diff
+    assert_eq!(preview.changed_lines.len(), 4);
+    assert_eq!(preview.changed_lines[0].line, 2);
+    assert_eq!(preview.matches.get("ipv4"), Some(&1));
+    assert_eq!(preview.matches.get("ipv6"), Some(&1));
+    assert!(preview.redacted.ends_with('\n'));
+}
+
+#[test]
+fn test_versions_are_not_ipv4() {
+    let redactor = Redactor::new(&RedactionSettings::default()).unwrap();
+    for line in [
+        "Loading sodium-1.2.3.4.jar",
+        "Found mod fabric_1.2.3.4",
+        "Running v1.2.3.4",
+    ] {
+        assert_eq!(redactor.redact(line), line);
+    }
+
+    assert_eq!(
+        redactor.redact("1.2.3.4 and 5.6.7.8 (tcp://9.9.9.9:25565)"),
+        "<ip> and <ip> (tcp://<ip>:25565)"
+    );
+}
+
+#[test]
+fn test_configurable_rules() {
+    let settings = RedactionSettings {
+        enabled: true,
+        disabled_rules: vec![BuiltinRedaction::Ipv4],
+        custom_rules: vec![CustomRedactionRule {
+            name: "username".to_string(),
+            pattern: r"(Setting user: )\w+".to_string(),
+            replacement: Some("${1}<player>".to_string()),
+        }],
+    };
+    let redacted = Redactor::new(&settings).unwrap().redact(LOG);
+    assert!(redacted.contains("Setting user: <player>"));
+    assert!(redacted.contains("Connecting to 192.168.178.20"));
+
+    let disabled = RedactionSettings {
+        enabled: false,
+        ..settings.clone()
+    };
+    assert_eq!(Redactor::new(&disabled).unwrap().redact(LOG), LOG);
+
+    let invalid = RedactionSettings {
+        custom_rules: vec![CustomRedactionRule {
+            name: "broken".to_string(),
+            pattern: "(unclosed".to_string(),
+            replacement: None,
+        }],
+        ..settings
+    };
+    assert!(Redactor::new(&invalid).is_err());
+}
+
+#[test]
+fn test_redacted_value_keeps_structure() {
+    let metadata: ProcessMetadata = serde_json::from_value(serde_json::json!({
+        "id": "6a1f3c7e-8d4b-4f2a-9b1e-2c3d4e5f6a7b",
+        "profile_id": "0b9c8d7e-6f5a-4b3c-2d1e-0f9e8d7c6b5a",
+        "start_time": "2025-06-01T12:00:00Z",
+        "state": "Running",
+        "pid": 4242,
+        "profile_name": "/home/steve/profiles/2025"
+    }))
+    .unwrap();
+    // Also matches inside the start time, which must not break the upload
+    let settings = RedactionSettings {
+        custom_rules: vec![CustomRedactionRule {
+            name: "years".to_string(),
+            pattern: r"20\d\d".to_string(),
+            replacement: Some("<year>".to_string()),
+        }],
+        ..RedactionSettings::default()
+    };
+
+    let redacted = Redactor::new(&settings)
+        .unwrap()
+        .redact_value(&metadata)
+        .unwrap();
+    assert_eq!(redacted["profile_name"], "/home/<user>/profiles/<year>");
+    assert_eq!(redacted["start_time"], "<year>-06-01T12:00:00Z");
+    assert_eq!(redacted["pid"], 4242);
+}
@@ -0,0 +1,74 @@
+// tests/snapshot_tests.rs
+
+use noriskclient_launcher_v3_lib::utils::snapshot_utils::{
+    capture_instance_files, diff_entries, restore_instance_files, BlobLocation,
+};
+use std::path::Path;
+use tokio::fs;
+
+async fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+    fs::write(path, content).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_snapshot_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let instance = dir.path().join("instance");
+    let store = dir.path().join("store");
+    let mod_cache = dir.path().join("mod_cache");
+
+    write(&instance.join("mods/sodium.jar"), "sodium 1").await;
+    write(&mod_cache.join("sodium.jar"), "sodium 1").await;
+    write(&instance.join("mods/custom.jar"), "custom").await;
+    write(&instance.join("config/sodium/options.json"), "{\"a\":1}").await;
+    write(&instance.join("saves/world/level.dat"), "world").await;
+    write(&instance.join("options.txt"), "fov:70").await;
+
+    let entries = capture_instance_files(&instance, false, Some(&store), &mod_cache)
+        .await
+        .unwrap();
+
+    let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec!["config/sodium/options.json", "mods/custom.jar", "mods/sodium.jar"]
+    );
+    // Deduplicated against the mod cache instead of copied
+    assert_eq!(
+        entries[2].location,
+        BlobLocation::ModCache { file_name: "sodium.jar".to_string() }
+    );
+    assert_eq!(entries[1].location, BlobLocation::Store);
+
+    // Change the instance after the snapshot
+    write(&instance.join("config/sodium/options.json"), "{\"a\":2}").await;
+    fs::remove_file(instance.join("mods/custom.jar")).await.unwrap();
+    write(&instance.join("mods/broken.jar"), "broken").await;
+    write(&instance.join("saves/world/level.dat"), "world 2").await;
+
+    let current = capture_instance_files(&instance, false, None, &mod_cache)
+        .await
+        .unwrap();
+    let diff = diff_entries(&entries, &current);
+    assert_eq!(diff.added, vec!["mods/broken.jar"]);
+    assert_eq!(diff.removed, vec!["mods/custom.jar"]);
+    assert_eq!(diff.modified, vec!["config/sodium/options.json"]);
+
+    let (written, removed) = restore_instance_files(&instance, &entries, false, &store, &mod_cache)
+        .await
+        .unwrap();
+    assert_eq!((written, removed), (2, 1));
+
+    assert_eq!(
+        fs::read_to_string(instance.join("config/sodium/options.json")).await.unwrap(),
+        "{\"a\":1}"
+    );
+    assert_eq!(fs::read_to_string(instance.join("mods/custom.jar")).await.unwrap(), "custom");
+    assert!(!instance.join("mods/broken.jar").exists());
+    // Saves weren't part of the snapshot and stay untouched
+    assert_eq!(
+        fs::read_to_string(instance.join("saves/world/level.dat")).await.unwrap(),
+        "world 2"
+    );
+}
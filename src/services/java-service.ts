@@ -0,0 +1,33 @@
+import { invoke } from "@tauri-apps/api/core";
+
+import type { InstalledJavaRuntime, JavaRuntimeUpgrade } from '../types/java';
+
+/**
+ * Lists the Java runtimes the launcher downloaded, with the profiles that use them.
+ */
+export const listJavaRuntimes = (): Promise<InstalledJavaRuntime[]> => {
+  return invoke('list_java_runtimes_command');
+};
+
+/**
+ * Removes a downloaded runtime. Fails if a profile or a running game uses it.
+ */
+export const uninstallJavaRuntime = (runtimeId: string): Promise<void> => {
+  return invoke('uninstall_java_runtime_command', { runtimeId });
+};
+
+/**
+ * Removes all downloaded runtimes no profile uses.
+ * @returns The ids of the removed runtimes.
+ */
+export const pruneJavaRuntimes = (): Promise<string[]> => {
+  return invoke('prune_java_runtimes_command');
+};
+
+/**
+ * Upgrades a runtime to the newest patch release of its major version.
+ * Running games keep their runtime, the upgrade is then applied before the next launch.
+ */
+export const upgradeJavaRuntime = (runtimeId: string): Promise<JavaRuntimeUpgrade> => {
+  return invoke('upgrade_java_runtime_command', { runtimeId });
+};
@@ -253,8 +253,67 @@ export async function getLogFileContent(logFilePath: string): Promise<string> {
     return await invoke<string>('get_log_file_content', { logFilePath });
 }
 
+// --- Structured log records (parsed in the backend) ---
+
+export type LogRecordLevel = LogLevel | 'FATAL';
+
+export interface LogRecord {
+    index: number; // Position in the file, starting at 0
+    timestamp: string | null; // As in the log, RFC 3339 for XML layout events
+    thread: string | null;
+    level: LogRecordLevel | null;
+    logger: string | null;
+    message: string;
+    stack_trace: string[]; // Lines following the message
+}
+
+export interface LogFilter {
+    levels?: LogRecordLevel[]; // Empty or missing: all levels
+    logger?: string | null; // Case-insensitive substring
+    text?: string | null; // Case-insensitive full-text search
+    pattern?: string | null; // Regex over message and stack trace
+    offset?: number;
+    limit?: number | null; // Defaults to 500
+}
+
+export interface LogPage {
+    records: LogRecord[];
+    total: number;
+    offset: number;
+}
+
+export interface LogSearchHit {
+    file: string;
+    record: LogRecord;
+}
+
+export interface LogSearchPage {
+    hits: LogSearchHit[];
+    total: number;
+    offset: number;
+    files_searched: number;
+}
+
+/**
+ * Parses a log file (.log or .log.gz) into records and returns one filtered page.
+ * @param logFilePath The full path to the log file.
+ * @param filter Level/logger/regex filter and paging.
+ */
+export async function queryLogFile(logFilePath: string, filter?: LogFilter): Promise<LogPage> {
+    return await invoke<LogPage>('query_log_file', { logFilePath, filter: filter ?? null });
+}
+
+/**
+ * Searches all log files of a profile, including rotated .log.gz files, newest first.
+ * @param profileId The ID of the profile.
+ * @param filter Level/logger/regex filter and paging.
+ */
+export async function searchProfileLogs(profileId: string, filter: LogFilter): Promise<LogSearchPage> {
+    return await invoke<LogSearchPage>('search_profile_logs', { profileId, filter });
+}
+
 /**
- * Uploads log content to mclo.gs.
+ * Uploads log content to mclo.gs. The backend masks it with the redaction rules first, see previewLogRedaction.
  * @param logContent The raw log content to upload.
  * @returns A promise that resolves to the URL of the uploaded log.
  */
@@ -263,6 +322,27 @@ export async function uploadLogToMclogs(logContent: string): Promise<string> {
     return await invoke<string>('upload_log_to_mclogs_command', { logContent });
 }
 
+export interface RedactedLine {
+    line: number; // 1-based
+    original: string;
+    redacted: string;
+}
+
+export interface RedactionPreview {
+    redacted: string; // Exactly what would be uploaded
+    changed_lines: RedactedLine[];
+    matches: Record<string, number>; // Replacements per rule name
+}
+
+/**
+ * Shows what would be masked before the log is uploaded, using the redaction rules of the launcher config.
+ * @param logContent The raw log content.
+ * @returns A promise that resolves to the redacted content and the changed lines.
+ */
+export async function previewLogRedaction(logContent: string): Promise<RedactionPreview> {
+    return await invoke<RedactionPreview>('preview_log_redaction', { logContent });
+}
+
 /**
  * Requests the operating system to open the directory containing the specified file path.
  * @param filePath The path to a file within the directory to open.
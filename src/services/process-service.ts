@@ -1,6 +1,16 @@
 import { invoke } from "@tauri-apps/api/core";
 // Import the actual type with corrected path
-import type { ProcessMetadata, CrashlogDto } from "../types/processState";
+import type {
+  ProcessMetadata,
+  CrashlogDto,
+  PlaySession,
+  ProfilePlaytime,
+  ResourceSample,
+  SessionRecord,
+  TargetPlaytime,
+  WeeklyPlaytime,
+} from "../types/processState";
+import type { CrashDiagnosis } from "../types/events";
 import { getLauncherConfig } from "./launcher-config-service";
 import flagsmith from "flagsmith";
 import { toast } from "react-hot-toast";
@@ -19,6 +29,58 @@ export async function isMinecraftRunning(profileId: string): Promise<boolean> {
   }
 }
 
+export async function analyzeProfileCrash(
+  profileId: string,
+  crashReportContent?: string,
+): Promise<CrashDiagnosis[]> {
+  return invoke<CrashDiagnosis[]>("analyze_profile_crash", {
+    profileId,
+    crashReportContent: crashReportContent ?? null,
+  });
+}
+
+export async function getProcessResourceSamples(
+  processId: string,
+): Promise<ResourceSample[]> {
+  return invoke<ResourceSample[]>("get_process_resource_samples", { processId });
+}
+
+export async function getSessionHistory(
+  profileId?: string,
+): Promise<SessionRecord[]> {
+  return invoke<SessionRecord[]>("get_session_history", {
+    profileId: profileId ?? null,
+  });
+}
+
+export async function getPlaySessions(
+  profileId?: string,
+): Promise<PlaySession[]> {
+  return invoke<PlaySession[]>("get_play_sessions", {
+    profileId: profileId ?? null,
+  });
+}
+
+export async function getPlaytimePerProfile(): Promise<ProfilePlaytime[]> {
+  return invoke<ProfilePlaytime[]>("get_playtime_per_profile");
+}
+
+export async function getPlaytimePerWeek(
+  profileId?: string,
+): Promise<WeeklyPlaytime[]> {
+  return invoke<WeeklyPlaytime[]>("get_playtime_per_week", {
+    profileId: profileId ?? null,
+  });
+}
+
+export async function getPlaytimePerServer(
+  profileId?: string,
+): Promise<TargetPlaytime[]> {
+  return invoke<TargetPlaytime[]>("get_playtime_per_server", {
+    profileId: profileId ?? null,
+  });
+}
+
 export async function killMinecraft(profileId: string): Promise<void> {
   return invoke<void>("kill_minecraft", { profileId });
 }
@@ -73,12 +135,16 @@ export async function getRunningProcesses(): Promise<ProcessMetadata[]> {
 }
 
 /**
- * Stops a specific running process by its ID.
+ * Stops a specific running process by its ID. The game gets the configured grace period
+ * to save and exit (progress arrives as `process_stop_stage` events), `force` kills it right away.
  */
-export async function stopProcess(processId: string): Promise<void> {
-  console.debug(`[ProcessService] Stopping process: ${processId}`);
+export async function stopProcess(
+  processId: string,
+  force = false,
+): Promise<void> {
+  console.debug(`[ProcessService] Stopping process: ${processId} (force: ${force})`);
   try {
-    await invoke<void>("stop_process", { processId });
+    await invoke<void>("stop_process", { processId, force });
   } catch (error) {
     console.error(`[ProcessService] Failed to stop process ${processId}:`, error);
     // Re-throw or handle as needed
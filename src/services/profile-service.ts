@@ -5,6 +5,7 @@ import type {
   CopyProfileParams,
   CreateProfileParams,
   CustomModInfo,
+  ExportLaunchCommandParams,
   ExportProfileParams,
   Profile,
   UpdateProfileParams,
@@ -15,12 +16,19 @@ import type {
   LocalContentItem,
   ImageSource,
   UploadProfileIconPayload,
+  OfflineReport,
+  ProfileSnapshotSummary,
+  SnapshotDiff,
+  SnapshotRestoreReport,
 } from "../types/profile";
 import type {
   DataPackInfo,
+  ModrinthInstallPlan,
   ModrinthVersion,
+  ProfileUpdatePlan,
   ResourcePackInfo,
   ShaderPackInfo,
+  UpdateSnapshot,
 } from "../types/modrinth";
 import { NoriskVersionsConfig } from "../types/noriskVersions";
 import { FileNode } from "../types/fileSystem";
@@ -74,6 +82,62 @@ export async function abortProfileLaunch(profileId: string): Promise<void> {
   return invoke<void>("abort_profile_launch", { profileId });
 }
 
+export async function checkProfileOfflineReadiness(
+  profileId: string,
+): Promise<OfflineReport> {
+  return invoke<OfflineReport>("check_profile_offline_readiness", { profileId });
+}
+
+export async function createProfileSnapshot(
+  profileId: string,
+  label?: string,
+  includeSaves = false,
+): Promise<ProfileSnapshotSummary> {
+  return invoke<ProfileSnapshotSummary>("create_profile_snapshot", {
+    profileId,
+    label: label ?? null,
+    includeSaves,
+  });
+}
+
+export async function listProfileSnapshots(
+  profileId: string,
+): Promise<ProfileSnapshotSummary[]> {
+  return invoke<ProfileSnapshotSummary[]>("list_profile_snapshots", {
+    profileId,
+  });
+}
+
+// Without `to` the snapshot is compared with the current profile state
+export async function diffProfileSnapshots(
+  profileId: string,
+  from: string,
+  to?: string,
+): Promise<SnapshotDiff> {
+  return invoke<SnapshotDiff>("diff_profile_snapshots", {
+    profileId,
+    from,
+    to: to ?? null,
+  });
+}
+
+export async function restoreProfileSnapshot(
+  profileId: string,
+  snapshotId: string,
+): Promise<SnapshotRestoreReport> {
+  return invoke<SnapshotRestoreReport>("restore_profile_snapshot", {
+    profileId,
+    snapshotId,
+  });
+}
+
+export async function deleteProfileSnapshot(
+  profileId: string,
+  snapshotId: string,
+): Promise<void> {
+  return invoke<void>("delete_profile_snapshot", { profileId, snapshotId });
+}
+
 export async function isProfileLaunching(profileId: string): Promise<boolean> {
   return invoke<boolean>("is_profile_launching", { profileId });
 }
@@ -88,6 +152,14 @@ export async function exportProfile(
   return invoke<string>("export_profile", { params });
 }
 
+// Installs the profile and resolves its launch command without starting the game, returns the
+// written file. Earlier exports are kept, the new one gets a numbered name
+export async function exportLaunchCommand(
+  params: ExportLaunchCommandParams,
+): Promise<string> {
+  return invoke<string>("export_launch_command", { params });
+}
+
 export async function getSystemRamMb(): Promise<number> {
   return invoke<number>("get_system_ram_mb");
 }
@@ -133,6 +205,53 @@ export async function addModrinthModToProfile(
   });
 }
 
+export async function resolveModrinthInstallPlan(
+  profileId: string,
+  versionId: string,
+): Promise<ModrinthInstallPlan> {
+  return invoke<ModrinthInstallPlan>("resolve_modrinth_install_plan", {
+    profileId,
+    versionId,
+  });
+}
+
+export async function applyModrinthInstallPlan(
+  profileId: string,
+  plan: ModrinthInstallPlan,
+): Promise<number> {
+  return invoke<number>("apply_modrinth_install_plan", { profileId, plan });
+}
+
+export async function checkProfileUpdates(
+  profileId: string,
+): Promise<ProfileUpdatePlan> {
+  return invoke<ProfileUpdatePlan>("check_profile_updates", { profileId });
+}
+
+export async function applyProfileUpdates(
+  plan: ProfileUpdatePlan,
+): Promise<UpdateSnapshot> {
+  return invoke<UpdateSnapshot>("apply_profile_updates", { plan });
+}
+
+export async function rollbackProfileUpdate(
+  profileId: string,
+  snapshotId?: string,
+): Promise<UpdateSnapshot> {
+  return invoke<UpdateSnapshot>("rollback_profile_update", {
+    profileId,
+    snapshotId: snapshotId ?? null,
+  });
+}
+
+export async function listProfileUpdateSnapshots(
+  profileId: string,
+): Promise<UpdateSnapshot[]> {
+  return invoke<UpdateSnapshot[]>("list_profile_update_snapshots", {
+    profileId,
+  });
+}
+
 export async function updateModrinthModVersion(
   profileId: string,
   modInstanceId: string,
@@ -196,6 +315,10 @@ export async function importProfileFromFile(): Promise<void> {
   return invoke<void>("import_profile_from_file");
 }
 
+export async function importInstanceFromFolder(): Promise<string | null> {
+  return invoke<string | null>("import_instance_from_folder");
+}
+
 export async function openProfileFolder(profileId: string): Promise<void> {
   return invoke<void>("open_profile_folder", { profileId });
 }
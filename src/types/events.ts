@@ -1,4 +1,4 @@
-import { ProcessMetadata } from "./processState";
+import { ProcessExitInfo, ProcessMetadata, ResourceSample } from "./processState";
 
 export enum EventType {
   InstallingJava = "installing_java",
@@ -27,6 +27,8 @@ export enum EventType {
   Error = "error",
   LaunchSuccessful = "launch_successful",
   CrashReportContentAvailable = "crash_report_content_available",
+  ProcessResourceSample = "process_resource_sample",
+  ProcessStopStage = "process_stop_stage",
 }
 
 export interface EventPayload {
@@ -45,9 +47,44 @@ export interface MinecraftProcessExitedPayload {
   success: boolean;
   process_metadata: ProcessMetadata | null;
   crash_report_content?: string;
+  crash_diagnoses: CrashDiagnosis[]; // Empty on a clean exit
+  exit_info: ProcessExitInfo | null;
+}
+
+export interface CrashDiagnosis {
+  rule_id: string;
+  title: string;
+  category: string;
+  severity: "critical" | "error" | "warning";
+  offending_mod: string | null;
+  cause: string;
+  suggested_action: string;
+  source: "crash_report" | "latest_log";
+  matched_line: string;
 }
 
 export interface CrashReportContentAvailablePayload {
   process_id: string;
   content: string;
 }
+
+export type ProcessStopStage =
+  | "graceful_requested" // SIGTERM sent / window close requested
+  | "shutting_down" // The game logged "Stopping!" and is saving
+  | "force_killing"
+  | "stopped"
+  | "failed";
+
+export interface ProcessStopStagePayload {
+  process_id: string;
+  profile_id: string;
+  stage: ProcessStopStage;
+  grace_period_secs: number;
+  message: string | null;
+}
+
+export interface ProcessResourceSamplePayload {
+  process_id: string;
+  profile_id: string;
+  sample: ResourceSample;
+}
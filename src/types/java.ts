@@ -22,4 +22,41 @@ export interface JavaInstallation {
   source: string;
   /** The architecture of the Java installation */
   architecture: JavaArchitecture;
-} 
\ No newline at end of file
+} 
+/** How a profile ends up with a downloaded runtime */
+export type JavaRuntimeUsage = 'custom_path' | 'auto_selected' | 'mod_requirement';
+
+export interface JavaRuntimeReference {
+  profile_id: string;
+  profile_name: string;
+  usage: JavaRuntimeUsage;
+}
+
+/**
+ * A Java runtime the launcher downloaded to meta/java.
+ * Mirrors the Rust InstalledJavaRuntime struct.
+ */
+export interface InstalledJavaRuntime {
+  /** Directory name, e.g. "zulu_17" */
+  id: string;
+  path: string;
+  java_binary: string | null;
+  distribution: string;
+  /** e.g. "Azul Systems, Inc." */
+  vendor: string | null;
+  major_version: number;
+  /** e.g. "17.0.9" or "1.8.0_392" */
+  version: string | null;
+  architecture: string | null;
+  size_bytes: number;
+  referenced_by: JavaRuntimeReference[];
+  /** A running game was started with it */
+  in_use: boolean;
+  /** Downloaded upgrade that is swapped in once no running game uses the runtime */
+  pending_version: string | null;
+}
+
+export type JavaRuntimeUpgrade =
+  | { status: 'up_to_date'; version: string | null }
+  | { status: 'upgraded'; from: string | null; to: string }
+  | { status: 'pending'; from: string | null; to: string };
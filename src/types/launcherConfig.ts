@@ -7,11 +7,80 @@ export interface Hooks {
   post_exit: string | null; // Option<String>
 }
 
+export type JvmPreset =
+  | "vanilla_g1"
+  | "aikar_g1"
+  | "generational_zgc" // Java 21+, falls back to vanilla_g1 on older Java
+  | "shenandoah"
+  | "none";
+
+export type JavaVendor =
+  | "zulu"
+  | "temurin"
+  | "corretto"
+  | "graalvm" // Java 17+
+  | "mojang"; // Mojang's runtime for the Minecraft version
+
 export interface MemorySettings {
   min: number; // u32
   max: number; // u32
 }
 
+export type Endpoint =
+  | "piston_meta"
+  | "piston_data"
+  | "libraries"
+  | "assets"
+  | "mojang_api"
+  | "mojang_session"
+  | "modrinth"
+  | "curse_forge"
+  | "curse_forge_cdn"
+  | "fabric_meta"
+  | "fabric_maven"
+  | "quilt_meta"
+  | "quilt_maven"
+  | "forge_maven"
+  | "neo_forge_maven"
+  | "zulu"
+  | "adoptium"
+  | "corretto"
+  | "graal_vm"
+  | "mclogs"
+  | "norisk_api"
+  | "norisk_api_staging"
+  | "norisk_cdn"
+  | "starlight"
+  | "blog";
+
+export interface EndpointOverride {
+  base_url: string | null; // Option<String>
+  mirrors: string[]; // Vec<String>, tried in order
+}
+
+// e.g. { libraries: { base_url: null, mirrors: ["https://bmclapi2.bangbang93.com/maven"] } }
+export type EndpointRegistry = Partial<Record<Endpoint, EndpointOverride>>;
+
+export type BuiltinRedaction =
+  | "tokens" // -Dnorisk.token, --accessToken, session IDs, JWTs
+  | "access_keys" // api_key=.., password: .., Bearer ..
+  | "home_paths"
+  | "ipv4"
+  | "ipv6"
+  | "emails";
+
+export interface CustomRedactionRule {
+  name: string;
+  pattern: string; // Regex
+  replacement: string | null; // Option<String>, defaults to "<redacted>", may use $1
+}
+
+export interface RedactionSettings {
+  enabled: boolean; // Off uploads logs as they are
+  disabled_rules: BuiltinRedaction[];
+  custom_rules: CustomRedactionRule[];
+}
+
 export interface LauncherConfig {
   version: number; // u32
   is_experimental: boolean;
@@ -26,4 +95,11 @@ export interface LauncherConfig {
   hide_on_process_start: boolean;
   global_memory_settings: MemorySettings;
   custom_game_directory: string | null; // Option<PathBuf>
+  endpoints: EndpointRegistry;
+  offline_mode: boolean; // Install/launch from cache only, no network calls
+  stop_grace_period_secs: number; // u64, time the game gets to exit before it is killed (at most 300)
+  jvm_preset: JvmPreset; // GC flags for every launch, profiles can override it
+  java_vendor: JavaVendor; // Where managed Java is downloaded from, profiles can override it
+  redaction: RedactionSettings; // What gets masked in logs and crash reports before uploading
+  curseforge_api_key: string | null; // Option<String>, used for CurseForge pack imports
 } 
\ No newline at end of file
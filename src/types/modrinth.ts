@@ -1,3 +1,5 @@
+import type { Mod } from "./profile";
+
 export interface ModrinthFileHash {
     sha512: string;
     sha1: string;
@@ -55,6 +57,108 @@ export interface ModrinthVersion {
     search_hit?: ModrinthSearchHit;
 }
 
+// --- Dependency resolution plan (resolve_modrinth_install_plan) ---
+
+export interface PlannedMod {
+    project_id: string;
+    version_id: string;
+    name: string;
+    version_number: string;
+    file_name: string;
+    download_url: string;
+    file_hash_sha1: string | null;
+    loaders: string[];
+    game_versions: string[];
+    required_by: string | null; // Project that pulled this mod in, null for the selected mod
+    replaces_version_id: string | null; // Installed version that gets replaced
+}
+
+export interface SatisfiedDependency {
+    project_id: string;
+    installed_version_id: string;
+    required_by: string;
+}
+
+export interface OptionalDependency {
+    project_id: string;
+    suggested_by: string;
+}
+
+export interface UnresolvedDependency {
+    project_id: string | null;
+    version_id: string | null;
+    required_by: string;
+    reason: string;
+}
+
+export type ConflictKind = "incompatible" | "version_mismatch" | "unsupported_by_profile";
+
+export interface DependencyConflict {
+    kind: ConflictKind;
+    project_id: string;
+    other_project_id: string | null;
+    message: string;
+}
+
+export interface ModrinthInstallPlan {
+    profile_id: string; // UUID
+    game_version: string;
+    loader: string; // ModLoader
+    mods: PlannedMod[];
+    satisfied: SatisfiedDependency[];
+    optional: OptionalDependency[];
+    unresolved: UnresolvedDependency[];
+    conflicts: DependencyConflict[]; // Plan can only be applied if conflicts and unresolved are empty
+}
+
+export type UpdateContentKind = "mod" | "resource_pack" | "shader_pack" | "data_pack";
+
+export interface ContentUpdate {
+    kind: UpdateContentKind;
+    mod_id: string | null; // UUID, only for mods
+    project_id: string;
+    name: string;
+    current_version_id: string | null;
+    current_version_number: string | null;
+    current_file_name: string;
+    current_path: string | null; // Only for packs
+    is_disabled: boolean;
+    new_version_id: string;
+    new_version_number: string;
+    new_file_name: string;
+    download_url: string;
+    new_file_hash_sha1: string | null;
+    new_game_versions: string[];
+    new_loaders: string[];
+    version_type: ModrinthVersionType;
+    date_published: string;
+    changelog: string | null;
+}
+
+export interface ProfileUpdatePlan {
+    profile_id: string; // UUID
+    game_version: string;
+    loader: string;
+    updates: ContentUpdate[]; // Remove entries to skip them when applying
+}
+
+export interface SnapshotFile {
+    kind: UpdateContentKind;
+    original_path: string;
+    sha1: string; // Object in the snapshot store
+    installed_path: string | null;
+}
+
+export interface UpdateSnapshot {
+    id: string; // UUID
+    profile_id: string; // UUID
+    profile_name: string;
+    created_at: string; // ISO 8601
+    previous_mods: Mod[];
+    updates: ContentUpdate[];
+    files: SnapshotFile[];
+}
+
 export interface ModrinthSearchResponse {
     hits: ModrinthSearchHit[];
     offset: number;
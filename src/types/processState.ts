@@ -31,6 +31,123 @@ export interface ProcessMetadata {
   norisk_pack?: string | null;
   profile_name?: string | null;
   profile_image_url?: string | null;
+  memory_max_mb?: number | null; // -Xmx in MB
+  exit_info?: ProcessExitInfo | null; // Set once the process has exited
+}
+
+/**
+ * How a game process ended.
+ * Corresponds to the Rust enum `ExitClassification`.
+ */
+export type ExitClassification =
+  | 'clean'
+  | 'crash'
+  | 'killed'
+  | 'oom_killed'
+  | 'possibly_oom_killed' // OOM killer was active, kernel log not readable to confirm it
+  | 'native_crash'
+  | 'unknown';
+
+/**
+ * Exit status and crash artifacts of a finished process.
+ * Corresponds to the Rust struct `ProcessExitInfo`.
+ */
+export interface ProcessExitInfo {
+  classification: ExitClassification;
+  exit_code: number | null;
+  signal: number | null;
+  signal_name: string | null;
+  intentionally_stopped: boolean;
+  native_crash_logs: string[]; // hs_err_pid<pid>.log files
+  crash_reports: string[];
+  exited_at: string; // DateTime<Utc> as ISO string
+}
+
+/**
+ * CPU, memory and thread usage of a game process and its children.
+ * Corresponds to the Rust struct `ResourceSample`.
+ */
+export interface ResourceSample {
+  timestamp: string; // DateTime<Utc> as ISO string
+  cpu_percent: number; // 100 = one fully used core
+  memory_bytes: number; // RSS
+  thread_count: number | null; // Linux only
+  process_count: number;
+}
+
+/**
+ * Summary of a finished game session.
+ * Corresponds to the Rust struct `SessionRecord`.
+ */
+export interface SessionRecord {
+  process_id: string;
+  profile_id: string;
+  profile_name: string | null;
+  minecraft_version: string | null;
+  modloader: string | null;
+  modloader_version: string | null;
+  norisk_pack: string | null;
+  started_at: string;
+  ended_at: string;
+  duration_secs: number;
+  peak_memory_bytes: number | null;
+  average_cpu_percent: number | null;
+  memory_max_mb: number | null;
+  exceeded_memory_max: boolean; // Peak RSS above -Xmx plus the non-heap allowance
+  exit_classification: ExitClassification | null;
+}
+
+/**
+ * Where the time of a play session was spent.
+ * Corresponds to the Rust enum `PlayTarget`.
+ */
+export type PlayTarget =
+  | { type: 'singleplayer'; world: string } // World folder name
+  | { type: 'multiplayer'; server: string }; // Lower case, without default port
+
+export interface PlaytimeSegment {
+  target: PlayTarget;
+  started_at: string;
+  ended_at: string | null;
+}
+
+/**
+ * One run of the game.
+ * Corresponds to the Rust struct `PlaySession`.
+ */
+export interface PlaySession {
+  process_id: string;
+  profile_id: string;
+  profile_name: string | null;
+  account_uuid: string | null;
+  account_name: string | null;
+  minecraft_version: string | null;
+  modloader: string | null;
+  started_at: string;
+  last_seen_at: string;
+  ended_at: string | null; // null while the game is running
+  segments: PlaytimeSegment[];
+}
+
+export interface ProfilePlaytime {
+  profile_id: string;
+  profile_name: string | null;
+  total_secs: number;
+  session_count: number;
+  last_played: string;
+}
+
+export interface WeeklyPlaytime {
+  week_start: string; // Monday (UTC), YYYY-MM-DD
+  total_secs: number; // Only the part of sessions that fell into this week
+  session_count: number; // Sessions running into the next week count for both
+}
+
+export interface TargetPlaytime {
+  target: PlayTarget;
+  total_secs: number;
+  visit_count: number;
+  last_played: string;
 }
 
 /**
@@ -1,4 +1,5 @@
 import { ContentType } from "./content";
+import type { JavaVendor, JvmPreset } from "./launcherConfig";
 
 export type ModLoader = "vanilla" | "forge" | "fabric" | "quilt" | "neoforge";
 export type ProfileState =
@@ -67,10 +68,27 @@ export interface ProfileSettings {
   fullscreen: boolean;
   extra_game_args: string[];           // Vec<String> -> string[] (Renamed from extra_args)
   custom_jvm_args: string | null;   // Option<String> -> string | null (New)
+  jvm_preset?: JvmPreset | null;    // Option<JvmPreset>, null uses the global preset. Ignored for standard profiles, they always use the global one
+  java_vendor?: JavaVendor | null;  // Option<JavaDistribution>, null uses the global vendor
+  env_vars?: Record<string, string>; // BTreeMap<String, String>, values can use hook placeholders
+  hooks?: ProfileHooks;
+}
+
+// "override": a profile hook replaces the global one, an empty string disables it
+// "chain": the global hook runs first (or wraps the profile's wrapper), then the profile's
+export type HookMode = "override" | "chain";
+
+// Hooks get NORISK_INSTANCE_DIR, NORISK_PROFILE_ID, NORISK_PROFILE_NAME, NORISK_JAVA_PATH,
+// NORISK_GAME_VERSION and NORISK_LOADER as env vars and as ${NAME} placeholders
+export interface ProfileHooks {
+  pre_launch: string | null;
+  wrapper: string | null;
+  post_exit: string | null;
+  mode: HookMode;
 }
 
 interface ModSourceBase {
-  type: "local" | "url" | "maven" | "embedded" | "modrinth";
+  type: "local" | "url" | "maven" | "embedded" | "modrinth" | "curseforge";
 }
 
 export interface ModSourceLocal extends ModSourceBase {
@@ -104,12 +122,22 @@ export interface ModSourceModrinth extends ModSourceBase {
   file_hash_sha1: string | null;
 }
 
+export interface ModSourceCurseForge extends ModSourceBase {
+  type: "curseforge";
+  project_id: number;
+  file_id: number;
+  file_name: string;
+  download_url: string | null;
+  file_hash_sha1: string | null;
+}
+
 export type ModSource =
   | ModSourceLocal
   | ModSourceUrl
   | ModSourceMaven
   | ModSourceEmbedded
-  | ModSourceModrinth;
+  | ModSourceModrinth
+  | ModSourceCurseForge;
 
 export interface Mod {
   id: string;
@@ -134,10 +162,34 @@ export interface NoriskInformation {
   is_experimental: boolean;
 }
 
+export type ModMetadataFormat = "fabric" | "quilt" | "forge" | "neo_forge" | "legacy_forge";
+export type ModEnvironment = "both" | "client" | "server";
+export type ModDependencyKind = "required" | "optional" | "incompatible";
+
+export interface ModDependency {
+  mod_id: string;
+  version_range?: string | null; // Alternatives are joined by " || "
+  kind: ModDependencyKind;
+}
+
+// Metadata a mod declares in its jar (fabric.mod.json, quilt.mod.json, mods.toml, mcmod.info)
+export interface ModMetadata {
+  format: ModMetadataFormat;
+  mod_id: string;
+  name?: string | null;
+  version?: string | null;
+  description?: string | null;
+  authors: string[];
+  dependencies: ModDependency[];
+  environment: ModEnvironment;
+  icon?: string | null; // Path of the icon inside the jar
+}
+
 export interface CustomModInfo {
   filename: string;
   is_enabled: boolean;
   path: string;
+  metadata?: ModMetadata | null;
 }
 
 export interface Profile {
@@ -200,11 +252,24 @@ export interface CopyProfileParams {
   include_files?: string[];
 }
 
+export type ProfileExportFormat = "noriskpack" | "mrpack";
+
 export interface ExportProfileParams {
   profile_id: string;
   file_name: string;
   include_files?: string[];
   open_folder: boolean;
+  format?: ProfileExportFormat; // Defaults to "noriskpack"
+}
+
+export type LaunchCommandFormat = "shell" | "json";
+
+export interface ExportLaunchCommandParams {
+  profile_id: string;
+  format?: LaunchCommandFormat; // Defaults to "shell"
+  include_secrets?: boolean; // Tokens are replaced with placeholders unless set
+  quick_play_singleplayer?: string;
+  quick_play_multiplayer?: string;
 }
 
 // --- Payload for upload_profile_icon command ---
@@ -353,6 +418,7 @@ export interface LocalContentItem {
   fallback_version?: string | null; // Fallback version from compatibility target
   id?: string | null; // Added optional ID field from ModProfileEntry.id
   associated_loader?: ModLoader | null; // Added associated_loader from ModProfileEntry
+  metadata?: ModMetadata | null; // Metadata read from the mod jar
   // Frontend specific fields can be added here if needed, e.g., for UI state
   // local_icon_data_url?: string; // Example if we were to add this later
 }
@@ -364,3 +430,57 @@ export interface LoadItemsParams {
   calculate_hashes: boolean;
   fetch_modrinth_data: boolean;
 }
+
+// --- Result of check_profile_offline_readiness ---
+export interface MissingArtifact {
+  step: string; // Install step, e.g. "libraries", "assets", "java"
+  description: string;
+  path: string; // Expected location in the local cache
+  url: string | null; // Option<String>
+}
+
+export interface OfflineReport {
+  profile_id: string; // UUID
+  profile_name: string;
+  missing: MissingArtifact[]; // Empty if the profile can be launched offline
+}
+
+export type SnapshotReason = "manual" | "before_launch" | "before_restore";
+
+export interface ProfileSnapshotSummary {
+  id: string; // UUID
+  profile_id: string; // UUID
+  label: string | null;
+  reason: SnapshotReason;
+  created_at: string; // ISO 8601
+  include_saves: boolean;
+  file_count: number;
+  total_size: number; // Bytes
+}
+
+export interface ModChange {
+  mod_id: string; // UUID
+  name: string;
+  kind: "added" | "removed" | "changed";
+  from_version: string | null;
+  to_version: string | null;
+}
+
+export interface SnapshotDiff {
+  from: string; // UUID
+  to: string | null; // null = current profile state
+  changed_profile_fields: string[];
+  mod_changes: ModChange[];
+  files: {
+    added: string[];
+    removed: string[];
+    modified: string[];
+  };
+}
+
+export interface SnapshotRestoreReport {
+  restored_from: string; // UUID
+  safety_snapshot: string; // UUID of the snapshot taken right before the restore
+  files_written: number;
+  files_removed: number;
+}